@@ -234,6 +234,7 @@ fn make_write_options<'a>(
         user_metadata: convert::read_map_field(env, options, "userMetadata")?,
         concurrent,
         chunk: convert::read_jlong_field_to_usize(env, options, "chunk")?,
+        ..Default::default()
     })
 }
 
@@ -247,6 +248,7 @@ fn make_list_options<'a>(
         recursive: convert::read_bool_field(env, options, "recursive").unwrap_or_default(),
         versions: convert::read_bool_field(env, options, "versions").unwrap_or_default(),
         deleted: convert::read_bool_field(env, options, "deleted").unwrap_or_default(),
+        ..Default::default()
     })
 }
 
@@ -272,5 +274,6 @@ fn make_stat_options(env: &mut JNIEnv, options: &JObject) -> Result<opendal::opt
             options,
             "overrideContentDisposition",
         )?,
+        ..Default::default()
     })
 }
@@ -93,6 +93,7 @@ impl From<StatOptions> for opendal::options::StatOptions {
             override_content_type: value.override_content_type,
             override_cache_control: value.override_cache_control,
             override_content_disposition: value.override_content_disposition,
+            ..Default::default()
         }
     }
 }
@@ -233,6 +234,7 @@ impl From<ReadOptions> for opendal::options::ReadOptions {
             override_content_type: value.content_type,
             override_cache_control: value.cache_control,
             override_content_disposition: value.content_disposition,
+            ..Default::default()
         }
     }
 }
@@ -344,6 +346,7 @@ impl From<ReaderOptions> for opendal::options::ReaderOptions {
             if_none_match: value.if_none_match,
             if_modified_since,
             if_unmodified_since,
+            ..Default::default()
         }
     }
 }
@@ -406,6 +409,7 @@ impl From<ListOptions> for opendal::options::ListOptions {
             recursive: value.recursive.unwrap_or_default(),
             versions: value.versions.unwrap_or_default(),
             deleted: value.deleted.unwrap_or_default(),
+            ..Default::default()
         }
     }
 }
@@ -500,6 +504,7 @@ impl From<WriteOptions> for opendal::options::WriteOptions {
             if_none_match: value.if_none_match,
             if_not_exists: value.if_not_exists.unwrap_or_default(),
             concurrent: value.concurrent.unwrap_or_default() as usize,
+            ..Default::default()
         }
     }
 }
@@ -514,6 +519,7 @@ impl From<DeleteOptions> for opendal::options::DeleteOptions {
     fn from(value: DeleteOptions) -> Self {
         Self {
             version: value.version,
+            ..Default::default()
         }
     }
 }
@@ -81,6 +81,7 @@ impl From<ReadOptions> for ocore::options::ReadOptions {
             override_content_type: opts.content_type,
             override_cache_control: opts.cache_control,
             override_content_disposition: opts.content_disposition,
+            ..Default::default()
         }
     }
 }
@@ -97,6 +98,7 @@ impl From<ReadOptions> for ocore::options::ReaderOptions {
             chunk: opts.chunk,
             gap: opts.gap,
             prefetch: opts.prefetch.unwrap_or_default(),
+            ..Default::default()
         }
     }
 }
@@ -115,6 +117,7 @@ impl From<WriteOptions> for ocore::options::WriteOptions {
             if_match: opts.if_match,
             if_none_match: opts.if_none_match,
             if_not_exists: opts.if_not_exists.unwrap_or(false),
+            ..Default::default()
         }
     }
 }
@@ -137,6 +140,7 @@ impl From<ListOptions> for ocore::options::ListOptions {
             recursive: opts.recursive.unwrap_or(false),
             versions: opts.versions.unwrap_or(false),
             deleted: opts.deleted.unwrap_or(false),
+            ..Default::default()
         }
     }
 }
@@ -165,6 +169,7 @@ impl From<StatOptions> for ocore::options::StatOptions {
             override_content_type: opts.content_type,
             override_cache_control: opts.cache_control,
             override_content_disposition: opts.content_disposition,
+            ..Default::default()
         }
     }
 }
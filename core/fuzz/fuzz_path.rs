@@ -0,0 +1,39 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use opendal::raw::normalize_path;
+use opendal::raw::resolve_dot_segments;
+
+fuzz_target!(|path: &str| {
+    let p = normalize_path(path);
+    assert_eq!(normalize_path(&p), p, "normalize must be idempotent");
+    assert!(
+        p == "/" || !p.starts_with('/'),
+        "normalized path must not be absolute"
+    );
+
+    let p = resolve_dot_segments(path);
+    assert_eq!(resolve_dot_segments(&p), p, "resolve must be idempotent");
+    assert_eq!(normalize_path(&p), p, "resolved path must be normalized");
+    assert!(
+        !p.split('/').any(|v| v == "." || v == ".."),
+        "resolved path must not escape root"
+    );
+});
@@ -27,6 +27,8 @@ use crate::*;
 ///
 /// - Lister implements `Iterator<Item = Result<Entry>>`.
 /// - Lister will return `None` if there is no more entries or error has been returned.
+/// - Lister drives the underlying async lister lazily, entries are fetched page by page
+///   instead of being collected, so it's safe to iterate over huge directories.
 pub struct Lister {
     handle: tokio::runtime::Handle,
     lister: Option<AsyncLister>,
@@ -45,6 +47,13 @@ impl Lister {
             lister: Some(lister),
         }
     }
+
+    /// Get the continuation token of this lister.
+    ///
+    /// Refer to [`crate::Lister::continuation_token`] for more details.
+    pub fn continuation_token(&self) -> Option<&str> {
+        self.lister.as_ref()?.continuation_token()
+    }
 }
 
 impl Iterator for Lister {
@@ -69,3 +78,50 @@ impl Drop for Lister {
         }
     }
 }
+
+#[cfg(all(test, feature = "tests"))]
+mod tests {
+    use super::*;
+    use crate::raw::tests::MockAccess;
+    use crate::raw::Operation;
+
+    #[test]
+    fn test_lister_is_lazy() -> Result<()> {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let _guard = rt.enter();
+
+        // The recursive list is emulated by listing dirs one by one.
+        let mock = MockAccess::new().with_capability(|cap| Capability {
+            list_with_recursive: false,
+            ..cap
+        });
+        mock.insert("dir/a", "Hello");
+        mock.insert("dir/sub/b", "World");
+        mock.inject_error(Operation::List, "dir/sub/", 1, || {
+            Error::new(ErrorKind::Unexpected, "mock error")
+        });
+        let op = blocking::Operator::new(OperatorBuilder::new(mock.clone()).finish())?;
+
+        let mut lister = op.lister_options(
+            "dir/",
+            options::ListOptions {
+                recursive: true,
+                ..Default::default()
+            },
+        )?;
+        // Entries are returned before the failed page is fetched.
+        assert_eq!(lister.next().unwrap()?.path(), "dir/a");
+        assert_eq!(mock.calls(Operation::List, "dir/sub/"), 0);
+        let mut paths = vec![];
+        let err = loop {
+            match lister.next().expect("error must be returned") {
+                Ok(entry) => paths.push(entry.path().to_string()),
+                Err(err) => break err,
+            }
+        };
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        assert_eq!(mock.calls(Operation::List, "dir/sub/"), 1);
+        assert!(!paths.contains(&"dir/sub/b".to_string()));
+        Ok(())
+    }
+}
@@ -15,6 +15,10 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
+use std::ops::RangeBounds;
+use std::time::Duration;
+
 use tokio::runtime::Handle;
 
 use crate::Operator as AsyncOperator;
@@ -240,6 +244,29 @@ impl Operator {
         self.handle.block_on(self.op.stat_options(path, opts))
     }
 
+    /// Get given path's metadata, returns `None` if it doesn't exist.
+    ///
+    /// Only [`ErrorKind::NotFound`] will be mapped to `Ok(None)`, other errors
+    /// will be returned as is.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use anyhow::Result;
+    /// use opendal::blocking;
+    /// use opendal::blocking::Operator;
+    /// fn test(op: blocking::Operator) -> Result<()> {
+    ///     if let Some(meta) = op.stat_if_exists("test")? {
+    ///         println!("file size: {}", meta.content_length())
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn stat_if_exists(&self, path: &str) -> Result<Option<Metadata>> {
+        self.handle.block_on(self.op.stat_if_exists(path))
+    }
+
     /// Check if this path exists or not.
     ///
     /// # Example
@@ -255,14 +282,36 @@ impl Operator {
     /// }
     /// ```
     pub fn exists(&self, path: &str) -> Result<bool> {
-        let r = self.stat(path);
-        match r {
-            Ok(_) => Ok(true),
-            Err(err) => match err.kind() {
-                ErrorKind::NotFound => Ok(false),
-                _ => Err(err),
-            },
-        }
+        Ok(self.stat_if_exists(path)?.is_some())
+    }
+
+    /// Get tags of the file at given path.
+    ///
+    /// Visit [`crate::Operator::tags`] for more details.
+    pub fn tags(&self, path: &str) -> Result<HashMap<String, String>> {
+        self.handle.block_on(self.op.tags(path))
+    }
+
+    /// Set tags of the file at given path, existing tags will be replaced.
+    ///
+    /// Visit [`crate::Operator::set_tags`] for more details.
+    pub fn set_tags(&self, path: &str, tags: HashMap<String, String>) -> Result<()> {
+        self.handle.block_on(self.op.set_tags(path, tags))
+    }
+
+    /// Abort an in-progress multipart upload of the file by upload id.
+    ///
+    /// Visit [`crate::Operator::abort_multipart`] for more details.
+    pub fn abort_multipart(&self, path: &str, upload_id: &str) -> Result<()> {
+        self.handle
+            .block_on(self.op.abort_multipart(path, upload_id))
+    }
+
+    /// List in-progress multipart uploads of files starting with the prefix.
+    ///
+    /// Visit [`crate::Operator::list_multipart_uploads`] for more details.
+    pub fn list_multipart_uploads(&self, prefix: &str) -> Result<Vec<crate::MultipartUpload>> {
+        self.handle.block_on(self.op.list_multipart_uploads(prefix))
     }
 
     /// Create a dir at given path.
@@ -323,6 +372,48 @@ impl Operator {
         self.handle.block_on(self.op.read_options(path, opts))
     }
 
+    /// Read the whole path as an iterator of lines.
+    ///
+    /// Lines are split by `\n` or `\r\n` by default. Visit [`blocking::StdLinesIterator`]
+    /// for more options.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use opendal::Result;
+    /// use opendal::blocking;
+    /// # use opendal::blocking::Operator;
+    /// #
+    /// # fn test(op: blocking::Operator) -> Result<()> {
+    /// for line in op.read_lines("path/to/file")? {
+    ///     println!("{}", line?);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_lines(&self, path: &str) -> Result<blocking::StdLinesIterator> {
+        let r = self.reader(path)?;
+        r.into_lines_iterator(..)
+    }
+
+    /// Read at most the first `n` bytes of the file at given path.
+    ///
+    /// See [`crate::Operator::read_head`] for more details.
+    pub fn read_head(&self, path: &str, n: u64) -> Result<Buffer> {
+        self.handle.block_on(self.op.read_head(path, n))
+    }
+
+    /// Read the given range of the file at given path along with its metadata.
+    ///
+    /// See [`crate::Operator::read_with_meta`] for more details.
+    pub fn read_with_meta(
+        &self,
+        path: &str,
+        range: impl RangeBounds<u64>,
+    ) -> Result<(Metadata, Buffer)> {
+        self.handle.block_on(self.op.read_with_meta(path, range))
+    }
+
     /// Create a new reader which can read the whole path.
     ///
     /// # Examples
@@ -376,6 +467,14 @@ impl Operator {
         self.write_options(path, bs, options::WriteOptions::default())
     }
 
+    /// Write data to the given path only if it doesn't exist yet.
+    ///
+    /// Returns `false` if the path already exists, see
+    /// [`crate::Operator::write_if_not_exists`] for more details.
+    pub fn write_if_not_exists(&self, path: &str, bs: impl Into<Buffer>) -> Result<bool> {
+        self.handle.block_on(self.op.write_if_not_exists(path, bs))
+    }
+
     /// Write data with options.
     ///
     /// # Notes
@@ -568,6 +667,14 @@ impl Operator {
         self.handle.block_on(self.op.remove_all(path))
     }
 
+    /// Remove the path and all nested dirs and files recursively with options,
+    /// returns the number of removed entries.
+    ///
+    /// Visit [`crate::Operator::remove_all_with`] for more details.
+    pub fn remove_all_options(&self, path: &str, opts: options::RemoveAllOptions) -> Result<usize> {
+        self.handle.block_on(self.op.remove_all_options(path, opts))
+    }
+
     /// List entries that starts with given `path` in parent dir.
     ///
     /// # Notes
@@ -637,6 +744,11 @@ impl Operator {
     ///
     /// # Notes
     ///
+    /// ## Streaming List
+    ///
+    /// Entries are fetched from the service page by page while iterating instead of being
+    /// collected, so the memory usage won't grow with the number of entries.
+    ///
     /// ## Recursively List
     ///
     /// This function only read the children of the given directory. To read
@@ -711,6 +823,13 @@ impl Operator {
             _ => Ok(()),
         }
     }
+
+    /// Wait for in-flight operations to finish before shutting down.
+    ///
+    /// Refer to [`crate::Operator::drain`] for more details.
+    pub fn drain(&self, timeout: Duration) -> Result<layers::DrainSummary> {
+        self.handle.block_on(self.op.drain(timeout))
+    }
 }
 
 impl From<Operator> for AsyncOperator {
@@ -23,5 +23,7 @@ pub use buffer_iterator::BufferIterator;
 
 mod std_bytes_iterator;
 pub use std_bytes_iterator::StdBytesIterator;
+mod std_lines_iterator;
+pub use std_lines_iterator::StdLinesIterator;
 mod std_reader;
 pub use std_reader::StdReader;
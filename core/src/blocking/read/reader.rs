@@ -21,6 +21,7 @@ use bytes::BufMut;
 
 use super::BufferIterator;
 use super::StdBytesIterator;
+use super::StdLinesIterator;
 use super::StdReader;
 use crate::Reader as AsyncReader;
 use crate::*;
@@ -112,6 +113,17 @@ impl Reader {
         let iter = self.handle.block_on(inner.into_bytes_stream(range))?;
         Ok(StdBytesIterator::new(self.handle.clone(), iter))
     }
+
+    /// Convert reader into [`StdLinesIterator`] which yields lines of given range.
+    #[inline]
+    pub fn into_lines_iterator(mut self, range: impl RangeBounds<u64>) -> Result<StdLinesIterator> {
+        let inner = self
+            .inner
+            .take()
+            .ok_or_else(|| Error::new(ErrorKind::Unexpected, "reader has been dropped"))?;
+        let iter = self.handle.block_on(inner.into_lines_stream(range))?;
+        Ok(StdLinesIterator::new(self.handle.clone(), iter))
+    }
 }
 
 /// Make sure the inner reader is dropped in async context.
@@ -0,0 +1,79 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use futures::StreamExt;
+
+use crate::*;
+
+/// StdLinesIterator is the adapter of [`Iterator`] for [`FuturesLinesStream`].
+///
+/// Visit [`FuturesLinesStream`] for how lines are split and decoded.
+///
+/// StdLinesIterator also implements [`Send`] and [`Sync`].
+pub struct StdLinesIterator {
+    handle: tokio::runtime::Handle,
+    inner: Option<FuturesLinesStream>,
+}
+
+impl StdLinesIterator {
+    /// NOTE: don't allow users to create StdLinesIterator directly.
+    #[inline]
+    pub(crate) fn new(handle: tokio::runtime::Handle, inner: FuturesLinesStream) -> Self {
+        StdLinesIterator {
+            handle,
+            inner: Some(inner),
+        }
+    }
+
+    /// Split lines by given terminator instead of `\n` and `\r\n`.
+    ///
+    /// Empty terminator will be ignored.
+    pub fn with_terminator(mut self, terminator: impl Into<Vec<u8>>) -> Self {
+        self.inner = self.inner.take().map(|v| v.with_terminator(terminator));
+        self
+    }
+
+    /// Replace invalid UTF-8 sequences with `U+FFFD` instead of returning an error.
+    pub fn with_lossy(mut self, lossy: bool) -> Self {
+        self.inner = self.inner.take().map(|v| v.with_lossy(lossy));
+        self
+    }
+}
+
+impl Iterator for StdLinesIterator {
+    type Item = Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Some(inner) = self.inner.as_mut() else {
+            return Some(Err(Error::new(
+                ErrorKind::Unexpected,
+                "reader has been dropped",
+            )));
+        };
+
+        self.handle.block_on(inner.next())
+    }
+}
+
+/// Make sure the inner reader is dropped in async context.
+impl Drop for StdLinesIterator {
+    fn drop(&mut self) {
+        if let Some(v) = self.inner.take() {
+            self.handle.block_on(async move { drop(v) });
+        }
+    }
+}
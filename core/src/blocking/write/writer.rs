@@ -73,6 +73,17 @@ impl Writer {
         self.handle.block_on(inner.write(bs))
     }
 
+    /// Flush the written data to the service without closing the writer.
+    ///
+    /// Check [`crate::Writer::flush`] for what flushed data guarantees.
+    pub fn flush(&mut self) -> Result<()> {
+        let Some(inner) = self.inner.as_mut() else {
+            return Err(Error::new(ErrorKind::Unexpected, "writer has been dropped"));
+        };
+
+        self.handle.block_on(inner.flush())
+    }
+
     /// Close the writer and make sure all data have been committed.
     ///
     /// ## Notes
@@ -0,0 +1,1124 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::mem;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::Weak;
+
+use bytes::Buf;
+use bytes::Bytes;
+use chrono::DateTime;
+use chrono::NaiveDate;
+use chrono::Utc;
+use miniz_oxide::inflate::stream::inflate;
+use miniz_oxide::inflate::stream::InflateState;
+use miniz_oxide::DataFormat;
+use miniz_oxide::MZError;
+use miniz_oxide::MZFlush;
+use miniz_oxide::MZStatus;
+use tokio::sync::OnceCell;
+
+use crate::raw::*;
+use crate::*;
+
+const EOCD_SIGNATURE: u32 = 0x06054b50;
+const EOCD_SIZE: usize = 22;
+const ZIP64_EOCD_LOCATOR_SIGNATURE: u32 = 0x07064b50;
+const ZIP64_EOCD_LOCATOR_SIZE: usize = 20;
+const ZIP64_EOCD_SIGNATURE: u32 = 0x06064b50;
+const ZIP64_EOCD_SIZE: usize = 56;
+const CENTRAL_HEADER_SIGNATURE: u32 = 0x02014b50;
+const CENTRAL_HEADER_SIZE: usize = 46;
+const LOCAL_HEADER_SIGNATURE: u32 = 0x04034b50;
+const LOCAL_HEADER_SIZE: usize = 30;
+const ZIP64_EXTRA_FIELD_ID: u16 = 0x0001;
+
+/// The size of archive tail to fetch, which is large enough to contain the end
+/// of central directory record with the longest comment and zip64 records.
+const TAIL_SIZE: u64 =
+    (EOCD_SIZE + u16::MAX as usize + ZIP64_EOCD_LOCATOR_SIZE + ZIP64_EOCD_SIZE) as u64;
+
+/// The size of buffer used to inflate compressed members.
+const INFLATE_BUFFER_SIZE: usize = 64 * 1024;
+
+const METHOD_STORED: u16 = 0;
+const METHOD_DEFLATED: u16 = 8;
+
+/// Copy version, conditions and extra headers from the args of member
+/// operations to the args of archive operations, so that all reads are
+/// against the archive that the caller asked for.
+macro_rules! forward_args {
+    ($from:expr, $to:expr) => {{
+        let from = $from;
+        let mut op = $to;
+        if let Some(v) = from.version() {
+            op = op.with_version(v);
+        }
+        if let Some(v) = from.if_match() {
+            op = op.with_if_match(v);
+        }
+        if let Some(v) = from.if_none_match() {
+            op = op.with_if_none_match(v);
+        }
+        if let Some(v) = from.if_modified_since() {
+            op = op.with_if_modified_since(v);
+        }
+        if let Some(v) = from.if_unmodified_since() {
+            op = op.with_if_unmodified_since(v);
+        }
+        if let Some(v) = from.headers() {
+            op = op.with_headers(v.clone());
+        }
+        op
+    }};
+}
+
+/// Expose members of zip archives as read-only virtual paths.
+///
+/// # Notes
+///
+/// `ArchiveLayer` treats paths like `{archive}{separator}{member}` as members
+/// inside the zip archive at `{archive}`, the separator is `!/` by default. For
+/// example, `data/archive.zip!/dir/file.csv` refers to `dir/file.csv` inside
+/// `data/archive.zip`.
+///
+/// The central directory is located by reading the tail of archive via a suffix
+/// range read (or `stat` and a range read if service doesn't support it), and
+/// member content is fetched by range reads against the archive. So reading a
+/// small member never downloads the whole archive.
+///
+/// - `read`, `stat` and `list` are supported on members. Members could be
+///   `stored` or `deflated`, zip64 archives are supported as well.
+/// - Writing to members is not supported.
+/// - The central directory is not cached, every operation on members will
+///   fetch it again.
+/// - Version, conditions and extra headers of member reads are forwarded to
+///   the reads against the archive.
+/// - Sequential chunked reads of a deflated member resume inflating from the
+///   previous chunk instead of inflating from the start again.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use opendal::layers::ArchiveLayer;
+/// # use opendal::services;
+/// # use opendal::Operator;
+/// # use opendal::Result;
+///
+/// # async fn test() -> Result<()> {
+/// let op = Operator::new(services::Memory::default())?
+///     .layer(ArchiveLayer::new())
+///     .finish();
+///
+/// let bs = op.read("data/archive.zip!/dir/file.csv").await?;
+/// Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ArchiveLayer {
+    separator: String,
+}
+
+impl Default for ArchiveLayer {
+    fn default() -> Self {
+        Self {
+            separator: "!/".to_string(),
+        }
+    }
+}
+
+impl ArchiveLayer {
+    /// Create a new `ArchiveLayer`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the separator between archive path and member path.
+    ///
+    /// Default to `!/`.
+    pub fn with_separator(mut self, separator: &str) -> Self {
+        self.separator = separator.to_string();
+        self
+    }
+}
+
+impl<A: Access> Layer<A> for ArchiveLayer {
+    type LayeredAccess = ArchiveAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccess {
+        ArchiveAccessor {
+            inner,
+            separator: self.separator.clone(),
+            parked: Arc::default(),
+        }
+    }
+}
+
+pub struct ArchiveAccessor<A: Access> {
+    inner: A,
+    separator: String,
+    parked: ParkedInflaters<A::Reader>,
+}
+
+impl<A: Access> Debug for ArchiveAccessor<A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ArchiveAccessor")
+            .field("inner", &self.inner)
+            .field("separator", &self.separator)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<A: Access> ArchiveAccessor<A> {
+    /// Split path into archive path and member path.
+    fn split<'a>(&self, path: &'a str) -> Option<(&'a str, &'a str)> {
+        path.split_once(self.separator.as_str())
+    }
+
+    async fn read_range(&self, path: &str, base: &OpRead, offset: u64, size: u64) -> Result<Bytes> {
+        if size == 0 {
+            return Ok(Bytes::new());
+        }
+
+        let args = base.clone().with_range(BytesRange::new(offset, Some(size)));
+        let (_, mut r) = self.inner.read(path, args).await?;
+        Ok(oio::Read::read_all(&mut r).await?.to_bytes())
+    }
+
+    async fn read_tail(&self, path: &str, base: &OpRead) -> Result<Bytes> {
+        if self.inner.info().full_capability().read_with_suffix_range {
+            let args = base.clone().with_suffix_range(TAIL_SIZE);
+            let (_, mut r) = self.inner.read(path, args).await?;
+            return Ok(oio::Read::read_all(&mut r).await?.to_bytes());
+        }
+
+        let size = self
+            .inner
+            .stat(path, forward_args!(base, OpStat::new()))
+            .await?
+            .into_metadata()
+            .content_length();
+        let offset = size.saturating_sub(TAIL_SIZE);
+        self.read_range(path, base, offset, size - offset).await
+    }
+
+    /// Load all entries from the central directory of archive.
+    ///
+    /// `base` is the args forwarded from the member operation.
+    async fn load_entries(&self, archive: &str, base: &OpRead) -> Result<Vec<ZipEntry>> {
+        let tail = self.read_tail(archive, base).await?;
+        let (tail_offset, cd_offset, cd_size) =
+            parse_eocd(&tail).map_err(|err| err.with_context("archive", archive))?;
+
+        let cd_end = cd_offset.checked_add(cd_size).ok_or_else(|| {
+            invalid_archive("zip central directory is invalid").with_context("archive", archive)
+        })?;
+
+        // Reuse the tail if the central directory has been fetched already.
+        let cd = if cd_offset >= tail_offset && cd_end - tail_offset <= tail.len() as u64 {
+            let start = (cd_offset - tail_offset) as usize;
+            tail.slice(start..start + cd_size as usize)
+        } else {
+            self.read_range(archive, base, cd_offset, cd_size).await?
+        };
+        parse_central_directory(&cd).map_err(|err| err.with_context("archive", archive))
+    }
+
+    async fn load_entry(&self, archive: &str, member: &str, base: &OpRead) -> Result<ZipEntry> {
+        self.load_entries(archive, base)
+            .await?
+            .into_iter()
+            .find(|e| e.name == member)
+            .ok_or_else(|| {
+                Error::new(ErrorKind::NotFound, "archive member not found")
+                    .with_context("archive", archive)
+                    .with_context("member", member)
+            })
+    }
+
+    /// Resume the inflater parked by the previous read of the same reader if
+    /// the requested range starts after it.
+    fn resume(&self, path: &str, args: &OpRead) -> Option<InflateReader<A::Reader>> {
+        let key = (path.to_string(), args.reader_id()?);
+        let parked = self.parked.lock().unwrap().remove(&key)?;
+
+        let (offset, size) = member_range(args, parked.inflater.size);
+        if offset < parked.inflater.position {
+            return None;
+        }
+        Some(InflateReader::new(
+            parked.inflater,
+            offset,
+            size,
+            self.park_handle(path, args),
+        ))
+    }
+
+    /// Build the handle used by the reader to park its inflater once the
+    /// requested range has been read.
+    ///
+    /// The reader id identifies all reads issued by the same reader, and the
+    /// shared stat cell tells whether the reader has gone.
+    fn park_handle(&self, path: &str, args: &OpRead) -> Option<ParkHandle<A::Reader>> {
+        Some(ParkHandle {
+            parked: self.parked.clone(),
+            key: (path.to_string(), args.reader_id()?),
+            reader: Arc::downgrade(args.shared_stat()?),
+        })
+    }
+}
+
+/// Calculate the offset and size to read from a member of given size.
+fn member_range(args: &OpRead, size: u64) -> (u64, u64) {
+    match args.suffix_range() {
+        Some(n) => (size.saturating_sub(n), size.min(n)),
+        None => {
+            let range = args.range();
+            let offset = range.offset().min(size);
+            let len = range.size().unwrap_or(u64::MAX).min(size - offset);
+            (offset, len)
+        }
+    }
+}
+
+impl<A: Access> LayeredAccess for ArchiveAccessor<A> {
+    type Inner = A;
+    type Reader = ThreeWays<A::Reader, Buffer, InflateReader<A::Reader>>;
+    type Writer = A::Writer;
+    type Lister = TwoWays<A::Lister, ArchiveLister>;
+    type Deleter = A::Deleter;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let Some((archive, member)) = self.split(path) else {
+            let (rp, r) = self.inner.read(path, args).await?;
+            return Ok((rp, ThreeWays::One(r)));
+        };
+        if let Some(r) = self.resume(path, &args) {
+            return Ok((RpRead::new(), ThreeWays::Three(r)));
+        }
+
+        let base = forward_args!(&args, OpRead::new());
+        let entry = self.load_entry(archive, member, &base).await?;
+        if entry.flags & 1 != 0 {
+            return Err(
+                Error::new(ErrorKind::Unsupported, "encrypted archive member")
+                    .with_context("archive", archive)
+                    .with_context("member", member),
+            );
+        }
+
+        let header = self
+            .read_range(
+                archive,
+                &base,
+                entry.header_offset,
+                LOCAL_HEADER_SIZE as u64,
+            )
+            .await?;
+        let invalid_header = || {
+            invalid_archive("zip local file header is invalid")
+                .with_context("archive", archive)
+                .with_context("member", member)
+        };
+        if header.len() < LOCAL_HEADER_SIZE || le_u32(&header, 0)? != LOCAL_HEADER_SIGNATURE {
+            return Err(invalid_header());
+        }
+        // The extra field in local header could differ from central directory.
+        let data_offset = entry
+            .header_offset
+            .checked_add(
+                LOCAL_HEADER_SIZE as u64
+                    + le_u16(&header, 26)? as u64
+                    + le_u16(&header, 28)? as u64,
+            )
+            .filter(|v| v.checked_add(entry.compressed_size).is_some())
+            .ok_or_else(invalid_header)?;
+
+        let (offset, size) = member_range(&args, entry.size);
+
+        match entry.method {
+            METHOD_STORED if size == 0 => Ok((RpRead::new(), ThreeWays::Two(Buffer::new()))),
+            METHOD_STORED => {
+                let offset = data_offset.checked_add(offset).ok_or_else(invalid_header)?;
+                let args = base.with_range(BytesRange::new(offset, Some(size)));
+                let (rp, r) = self.inner.read(archive, args).await?;
+                Ok((rp, ThreeWays::One(r)))
+            }
+            METHOD_DEFLATED => {
+                let data_args =
+                    base.with_range(BytesRange::new(data_offset, Some(entry.compressed_size)));
+                let (rp, r) = self.inner.read(archive, data_args).await?;
+                let r = InflateReader::new(
+                    Inflater::new(r, entry.size),
+                    offset,
+                    size,
+                    self.park_handle(path, &args),
+                );
+                Ok((rp, ThreeWays::Three(r)))
+            }
+            method => Err(Error::new(
+                ErrorKind::Unsupported,
+                "archive member compression method is not supported",
+            )
+            .with_context("archive", archive)
+            .with_context("member", member)
+            .with_context("method", method.to_string())),
+        }
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        if self.split(path).is_some() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "archive member is read-only",
+            ));
+        }
+
+        self.inner.write(path, args).await
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let Some((archive, member)) = self.split(path) else {
+            return self.inner.stat(path, args).await;
+        };
+
+        let base = forward_args!(&args, OpRead::new());
+        if !member.is_empty() && !member.ends_with('/') {
+            let entry = self.load_entry(archive, member, &base).await?;
+            return Ok(RpStat::new(entry.metadata()));
+        }
+
+        // Directories could be implied by members without an entry.
+        let entries = self.load_entries(archive, &base).await?;
+        if member.is_empty() || entries.iter().any(|e| e.name.starts_with(member)) {
+            return Ok(RpStat::new(Metadata::new(EntryMode::DIR)));
+        }
+        Err(Error::new(ErrorKind::NotFound, "archive member not found")
+            .with_context("archive", archive)
+            .with_context("member", member))
+    }
+
+    async fn delete(&self) -> Result<(RpDelete, Self::Deleter)> {
+        self.inner.delete().await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        let Some((archive, member)) = self.split(path) else {
+            let (rp, l) = self.inner.list(path, args).await?;
+            return Ok((rp, TwoWays::One(l)));
+        };
+
+        let entries = self.load_entries(archive, &OpRead::new()).await?;
+        let prefix = format!("{archive}{}", self.separator);
+        let l = ArchiveLister::new(&prefix, member, entries, args.recursive());
+        Ok((RpList::default(), TwoWays::Two(l)))
+    }
+}
+
+/// An entry in the central directory.
+#[derive(Debug, Clone)]
+struct ZipEntry {
+    name: String,
+    flags: u16,
+    method: u16,
+    last_modified: Option<DateTime<Utc>>,
+    compressed_size: u64,
+    size: u64,
+    header_offset: u64,
+}
+
+impl ZipEntry {
+    fn metadata(&self) -> Metadata {
+        if self.name.ends_with('/') {
+            return Metadata::new(EntryMode::DIR);
+        }
+
+        let mut meta = Metadata::new(EntryMode::FILE).with_content_length(self.size);
+        if let Some(v) = self.last_modified {
+            meta.set_last_modified(v);
+        }
+        meta
+    }
+}
+
+/// Read `N` bytes at `pos`, returns error if `bs` is too short.
+fn le_bytes<const N: usize>(bs: &[u8], pos: usize) -> Result<[u8; N]> {
+    pos.checked_add(N)
+        .and_then(|end| bs.get(pos..end))
+        .and_then(|v| v.try_into().ok())
+        .ok_or_else(|| invalid_archive("zip record is truncated"))
+}
+
+fn le_u16(bs: &[u8], pos: usize) -> Result<u16> {
+    le_bytes(bs, pos).map(u16::from_le_bytes)
+}
+
+fn le_u32(bs: &[u8], pos: usize) -> Result<u32> {
+    le_bytes(bs, pos).map(u32::from_le_bytes)
+}
+
+fn le_u64(bs: &[u8], pos: usize) -> Result<u64> {
+    le_bytes(bs, pos).map(u64::from_le_bytes)
+}
+
+fn invalid_archive(msg: &'static str) -> Error {
+    Error::new(ErrorKind::Unexpected, msg)
+}
+
+/// Parse the end of central directory record from the tail of archive.
+///
+/// Returns the offset of tail in archive, and the offset and size of
+/// central directory.
+fn parse_eocd(tail: &[u8]) -> Result<(u64, u64, u64)> {
+    if tail.len() < EOCD_SIZE {
+        return Err(invalid_archive("zip end of central directory not found"));
+    }
+
+    // The record is followed by a variable length comment.
+    let pos = (0..=tail.len() - EOCD_SIZE)
+        .rev()
+        .find(|&p| {
+            matches!(le_u32(tail, p), Ok(EOCD_SIGNATURE))
+                && le_u16(tail, p + 20).is_ok_and(|n| p + EOCD_SIZE + n as usize == tail.len())
+        })
+        .ok_or_else(|| invalid_archive("zip end of central directory not found"))?;
+
+    if pos >= ZIP64_EOCD_LOCATOR_SIZE
+        && le_u32(tail, pos - ZIP64_EOCD_LOCATOR_SIZE)? == ZIP64_EOCD_LOCATOR_SIGNATURE
+    {
+        let locator = pos - ZIP64_EOCD_LOCATOR_SIZE;
+        let eocd64_offset = le_u64(tail, locator + 8)?;
+        // The zip64 record is followed by a variable length extensible data.
+        let eocd64 = (0..=locator.saturating_sub(ZIP64_EOCD_SIZE))
+            .rev()
+            .find(|&p| {
+                locator >= ZIP64_EOCD_SIZE
+                    && matches!(le_u32(tail, p), Ok(ZIP64_EOCD_SIGNATURE))
+                    && le_u64(tail, p + 4)
+                        .ok()
+                        .and_then(|n| (p as u64 + 12).checked_add(n))
+                        == Some(locator as u64)
+            })
+            .ok_or_else(|| invalid_archive("zip64 end of central directory not found"))?;
+
+        let cd_size = le_u64(tail, eocd64 + 40)?;
+        let cd_offset = le_u64(tail, eocd64 + 48)?;
+        let tail_offset = eocd64_offset
+            .checked_sub(eocd64 as u64)
+            .ok_or_else(|| invalid_archive("zip64 end of central directory is invalid"))?;
+        return Ok((tail_offset, cd_offset, cd_size));
+    }
+
+    let cd_size = le_u32(tail, pos + 12)? as u64;
+    let cd_offset = le_u32(tail, pos + 16)? as u64;
+    // Central directory is placed right before the end of central directory.
+    let tail_offset = cd_offset
+        .checked_add(cd_size)
+        .and_then(|v| v.checked_sub(pos as u64))
+        .ok_or_else(|| invalid_archive("zip end of central directory is invalid"))?;
+    Ok((tail_offset, cd_offset, cd_size))
+}
+
+fn parse_central_directory(cd: &[u8]) -> Result<Vec<ZipEntry>> {
+    let mut entries = Vec::new();
+
+    let mut pos = 0;
+    while pos + CENTRAL_HEADER_SIZE <= cd.len() && le_u32(cd, pos)? == CENTRAL_HEADER_SIGNATURE {
+        let name_start = pos + CENTRAL_HEADER_SIZE;
+        let extra_start = name_start + le_u16(cd, pos + 28)? as usize;
+        let extra_end = extra_start + le_u16(cd, pos + 30)? as usize;
+        let end = extra_end + le_u16(cd, pos + 32)? as usize;
+        if end > cd.len() {
+            return Err(invalid_archive("zip central directory is truncated"));
+        }
+
+        let mut entry = ZipEntry {
+            name: String::from_utf8_lossy(&cd[name_start..extra_start]).into_owned(),
+            flags: le_u16(cd, pos + 8)?,
+            method: le_u16(cd, pos + 10)?,
+            last_modified: parse_dos_datetime(le_u16(cd, pos + 14)?, le_u16(cd, pos + 12)?),
+            compressed_size: le_u32(cd, pos + 20)? as u64,
+            size: le_u32(cd, pos + 24)? as u64,
+            header_offset: le_u32(cd, pos + 42)? as u64,
+        };
+        parse_zip64_extra(&cd[extra_start..extra_end], &mut entry)?;
+        entries.push(entry);
+
+        pos = end;
+    }
+    Ok(entries)
+}
+
+/// Fill the fields overflowed in central directory from zip64 extra field.
+fn parse_zip64_extra(mut extra: &[u8], entry: &mut ZipEntry) -> Result<()> {
+    while extra.len() >= 4 {
+        let id = le_u16(extra, 0)?;
+        let size = le_u16(extra, 2)? as usize;
+        if extra.len() < 4 + size {
+            return Err(invalid_archive("zip extra field is truncated"));
+        }
+
+        if id == ZIP64_EXTRA_FIELD_ID {
+            // Only the overflowed fields are present, in the fixed order.
+            let mut field = &extra[4..4 + size];
+            for v in [
+                &mut entry.size,
+                &mut entry.compressed_size,
+                &mut entry.header_offset,
+            ] {
+                if *v != u32::MAX as u64 {
+                    continue;
+                }
+                if field.len() < 8 {
+                    return Err(invalid_archive("zip64 extra field is truncated"));
+                }
+                *v = le_u64(field, 0)?;
+                field = &field[8..];
+            }
+            return Ok(());
+        }
+        extra = &extra[4 + size..];
+    }
+    Ok(())
+}
+
+fn parse_dos_datetime(date: u16, time: u16) -> Option<DateTime<Utc>> {
+    NaiveDate::from_ymd_opt(
+        1980 + (date >> 9) as i32,
+        ((date >> 5) & 0xf) as u32,
+        (date & 0x1f) as u32,
+    )?
+    .and_hms_opt(
+        (time >> 11) as u32,
+        ((time >> 5) & 0x3f) as u32,
+        ((time & 0x1f) * 2) as u32,
+    )
+    .map(|v| v.and_utc())
+}
+
+/// Inflaters parked by readers once their requested range has been read,
+/// keyed by the member path and the id of the reader.
+type ParkedInflaters<R> = Arc<Mutex<HashMap<(String, u64), ParkedInflater<R>>>>;
+
+struct ParkedInflater<R> {
+    /// The reader that parked the inflater, used to drop the inflater once
+    /// the reader has gone.
+    reader: Weak<OnceCell<Metadata>>,
+    inflater: Inflater<R>,
+}
+
+/// The handle used by [`InflateReader`] to park its inflater.
+struct ParkHandle<R> {
+    parked: ParkedInflaters<R>,
+    key: (String, u64),
+    reader: Weak<OnceCell<Metadata>>,
+}
+
+/// The state of inflating a deflated member.
+struct Inflater<R> {
+    inner: R,
+    state: Box<InflateState>,
+    /// The compressed data that has not been consumed.
+    input: Buffer,
+    /// The decompressed data that has not been consumed.
+    output: Bytes,
+    /// The offset of `output` in the decompressed member.
+    position: u64,
+    /// The size of the decompressed member.
+    size: u64,
+    finished: bool,
+}
+
+impl<R: oio::Read> Inflater<R> {
+    fn new(inner: R, size: u64) -> Self {
+        Self {
+            inner,
+            state: InflateState::new_boxed(DataFormat::Raw),
+            input: Buffer::new(),
+            output: Bytes::new(),
+            position: 0,
+            size,
+            finished: false,
+        }
+    }
+
+    /// Consume `n` bytes of decompressed data.
+    fn consume(&mut self, n: usize) -> Bytes {
+        self.position += n as u64;
+        self.output.split_to(n)
+    }
+
+    /// Inflate the next piece of decompressed data into `output`.
+    async fn fill(&mut self) -> Result<()> {
+        loop {
+            if self.finished {
+                return Err(invalid_archive("archive member is shorter than expected"));
+            }
+
+            let eof = if self.input.is_empty() {
+                self.input = self.inner.read().await?;
+                self.input.is_empty()
+            } else {
+                false
+            };
+
+            let mut output = vec![0; INFLATE_BUFFER_SIZE];
+            let res = inflate(
+                &mut self.state,
+                self.input.chunk(),
+                &mut output,
+                MZFlush::None,
+            );
+            self.input.advance(res.bytes_consumed);
+            match res.status {
+                Ok(MZStatus::StreamEnd) => self.finished = true,
+                Ok(_) => {}
+                Err(MZError::Buf) if eof => {
+                    return Err(invalid_archive("archive member is truncated"));
+                }
+                Err(MZError::Buf) => {}
+                Err(err) => {
+                    return Err(invalid_archive("archive member is corrupted")
+                        .with_context("error", format!("{err:?}")));
+                }
+            }
+
+            if res.bytes_written > 0 {
+                output.truncate(res.bytes_written);
+                self.output = Bytes::from(output);
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Inflate a deflated member and return the requested range of it.
+///
+/// The inflater will be parked once the requested range has been read, so
+/// that the next chunk read by the same reader can resume from it.
+pub struct InflateReader<R> {
+    /// The inflater, which will be taken once parked.
+    inflater: Option<Inflater<R>>,
+    /// The decompressed bytes to skip before the requested range.
+    skip: u64,
+    /// The decompressed bytes left in the requested range.
+    remaining: u64,
+    /// The decompressed data left by `read_into`.
+    pending: Buffer,
+    park: Option<ParkHandle<R>>,
+}
+
+impl<R> InflateReader<R> {
+    fn new(inflater: Inflater<R>, offset: u64, size: u64, park: Option<ParkHandle<R>>) -> Self {
+        Self {
+            skip: offset - inflater.position,
+            inflater: Some(inflater),
+            remaining: size,
+            pending: Buffer::new(),
+            park,
+        }
+    }
+
+    /// Park the inflater if there is still data left in the member.
+    fn park(&mut self) {
+        let (Some(handle), Some(inflater)) = (self.park.take(), self.inflater.take()) else {
+            return;
+        };
+        if inflater.position >= inflater.size {
+            return;
+        }
+
+        let mut parked = handle.parked.lock().unwrap();
+        // Drop inflaters parked by readers that have gone.
+        parked.retain(|_, p| p.reader.strong_count() > 0);
+        parked.insert(
+            handle.key,
+            ParkedInflater {
+                reader: handle.reader,
+                inflater,
+            },
+        );
+    }
+}
+
+impl<R: oio::Read> oio::Read for InflateReader<R> {
+    async fn read(&mut self) -> Result<Buffer> {
+        if !self.pending.is_empty() {
+            return Ok(mem::take(&mut self.pending));
+        }
+
+        loop {
+            if self.remaining == 0 {
+                return Ok(Buffer::new());
+            }
+            let Some(inflater) = self.inflater.as_mut() else {
+                return Err(Error::new(
+                    ErrorKind::Unexpected,
+                    "inflater has been parked",
+                ));
+            };
+            if inflater.output.is_empty() {
+                inflater.fill().await?;
+            }
+
+            let skipped = self.skip.min(inflater.output.len() as u64) as usize;
+            inflater.consume(skipped);
+            self.skip -= skipped as u64;
+            let n = self.remaining.min(inflater.output.len() as u64) as usize;
+            if n == 0 {
+                continue;
+            }
+
+            let bs = inflater.consume(n);
+            self.remaining -= n as u64;
+            if self.remaining == 0 {
+                self.park();
+            }
+            return Ok(Buffer::from(bs));
+        }
+    }
+
+    async fn read_into(&mut self, dst: &mut [u8]) -> Result<usize> {
+        if dst.is_empty() {
+            return Ok(0);
+        }
+
+        // Keep the decompressed data that `dst` can't hold for the next call.
+        if self.pending.is_empty() {
+            self.pending = self.read().await?;
+        }
+        let n = self.pending.len().min(dst.len());
+        self.pending.copy_to_slice(&mut dst[..n]);
+        Ok(n)
+    }
+}
+
+/// List members of archive from the central directory.
+pub struct ArchiveLister {
+    entries: std::vec::IntoIter<oio::Entry>,
+}
+
+impl ArchiveLister {
+    fn new(prefix: &str, member: &str, entries: Vec<ZipEntry>, recursive: bool) -> Self {
+        let dir = &member[..member.rfind('/').map_or(0, |i| i + 1)];
+
+        let mut found = BTreeMap::new();
+        for entry in entries.iter().filter(|e| e.name.starts_with(member)) {
+            if entry.name == dir {
+                continue;
+            }
+            let rest = &entry.name[dir.len()..];
+            // Directories could be implied by members without an entry.
+            let mut start = 0;
+            while let Some(i) = rest[start..].find('/') {
+                let end = start + i + 1;
+                if end == rest.len() {
+                    break;
+                }
+                found
+                    .entry(format!("{dir}{}", &rest[..end]))
+                    .or_insert_with(|| Metadata::new(EntryMode::DIR));
+                if !recursive {
+                    break;
+                }
+                start = end;
+            }
+            if recursive || !rest.trim_end_matches('/').contains('/') {
+                found.insert(entry.name.clone(), entry.metadata());
+            }
+        }
+
+        let mut listed = Vec::with_capacity(found.len() + 1);
+        if member == dir {
+            listed.push(oio::Entry::new(
+                &format!("{prefix}{dir}"),
+                Metadata::new(EntryMode::DIR),
+            ));
+        }
+        listed.extend(
+            found
+                .into_iter()
+                .map(|(name, meta)| oio::Entry::new(&format!("{prefix}{name}"), meta)),
+        );
+        Self {
+            entries: listed.into_iter(),
+        }
+    }
+}
+
+impl oio::List for ArchiveLister {
+    async fn next(&mut self) -> Result<Option<oio::Entry>> {
+        Ok(self.entries.next())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use miniz_oxide::deflate::compress_to_vec;
+
+    use super::*;
+    use crate::services;
+
+    /// Build a zip archive with given members and method.
+    fn build_zip(members: &[(&str, &[u8], u16)]) -> Vec<u8> {
+        let mut data = Vec::new();
+        let mut cd = Vec::new();
+        for (name, content, method) in members {
+            let compressed = match *method {
+                METHOD_DEFLATED => compress_to_vec(content, 6),
+                _ => content.to_vec(),
+            };
+            let offset = data.len() as u32;
+            // 2024-01-02 03:04:06
+            let (time, date) = (3 << 11 | 4 << 5 | 3, 44 << 9 | 1 << 5 | 2);
+
+            data.extend_from_slice(&LOCAL_HEADER_SIGNATURE.to_le_bytes());
+            data.extend_from_slice(&[20, 0, 0, 0]);
+            data.extend_from_slice(&method.to_le_bytes());
+            data.extend_from_slice(&[time as u16, date as u16].map(u16::to_le_bytes).concat());
+            data.extend_from_slice(&[0; 4]);
+            data.extend_from_slice(&(compressed.len() as u32).to_le_bytes());
+            data.extend_from_slice(&(content.len() as u32).to_le_bytes());
+            data.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            // Local extra field is different from central directory.
+            data.extend_from_slice(&4u16.to_le_bytes());
+            data.extend_from_slice(name.as_bytes());
+            data.extend_from_slice(&[0xfe, 0xca, 0, 0]);
+            data.extend_from_slice(&compressed);
+
+            cd.extend_from_slice(&CENTRAL_HEADER_SIGNATURE.to_le_bytes());
+            cd.extend_from_slice(&[20, 0, 20, 0, 0, 0]);
+            cd.extend_from_slice(&method.to_le_bytes());
+            cd.extend_from_slice(&[time as u16, date as u16].map(u16::to_le_bytes).concat());
+            cd.extend_from_slice(&[0; 4]);
+            cd.extend_from_slice(&(compressed.len() as u32).to_le_bytes());
+            cd.extend_from_slice(&(content.len() as u32).to_le_bytes());
+            cd.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            cd.extend_from_slice(&[0; 12]);
+            cd.extend_from_slice(&offset.to_le_bytes());
+            cd.extend_from_slice(name.as_bytes());
+        }
+
+        let comment = b"archive comment";
+        let (cd_offset, cd_size) = (data.len() as u32, cd.len() as u32);
+        data.extend_from_slice(&cd);
+        data.extend_from_slice(&EOCD_SIGNATURE.to_le_bytes());
+        data.extend_from_slice(&[0; 4]);
+        data.extend_from_slice(&(members.len() as u16).to_le_bytes());
+        data.extend_from_slice(&(members.len() as u16).to_le_bytes());
+        data.extend_from_slice(&cd_size.to_le_bytes());
+        data.extend_from_slice(&cd_offset.to_le_bytes());
+        data.extend_from_slice(&(comment.len() as u16).to_le_bytes());
+        data.extend_from_slice(comment);
+        data
+    }
+
+    #[tokio::test]
+    async fn test_archive() -> Result<()> {
+        let large = "Hello, World!".repeat(20000);
+        let archive = build_zip(&[
+            ("hello.txt", b"Hello, World!", METHOD_STORED),
+            ("dir/", b"", METHOD_STORED),
+            ("dir/large.txt", large.as_bytes(), METHOD_DEFLATED),
+            ("implied/empty.txt", b"", METHOD_DEFLATED),
+        ]);
+
+        let op = Operator::new(services::Memory::default())?
+            .layer(ArchiveLayer::new())
+            .finish();
+        op.write("data.zip", archive).await?;
+
+        assert_eq!(
+            op.read("data.zip!/hello.txt").await?.to_vec(),
+            b"Hello, World!"
+        );
+        assert_eq!(
+            op.read_with("data.zip!/hello.txt")
+                .range(7..12)
+                .await?
+                .to_vec(),
+            b"World"
+        );
+        assert_eq!(
+            op.read("data.zip!/dir/large.txt").await?.to_vec(),
+            large.as_bytes()
+        );
+        assert_eq!(
+            op.read_with("data.zip!/dir/large.txt")
+                .range(100000..100013)
+                .await?
+                .to_vec(),
+            &large.as_bytes()[100000..100013]
+        );
+        assert!(op.read("data.zip!/implied/empty.txt").await?.is_empty());
+
+        let meta = op.stat("data.zip!/dir/large.txt").await?;
+        assert_eq!(meta.content_length(), large.len() as u64);
+        assert_eq!(
+            meta.last_modified().unwrap().to_rfc3339(),
+            "2024-01-02T03:04:06+00:00"
+        );
+        assert!(op.stat("data.zip!/implied/").await?.is_dir());
+        assert_eq!(
+            op.stat("data.zip!/missing.txt").await.unwrap_err().kind(),
+            ErrorKind::NotFound
+        );
+
+        let paths = |entries: Vec<Entry>| {
+            entries
+                .into_iter()
+                .map(|e| e.path().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            paths(op.list("data.zip!/").await?),
+            [
+                "data.zip!/",
+                "data.zip!/dir/",
+                "data.zip!/hello.txt",
+                "data.zip!/implied/"
+            ]
+        );
+        assert_eq!(
+            paths(op.list_with("data.zip!/").recursive(true).await?),
+            [
+                "data.zip!/",
+                "data.zip!/dir/",
+                "data.zip!/dir/large.txt",
+                "data.zip!/hello.txt",
+                "data.zip!/implied/",
+                "data.zip!/implied/empty.txt"
+            ]
+        );
+
+        assert_eq!(
+            op.write("data.zip!/new.txt", "new")
+                .await
+                .unwrap_err()
+                .kind(),
+            ErrorKind::Unsupported
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_archive_chunked_read() -> Result<()> {
+        let large = "Hello, World!".repeat(20000);
+        let archive = build_zip(&[("large.txt", large.as_bytes(), METHOD_DEFLATED)]);
+
+        let op = Operator::new(services::Memory::default())?.finish();
+        op.write("data.zip", archive).await?;
+        let acc = ArchiveLayer::new().layer(op.clone().into_inner());
+        let path = "data.zip!/large.txt";
+        let read = |args: OpRead| async {
+            let (_, mut r) = LayeredAccess::read(&acc, path, args).await?;
+            oio::Read::read_all(&mut r).await
+        };
+
+        // The inflater is parked once the range has been read.
+        let args = OpRead::new().with_shared_stat();
+        let key = (path.to_string(), args.reader_id().unwrap());
+        let bs = read(args.clone().with_range(BytesRange::new(0, Some(10)))).await?;
+        assert_eq!(bs.to_vec(), &large.as_bytes()[..10]);
+        assert!(acc.parked.lock().unwrap().contains_key(&key));
+
+        // Reads of other readers can't resume it.
+        let bs = read(
+            OpRead::new()
+                .with_shared_stat()
+                .with_range(BytesRange::new(20, Some(10))),
+        )
+        .await?;
+        assert_eq!(bs.to_vec(), &large.as_bytes()[20..30]);
+        assert!(acc.parked.lock().unwrap().contains_key(&key));
+
+        // The following read of the same reader resumes it.
+        let resumed = args.clone().with_range(BytesRange::new(100000, Some(10)));
+        let (_, mut r) = LayeredAccess::read(&acc, path, resumed).await?;
+        assert!(!acc.parked.lock().unwrap().contains_key(&key));
+        let bs = oio::Read::read_all(&mut r).await?;
+        assert_eq!(bs.to_vec(), &large.as_bytes()[100000..100010]);
+
+        // Reads before the parked position will inflate from the start again.
+        let bs = read(args.clone().with_range(BytesRange::new(0, Some(10)))).await?;
+        assert_eq!(bs.to_vec(), &large.as_bytes()[..10]);
+
+        let op = op.layer(ArchiveLayer::new());
+        let bs = op.reader_with(path).chunk(4096).await?.read(..).await?;
+        assert_eq!(bs.to_vec(), large.as_bytes());
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_truncated_archive() {
+        let archive = build_zip(&[("hello.txt", b"Hello, World!", METHOD_STORED)]);
+        let err = parse_eocd(&archive[..archive.len() - 1]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+    }
+
+    /// Build the tail of a zip64 archive with given zip64 record fields.
+    fn build_zip64_tail(record_size: u64, cd_size: u64, cd_offset: u64) -> Vec<u8> {
+        let mut tail = Vec::new();
+        tail.extend_from_slice(&ZIP64_EOCD_SIGNATURE.to_le_bytes());
+        tail.extend_from_slice(&record_size.to_le_bytes());
+        tail.extend_from_slice(&[0; 28]);
+        tail.extend_from_slice(&cd_size.to_le_bytes());
+        tail.extend_from_slice(&cd_offset.to_le_bytes());
+
+        tail.extend_from_slice(&ZIP64_EOCD_LOCATOR_SIGNATURE.to_le_bytes());
+        tail.extend_from_slice(&[0; 4]);
+        tail.extend_from_slice(&0u64.to_le_bytes());
+        tail.extend_from_slice(&1u32.to_le_bytes());
+
+        tail.extend_from_slice(&EOCD_SIGNATURE.to_le_bytes());
+        tail.extend_from_slice(&[0xff; 16]);
+        tail.extend_from_slice(&0u16.to_le_bytes());
+        tail
+    }
+
+    #[tokio::test]
+    async fn test_parse_overflowed_archive() -> Result<()> {
+        let err = parse_eocd(&build_zip64_tail(u64::MAX, 0, 0)).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+
+        let op = Operator::new(services::Memory::default())?
+            .layer(ArchiveLayer::new())
+            .finish();
+        op.write("data.zip", build_zip64_tail(44, 1, u64::MAX))
+            .await?;
+        let err = op.stat("data.zip!/hello.txt").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_truncated_record() {
+        assert!(le_u16(&[1], 0).is_err());
+        assert!(le_u32(&[1, 2, 3, 4], 1).is_err());
+        assert!(le_u64(&[0; 8], usize::MAX).is_err());
+        assert_eq!(le_u32(&[1, 0, 0, 0], 0).unwrap(), 1);
+    }
+}
@@ -136,6 +136,11 @@ impl<R: oio::Read> oio::Read for AsyncBacktraceWrapper<R> {
     async fn read(&mut self) -> Result<Buffer> {
         self.inner.read().await
     }
+
+    #[async_backtrace::framed]
+    async fn read_into(&mut self, dst: &mut [u8]) -> Result<usize> {
+        self.inner.read_into(dst).await
+    }
 }
 
 impl<R: oio::Write> oio::Write for AsyncBacktraceWrapper<R> {
@@ -144,6 +149,11 @@ impl<R: oio::Write> oio::Write for AsyncBacktraceWrapper<R> {
         self.inner.write(bs).await
     }
 
+    #[async_backtrace::framed]
+    async fn flush(&mut self) -> Result<()> {
+        self.inner.flush().await
+    }
+
     #[async_backtrace::framed]
     async fn close(&mut self) -> Result<Metadata> {
         self.inner.close().await
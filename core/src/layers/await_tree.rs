@@ -157,6 +157,13 @@ impl<R: oio::Read> oio::Read for AwaitTreeWrapper<R> {
             .instrument_await(format!("opendal::{}", Operation::Read))
             .await
     }
+
+    async fn read_into(&mut self, dst: &mut [u8]) -> Result<usize> {
+        self.inner
+            .read_into(dst)
+            .instrument_await(format!("opendal::{}", Operation::Read))
+            .await
+    }
 }
 
 impl<R: oio::Write> oio::Write for AwaitTreeWrapper<R> {
@@ -166,6 +173,12 @@ impl<R: oio::Write> oio::Write for AwaitTreeWrapper<R> {
             .instrument_await(format!("opendal::{}", Operation::Write.into_static()))
     }
 
+    fn flush(&mut self) -> impl Future<Output = Result<()>> + MaybeSend {
+        self.inner
+            .flush()
+            .instrument_await(format!("opendal::{}", Operation::Write.into_static()))
+    }
+
     fn abort(&mut self) -> impl Future<Output = Result<()>> + MaybeSend {
         self.inner
             .abort()
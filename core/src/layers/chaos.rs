@@ -167,4 +167,12 @@ impl<R: oio::Read> oio::Read for ChaosReader<R> {
             Err(Self::unexpected_eof())
         }
     }
+
+    async fn read_into(&mut self, dst: &mut [u8]) -> Result<usize> {
+        if self.i_feel_lucky() {
+            self.inner.read_into(dst).await
+        } else {
+            Err(Self::unexpected_eof())
+        }
+    }
 }
@@ -0,0 +1,409 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Write;
+
+use base64::engine::general_purpose;
+use base64::Engine;
+use md5::Digest;
+
+use crate::raw::*;
+use crate::*;
+
+/// The digest algorithm used by [`ChecksumLayer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ChecksumAlgorithm {
+    /// CRC32C, the expected value is read from [`Metadata::content_crc32c`],
+    /// or the `opendal-checksum-crc32c` user metadata if the service doesn't
+    /// return one.
+    Crc32c,
+    /// MD5, the expected value is read from `Content-MD5`.
+    Md5,
+    /// SHA256, the expected value is read from [`Metadata::content_sha256`],
+    /// or the `opendal-checksum-sha256` user metadata if the service doesn't
+    /// return one.
+    Sha256,
+}
+
+impl ChecksumAlgorithm {
+    /// The user metadata key used to store the expected value for services
+    /// that don't return the checksum natively.
+    pub fn user_metadata_key(&self) -> &'static str {
+        match self {
+            ChecksumAlgorithm::Crc32c => "opendal-checksum-crc32c",
+            ChecksumAlgorithm::Md5 => "opendal-checksum-md5",
+            ChecksumAlgorithm::Sha256 => "opendal-checksum-sha256",
+        }
+    }
+
+    fn expected(&self, meta: &Metadata) -> Option<String> {
+        let native = match self {
+            ChecksumAlgorithm::Crc32c => meta.content_crc32c(),
+            ChecksumAlgorithm::Md5 => meta.content_md5(),
+            ChecksumAlgorithm::Sha256 => meta.content_sha256(),
+        };
+        native
+            .or_else(|| {
+                meta.user_metadata()?
+                    .get(self.user_metadata_key())
+                    .map(|v| v.as_str())
+            })
+            .map(|v| v.to_string())
+    }
+
+    fn hasher(&self) -> Hasher {
+        match self {
+            ChecksumAlgorithm::Crc32c => Hasher::Crc32c(0),
+            ChecksumAlgorithm::Md5 => Hasher::Md5(md5::Md5::new()),
+            ChecksumAlgorithm::Sha256 => Hasher::Sha256(sha2::Sha256::new()),
+        }
+    }
+}
+
+/// Add checksum verification for read operations.
+///
+/// # Notes
+///
+/// `ChecksumLayer` will take the expected digest from the metadata returned by read, or
+/// fetch it via `stat` if the read doesn't return one. The digest is accumulated over the
+/// returned buffers and compared at EOF. Reads will fail with [`ErrorKind::Unexpected`]
+/// if the digest mismatches.
+///
+/// The checksum returned by service natively will be preferred, for example, `Content-MD5`,
+/// `x-amz-checksum-crc32c` of S3 and `crc32c` of GCS. For services that don't return it,
+/// the expected digest will be read from the user metadata named by
+/// [`ChecksumAlgorithm::user_metadata_key`], like `opendal-checksum-sha256`.
+///
+/// The expected digest can be either hex or base64 encoded. Verification will be
+/// skipped if:
+///
+/// - the service doesn't return the expected digest.
+/// - only part of the file is requested, for example, range reads or chunked reads.
+/// - users skip it via `op.read_with(path).skip_checksum(true)`.
+/// - the content is decompressed via `op.read_with(path).decompress(true)`.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use opendal::layers::ChecksumAlgorithm;
+/// # use opendal::layers::ChecksumLayer;
+/// # use opendal::services;
+/// # use opendal::Operator;
+/// # use opendal::Result;
+///
+/// # fn main() -> Result<()> {
+/// let _ = Operator::new(services::Memory::default())?
+///     .layer(ChecksumLayer::new(ChecksumAlgorithm::Md5))
+///     .finish();
+/// Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ChecksumLayer {
+    algorithm: ChecksumAlgorithm,
+}
+
+impl ChecksumLayer {
+    /// Create a new `ChecksumLayer` with given algorithm.
+    pub fn new(algorithm: ChecksumAlgorithm) -> Self {
+        Self { algorithm }
+    }
+}
+
+impl<A: Access> Layer<A> for ChecksumLayer {
+    type LayeredAccess = ChecksumAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccess {
+        ChecksumAccessor {
+            inner,
+            algorithm: self.algorithm,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ChecksumAccessor<A: Access> {
+    inner: A,
+    algorithm: ChecksumAlgorithm,
+}
+
+impl<A: Access> LayeredAccess for ChecksumAccessor<A> {
+    type Inner = A;
+    type Reader = ChecksumReader<A::Reader>;
+    type Writer = A::Writer;
+    type Lister = A::Lister;
+    type Deleter = A::Deleter;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        if args.skip_checksum()
+            || args.decompress()
+            || !args.range().is_full()
+            || args.suffix_range().is_some()
+            || !self.inner.info().full_capability().stat
+        {
+            let (rp, r) = self.inner.read(path, args).await?;
+            return Ok((rp, ChecksumReader::new(r, None)));
+        }
+
+        let version = args.version().map(|v| v.to_string());
+        let (rp, r) = self.inner.read(path, args).await?;
+
+        // Only stat while the read doesn't return the expected digest.
+        let (expected, size) = match rp.metadata().and_then(|meta| {
+            let expected = self.algorithm.expected(meta)?;
+            Some((expected, meta.content_length()))
+        }) {
+            Some(v) => v,
+            None => {
+                let mut op = OpStat::new();
+                if let Some(version) = version {
+                    op = op.with_version(&version);
+                }
+                let meta = self.inner.stat(path, op).await?.into_metadata();
+                match self.algorithm.expected(&meta) {
+                    Some(expected) => (expected, meta.content_length()),
+                    None => return Ok((rp, ChecksumReader::new(r, None))),
+                }
+            }
+        };
+        let checker = Checker {
+            algorithm: self.algorithm,
+            hasher: self.algorithm.hasher(),
+            expected,
+            size,
+        };
+        Ok((rp, ChecksumReader::new(r, Some(checker))))
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.inner.write(path, args).await
+    }
+
+    async fn delete(&self) -> Result<(RpDelete, Self::Deleter)> {
+        self.inner.delete().await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        self.inner.list(path, args).await
+    }
+}
+
+enum Hasher {
+    Crc32c(u32),
+    Md5(md5::Md5),
+    Sha256(sha2::Sha256),
+}
+
+impl Hasher {
+    fn update(&mut self, bs: &[u8]) {
+        match self {
+            Hasher::Crc32c(crc) => *crc = crc32c::crc32c_append(*crc, bs),
+            Hasher::Md5(h) => h.update(bs),
+            Hasher::Sha256(h) => h.update(bs),
+        }
+    }
+
+    fn finalize(self) -> Vec<u8> {
+        match self {
+            Hasher::Crc32c(crc) => crc.to_be_bytes().to_vec(),
+            Hasher::Md5(h) => h.finalize().to_vec(),
+            Hasher::Sha256(h) => h.finalize().to_vec(),
+        }
+    }
+}
+
+struct Checker {
+    algorithm: ChecksumAlgorithm,
+    hasher: Hasher,
+    expected: String,
+    size: u64,
+}
+
+pub struct ChecksumReader<R> {
+    inner: R,
+    checker: Option<Checker>,
+    read: u64,
+}
+
+impl<R> ChecksumReader<R> {
+    fn new(inner: R, checker: Option<Checker>) -> Self {
+        Self {
+            inner,
+            checker,
+            read: 0,
+        }
+    }
+
+    fn check(&mut self) -> Result<()> {
+        let Some(checker) = self.checker.take() else {
+            return Ok(());
+        };
+        // Size mismatch will be reported by `CompleteLayer`, we only check
+        // the digest if all content has been read.
+        if checker.size != self.read {
+            return Ok(());
+        }
+
+        let digest = checker.hasher.finalize();
+        let expected = checker.expected.trim_matches('"');
+        let hex = digest.iter().fold(String::new(), |mut s, b| {
+            let _ = write!(s, "{b:02x}");
+            s
+        });
+        if expected.eq_ignore_ascii_case(&hex)
+            || expected == general_purpose::STANDARD.encode(&digest)
+        {
+            return Ok(());
+        }
+
+        Err(
+            Error::new(ErrorKind::Unexpected, "reader got mismatched checksum")
+                .with_context("algorithm", format!("{:?}", checker.algorithm))
+                .with_context("expect", expected)
+                .with_context("actual", hex),
+        )
+    }
+}
+
+impl<R: oio::Read> oio::Read for ChecksumReader<R> {
+    async fn read(&mut self) -> Result<Buffer> {
+        let buf = self.inner.read().await?;
+
+        if buf.is_empty() {
+            self.check()?;
+        } else {
+            self.read += buf.len() as u64;
+            if let Some(checker) = self.checker.as_mut() {
+                buf.clone().for_each(|bs| checker.hasher.update(&bs));
+            }
+        }
+
+        Ok(buf)
+    }
+
+    async fn read_into(&mut self, dst: &mut [u8]) -> Result<usize> {
+        let n = self.inner.read_into(dst).await?;
+
+        if n == 0 && !dst.is_empty() {
+            self.check()?;
+        } else {
+            self.read += n as u64;
+            if let Some(checker) = self.checker.as_mut() {
+                checker.hasher.update(&dst[..n]);
+            }
+        }
+
+        Ok(n)
+    }
+}
+
+#[cfg(all(test, feature = "tests"))]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::raw::tests::MockAccess;
+
+    const CONTENT: &[u8] = b"Hello, World!";
+
+    fn hex_of(bs: &[u8]) -> String {
+        bs.iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    /// Build a mock service storing `CONTENT` with given md5.
+    fn new_mock(md5: String) -> MockAccess {
+        let crc32c = crc32c::crc32c(CONTENT).to_be_bytes();
+        let sha256 = hex_of(&sha2::Sha256::digest(CONTENT));
+        let mock = MockAccess::new();
+        mock.insert("test", CONTENT);
+        mock.inject_stat(
+            "test",
+            Metadata::new(EntryMode::FILE)
+                .with_content_length(CONTENT.len() as u64)
+                .with_content_md5(md5)
+                .with_content_crc32c(general_purpose::STANDARD.encode(crc32c))
+                .with_user_metadata(HashMap::from([(
+                    "opendal-checksum-sha256".to_string(),
+                    sha256,
+                )])),
+        );
+        mock
+    }
+
+    fn new_operator(mock: MockAccess, algorithm: ChecksumAlgorithm) -> Operator {
+        OperatorBuilder::new(mock)
+            .finish()
+            .layer(ChecksumLayer::new(algorithm))
+    }
+
+    #[tokio::test]
+    async fn test_checksum_match() {
+        let md5 = general_purpose::STANDARD.encode(md5::Md5::digest(CONTENT));
+        for algorithm in [
+            ChecksumAlgorithm::Md5,
+            ChecksumAlgorithm::Crc32c,
+            ChecksumAlgorithm::Sha256,
+        ] {
+            let op = new_operator(new_mock(md5.clone()), algorithm);
+            let bs = op.read("test").await.expect("read must succeed");
+            assert_eq!(bs.to_vec(), CONTENT);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_checksum_mismatch() {
+        let op = new_operator(new_mock(hex_of(&[0; 16])), ChecksumAlgorithm::Md5);
+        let err = op.read("test").await.expect_err("read must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+
+        // Verification should be skipped for range reads and skip_checksum.
+        let bs = op
+            .read_with("test")
+            .range(0..5)
+            .await
+            .expect("read must succeed");
+        assert_eq!(bs.to_vec(), &CONTENT[..5]);
+        let bs = op
+            .read_with("test")
+            .skip_checksum(true)
+            .await
+            .expect("read must succeed");
+        assert_eq!(bs.to_vec(), CONTENT);
+    }
+
+    #[tokio::test]
+    async fn test_checksum_from_read_metadata() {
+        let mock = new_mock(hex_of(&[0; 16])).with_read_metadata();
+        let op = new_operator(mock.clone(), ChecksumAlgorithm::Crc32c);
+        let bs = op.read("test").await.expect("read must succeed");
+        assert_eq!(bs.to_vec(), CONTENT);
+        let err = new_operator(mock.clone(), ChecksumAlgorithm::Md5)
+            .read("test")
+            .await
+            .expect_err("read must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+
+        // The read returns the expected digest, no stat is needed.
+        assert_eq!(mock.calls(Operation::Stat, "test"), 0);
+    }
+}
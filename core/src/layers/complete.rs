@@ -16,11 +16,28 @@
 // under the License.
 
 use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
 use std::fmt::Debug;
 use std::fmt::Formatter;
+use std::future::Future;
+use std::mem;
 use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
+use bytes::Buf;
+use chrono::DateTime;
+use chrono::Utc;
+use md5::Digest;
 
 use crate::raw::oio::FlatLister;
+use crate::raw::oio::GlobLister;
+use crate::raw::oio::MetadataLister;
 use crate::raw::oio::PrefixLister;
 use crate::raw::*;
 use crate::*;
@@ -30,9 +47,9 @@ use crate::*;
 ///
 /// # Notes
 ///
-/// CompleteLayer is not a public accessible layer that can be used by
-/// external users. OpenDAL will make sure every accessor will apply this
-/// layer once and only once.
+/// OpenDAL will make sure every accessor will apply this layer with default
+/// options. Users only need to apply it again to customize the completion
+/// behavior, like changing the [`DirMarker`].
 ///
 /// # Internal
 ///
@@ -41,6 +58,11 @@ use crate::*;
 /// ## Stat Completion
 ///
 /// Not all services support stat dir natively, but we can simulate it via list.
+/// Non-recursive list will be preferred since it's usually answered faster.
+///
+/// Dirs not found will be remembered for a second, so that repeated stat of the
+/// same missing dir will not hammer the service. Creating dirs or writing files
+/// through the same operator will forget them immediately.
 ///
 /// ## List Completion
 ///
@@ -52,7 +74,143 @@ use crate::*;
 ///
 /// - If support `list_with_recursive`, return directly.
 /// - if not, wrap with [`FlatLister`].
-pub struct CompleteLayer;
+///
+/// The order of entries is unspecified unless `sorted` is requested:
+///
+/// - Recursive lists will be returned directly if service supports `list_with_sorted`,
+///   otherwise they will be walked by a sorted [`FlatLister`].
+/// - Non-recursive lists will always be buffered and sorted in memory, since
+///   services may return the dirs of a page before its files.
+///
+/// Entries will be stat concurrently if `metadata` is requested but the service
+/// doesn't support `list_has_metadata`.
+///
+/// `modified_after` and `modified_before` will be forwarded to services that
+/// support `list_with_modified_after` and `list_with_modified_before`, otherwise
+/// files will be filtered by their `last_modified`, which will be stat the same
+/// way as `metadata` if needed.
+///
+/// ## Create Dir Completion
+///
+/// Services that can't create dir natively but support `write_can_empty` and
+/// `list` will have `create_dir` simulated by writing an empty object. The
+/// object written is controlled by [`DirMarker`], which could be changed via
+/// [`CompleteLayer::with_dir_marker`].
+///
+/// # Examples
+///
+/// ```no_run
+/// # use opendal::layers::CompleteLayer;
+/// # use opendal::layers::DirMarker;
+/// # use opendal::services;
+/// # use opendal::Operator;
+/// # use opendal::Result;
+///
+/// # fn main() -> Result<()> {
+/// let _ = Operator::new(services::Memory::default())?
+///     .layer(CompleteLayer::default().with_dir_marker(DirMarker::Suffix(".keep")))
+///     .finish();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct CompleteLayer {
+    dir_marker: DirMarker,
+    lenient_read: bool,
+    clamp_presign_expiry: bool,
+    clock: Arc<dyn Clock>,
+}
+
+impl Default for CompleteLayer {
+    fn default() -> Self {
+        Self {
+            dir_marker: DirMarker::default(),
+            lenient_read: false,
+            clamp_presign_expiry: false,
+            clock: Arc::new(SystemClock),
+        }
+    }
+}
+
+impl CompleteLayer {
+    /// Set the dir marker used while simulating `create_dir`.
+    ///
+    /// Default to [`DirMarker::Path`].
+    pub fn with_dir_marker(mut self, dir_marker: DirMarker) -> Self {
+        self.dir_marker = dir_marker;
+        self
+    }
+
+    /// Tolerate short reads instead of returning errors.
+    ///
+    /// Some services could return less data than requested, for example,
+    /// while the file is truncated concurrently. With lenient read enabled,
+    /// readers will return the partial data with a warning log instead.
+    ///
+    /// Reading more data than requested is always an error.
+    ///
+    /// Default to `false`.
+    pub fn with_lenient_read(mut self, lenient_read: bool) -> Self {
+        self.lenient_read = lenient_read;
+        self
+    }
+
+    /// Clamp the expiry of presign to the service's max instead of returning errors.
+    ///
+    /// Presign with an expiry beyond [`Capability::presign_max_expiry`] will
+    /// be rejected upfront by default, since the generated URL would fail at
+    /// use time.
+    ///
+    /// Default to `false`.
+    pub fn with_clamp_presign_expiry(mut self, clamp_presign_expiry: bool) -> Self {
+        self.clamp_presign_expiry = clamp_presign_expiry;
+        self
+    }
+
+    /// Set the clock used to expire the remembered missing dirs and to back
+    /// off the read-after-write retries.
+    ///
+    /// It's useful to verify them in tests without real sleeps, see [`Clock`]
+    /// for more details.
+    pub fn with_clock(mut self, clock: impl Clock) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+}
+
+/// DirMarker decides which object will be written to represent a directory
+/// while `create_dir` is simulated by [`CompleteLayer`].
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DirMarker {
+    /// Write an empty object at the directory path itself, like `dir/`.
+    #[default]
+    Path,
+    /// Write an empty object at the directory path with given suffix
+    /// appended, like `dir/.keep` for `DirMarker::Suffix(".keep")`.
+    ///
+    /// Marker objects will be recognized as directories by `stat` and
+    /// won't be returned as files by `list`.
+    Suffix(&'static str),
+}
+
+impl DirMarker {
+    /// Build the marker object path for given dir path.
+    fn marker_path(&self, path: &str) -> String {
+        match self {
+            DirMarker::Path => path.to_string(),
+            DirMarker::Suffix(suffix) => format!("{path}{suffix}"),
+        }
+    }
+
+    /// Return the dir path that given path represents if it's a marker object.
+    fn dir_path<'a>(&self, path: &'a str) -> Option<&'a str> {
+        match self {
+            DirMarker::Path => None,
+            DirMarker::Suffix(suffix) => path.strip_suffix(suffix).filter(|dir| dir.ends_with('/')),
+        }
+    }
+}
 
 impl<A: Access> Layer<A> for CompleteLayer {
     type LayeredAccess = CompleteAccessor<A>;
@@ -69,6 +227,68 @@ impl<A: Access> Layer<A> for CompleteLayer {
         CompleteAccessor {
             info,
             inner: Arc::new(inner),
+            dir_marker: self.dir_marker,
+            lenient_read: self.lenient_read,
+            clamp_presign_expiry: self.clamp_presign_expiry,
+            missing_dirs: Arc::new(MissingDirs::new(self.clock.clone())),
+            clock: self.clock.clone(),
+        }
+    }
+}
+
+/// The max number of missing dirs that will be remembered.
+const MISSING_DIRS_CAPACITY: usize = 1024;
+
+/// The duration that missing dirs will be remembered.
+const MISSING_DIRS_TTL: Duration = Duration::from_secs(1);
+
+/// MissingDirs remembers the dirs that are not found by simulated stat for a
+/// short while, so that callers stat the same missing dir in a loop will not
+/// send list requests repeatedly.
+struct MissingDirs {
+    clock: Arc<dyn Clock>,
+    /// The missing dirs and the instant they expire.
+    entries: Mutex<HashMap<String, Instant>>,
+}
+
+impl MissingDirs {
+    fn new(clock: Arc<dyn Clock>) -> Self {
+        Self {
+            clock,
+            entries: Mutex::default(),
+        }
+    }
+
+    fn contains(&self, path: &str) -> bool {
+        let now = self.clock.now();
+        let mut entries = self.entries.lock().unwrap();
+        match entries.get(path) {
+            Some(expire_at) if *expire_at > now => true,
+            Some(_) => {
+                entries.remove(path);
+                false
+            }
+            None => false,
+        }
+    }
+
+    fn insert(&self, path: &str) {
+        let now = self.clock.now();
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() >= MISSING_DIRS_CAPACITY {
+            entries.retain(|_, expire_at| *expire_at > now);
+        }
+        if entries.len() >= MISSING_DIRS_CAPACITY {
+            entries.clear();
+        }
+        entries.insert(path.to_string(), now + MISSING_DIRS_TTL);
+    }
+
+    /// Forget all parent dirs of given path since they could exist now.
+    fn invalidate(&self, path: &str) {
+        let mut entries = self.entries.lock().unwrap();
+        if !entries.is_empty() {
+            entries.retain(|dir, _| !path.starts_with(dir.as_str()));
         }
     }
 }
@@ -77,6 +297,11 @@ impl<A: Access> Layer<A> for CompleteLayer {
 pub struct CompleteAccessor<A: Access> {
     info: Arc<AccessorInfo>,
     inner: Arc<A>,
+    dir_marker: DirMarker,
+    lenient_read: bool,
+    clamp_presign_expiry: bool,
+    missing_dirs: Arc<MissingDirs>,
+    clock: Arc<dyn Clock>,
 }
 
 impl<A: Access> Debug for CompleteAccessor<A> {
@@ -86,14 +311,23 @@ impl<A: Access> Debug for CompleteAccessor<A> {
 }
 
 impl<A: Access> CompleteAccessor<A> {
+    /// Read with the read-after-write retry applied.
+    async fn complete_read(&self, path: &str, args: OpRead) -> Result<(RpRead, A::Reader)> {
+        let retry = args.read_after_write_retry();
+        retry_read_after_write(&*self.clock, retry, || self.inner.read(path, args.clone())).await
+    }
+
     async fn complete_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        self.missing_dirs.invalidate(path);
+
         let capability = self.info.native_capability();
         if capability.create_dir {
             return self.inner().create_dir(path, args).await;
         }
 
         if capability.write_can_empty && capability.list {
-            let (_, mut w) = self.inner.write(path, OpWrite::default()).await?;
+            let marker = self.dir_marker.marker_path(path);
+            let (_, mut w) = self.inner.write(&marker, OpWrite::default()).await?;
             oio::Write::close(&mut w).await?;
             return Ok(RpCreateDir::default());
         }
@@ -103,6 +337,10 @@ impl<A: Access> CompleteAccessor<A> {
 
     async fn complete_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
         let capability = self.info.native_capability();
+        // Accessor could be called without `Operator`, make sure the path is
+        // normalized the same way before deciding whether it's a dir.
+        let path = normalize_path(path);
+        let path = path.as_str();
 
         if path == "/" {
             return Ok(RpStat::new(Metadata::new(EntryMode::DIR)));
@@ -122,16 +360,36 @@ impl<A: Access> CompleteAccessor<A> {
             return Ok(RpStat::new(meta));
         }
 
+        // Check the marker object if dir is created with a custom marker.
+        if path.ends_with('/') && self.dir_marker != DirMarker::Path {
+            let marker = self.dir_marker.marker_path(path);
+            match self.inner.stat(&marker, args.clone()).await {
+                Ok(_) => return Ok(RpStat::new(Metadata::new(EntryMode::DIR))),
+                Err(err) if err.kind() == ErrorKind::NotFound => {}
+                Err(err) => return Err(err),
+            }
+        }
+
         // Otherwise, we can simulate stat dir via `list`.
-        if path.ends_with('/') && capability.list_with_recursive {
-            let (_, mut l) = self
-                .inner
-                .list(path, OpList::default().with_recursive(true).with_limit(1))
-                .await?;
+        if path.ends_with('/') && capability.list {
+            // Dirs could be created while retrying read after write.
+            let remembered = args.read_after_write_retry().is_none();
+            if remembered && self.missing_dirs.contains(path) {
+                return Err(Error::new(
+                    ErrorKind::NotFound,
+                    "the directory is not found",
+                ));
+            }
+
+            // Use non-recursive list since services answer it with delimiter,
+            // which is much faster than recursive list on huge prefixes.
+            let args = OpList::default().with_recursive(false).with_limit(1);
+            let (_, mut l) = self.inner.list(path, args).await?;
 
             return if oio::List::next(&mut l).await?.is_some() {
                 Ok(RpStat::new(Metadata::new(EntryMode::DIR)))
             } else {
+                self.missing_dirs.insert(path);
                 Err(Error::new(
                     ErrorKind::NotFound,
                     "the directory is not found",
@@ -144,6 +402,96 @@ impl<A: Access> CompleteAccessor<A> {
     }
 
     async fn complete_list(
+        &self,
+        path: &str,
+        mut args: OpList,
+    ) -> Result<(
+        RpList,
+        LimitLister<
+            ModifiedLister<
+                MetadataLister<
+                    Arc<A>,
+                    GlobLister<
+                        DirMarkerLister<DedupLister<SortedLister<CompleteLister<A, A::Lister>>>>,
+                    >,
+                >,
+            >,
+        >,
+    )> {
+        let cap = self.info.native_capability();
+        let path = normalize_path(path);
+        let path = path.as_str();
+        let recursive = args.recursive();
+        let glob = args.glob().map(|v| v.to_string());
+        let max_entries = args.max_entries();
+        let dirs_only = args.dirs_only();
+
+        // Entries are matched relative to the dir of given path.
+        let root = get_dir(path);
+        // Narrow the recursive list request by the literal prefix of glob, entries
+        // outside of it will never match.
+        let list_path = match glob.as_deref() {
+            Some(glob) if recursive && path.ends_with('/') => {
+                match (path, GlobLister::<()>::literal_prefix(glob)) {
+                    (_, "") => path.to_string(),
+                    ("/", prefix) => prefix.to_string(),
+                    (_, prefix) => format!("{path}{prefix}"),
+                }
+            }
+            _ => path.to_string(),
+        };
+
+        let dedup = args.dedup();
+        // Entries are always in order if sorted is requested, either by the
+        // service, the walk of recursive list or the SortedLister.
+        let dedup_sorted = args.sorted();
+        // `list_with_sorted` only covers recursive list, non-recursive list
+        // may return the dirs of a page before its files.
+        let sorted = args.sorted() && !recursive;
+        // The modified time filters are pushed down only if the list request
+        // will be forwarded to the service with the args, otherwise entries
+        // will be filtered by ourselves.
+        let forwarded = !recursive || list_natively_recursive(cap, &args);
+        let modified_after = args
+            .modified_after()
+            .filter(|_| !(forwarded && cap.list_with_modified_after));
+        let modified_before = args
+            .modified_before()
+            .filter(|_| !(forwarded && cap.list_with_modified_before));
+        let metadata = (args.metadata() || modified_after.is_some() || modified_before.is_some())
+            && !cap.list_has_metadata;
+        let concurrent = args.concurrent();
+        let rewrite = recursive && list_natively_recursive(cap, &args);
+        // Don't fetch more than needed in the first page. Entries could be
+        // dropped while filtering, in which case the page size is left to the
+        // service so that we don't request lots of small pages.
+        let filtered = glob.is_some()
+            || dirs_only
+            || dedup
+            || modified_after.is_some()
+            || modified_before.is_some()
+            || self.dir_marker != DirMarker::Path
+            || (!path.ends_with('/') && !list_natively_recursive(cap, &args));
+        if let (Some(max), None, false) = (max_entries, args.limit(), filtered) {
+            args = args.with_limit(max.max(1));
+        }
+        let (rp, l) = self.complete_list_inner(&list_path, args).await?;
+        // Recursive list is already sorted natively or while walking, only the
+        // entries of non-recursive list need to be sorted here.
+        let l = SortedLister::new(l, sorted);
+        let l = DedupLister::new(l, dedup, dedup_sorted);
+        // Only services that list recursively natively won't return the
+        // directories themselves, we need to yield dirs from the markers.
+        let l = DirMarkerLister::new(l, path, self.dir_marker, rewrite, dirs_only);
+        let l = GlobLister::new(l, root, glob.as_deref())?;
+        // Fetch metadata after filtering so that skipped entries won't be stat.
+        let l = MetadataLister::new(self.inner.clone(), l, concurrent, metadata);
+        let l = ModifiedLister::new(l, modified_after, modified_before);
+        let l = LimitLister::new(l, max_entries);
+        Ok((rp, l))
+    }
+
+    async fn complete_list_inner(
         &self,
         path: &str,
         args: OpList,
@@ -151,8 +499,9 @@ impl<A: Access> CompleteAccessor<A> {
         let cap = self.info.native_capability();
 
         let recursive = args.recursive();
+        let sorted = args.sorted();
 
-        match (recursive, cap.list_with_recursive) {
+        match (recursive, list_natively_recursive(cap, &args)) {
             // - If service can list_with_recursive, we can forward list to it directly.
             (_, true) => {
                 let (rp, p) = self.inner.list(path, args).await?;
@@ -162,11 +511,15 @@ impl<A: Access> CompleteAccessor<A> {
             (true, false) => {
                 // Forward path that ends with /
                 if path.ends_with('/') {
-                    let p = FlatLister::new(self.inner.clone(), path);
+                    let p = FlatLister::new(self.inner.clone(), path)
+                        .with_concurrent(args.concurrent())
+                        .with_sorted(sorted);
                     Ok((RpList::default(), CompleteLister::Two(p)))
                 } else {
                     let parent = get_parent(path);
-                    let p = FlatLister::new(self.inner.clone(), parent);
+                    let p = FlatLister::new(self.inner.clone(), parent)
+                        .with_concurrent(args.concurrent())
+                        .with_sorted(sorted);
                     let p = PrefixLister::new(p, path);
                     Ok((RpList::default(), CompleteLister::Four(p)))
                 }
@@ -192,8 +545,17 @@ impl<A: Access> CompleteAccessor<A> {
 impl<A: Access> LayeredAccess for CompleteAccessor<A> {
     type Inner = A;
     type Reader = CompleteReader<A::Reader>;
-    type Writer = CompleteWriter<A::Writer>;
-    type Lister = CompleteLister<A, A::Lister>;
+    type Writer = CompleteWriter<A>;
+    type Lister = LimitLister<
+        ModifiedLister<
+            MetadataLister<
+                Arc<A>,
+                GlobLister<
+                    DirMarkerLister<DedupLister<SortedLister<CompleteLister<A, A::Lister>>>>,
+                >,
+            >,
+        >,
+    >;
     type Deleter = A::Deleter;
 
     fn inner(&self) -> &Self::Inner {
@@ -209,21 +571,84 @@ impl<A: Access> LayeredAccess for CompleteAccessor<A> {
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
-        let size = args.range().size();
-        self.inner
-            .read(path, args)
-            .await
-            .map(|(rp, r)| (rp, CompleteReader::new(r, size)))
+        let suffix_range = args.suffix_range();
+        let size = suffix_range.or(args.range().size());
+        let lenient = self.lenient_read || args.lenient();
+        let max_size = args.max_size();
+        if let (Some(size), Some(max_size)) = (size, max_size) {
+            check_max_size(size, max_size)?;
+        }
+
+        let (rp, r) = self.complete_read(path, args).await?;
+        if let (Some(size), Some(max_size)) = (rp.size(), max_size) {
+            check_max_size(size, max_size)?;
+        }
+        // Services return the whole file for a suffix range if the file is
+        // smaller, expect less data only if the file size is reported.
+        let size = match suffix_range {
+            Some(n) => {
+                let file_size = rp
+                    .range()
+                    .and_then(|v| v.size())
+                    .or(rp.metadata().map(|v| v.content_length()));
+                Some(file_size.map_or(n, |v| v.min(n)))
+            }
+            None => size,
+        };
+        let r = if lenient {
+            CompleteReader::new_lenient(r, size)
+        } else {
+            CompleteReader::new(r, size)
+        };
+        Ok((rp, r.with_max_size(max_size)))
     }
 
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.missing_dirs.invalidate(path);
+
+        // Defer the write until the first buffer comes so that we can detect
+        // the content type from it.
+        if args.content_type_detection()
+            && args.content_type().is_none()
+            && self.info.full_capability().write_with_content_type
+        {
+            let w = CompleteWriter::new_pending(self.inner.clone(), path, args.clone())
+                .with_align(&self.info.full_capability())
+                .with_verify(self.inner.clone(), path, &args)
+                .with_stat_after_close(self.inner.clone(), path, &args)
+                .with_replay(self.inner.clone(), path, args);
+            return Ok((RpWrite::new(), w));
+        }
+
         let (rp, w) = self.inner.write(path, args.clone()).await?;
-        let w = CompleteWriter::new(w, args.append());
+        let append = args
+            .append()
+            .then(|| (self.inner.clone(), path.to_string()));
+        let w = CompleteWriter::new(w, append)
+            .with_content_length(args.content_length())
+            .with_align(&self.info.full_capability())
+            .with_verify(self.inner.clone(), path, &args)
+            .with_stat_after_close(self.inner.clone(), path, &args)
+            .with_replay(self.inner.clone(), path, args);
         Ok((rp, w))
     }
 
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.missing_dirs.invalidate(to);
+        self.inner.copy(from, to, args).await
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.missing_dirs.invalidate(to);
+        self.inner.rename(from, to, args).await
+    }
+
     async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
-        self.complete_stat(path, args).await
+        let retry = args.read_after_write_retry();
+        retry_read_after_write(&*self.clock, retry, || {
+            self.complete_stat(path, args.clone())
+        })
+        .await
     }
 
     async fn delete(&self) -> Result<(RpDelete, Self::Deleter)> {
@@ -235,17 +660,368 @@ impl<A: Access> LayeredAccess for CompleteAccessor<A> {
     }
 
     async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
-        self.inner.presign(path, args).await
+        let expire = complete_presign_expire(
+            &self.info.native_capability(),
+            args.expire(),
+            self.clamp_presign_expiry,
+        )
+        .map_err(|err| err.with_context("path", path))?;
+        let (_, op) = args.into_parts();
+
+        self.inner.presign(path, OpPresign::new(op, expire)).await
     }
 }
 
+/// Validate the expiry of presign against the service's max.
+///
+/// The expiry will be clamped to the max instead if `clamp` is enabled.
+fn complete_presign_expire(cap: &Capability, expire: Duration, clamp: bool) -> Result<Duration> {
+    if expire.is_zero() {
+        return Err(Error::new(
+            ErrorKind::ConfigInvalid,
+            "presign expire must be greater than zero",
+        )
+        .with_operation(Operation::Presign));
+    }
+
+    match cap.presign_max_expiry {
+        Some(max) if expire > max => {
+            if clamp {
+                return Ok(max);
+            }
+            Err(Error::new(
+                ErrorKind::Unsupported,
+                "presign expire exceeds the max expiry supported by service",
+            )
+            .with_operation(Operation::Presign)
+            .with_context("expire", format!("{expire:?}"))
+            .with_context("max_expiry", format!("{max:?}")))
+        }
+        _ => Ok(expire),
+    }
+}
+
+/// Retry `NotFound` returned by `f` while `read_after_write_retry` is set.
+///
+/// Other errors are returned directly and left to `RetryLayer`.
+async fn retry_read_after_write<T, F, Fut>(
+    clock: &dyn Clock,
+    retry: Option<(usize, Duration)>,
+    mut f: F,
+) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let Some((max, backoff)) = retry else {
+        return f().await;
+    };
+
+    let mut attempt = 0;
+    loop {
+        match f().await {
+            Err(err) if err.kind() == ErrorKind::NotFound && attempt < max => {
+                attempt += 1;
+                clock.sleep(backoff).await;
+            }
+            Err(err) if err.kind() == ErrorKind::NotFound && attempt > 0 => {
+                return Err(err.with_context("read_after_write_retry", attempt.to_string()));
+            }
+            res => return res,
+        }
+    }
+}
+
+/// Check if the list could be forwarded to the service that lists recursively.
+///
+/// Sorted recursive list will be walked by ourselves if the service can't
+/// return entries in order natively.
+fn list_natively_recursive(cap: Capability, args: &OpList) -> bool {
+    cap.list_with_recursive && (cap.list_with_sorted || !(args.recursive() && args.sorted()))
+}
+
 pub type CompleteLister<A, P> =
     FourWays<P, FlatLister<Arc<A>, P>, PrefixLister<P>, PrefixLister<FlatLister<Arc<A>, P>>>;
 
+/// DirMarkerLister hides the dir marker objects from the listing.
+///
+/// Markers will be yielded as dirs if `rewrite` is enabled, otherwise they
+/// will be skipped since the dirs have been returned by the inner lister.
+///
+/// Files will be skipped if `dirs_only` is enabled.
+pub struct DirMarkerLister<L> {
+    inner: L,
+    path: String,
+    dir_marker: DirMarker,
+    rewrite: bool,
+    dirs_only: bool,
+}
+
+impl<L> DirMarkerLister<L> {
+    fn new(inner: L, path: &str, dir_marker: DirMarker, rewrite: bool, dirs_only: bool) -> Self {
+        Self {
+            inner,
+            path: path.to_string(),
+            dir_marker,
+            rewrite,
+            dirs_only,
+        }
+    }
+}
+
+impl<L: oio::List> oio::List for DirMarkerLister<L> {
+    async fn next(&mut self) -> Result<Option<oio::Entry>> {
+        if self.dir_marker == DirMarker::Path && !self.dirs_only {
+            return self.inner.next().await;
+        }
+
+        loop {
+            let Some(entry) = self.inner.next().await? else {
+                return Ok(None);
+            };
+            if entry.mode().is_dir() {
+                return Ok(Some(entry));
+            }
+            match self.dir_marker.dir_path(entry.path()) {
+                None if self.dirs_only => continue,
+                None => return Ok(Some(entry)),
+                Some(dir) if self.rewrite && dir != self.path => {
+                    return Ok(Some(oio::Entry::new(dir, Metadata::new(EntryMode::DIR))));
+                }
+                Some(_) => continue,
+            }
+        }
+    }
+}
+
+/// SortedLister buffers all entries of the inner lister and returns them in
+/// lexicographic order of their paths if `sorted` is enabled.
+///
+/// The memory usage grows with the number of entries in the listed dir since
+/// it's only used for non-recursive list.
+pub struct SortedLister<L> {
+    inner: L,
+    sorted: bool,
+    entries: Option<VecDeque<oio::Entry>>,
+}
+
+impl<L> SortedLister<L> {
+    fn new(inner: L, sorted: bool) -> Self {
+        Self {
+            inner,
+            sorted,
+            entries: None,
+        }
+    }
+}
+
+impl<L: oio::List> oio::List for SortedLister<L> {
+    async fn next(&mut self) -> Result<Option<oio::Entry>> {
+        if !self.sorted {
+            return self.inner.next().await;
+        }
+
+        if self.entries.is_none() {
+            let mut entries = vec![];
+            while let Some(entry) = self.inner.next().await? {
+                entries.push(entry);
+            }
+            // Use stable sort to keep the order of versions of the same path.
+            entries.sort_by(|a, b| a.path().cmp(b.path()));
+            self.entries = Some(entries.into());
+        }
+
+        Ok(self.entries.as_mut().and_then(|v| v.pop_front()))
+    }
+}
+
+/// The max number of keys kept by [`DedupLister`] for unsorted listing.
+const DEDUP_WINDOW: usize = 10_000;
+
+/// DedupLister skips the entries that have been returned already if `dedup`
+/// is enabled.
+///
+/// Entries are keyed by their path and version, so versions of the same path
+/// won't be skipped.
+///
+/// - For sorted listing, duplicates are adjacent, only the last key is kept.
+/// - Otherwise, the last [`DEDUP_WINDOW`] keys are kept in a set. Duplicates
+///   that are further apart than the window won't be detected.
+pub struct DedupLister<L> {
+    inner: L,
+    dedup: bool,
+    sorted: bool,
+    last: Option<(String, Option<String>)>,
+    seen: HashSet<(String, Option<String>)>,
+    window: VecDeque<(String, Option<String>)>,
+}
+
+impl<L> DedupLister<L> {
+    fn new(inner: L, dedup: bool, sorted: bool) -> Self {
+        Self {
+            inner,
+            dedup,
+            sorted,
+            last: None,
+            seen: HashSet::new(),
+            window: VecDeque::new(),
+        }
+    }
+
+    /// Record the key of given entry, returns `false` if it has been seen.
+    fn insert(&mut self, entry: &oio::Entry) -> bool {
+        let key = (
+            entry.path().to_string(),
+            entry.metadata().version().map(|v| v.to_string()),
+        );
+
+        if self.sorted {
+            if self.last.as_ref() == Some(&key) {
+                return false;
+            }
+            self.last = Some(key);
+            return true;
+        }
+
+        if self.seen.contains(&key) {
+            return false;
+        }
+        if self.window.len() >= DEDUP_WINDOW {
+            if let Some(oldest) = self.window.pop_front() {
+                self.seen.remove(&oldest);
+            }
+        }
+        self.window.push_back(key.clone());
+        self.seen.insert(key);
+        true
+    }
+}
+
+impl<L: oio::List> oio::List for DedupLister<L> {
+    async fn next(&mut self) -> Result<Option<oio::Entry>> {
+        if !self.dedup {
+            return self.inner.next().await;
+        }
+
+        while let Some(entry) = self.inner.next().await? {
+            if self.insert(&entry) {
+                return Ok(Some(entry));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// ModifiedLister skips the files whose `last_modified` is out of the range
+/// of `modified_after` and `modified_before`.
+///
+/// Dirs and files without `last_modified` will be returned directly.
+pub struct ModifiedLister<L> {
+    inner: L,
+    modified_after: Option<DateTime<Utc>>,
+    modified_before: Option<DateTime<Utc>>,
+}
+
+impl<L> ModifiedLister<L> {
+    fn new(
+        inner: L,
+        modified_after: Option<DateTime<Utc>>,
+        modified_before: Option<DateTime<Utc>>,
+    ) -> Self {
+        Self {
+            inner,
+            modified_after,
+            modified_before,
+        }
+    }
+
+    fn matches(&self, entry: &oio::Entry) -> bool {
+        if entry.mode().is_dir() {
+            return true;
+        }
+        let Some(last_modified) = entry.metadata().last_modified() else {
+            return true;
+        };
+
+        self.modified_after.is_none_or(|t| last_modified > t)
+            && self.modified_before.is_none_or(|t| last_modified < t)
+    }
+}
+
+impl<L: oio::List> oio::List for ModifiedLister<L> {
+    async fn next(&mut self) -> Result<Option<oio::Entry>> {
+        if self.modified_after.is_none() && self.modified_before.is_none() {
+            return self.inner.next().await;
+        }
+
+        while let Some(entry) = self.inner.next().await? {
+            if self.matches(&entry) {
+                return Ok(Some(entry));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// LimitLister stops listing once `max_entries` entries have been returned.
+///
+/// The inner lister will be dropped at the limit so that no more pages will
+/// be requested and its resources will be released.
+pub struct LimitLister<L> {
+    inner: Option<L>,
+    remaining: Option<usize>,
+}
+
+impl<L> LimitLister<L> {
+    fn new(inner: L, max_entries: Option<usize>) -> Self {
+        Self {
+            inner: Some(inner),
+            remaining: max_entries,
+        }
+    }
+}
+
+impl<L: oio::List> oio::List for LimitLister<L> {
+    async fn next(&mut self) -> Result<Option<oio::Entry>> {
+        let Some(inner) = self.inner.as_mut().filter(|_| self.remaining != Some(0)) else {
+            self.inner = None;
+            return Ok(None);
+        };
+
+        let entry = inner.next().await?;
+        if let (Some(_), Some(remaining)) = (&entry, self.remaining.as_mut()) {
+            *remaining -= 1;
+        }
+        if entry.is_none() || self.remaining == Some(0) {
+            self.inner = None;
+        }
+        Ok(entry)
+    }
+}
+
+/// Check the size of data to read against the `max_size` of read.
+pub(crate) fn check_max_size(size: u64, max_size: u64) -> Result<()> {
+    if size <= max_size {
+        return Ok(());
+    }
+
+    Err(
+        Error::new(ErrorKind::RangeNotSatisfied, "read size exceeds max_size")
+            .with_context("max_size", max_size)
+            .with_context("size", size),
+    )
+}
+
 pub struct CompleteReader<R> {
     inner: R,
     size: Option<u64>,
     read: u64,
+    lenient: bool,
+    max_size: Option<u64>,
+    /// Whether inner reader doesn't support `read_into`, data will be read
+    /// by `read` and kept in `pending` if `dst` can't hold all of them.
+    fallback: bool,
+    pending: Buffer,
 }
 
 impl<R> CompleteReader<R> {
@@ -254,6 +1030,24 @@ impl<R> CompleteReader<R> {
             inner,
             size,
             read: 0,
+            lenient: false,
+            max_size: None,
+            fallback: false,
+            pending: Buffer::new(),
+        }
+    }
+
+    /// Fail the read once more than `max_size` bytes have been read.
+    pub fn with_max_size(mut self, max_size: Option<u64>) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// Create a reader that tolerates getting too little data.
+    pub fn new_lenient(inner: R, size: Option<u64>) -> Self {
+        Self {
+            lenient: true,
+            ..Self::new(inner, size)
         }
     }
 
@@ -264,6 +1058,13 @@ impl<R> CompleteReader<R> {
 
         match self.read.cmp(&size) {
             Ordering::Equal => Ok(()),
+            Ordering::Less if self.lenient => {
+                log::warn!(
+                    "reader got too little data, expect: {size}, actual: {}",
+                    self.read
+                );
+                Ok(())
+            }
             Ordering::Less => Err(
                 Error::new(ErrorKind::Unexpected, "reader got too little data")
                     .with_context("expect", size)
@@ -278,37 +1079,400 @@ impl<R> CompleteReader<R> {
     }
 }
 
+impl<R> CompleteReader<R> {
+    /// Record the size of data read from inner reader, `0` means EOF.
+    fn record(&mut self, n: usize) -> Result<()> {
+        if n == 0 {
+            return self.check();
+        }
+
+        self.read += n as u64;
+        if let Some(max_size) = self.max_size {
+            check_max_size(self.read, max_size)?;
+        }
+        Ok(())
+    }
+}
+
 impl<R: oio::Read> oio::Read for CompleteReader<R> {
     async fn read(&mut self) -> Result<Buffer> {
+        // Return the data left by `read_into` first.
+        if !self.pending.is_empty() {
+            return Ok(mem::take(&mut self.pending));
+        }
+
         let buf = self.inner.read().await?;
+        self.record(buf.len())?;
+        Ok(buf)
+    }
 
-        if buf.is_empty() {
-            self.check()?;
-        } else {
-            self.read += buf.len() as u64;
+    async fn read_into(&mut self, dst: &mut [u8]) -> Result<usize> {
+        if dst.is_empty() {
+            return Ok(0);
         }
 
-        Ok(buf)
+        if self.pending.is_empty() && !self.fallback {
+            match self.inner.read_into(dst).await {
+                Ok(n) => {
+                    self.record(n)?;
+                    return Ok(n);
+                }
+                // Nothing has been consumed, fallback to `read` instead.
+                Err(err) if err.kind() == ErrorKind::Unsupported => self.fallback = true,
+                Err(err) => return Err(err),
+            }
+        }
+
+        if self.pending.is_empty() {
+            self.pending = oio::Read::read(self).await?;
+        }
+        let n = self.pending.len().min(dst.len());
+        self.pending.copy_to_slice(&mut dst[..n]);
+        Ok(n)
     }
 }
 
-pub struct CompleteWriter<W> {
-    inner: Option<W>,
-    append: bool,
+pub struct CompleteWriter<A: Access> {
+    inner: Option<A::Writer>,
+    /// The write that has not been sent to the service yet, only used by
+    /// content type detection.
+    pending: Option<(Arc<A>, String, OpWrite)>,
+    /// The accessor and path of the appended object, used to stat the size
+    /// of object after append if the service doesn't return it.
+    append: Option<(Arc<A>, String)>,
+    /// The expected size of data declared by `content_length`.
+    content_length: Option<u64>,
+    /// The alignment required by the service for every part except the last one.
+    align: Option<usize>,
+    /// The unaligned tail of written data, which will be sent with the next
+    /// write or as the last part while closing.
+    tail: oio::QueueBuf,
     size: u64,
+    /// The size of data that has been flushed to the service.
+    flushed: u64,
+    replay: Option<Replay<A>>,
+    /// Used to verify the written object after close if `verify` is set.
+    verify: Option<Verify<A>>,
+    /// The accessor and path of the written object, used to fill the
+    /// metadata after close if `stat_after_close` is set.
+    stat_after_close: Option<(Arc<A>, String)>,
 }
 
-impl<W> CompleteWriter<W> {
-    pub fn new(inner: W, append: bool) -> CompleteWriter<W> {
-        CompleteWriter {
-            inner: Some(inner),
-            append,
-            size: 0,
-        }
-    }
+/// Verify keeps what's needed to check the written object after close.
+struct Verify<A: Access> {
+    acc: Arc<A>,
+    path: String,
+    /// The md5 of written data, compared with the `content_md5` returned by stat.
+    hasher: md5::Md5,
+}
 
-    fn check(&self, content_length: u64) -> Result<()> {
-        if self.append || content_length == 0 {
+/// Replay keeps the written data so that a failed close can be retried
+/// by writing all data again.
+struct Replay<A: Access> {
+    acc: Arc<A>,
+    path: String,
+    args: OpWrite,
+
+    limit: usize,
+    /// The written data, will be `None` once its size exceeds the limit.
+    bufs: Option<Vec<Buffer>>,
+    size: usize,
+    /// Whether the write should be replayed before next close.
+    required: bool,
+}
+
+impl<A: Access> Replay<A> {
+    fn push(&mut self, bs: &Buffer) {
+        let Some(bufs) = self.bufs.as_mut() else {
+            return;
+        };
+
+        self.size += bs.len();
+        if self.size > self.limit {
+            self.bufs = None;
+        } else {
+            bufs.push(bs.clone());
+        }
+    }
+}
+
+impl<A: Access> CompleteWriter<A> {
+    pub fn new(inner: A::Writer, append: Option<(Arc<A>, String)>) -> CompleteWriter<A> {
+        CompleteWriter {
+            inner: Some(inner),
+            pending: None,
+            append,
+            content_length: None,
+            align: None,
+            tail: oio::QueueBuf::new(),
+            size: 0,
+            flushed: 0,
+            replay: None,
+            verify: None,
+            stat_after_close: None,
+        }
+    }
+
+    fn new_pending(acc: Arc<A>, path: &str, args: OpWrite) -> CompleteWriter<A> {
+        CompleteWriter {
+            inner: None,
+            append: args.append().then(|| (acc.clone(), path.to_string())),
+            content_length: args.content_length(),
+            pending: Some((acc, path.to_string(), args)),
+            align: None,
+            tail: oio::QueueBuf::new(),
+            size: 0,
+            flushed: 0,
+            replay: None,
+            verify: None,
+            stat_after_close: None,
+        }
+    }
+
+    /// Validate the written data against the declared `content_length`.
+    fn with_content_length(mut self, content_length: Option<u64>) -> CompleteWriter<A> {
+        self.content_length = content_length;
+        self
+    }
+
+    /// Re-chunk the written data into aligned parts if the service requires
+    /// `write_multi_align_size`, only the last part could be unaligned.
+    fn with_align(mut self, cap: &Capability) -> CompleteWriter<A> {
+        self.align = cap
+            .write_multi_align_size
+            .filter(|v| *v > 0 && cap.write_can_multi);
+        self
+    }
+
+    /// Verify the written object after close if `verify` is set.
+    ///
+    /// Append writes are not verified since the object could be larger than
+    /// the written data.
+    fn with_verify(mut self, acc: Arc<A>, path: &str, args: &OpWrite) -> CompleteWriter<A> {
+        if args.verify() && !args.append() {
+            self.verify = Some(Verify {
+                acc,
+                path: path.to_string(),
+                hasher: md5::Md5::new(),
+            });
+        }
+        self
+    }
+
+    /// Stat the written object and make sure it matches the written data.
+    ///
+    /// Returns the stat result so that it can be reused by `fill_metadata`.
+    async fn verify(&mut self, ret: &Metadata) -> Result<Option<Metadata>> {
+        let Some(Verify { acc, path, hasher }) = self.verify.take() else {
+            return Ok(None);
+        };
+
+        // The writer has been closed, retrying close can't fix a temporary
+        // stat failure, mark it persistent to stop `RetryLayer`.
+        let meta = acc
+            .stat(&path, OpStat::new())
+            .await
+            .map_err(|err| {
+                let temporary = err.is_temporary();
+                err.with_persistent(temporary)
+                    .with_context("reason", "stat written object for verify failed")
+            })?
+            .into_metadata();
+        if meta.content_length() != self.size {
+            return Err(
+                Error::new(ErrorKind::Unexpected, "written object size mismatch")
+                    .with_context("path", path)
+                    .with_context("expect", self.size)
+                    .with_context("actual", meta.content_length()),
+            );
+        }
+        if let (Some(expect), Some(actual)) = (ret.etag(), meta.etag()) {
+            if expect != actual {
+                return Err(
+                    Error::new(ErrorKind::Unexpected, "written object etag mismatch")
+                        .with_context("path", path)
+                        .with_context("expect", expect)
+                        .with_context("actual", actual),
+                );
+            }
+        }
+        if let Some(actual) = meta.content_md5() {
+            let expect = BASE64_STANDARD.encode(hasher.finalize());
+            if expect != actual {
+                return Err(
+                    Error::new(ErrorKind::Unexpected, "written object checksum mismatch")
+                        .with_context("path", path)
+                        .with_context("expect", expect)
+                        .with_context("actual", actual),
+                );
+            }
+        }
+        Ok(Some(meta))
+    }
+
+    /// Fill the metadata returned by close via stat if `stat_after_close` is set.
+    fn with_stat_after_close(
+        mut self,
+        acc: Arc<A>,
+        path: &str,
+        args: &OpWrite,
+    ) -> CompleteWriter<A> {
+        if args.stat_after_close() {
+            self.stat_after_close = Some((acc, path.to_string()));
+        }
+        self
+    }
+
+    /// Fill the fields missing in `ret` from the stat of written object.
+    ///
+    /// `stat` is the result of the stat that has been sent after close, the
+    /// object will only be stat again if there is none.
+    async fn fill_metadata(&self, ret: &mut Metadata, stat: Option<Metadata>) {
+        let Some((acc, path)) = &self.stat_after_close else {
+            return;
+        };
+        if ret.etag().is_some()
+            && ret.version().is_some()
+            && ret.content_md5().is_some()
+            && ret.last_modified().is_some()
+        {
+            return;
+        }
+
+        let meta = match stat {
+            Some(meta) => meta,
+            // The data has been committed, close must not fail here.
+            None => match acc.stat(path, OpStat::new()).await {
+                Ok(rp) => rp.into_metadata(),
+                Err(err) => {
+                    log::warn!("stat object after close failed: {err}");
+                    return;
+                }
+            },
+        };
+        if let (None, Some(v)) = (ret.etag(), meta.etag()) {
+            ret.set_etag(v);
+        }
+        if let (None, Some(v)) = (ret.version(), meta.version()) {
+            ret.set_version(v);
+        }
+        if let (None, Some(v)) = (ret.content_md5(), meta.content_md5()) {
+            ret.set_content_md5(v);
+        }
+        if let (None, Some(v)) = (ret.last_modified(), meta.last_modified()) {
+            ret.set_last_modified(v);
+        }
+    }
+
+    /// Retain written data for replaying if `replay_buffer` is set.
+    ///
+    /// Append writes can't be replayed since the data has been committed.
+    fn with_replay(mut self, acc: Arc<A>, path: &str, args: OpWrite) -> CompleteWriter<A> {
+        let Some(limit) = args.replay_buffer() else {
+            return self;
+        };
+        if args.append() {
+            return self;
+        }
+
+        self.replay = Some(Replay {
+            acc,
+            path: path.to_string(),
+            args,
+            limit,
+            bufs: Some(Vec::new()),
+            size: 0,
+            required: false,
+        });
+        self
+    }
+
+    /// Replay the write with a new inner writer.
+    async fn replay(&mut self) -> Result<()> {
+        let Some(replay) = self.replay.as_mut().filter(|v| v.required) else {
+            return Ok(());
+        };
+        let Some(bufs) = replay.bufs.as_ref() else {
+            return Ok(());
+        };
+
+        let (_, mut w) = replay.acc.write(&replay.path, replay.args.clone()).await?;
+        for bs in bufs {
+            if let Err(err) = oio::Write::write(&mut w, bs.clone()).await {
+                let _ = oio::Write::abort(&mut w).await;
+                return Err(err);
+            }
+        }
+
+        // The previous writer is broken, abort it in best effort.
+        if let Some(mut prev) = self.inner.replace(w) {
+            let _ = oio::Write::abort(&mut prev).await;
+        }
+        replay.required = false;
+        Ok(())
+    }
+
+    /// Send the unaligned tail as the last part.
+    async fn write_tail(&mut self) -> Result<()> {
+        if self.tail.is_empty() {
+            return Ok(());
+        }
+
+        let bs = self.tail.clone().collect();
+        let w = self.writer(None).await?;
+        oio::Write::write(w, bs.clone()).await?;
+        self.tail.clear();
+        if let Some(replay) = self.replay.as_mut() {
+            replay.push(&bs);
+        }
+        Ok(())
+    }
+
+    /// Mark the write to be replayed if the close error could be retried.
+    fn on_close_error(&mut self, err: Error) -> Error {
+        let Some(replay) = self.replay.as_mut() else {
+            return err;
+        };
+        if !err.is_temporary() {
+            return err;
+        }
+
+        if replay.bufs.is_none() {
+            return err
+                .set_persistent()
+                .with_context("replay_buffer", replay.limit)
+                .with_context(
+                    "reason",
+                    "written data exceeds the replay buffer and has been streamed to service",
+                );
+        }
+        replay.required = true;
+        err
+    }
+
+    /// Get the inner writer, the pending write will be sent with the content
+    /// type detected from `bs`.
+    async fn writer(&mut self, bs: Option<&Buffer>) -> Result<&mut A::Writer> {
+        if let Some((acc, path, args)) = &self.pending {
+            let mut args = args.clone();
+            if let Some(v) = bs.and_then(|bs| detect_content_type(bs.chunk())) {
+                args = args.with_content_type(v);
+            }
+            if let Some(replay) = self.replay.as_mut() {
+                replay.args = args.clone();
+            }
+            let (_, w) = acc.write(path, args).await?;
+            self.inner = Some(w);
+            self.pending = None;
+        }
+
+        self.inner
+            .as_mut()
+            .ok_or_else(|| Error::new(ErrorKind::Unexpected, "writer has been closed or aborted"))
+    }
+
+    fn check(&self, content_length: u64) -> Result<()> {
+        if self.append.is_some() || content_length == 0 {
             return Ok(());
         }
 
@@ -317,62 +1481,194 @@ impl<W> CompleteWriter<W> {
             Ordering::Less => Err(
                 Error::new(ErrorKind::Unexpected, "writer got too little data")
                     .with_context("expect", content_length)
-                    .with_context("actual", self.size),
+                    .with_context("actual", self.size)
+                    .with_context("flushed", self.flushed),
             ),
             Ordering::Greater => Err(
                 Error::new(ErrorKind::Unexpected, "writer got too much data")
                     .with_context("expect", content_length)
-                    .with_context("actual", self.size),
+                    .with_context("actual", self.size)
+                    .with_context("flushed", self.flushed),
             ),
         }
     }
 }
 
+/// Guess the content type from the magic bytes of common formats.
+fn detect_content_type(bs: &[u8]) -> Option<&'static str> {
+    const MAGIC: &[(&[u8], &str)] = &[
+        (b"\x89PNG\r\n\x1a\n", "image/png"),
+        (b"\xff\xd8\xff", "image/jpeg"),
+        (b"GIF87a", "image/gif"),
+        (b"GIF89a", "image/gif"),
+        (b"BM", "image/bmp"),
+        (b"%PDF-", "application/pdf"),
+        (b"PK\x03\x04", "application/zip"),
+        (b"\x1f\x8b", "application/gzip"),
+        (b"BZh", "application/x-bzip2"),
+        (b"7z\xbc\xaf\x27\x1c", "application/x-7z-compressed"),
+        (b"\xfd7zXZ\x00", "application/x-xz"),
+        (b"\x28\xb5\x2f\xfd", "application/zstd"),
+        (b"\x00asm", "application/wasm"),
+        (b"ID3", "audio/mpeg"),
+        (b"OggS", "audio/ogg"),
+        (b"fLaC", "audio/flac"),
+    ];
+
+    if let Some((_, mime)) = MAGIC.iter().find(|(magic, _)| bs.starts_with(magic)) {
+        return Some(mime);
+    }
+
+    // RIFF containers and ISO base media files carry their type after a header.
+    match (bs.get(..4), bs.get(4..8), bs.get(8..12)) {
+        (Some(b"RIFF"), _, Some(b"WEBP")) => Some("image/webp"),
+        (Some(b"RIFF"), _, Some(b"WAVE")) => Some("audio/wav"),
+        (_, Some(b"ftyp"), _) => Some("video/mp4"),
+        _ => None,
+    }
+}
+
 /// Check if the writer has been closed or aborted while debug_assertions
 /// enabled. This code will never be executed in release mode.
 #[cfg(debug_assertions)]
-impl<W> Drop for CompleteWriter<W> {
+impl<A: Access> Drop for CompleteWriter<A> {
     fn drop(&mut self) {
-        if self.inner.is_some() {
+        if self.inner.is_some() || self.pending.is_some() {
             log::warn!("writer has not been closed or aborted, must be a bug")
         }
     }
 }
 
-impl<W> oio::Write for CompleteWriter<W>
-where
-    W: oio::Write,
-{
+impl<A: Access> oio::Write for CompleteWriter<A> {
     async fn write(&mut self, bs: Buffer) -> Result<()> {
-        let w = self.inner.as_mut().ok_or_else(|| {
-            Error::new(ErrorKind::Unexpected, "writer has been closed or aborted")
-        })?;
-
         let len = bs.len();
-        w.write(bs).await?;
+        if let Some(content_length) = self.content_length {
+            if self.size + len as u64 > content_length {
+                return Err(
+                    Error::new(ErrorKind::Unexpected, "writer got too much data")
+                        .with_context("expect", content_length)
+                        .with_context("actual", self.size + len as u64),
+                );
+            }
+        }
+
+        self.writer(Some(&bs)).await?;
+
+        // Keep the tail before this write so that the write can be retried.
+        let prev_tail = self.tail.clone();
+        let part = match self.align {
+            None => Some(bs.clone()),
+            Some(align) => {
+                self.tail.push(bs.clone());
+                let n = self.tail.len() / align * align;
+                (n > 0).then(|| {
+                    let mut tail = self.tail.take().collect();
+                    let part = tail.slice(..n);
+                    tail.advance(n);
+                    self.tail.push(tail);
+                    part
+                })
+            }
+        };
+        if let Some(part) = part {
+            let w = self.writer(None).await?;
+            if let Err(err) = w.write(part.clone()).await {
+                self.tail = prev_tail;
+                return Err(err);
+            }
+            if let Some(replay) = self.replay.as_mut() {
+                replay.push(&part);
+            }
+        }
         self.size += len as u64;
+        if let Some(verify) = self.verify.as_mut() {
+            bs.clone().for_each(|b| verify.hasher.update(&b));
+        }
 
         Ok(())
     }
 
-    async fn close(&mut self) -> Result<Metadata> {
+    async fn flush(&mut self) -> Result<()> {
+        // Nothing has been sent to the service yet.
+        if self.pending.is_some() {
+            return Ok(());
+        }
+
         let w = self.inner.as_mut().ok_or_else(|| {
             Error::new(ErrorKind::Unexpected, "writer has been closed or aborted")
         })?;
 
+        w.flush().await?;
+        self.flushed = self.size - self.tail.len() as u64;
+
+        Ok(())
+    }
+
+    async fn close(&mut self) -> Result<Metadata> {
+        // Check before closing so that incomplete data will not be committed.
+        if let Some(content_length) = self.content_length {
+            if self.size < content_length {
+                return Err(
+                    Error::new(ErrorKind::Unexpected, "writer got too little data")
+                        .with_context("expect", content_length)
+                        .with_context("actual", self.size),
+                );
+            }
+        }
+
+        self.replay().await?;
+        self.write_tail().await?;
+        let w = self.writer(None).await?;
+
         // we must return `Err` before setting inner to None; otherwise,
         // we won't be able to retry `close` in `RetryLayer`.
-        let mut ret = w.close().await?;
+        let mut ret = match w.close().await {
+            Ok(v) => v,
+            Err(err) => return Err(self.on_close_error(err)),
+        };
         self.check(ret.content_length())?;
-        if ret.content_length() == 0 {
-            ret = ret.with_content_length(self.size);
-        }
         self.inner = None;
 
+        // The stat of written object after close, shared by the checks below
+        // so that the object will be stat at most once.
+        let mut stat = None;
+        match &self.append {
+            // Only a size larger than appended data must be the size of object
+            // after append, stat the object if service doesn't return it.
+            Some((acc, path)) if ret.content_length() <= self.size => {
+                match acc.stat(path, OpStat::new()).await {
+                    Ok(rp) => {
+                        let meta = rp.into_metadata();
+                        ret = ret.with_content_length(meta.content_length());
+                        stat = Some(meta);
+                    }
+                    // The data has been appended, close must not fail here. But
+                    // the size is unknown, don't report a guessed one.
+                    Err(err) => {
+                        log::warn!("stat object after append failed: {err}");
+                        ret.unset_content_length();
+                    }
+                }
+            }
+            Some(_) => {}
+            None if ret.content_length() == 0 => ret = ret.with_content_length(self.size),
+            None => {}
+        }
+
+        if let Some(meta) = self.verify(&ret).await? {
+            stat = Some(meta);
+        }
+        self.fill_metadata(&mut ret, stat).await;
         Ok(ret)
     }
 
     async fn abort(&mut self) -> Result<()> {
+        self.tail.clear();
+        // Nothing has been sent to the service yet.
+        if self.pending.take().is_some() {
+            return Ok(());
+        }
+
         let w = self.inner.as_mut().ok_or_else(|| {
             Error::new(ErrorKind::Unexpected, "writer has been closed or aborted")
         })?;
@@ -383,3 +1679,651 @@ where
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_detect_content_type() {
+        let cases: &[(&[u8], Option<&str>)] = &[
+            (b"\x89PNG\r\n\x1a\n\x00", Some("image/png")),
+            (b"%PDF-1.7", Some("application/pdf")),
+            (b"RIFF\x00\x00\x00\x00WEBPVP8 ", Some("image/webp")),
+            (b"\x00\x00\x00\x18ftypmp42", Some("video/mp4")),
+            (b"hello, world", None),
+            (b"", None),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(detect_content_type(input), *expected, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn test_dir_marker() {
+        let marker = DirMarker::default();
+        assert_eq!(marker.marker_path("a/b/"), "a/b/");
+        assert_eq!(marker.dir_path("a/b/"), None);
+
+        let marker = DirMarker::Suffix(".keep");
+        assert_eq!(marker.marker_path("a/b/"), "a/b/.keep");
+        assert_eq!(marker.dir_path("a/b/.keep"), Some("a/b/"));
+        assert_eq!(marker.dir_path("a/b.keep"), None);
+        assert_eq!(marker.dir_path("a/b/c"), None);
+    }
+
+    #[test]
+    fn test_complete_presign_expire() {
+        let day = Duration::from_secs(24 * 60 * 60);
+        let cap = Capability {
+            presign_max_expiry: Some(7 * day),
+            ..Default::default()
+        };
+
+        assert_eq!(complete_presign_expire(&cap, day, false).unwrap(), day);
+        assert_eq!(
+            complete_presign_expire(&cap, 7 * day, false).unwrap(),
+            7 * day
+        );
+        let err = complete_presign_expire(&cap, 8 * day, false).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        assert_eq!(
+            complete_presign_expire(&cap, 8 * day, true).unwrap(),
+            7 * day
+        );
+        let err = complete_presign_expire(&cap, Duration::ZERO, true).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+
+        // No limit if the service doesn't declare one.
+        let cap = Capability::default();
+        assert_eq!(
+            complete_presign_expire(&cap, 30 * day, false).unwrap(),
+            30 * day
+        );
+    }
+
+    /// A reader that only implements `read`.
+    struct ChunkedReader(VecDeque<Buffer>);
+
+    impl oio::Read for ChunkedReader {
+        async fn read(&mut self) -> Result<Buffer> {
+            Ok(self.0.pop_front().unwrap_or_default())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reader_read_into_fallback() -> Result<()> {
+        let inner = ChunkedReader(VecDeque::from([
+            Buffer::from("Hello, World!"),
+            Buffer::from("!"),
+        ]));
+        let mut r = CompleteReader::new(inner, Some(14));
+
+        let mut dst = [0; 8];
+        assert_eq!(oio::Read::read_into(&mut r, &mut dst).await?, 8);
+        assert_eq!(&dst, b"Hello, W");
+        assert_eq!(oio::Read::read_into(&mut r, &mut dst).await?, 5);
+        assert_eq!(&dst[..5], b"orld!");
+        assert_eq!(oio::Read::read_into(&mut r, &mut dst[..2]).await?, 1);
+        assert_eq!(&dst[..1], b"!");
+        assert_eq!(oio::Read::read_into(&mut r, &mut dst).await?, 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_lenient_reader() {
+        let data = bytes::Bytes::from_static(b"hello");
+
+        let mut r = CompleteReader::new(data.clone(), Some(10));
+        assert_eq!(oio::Read::read(&mut r).await.unwrap().len(), 5);
+        assert!(oio::Read::read(&mut r).await.is_err());
+
+        let mut r = CompleteReader::new_lenient(data.clone(), Some(10));
+        assert_eq!(oio::Read::read(&mut r).await.unwrap().len(), 5);
+        assert!(oio::Read::read(&mut r).await.unwrap().is_empty());
+
+        let mut r = CompleteReader::new_lenient(data, Some(3));
+        assert_eq!(oio::Read::read(&mut r).await.unwrap().len(), 5);
+        assert!(oio::Read::read(&mut r).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_read_max_size() -> Result<()> {
+        let op = Operator::new(crate::services::Memory::default())?.finish();
+        op.write("test", "Hello, World!").await?;
+
+        let bs = op.read_with("test").max_size(13).await?;
+        assert_eq!(bs.to_vec(), b"Hello, World!");
+        let err = op.read_with("test").max_size(12).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::RangeNotSatisfied);
+        // The range is checked before sending the request.
+        let err = op
+            .read_with("test")
+            .range(0..20)
+            .max_size(12)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::RangeNotSatisfied);
+        assert!(op.read_with("test").range(0..5).max_size(12).await.is_ok());
+
+        Ok(())
+    }
+
+    #[cfg(feature = "tests")]
+    #[tokio::test]
+    async fn test_writer_align() -> Result<()> {
+        use crate::raw::tests::MockAccess;
+
+        let mock = MockAccess::new().with_capability(|cap| Capability {
+            write_multi_align_size: Some(4),
+            ..cap
+        });
+        let acc = CompleteLayer::default().layer(mock.clone());
+        let op = OperatorBuilder::new(mock.clone()).finish();
+
+        // Unaligned writes are re-chunked into aligned parts.
+        let (_, mut w) = Access::write(&acc, "test", OpWrite::new()).await?;
+        for bs in ["abcd", "ef", "ghij", "k"] {
+            oio::Write::write(&mut w, Buffer::from(bs)).await?;
+        }
+        oio::Write::close(&mut w).await?;
+        assert_eq!(op.read("test").await?.to_vec(), b"abcdefghijk");
+        assert_eq!(mock.take_written_parts("test"), [4, 4, 3]);
+
+        // Writes through the operator are chunked by the alignment.
+        let mut w = op.writer("test").await?;
+        w.write("abcdef").await?;
+        w.write("gh").await?;
+        w.write("i").await?;
+        w.close().await?;
+        assert_eq!(op.read("test").await?.to_vec(), b"abcdefghi");
+        assert_eq!(mock.take_written_parts("test"), [4, 4, 1]);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "tests")]
+    #[tokio::test]
+    async fn test_writer_replay() -> Result<()> {
+        use crate::layers::RetryLayer;
+        use crate::raw::tests::MockAccess;
+
+        let mock = MockAccess::new();
+        mock.inject_error(Operation::Write, "test", 2, || {
+            Error::new(ErrorKind::Unexpected, "connection reset").set_temporary()
+        });
+        let op = OperatorBuilder::new(mock.clone())
+            .finish()
+            .layer(RetryLayer::new().with_min_delay(Duration::ZERO));
+
+        let mut w = op.writer_with("test").replay_buffer(1024).await?;
+        w.write("Hello, ").await?;
+        w.write("World!").await?;
+        w.close().await?;
+        assert_eq!(op.read("test").await?.to_vec(), b"Hello, World!");
+
+        Ok(())
+    }
+
+    #[cfg(feature = "tests")]
+    #[tokio::test]
+    async fn test_writer_replay_exceeds_limit() -> Result<()> {
+        use crate::raw::tests::MockAccess;
+
+        let mock = MockAccess::new();
+        mock.inject_error(Operation::Write, "test", 1, || {
+            Error::new(ErrorKind::Unexpected, "connection reset").set_temporary()
+        });
+        let op = OperatorBuilder::new(mock.clone()).finish();
+
+        let mut w = op.writer_with("test").replay_buffer(4).await?;
+        w.write("Hello, World!").await?;
+        let err = w.close().await.expect_err("close must fail");
+        assert!(!err.is_temporary(), "close can't be retried");
+        w.abort().await?;
+
+        Ok(())
+    }
+
+    #[cfg(feature = "tests")]
+    #[tokio::test]
+    async fn test_writer_append_size() -> Result<()> {
+        use crate::raw::tests::MockAccess;
+
+        let mock = MockAccess::new();
+        let op = OperatorBuilder::new(mock.clone()).finish();
+
+        op.write("test", "Hello").await?;
+        let meta = op.write_with("test", ", World!").append(true).await?;
+        assert_eq!(meta.content_length(), 13);
+        assert_eq!(op.read("test").await?.to_vec(), b"Hello, World!");
+
+        // The size of appended data is not the size of object.
+        let meta = op.write_with("test", "!").append(true).await?;
+        assert_eq!(meta.content_length(), 14);
+
+        // The size is unknown if stat failed after append.
+        mock.inject_error(Operation::Stat, "test", 1, || {
+            Error::new(ErrorKind::Unexpected, "connection reset").set_temporary()
+        });
+        let meta = op.write_with("test", "!").append(true).await?;
+        assert_eq!(meta.content_length(), 0);
+        assert_eq!(op.read("test").await?.to_vec(), b"Hello, World!!!");
+
+        Ok(())
+    }
+
+    #[cfg(feature = "tests")]
+    #[tokio::test]
+    async fn test_writer_content_length() -> Result<()> {
+        use crate::raw::tests::MockAccess;
+
+        let op = OperatorBuilder::new(MockAccess::new()).finish();
+
+        let mut w = op.writer_with("test").content_length(5).await?;
+        w.write("Hel").await?;
+        let err = w.write("lo, World!").await.expect_err("write must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        w.abort().await?;
+
+        let mut w = op.writer_with("test").content_length(13).await?;
+        w.write("Hello").await?;
+        let err = w.close().await.expect_err("close must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        w.abort().await?;
+        assert!(!op.exists("test").await?);
+
+        op.write_with("test", "Hello").content_length(5).await?;
+        assert_eq!(op.read("test").await?.to_vec(), b"Hello");
+
+        Ok(())
+    }
+
+    #[cfg(feature = "tests")]
+    #[tokio::test]
+    async fn test_writer_verify() -> Result<()> {
+        use crate::raw::tests::MockAccess;
+
+        let mock = MockAccess::new();
+        let op = OperatorBuilder::new(mock.clone()).finish();
+
+        let meta = op.write_with("test", "Hello").verify(true).await?;
+        assert_eq!(meta.content_length(), 5);
+
+        // The service acknowledges the write but loses some data.
+        mock.inject_corruption(Operation::Write, "test", |bs| {
+            bs.slice(..bs.len().saturating_sub(2))
+        });
+        assert!(op.write("test", "Hello, World!").await.is_ok());
+        let err = op
+            .write_with("test", "Hello, World!")
+            .verify(true)
+            .await
+            .expect_err("verify must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        assert_eq!(err.context("actual"), Some("11"));
+
+        // The service stores the same size of data but with wrong content.
+        mock.inject_corruption(Operation::Write, "test", |bs| {
+            let mut bs = bs.to_vec();
+            if let Some(b) = bs.first_mut() {
+                *b = !*b;
+            }
+            Buffer::from(bs)
+        });
+        let err = op
+            .write_with("test", "Hello")
+            .verify(true)
+            .await
+            .expect_err("verify must fail");
+        assert!(err.to_string().contains("checksum mismatch"));
+
+        Ok(())
+    }
+
+    #[cfg(feature = "tests")]
+    #[tokio::test]
+    async fn test_writer_verify_stat_failed() -> Result<()> {
+        use crate::layers::RetryLayer;
+        use crate::raw::tests::MockAccess;
+
+        let mock = MockAccess::new();
+        mock.inject_error(Operation::Stat, "test", 1, || {
+            Error::new(ErrorKind::Unexpected, "connection reset").set_temporary()
+        });
+        let op = OperatorBuilder::new(mock.clone())
+            .finish()
+            .layer(RetryLayer::new().with_min_delay(Duration::ZERO));
+
+        // The closed writer must not be retried.
+        let err = op
+            .write_with("test", "Hello")
+            .verify(true)
+            .await
+            .expect_err("verify must fail");
+        assert!(err.is_persistent());
+        assert_eq!(
+            err.context("reason"),
+            Some("stat written object for verify failed")
+        );
+        assert_eq!(op.read("test").await?.to_vec(), b"Hello");
+
+        Ok(())
+    }
+
+    #[cfg(feature = "tests")]
+    #[tokio::test]
+    async fn test_writer_stat_after_close() -> Result<()> {
+        use md5::Digest;
+
+        use crate::raw::tests::MockAccess;
+
+        let mock = MockAccess::new();
+        let op = OperatorBuilder::new(mock.clone()).finish();
+        let etag = |s: &str| format!("\"{:x}\"", md5::Md5::digest(s));
+
+        let meta = op.write("test", "Hello").await?;
+        assert_eq!(meta.etag(), None);
+
+        let meta = op
+            .write_with("test", "Hello")
+            .stat_after_close(true)
+            .await?;
+        assert_eq!(meta.content_length(), 5);
+        assert_eq!(meta.etag(), Some(etag("Hello").as_str()));
+
+        let mut w = op.writer_with("test").stat_after_close(true).await?;
+        w.write("Hello, World!").await?;
+        let meta = w.close().await?;
+        assert_eq!(meta.etag(), Some(etag("Hello, World!").as_str()));
+
+        // The stat for verify is reused to fill the metadata.
+        let stats = mock.calls(Operation::Stat, "test");
+        let meta = op
+            .write_with("test", "Hello")
+            .verify(true)
+            .stat_after_close(true)
+            .await?;
+        assert_eq!(meta.etag(), Some(etag("Hello").as_str()));
+        assert_eq!(mock.calls(Operation::Stat, "test"), stats + 1);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "tests")]
+    #[tokio::test]
+    async fn test_read_after_write_retry() -> Result<()> {
+        use std::pin::pin;
+
+        use crate::layers::TypeEraseLayer;
+        use crate::raw::tests::MockAccess;
+        use crate::raw::tests::MockClock;
+
+        let clock = MockClock::new();
+        let mock = MockAccess::new();
+        mock.insert("test", "Hello");
+        let layer = CompleteLayer::default().with_clock(clock.clone());
+        let acc = Arc::new(TypeEraseLayer.layer(layer.layer(mock.clone()))) as Accessor;
+        let op = Operator::from_inner(acc);
+        let not_found = || Error::new(ErrorKind::NotFound, "not found");
+        let backoff = Duration::from_secs(1);
+
+        mock.inject_error(Operation::Read, "test", 1, not_found);
+        let err = op.read("test").await.expect_err("read must fail");
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        // Every retry waits for the backoff.
+        mock.inject_error(Operation::Read, "test", 2, not_found);
+        let mut fut = pin!(async {
+            op.read_with("test")
+                .read_after_write_retry(2, backoff)
+                .await
+        });
+        assert!(futures::poll!(fut.as_mut()).is_pending());
+        clock.advance(backoff);
+        assert!(futures::poll!(fut.as_mut()).is_pending());
+        clock.advance(backoff);
+        assert_eq!(fut.await?.to_vec(), b"Hello");
+
+        mock.inject_error(Operation::Read, "test", 3, not_found);
+        let mut fut = pin!(async {
+            op.read_with("test")
+                .read_after_write_retry(2, backoff)
+                .await
+        });
+        assert!(futures::poll!(fut.as_mut()).is_pending());
+        clock.advance(backoff);
+        assert!(futures::poll!(fut.as_mut()).is_pending());
+        clock.advance(backoff);
+        let err = fut.await.expect_err("read must fail");
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+        assert_eq!(err.context("read_after_write_retry"), Some("2"));
+
+        // Chunked reads stat the file first, which should be retried too.
+        mock.inject_error(Operation::Stat, "test", 2, not_found);
+        let mut fut = pin!(async {
+            op.reader_with("test")
+                .chunk(2)
+                .read_after_write_retry(2, backoff)
+                .await?
+                .read(..)
+                .await
+        });
+        assert!(futures::poll!(fut.as_mut()).is_pending());
+        clock.advance(backoff);
+        assert!(futures::poll!(fut.as_mut()).is_pending());
+        clock.advance(backoff);
+        assert_eq!(fut.await?.to_vec(), b"Hello");
+
+        Ok(())
+    }
+
+    struct VecLister(VecDeque<oio::Entry>);
+
+    impl oio::List for VecLister {
+        async fn next(&mut self) -> Result<Option<oio::Entry>> {
+            Ok(self.0.pop_front())
+        }
+    }
+
+    async fn collect_dedup(entries: &[(&str, Option<&str>)], sorted: bool) -> Result<Vec<String>> {
+        let entries = entries
+            .iter()
+            .map(|(path, version)| {
+                let mut meta = Metadata::new(EntryMode::FILE);
+                if let Some(v) = version {
+                    meta.set_version(v);
+                }
+                oio::Entry::new(path, meta)
+            })
+            .collect();
+        let mut l = DedupLister::new(VecLister(entries), true, sorted);
+
+        let mut paths = vec![];
+        while let Some(entry) = oio::List::next(&mut l).await? {
+            let version = entry.metadata().version().unwrap_or_default();
+            paths.push(format!("{}@{version}", entry.path()));
+        }
+        Ok(paths)
+    }
+
+    #[tokio::test]
+    async fn test_dedup_lister() -> Result<()> {
+        let paths = collect_dedup(
+            &[
+                ("a", None),
+                ("b", None),
+                ("a", None),
+                ("b", Some("1")),
+                ("c", None),
+                ("b", None),
+            ],
+            false,
+        )
+        .await?;
+        assert_eq!(paths, ["a@", "b@", "b@1", "c@"]);
+
+        let paths =
+            collect_dedup(&[("a", None), ("a", None), ("b", None), ("b", None)], true).await?;
+        assert_eq!(paths, ["a@", "b@"]);
+        Ok(())
+    }
+
+    /// Build a mock service that returns `dir/b` before `dir/a`.
+    #[cfg(feature = "tests")]
+    fn unsorted_list_service(sorted: bool) -> crate::raw::tests::MockAccess {
+        let mock = crate::raw::tests::MockAccess::new().with_capability(|cap| Capability {
+            list_with_sorted: sorted,
+            ..cap
+        });
+        let entries = ["dir/b", "dir/a"]
+            .into_iter()
+            .map(|path| oio::Entry::new(path, Metadata::new(EntryMode::FILE)))
+            .collect();
+        mock.inject_list("dir/", entries);
+        mock
+    }
+
+    #[cfg(feature = "tests")]
+    #[tokio::test]
+    async fn test_list_sorted() -> Result<()> {
+        let op = OperatorBuilder::new(unsorted_list_service(false)).finish();
+        let entries = op.list_with("dir/").sorted(true).await?;
+        let paths: Vec<_> = entries.iter().map(|v| v.path()).collect();
+        assert_eq!(paths, ["dir/a", "dir/b"]);
+
+        // Non-recursive lists are always sorted, even if the service sorts
+        // recursive lists natively.
+        let op = OperatorBuilder::new(unsorted_list_service(true)).finish();
+        let entries = op.list_with("dir/").sorted(true).await?;
+        let paths: Vec<_> = entries.iter().map(|v| v.path()).collect();
+        assert_eq!(paths, ["dir/a", "dir/b"]);
+
+        // Recursive lists of services that sort natively are not buffered and
+        // sorted again.
+        let entries = op.list_with("dir/").recursive(true).sorted(true).await?;
+        let paths: Vec<_> = entries.iter().map(|v| v.path()).collect();
+        assert_eq!(paths, ["dir/b", "dir/a"]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_stat_and_list_normalize_path() -> Result<()> {
+        let op = Operator::new(crate::services::Memory::default())?.finish();
+        op.write("dir/file", "Hello").await?;
+
+        // Call the accessor directly to bypass the normalization of operator.
+        let acc = op.into_inner();
+        let meta = acc.stat("dir//", OpStat::new()).await?.into_metadata();
+        assert!(meta.is_dir());
+        let meta = acc.stat("//dir/file", OpStat::new()).await?.into_metadata();
+        assert_eq!(meta.content_length(), 5);
+        // `.` and `..` are kept as is.
+        let err = acc.stat("dir/x/../", OpStat::new()).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        let (_, mut l) = acc.list("dir//", OpList::new()).await?;
+        let mut paths = vec![];
+        while let Some(e) = oio::List::next(&mut l).await? {
+            paths.push(e.path().to_string());
+        }
+        assert!(paths.contains(&"dir/file".to_string()));
+        Ok(())
+    }
+
+    #[cfg(feature = "tests")]
+    #[tokio::test]
+    async fn test_list_max_entries_limit() -> Result<()> {
+        let mock = unsorted_list_service(false);
+        let op = OperatorBuilder::new(mock.clone()).finish();
+        let entries = op.list_with("dir/").max_entries(1).await?;
+        assert_eq!(entries.len(), 1);
+        // The page size is left to the service if entries could be filtered.
+        op.list_with("dir/").max_entries(1).dirs_only(true).await?;
+        op.list_with("dir/").max_entries(1).limit(10).await?;
+        let limits: Vec<_> = mock.list_args("dir/").iter().map(|v| v.limit()).collect();
+        assert_eq!(limits, [Some(1), None, Some(10)]);
+        Ok(())
+    }
+
+    #[cfg(feature = "tests")]
+    #[tokio::test]
+    async fn test_read_suffix_range() -> Result<()> {
+        use crate::raw::tests::MockAccess;
+
+        // Build a mock service serving suffix range reads of `Hello`, the last
+        // byte will be dropped silently if `lost` is set.
+        let new_operator = |report_size: bool, lost: bool| {
+            let mut mock = MockAccess::new().with_capability(|cap| Capability {
+                read_with_suffix_range: true,
+                ..cap
+            });
+            if report_size {
+                mock = mock.with_read_metadata();
+            }
+            mock.insert("f", "Hello");
+            if lost {
+                mock.inject_corruption(Operation::Read, "f", |bs| {
+                    bs.slice(..bs.len().saturating_sub(1))
+                });
+            }
+            OperatorBuilder::new(mock).finish()
+        };
+
+        let op = new_operator(true, false);
+        assert_eq!(op.read_with("f").suffix_range(3).await?.to_vec(), b"llo");
+        // The whole file is returned if it's shorter than the suffix range.
+        assert_eq!(op.read_with("f").suffix_range(10).await?.to_vec(), b"Hello");
+
+        // Data lost is still detected.
+        let op = new_operator(true, true);
+        assert!(op.read_with("f").suffix_range(3).await.is_err());
+        assert!(op.read_with("f").suffix_range(10).await.is_err());
+
+        // The suffix range is expected in full if the file size is unknown.
+        let op = new_operator(false, false);
+        assert_eq!(op.read_with("f").suffix_range(3).await?.to_vec(), b"llo");
+        assert!(op.read_with("f").suffix_range(10).await.is_err());
+        Ok(())
+    }
+
+    #[cfg(feature = "tests")]
+    #[tokio::test]
+    async fn test_stat_remember_missing_dirs() -> Result<()> {
+        use crate::layers::TypeEraseLayer;
+        use crate::raw::tests::MockAccess;
+        use crate::raw::tests::MockClock;
+
+        let clock = MockClock::new();
+        let mock = MockAccess::new().with_capability(|cap| Capability {
+            create_dir: false,
+            ..cap
+        });
+        let layer = CompleteLayer::default().with_clock(clock.clone());
+        let acc = Arc::new(TypeEraseLayer.layer(layer.layer(mock.clone()))) as Accessor;
+        let op = Operator::from_inner(acc);
+
+        let err = op.stat("dir/").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+        // The missing dir is remembered, list will not be sent again.
+        mock.inject_error(Operation::List, "dir/", 1, || {
+            Error::new(ErrorKind::Unexpected, "list must not be sent")
+        });
+        let err = op.stat("dir/").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        clock.advance(MISSING_DIRS_TTL);
+        let err = op.stat("dir/").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+
+        // Writing files in the dir through the same accessor forgets it.
+        let err = op.stat("dir/").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+        op.write("dir/file", "Hello").await?;
+        assert!(op.stat("dir/").await?.is_dir());
+        Ok(())
+    }
+}
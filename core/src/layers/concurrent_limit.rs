@@ -16,13 +16,20 @@
 // under the License.
 
 use std::fmt::Debug;
+use std::pin::pin;
 use std::pin::Pin;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::task::Context;
 use std::task::Poll;
+use std::time::Duration;
 
+use futures::FutureExt;
 use futures::Stream;
 use futures::StreamExt;
+use tokio::sync::Notify;
 use tokio::sync::OwnedSemaphorePermit;
 use tokio::sync::Semaphore;
 
@@ -82,10 +89,66 @@ use crate::*;
 /// Ok(())
 /// # }
 /// ```
+///
+/// Limit the total in-flight bytes of reads and writes:
+///
+/// ```no_run
+/// # use opendal::layers::ConcurrentLimitLayer;
+/// # use opendal::services;
+/// # use opendal::Operator;
+/// # use opendal::Result;
+///
+/// # fn main() -> Result<()> {
+/// let _ = Operator::new(services::Memory::default())?
+///     .layer(
+///         ConcurrentLimitLayer::new(1024)
+///             .with_byte_budget(256 * 1024 * 1024)
+///             .with_unknown_size_estimate(8 * 1024 * 1024),
+///     )
+///     .finish();
+/// Ok(())
+/// # }
+/// ```
+///
+/// # Drain
+///
+/// The layer also tracks in-flight operations, so that [`Operator::drain`]
+/// can wait for them to finish before shutting down. Operators sharing the
+/// same layer will be drained together.
+///
+/// Drain is a terminal shutdown state: operators drained will reject all new
+/// operations forever. Build new operators with a new layer if needed.
 #[derive(Clone)]
 pub struct ConcurrentLimitLayer {
     operation_semaphore: Arc<Semaphore>,
     http_semaphore: Option<Arc<Semaphore>>,
+    byte_budget: Option<ByteBudget>,
+    unknown_size_estimate: u64,
+    in_flight: Arc<InFlightTracker>,
+}
+
+/// The default bytes reserved for reads and writes without a known size.
+const DEFAULT_UNKNOWN_SIZE_ESTIMATE: u64 = 4 * 1024 * 1024;
+
+#[derive(Debug, Clone)]
+struct ByteBudget {
+    semaphore: Arc<Semaphore>,
+    total: u64,
+}
+
+impl ByteBudget {
+    /// Acquire `size` bytes from the budget.
+    ///
+    /// The size is capped by the total budget so that a single large
+    /// request can't wait forever.
+    async fn acquire(&self, size: u64) -> OwnedSemaphorePermit {
+        let n = size.min(self.total).min(u32::MAX as u64) as u32;
+        self.semaphore
+            .clone()
+            .acquire_many_owned(n)
+            .await
+            .expect("semaphore must be valid")
+    }
 }
 
 impl ConcurrentLimitLayer {
@@ -96,6 +159,9 @@ impl ConcurrentLimitLayer {
         Self {
             operation_semaphore: Arc::new(Semaphore::new(permits)),
             http_semaphore: None,
+            byte_budget: None,
+            unknown_size_estimate: DEFAULT_UNKNOWN_SIZE_ESTIMATE,
+            in_flight: Arc::new(InFlightTracker::new(Arc::new(SystemClock))),
         }
     }
 
@@ -107,6 +173,148 @@ impl ConcurrentLimitLayer {
         self.http_semaphore = Some(Arc::new(Semaphore::new(permits)));
         self
     }
+
+    /// Set a limit for the total in-flight bytes of reads and writes.
+    ///
+    /// Reads will reserve the size of their range, and writes will reserve
+    /// their declared `content_length`. Reads and writes without a known size
+    /// will reserve the unknown size estimate instead.
+    /// The reserved bytes will be released once the reader or writer is
+    /// finished or dropped.
+    ///
+    /// A single request will reserve at most `bytes`.
+    pub fn with_byte_budget(mut self, bytes: u64) -> Self {
+        let total = bytes.min(Semaphore::MAX_PERMITS as u64);
+        self.byte_budget = Some(ByteBudget {
+            semaphore: Arc::new(Semaphore::new(total as usize)),
+            total,
+        });
+        self
+    }
+
+    /// Set the bytes reserved for reads and writes without a known size.
+    ///
+    /// Only takes effect while byte budget is set. Default to 4 MiB.
+    pub fn with_unknown_size_estimate(mut self, bytes: u64) -> Self {
+        self.unknown_size_estimate = bytes;
+        self
+    }
+
+    /// Set the clock used to track the timeout of drain.
+    ///
+    /// It's useful to verify drain in tests without real sleeps, see [`Clock`]
+    /// for more details.
+    pub fn with_clock(mut self, clock: impl Clock) -> Self {
+        self.in_flight = Arc::new(InFlightTracker::new(Arc::new(clock)));
+        self
+    }
+}
+
+/// The summary of [`Operator::drain`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct DrainSummary {
+    /// The number of in-flight operations that finished before the timeout.
+    pub completed: usize,
+    /// The number of in-flight operations that were still running at the
+    /// timeout and have been aborted.
+    pub aborted: usize,
+}
+
+/// InFlightTracker tracks the in-flight operations of operators wrapped by
+/// [`ConcurrentLimitLayer`].
+///
+/// Operations only touch the atomic counters, so they never contend on a lock.
+#[derive(Debug)]
+pub(crate) struct InFlightTracker {
+    clock: Arc<dyn Clock>,
+    running: AtomicUsize,
+    /// New operations will be rejected once draining started.
+    draining: AtomicBool,
+    /// Operations still running will be aborted on their next call.
+    aborted: AtomicBool,
+    notify: Notify,
+}
+
+impl InFlightTracker {
+    fn new(clock: Arc<dyn Clock>) -> Self {
+        Self {
+            clock,
+            running: AtomicUsize::new(0),
+            draining: AtomicBool::new(false),
+            aborted: AtomicBool::new(false),
+            notify: Notify::new(),
+        }
+    }
+
+    /// Start a new operation, returns error if the tracker is draining.
+    fn start(self: &Arc<Self>) -> Result<InFlightGuard> {
+        // Count the operation before checking so that drain either sees it
+        // running or it sees drain started.
+        self.running.fetch_add(1, Ordering::SeqCst);
+        let guard = InFlightGuard(self.clone());
+        if self.draining.load(Ordering::SeqCst) {
+            return Err(Error::new(
+                ErrorKind::Unexpected,
+                "operator has been drained, no new operations are allowed",
+            ));
+        }
+        Ok(guard)
+    }
+
+    fn is_aborted(&self) -> bool {
+        self.aborted.load(Ordering::Acquire)
+    }
+
+    /// Reject new operations and wait for the in-flight ones to finish.
+    ///
+    /// Operations still running after `timeout` will be aborted on their
+    /// next call. The tracker can't be resumed after drain.
+    pub async fn drain(&self, timeout: Duration) -> DrainSummary {
+        self.draining.store(true, Ordering::SeqCst);
+        let total = self.running.load(Ordering::SeqCst);
+
+        let wait = async {
+            loop {
+                let mut notified = pin!(self.notify.notified());
+                // Register the waiter before checking to avoid missing notifications.
+                notified.as_mut().enable();
+                if self.running.load(Ordering::SeqCst) == 0 {
+                    return;
+                }
+                notified.await;
+            }
+        };
+        futures::select! {
+            _ = wait.fuse() => {},
+            _ = self.clock.sleep(timeout).fuse() => {},
+        }
+
+        self.aborted.store(true, Ordering::Release);
+        let running = self.running.load(Ordering::SeqCst);
+        DrainSummary {
+            completed: total.saturating_sub(running),
+            aborted: running,
+        }
+    }
+}
+
+/// InFlightGuard marks an operation as finished once dropped.
+struct InFlightGuard(Arc<InFlightTracker>);
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        if self.0.running.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.0.notify.notify_waiters();
+        }
+    }
+}
+
+fn new_drained_error() -> Error {
+    Error::new(
+        ErrorKind::Unexpected,
+        "operation has been aborted since operator is drained",
+    )
 }
 
 impl<A: Access> Layer<A> for ConcurrentLimitLayer {
@@ -114,6 +322,7 @@ impl<A: Access> Layer<A> for ConcurrentLimitLayer {
 
     fn layer(&self, inner: A) -> Self::LayeredAccess {
         let info = inner.info();
+        info.set_in_flight_tracker(self.in_flight.clone());
 
         // Update http client with metrics http fetcher.
         info.update_http_client(|client| {
@@ -126,6 +335,9 @@ impl<A: Access> Layer<A> for ConcurrentLimitLayer {
         ConcurrentLimitAccessor {
             inner,
             semaphore: self.operation_semaphore.clone(),
+            byte_budget: self.byte_budget.clone(),
+            unknown_size_estimate: self.unknown_size_estimate,
+            in_flight: self.in_flight.clone(),
         }
     }
 }
@@ -179,6 +391,17 @@ where
 pub struct ConcurrentLimitAccessor<A: Access> {
     inner: A,
     semaphore: Arc<Semaphore>,
+    byte_budget: Option<ByteBudget>,
+    unknown_size_estimate: u64,
+    in_flight: Arc<InFlightTracker>,
+}
+
+impl<A: Access> ConcurrentLimitAccessor<A> {
+    async fn acquire_bytes(&self, size: Option<u64>) -> Option<OwnedSemaphorePermit> {
+        let budget = self.byte_budget.as_ref()?;
+        let size = size.unwrap_or(self.unknown_size_estimate);
+        Some(budget.acquire(size).await)
+    }
 }
 
 impl<A: Access> LayeredAccess for ConcurrentLimitAccessor<A> {
@@ -193,6 +416,7 @@ impl<A: Access> LayeredAccess for ConcurrentLimitAccessor<A> {
     }
 
     async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        let _guard = self.in_flight.start()?;
         let _permit = self
             .semaphore
             .acquire()
@@ -203,6 +427,8 @@ impl<A: Access> LayeredAccess for ConcurrentLimitAccessor<A> {
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let guard = self.in_flight.start()?;
+        let byte_permit = self.acquire_bytes(args.range().size()).await;
         let permit = self
             .semaphore
             .clone()
@@ -210,13 +436,15 @@ impl<A: Access> LayeredAccess for ConcurrentLimitAccessor<A> {
             .await
             .expect("semaphore must be valid");
 
-        self.inner
-            .read(path, args)
-            .await
-            .map(|(rp, r)| (rp, ConcurrentLimitWrapper::new(r, permit)))
+        self.inner.read(path, args).await.map(|(rp, r)| {
+            let r = ConcurrentLimitWrapper::new(r, permit, guard).with_byte_permit(byte_permit);
+            (rp, r)
+        })
     }
 
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let guard = self.in_flight.start()?;
+        let byte_permit = self.acquire_bytes(args.content_length()).await;
         let permit = self
             .semaphore
             .clone()
@@ -224,13 +452,14 @@ impl<A: Access> LayeredAccess for ConcurrentLimitAccessor<A> {
             .await
             .expect("semaphore must be valid");
 
-        self.inner
-            .write(path, args)
-            .await
-            .map(|(rp, w)| (rp, ConcurrentLimitWrapper::new(w, permit)))
+        self.inner.write(path, args).await.map(|(rp, w)| {
+            let w = ConcurrentLimitWrapper::new(w, permit, guard).with_byte_permit(byte_permit);
+            (rp, w)
+        })
     }
 
     async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let _guard = self.in_flight.start()?;
         let _permit = self
             .semaphore
             .acquire()
@@ -241,6 +470,7 @@ impl<A: Access> LayeredAccess for ConcurrentLimitAccessor<A> {
     }
 
     async fn delete(&self) -> Result<(RpDelete, Self::Deleter)> {
+        let guard = self.in_flight.start()?;
         let permit = self
             .semaphore
             .clone()
@@ -251,10 +481,11 @@ impl<A: Access> LayeredAccess for ConcurrentLimitAccessor<A> {
         self.inner
             .delete()
             .await
-            .map(|(rp, w)| (rp, ConcurrentLimitWrapper::new(w, permit)))
+            .map(|(rp, w)| (rp, ConcurrentLimitWrapper::new(w, permit, guard)))
     }
 
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        let guard = self.in_flight.start()?;
         let permit = self
             .semaphore
             .clone()
@@ -265,7 +496,7 @@ impl<A: Access> LayeredAccess for ConcurrentLimitAccessor<A> {
         self.inner
             .list(path, args)
             .await
-            .map(|(rp, s)| (rp, ConcurrentLimitWrapper::new(s, permit)))
+            .map(|(rp, s)| (rp, ConcurrentLimitWrapper::new(s, permit, guard)))
     }
 }
 
@@ -274,49 +505,235 @@ pub struct ConcurrentLimitWrapper<R> {
 
     // Hold on this permit until this reader has been dropped.
     _permit: OwnedSemaphorePermit,
+    // Hold on the reserved bytes until this reader has been finished or dropped.
+    byte_permit: Option<OwnedSemaphorePermit>,
+    // Mark this operation as in-flight until it has been finished or dropped.
+    in_flight: Option<InFlightGuard>,
+    drained: bool,
 }
 
 impl<R> ConcurrentLimitWrapper<R> {
-    fn new(inner: R, permit: OwnedSemaphorePermit) -> Self {
+    fn new(inner: R, permit: OwnedSemaphorePermit, in_flight: InFlightGuard) -> Self {
         Self {
             inner,
             _permit: permit,
+            byte_permit: None,
+            in_flight: Some(in_flight),
+            drained: false,
+        }
+    }
+
+    fn with_byte_permit(mut self, byte_permit: Option<OwnedSemaphorePermit>) -> Self {
+        self.byte_permit = byte_permit;
+        self
+    }
+
+    /// Check if this operation has been aborted by drain.
+    fn check_drained(&mut self) -> Result<()> {
+        if !self.drained && self.in_flight.as_ref().is_some_and(|v| v.0.is_aborted()) {
+            self.drained = true;
+            self.in_flight = None;
+            self.byte_permit = None;
+        }
+        if self.drained {
+            return Err(new_drained_error());
         }
+        Ok(())
     }
 }
 
 impl<R: oio::Read> oio::Read for ConcurrentLimitWrapper<R> {
     async fn read(&mut self) -> Result<Buffer> {
-        self.inner.read().await
+        self.check_drained()?;
+        let bs = self.inner.read().await?;
+        if bs.is_empty() {
+            self.byte_permit = None;
+            self.in_flight = None;
+        }
+        Ok(bs)
+    }
+
+    async fn read_into(&mut self, dst: &mut [u8]) -> Result<usize> {
+        self.check_drained()?;
+        let n = self.inner.read_into(dst).await?;
+        if n == 0 && !dst.is_empty() {
+            self.byte_permit = None;
+            self.in_flight = None;
+        }
+        Ok(n)
+    }
+}
+
+impl<R: oio::Write> ConcurrentLimitWrapper<R> {
+    /// Abort the upload if this writer has been aborted by drain, so that
+    /// uploads like multipart won't be left behind.
+    async fn abort_if_drained(&mut self) -> Result<()> {
+        if self.drained {
+            return Err(new_drained_error());
+        }
+        if let Err(err) = self.check_drained() {
+            return match self.inner.abort().await {
+                Ok(()) => Err(err),
+                Err(abort_err) => Err(err.with_context("abort_error", abort_err)),
+            };
+        }
+        Ok(())
     }
 }
 
 impl<R: oio::Write> oio::Write for ConcurrentLimitWrapper<R> {
     async fn write(&mut self, bs: Buffer) -> Result<()> {
+        self.abort_if_drained().await?;
         self.inner.write(bs).await
     }
 
+    async fn flush(&mut self) -> Result<()> {
+        self.abort_if_drained().await?;
+        self.inner.flush().await
+    }
+
     async fn close(&mut self) -> Result<Metadata> {
-        self.inner.close().await
+        self.abort_if_drained().await?;
+        let meta = self.inner.close().await?;
+        self.byte_permit = None;
+        self.in_flight = None;
+        Ok(meta)
     }
 
     async fn abort(&mut self) -> Result<()> {
-        self.inner.abort().await
+        // The upload has been aborted by drain already.
+        if self.drained {
+            return Ok(());
+        }
+        self.inner.abort().await?;
+        self.byte_permit = None;
+        self.in_flight = None;
+        Ok(())
     }
 }
 
 impl<R: oio::List> oio::List for ConcurrentLimitWrapper<R> {
     async fn next(&mut self) -> Result<Option<oio::Entry>> {
-        self.inner.next().await
+        self.check_drained()?;
+        let de = self.inner.next().await?;
+        if de.is_none() {
+            self.in_flight = None;
+        }
+        Ok(de)
     }
 }
 
 impl<R: oio::Delete> oio::Delete for ConcurrentLimitWrapper<R> {
     fn delete(&mut self, path: &str, args: OpDelete) -> Result<()> {
+        self.check_drained()?;
         self.inner.delete(path, args)
     }
 
     async fn flush(&mut self) -> Result<usize> {
+        self.check_drained()?;
         self.inner.flush().await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::services;
+    use crate::Operator;
+
+    #[tokio::test]
+    async fn test_byte_budget() -> Result<()> {
+        let op = Operator::new(services::Memory::default())?
+            .layer(ConcurrentLimitLayer::new(1024).with_byte_budget(16))
+            .finish();
+        op.write("test", vec![0; 16]).await?;
+
+        let acc = op.into_inner();
+        let read = |size: u64| {
+            let args = OpRead::new().with_range(BytesRange::new(0, Some(size)));
+            tokio::time::timeout(Duration::from_millis(100), acc.read("test", args))
+        };
+
+        let (_, reader) = read(16).await.expect("must not be blocked")?;
+
+        // The budget has been exhausted by the first reader.
+        assert!(read(8).await.is_err());
+
+        // Dropping the reader should release the budget.
+        drop(reader);
+        assert!(read(8).await.is_ok());
+
+        // Writes reserve their declared size.
+        let args = OpWrite::new().with_content_length(12);
+        let (_, mut writer) = acc.write("test", args).await?;
+        assert!(read(8).await.is_err());
+        assert!(read(4).await.is_ok());
+        oio::Write::abort(&mut writer).await?;
+        assert!(read(8).await.is_ok());
+
+        Ok(())
+    }
+
+    #[cfg(feature = "tests")]
+    #[tokio::test]
+    async fn test_drain() -> Result<()> {
+        use crate::raw::tests::MockClock;
+
+        let clock = MockClock::new();
+        let op = Operator::new(services::Memory::default())?
+            .layer(ConcurrentLimitLayer::new(1024).with_clock(clock.clone()))
+            .finish();
+        op.write("test", "Hello, World!").await?;
+
+        let mut finished = op.writer("finished").await?;
+        let mut running = op.writer("running").await?;
+        running.write("Hello").await?;
+
+        let mut drain = pin!(op.drain(Duration::from_secs(60)));
+        assert!(futures::poll!(drain.as_mut()).is_pending());
+        finished.close().await?;
+        assert!(futures::poll!(drain.as_mut()).is_pending());
+
+        clock.advance(Duration::from_secs(60));
+        let summary = drain.await?;
+        assert_eq!(summary.completed, 1);
+        assert_eq!(summary.aborted, 1);
+
+        // The running writer has been aborted.
+        assert!(running.write("World").await.is_err());
+        assert!(!op.exists("running").await.unwrap_or_default());
+
+        // New operations will be rejected.
+        assert!(op.read("test").await.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_drain_finished_reader_and_lister() -> Result<()> {
+        let op = Operator::new(services::Memory::default())?
+            .layer(ConcurrentLimitLayer::new(1024))
+            .finish();
+        op.write("dir/test", "Hello, World!").await?;
+
+        // Readers and listers that reached the end are no longer in-flight
+        // even if they are still alive.
+        let acc = op.clone().into_inner();
+        let (_, mut reader) = acc.read("dir/test", OpRead::new()).await?;
+        oio::Read::read_all(&mut reader).await?;
+        let (_, mut lister) = acc.list("dir/", OpList::new()).await?;
+        while oio::List::next(&mut lister).await?.is_some() {}
+
+        let Poll::Ready(summary) = futures::poll!(pin!(op.drain(Duration::from_secs(60)))) else {
+            panic!("drain must not wait for finished operations");
+        };
+        let summary = summary?;
+        assert_eq!(summary.completed, 0);
+        assert_eq!(summary.aborted, 0);
+
+        drop(reader);
+        drop(lister);
+        Ok(())
+    }
+}
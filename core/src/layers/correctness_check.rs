@@ -62,6 +62,31 @@ pub(crate) fn new_unsupported_error(info: &AccessorInfo, op: Operation, args: &s
     .with_operation(op)
 }
 
+/// Check extra headers against the capability and reserved headers.
+fn check_headers(
+    info: &AccessorInfo,
+    op: Operation,
+    supported: bool,
+    headers: Option<&http::HeaderMap>,
+) -> Result<()> {
+    let Some(headers) = headers.filter(|v| !v.is_empty()) else {
+        return Ok(());
+    };
+    if !supported {
+        return Err(new_unsupported_error(info, op, "headers"));
+    }
+    if let Some(name) = headers.keys().find(|name| is_reserved_header(name)) {
+        return Err(Error::new(
+            ErrorKind::Unsupported,
+            "reserved header can't be set as extra header",
+        )
+        .with_operation(op)
+        .with_context("service", info.scheme())
+        .with_context("header", name.as_str()));
+    }
+    Ok(())
+}
+
 pub struct CorrectnessAccessor<A: Access> {
     info: Arc<AccessorInfo>,
     inner: A,
@@ -127,6 +152,26 @@ impl<A: Access> LayeredAccess for CorrectnessAccessor<A> {
                 "if_unmodified_since",
             ));
         }
+        if !capability.read_with_multi_range && !args.ranges().is_empty() {
+            return Err(new_unsupported_error(
+                self.info.as_ref(),
+                Operation::Read,
+                "ranges",
+            ));
+        }
+        if !capability.read_with_decompress && args.decompress() {
+            return Err(new_unsupported_error(
+                self.info.as_ref(),
+                Operation::Read,
+                "decompress",
+            ));
+        }
+        check_headers(
+            &self.info,
+            Operation::Read,
+            capability.read_with_headers,
+            args.headers(),
+        )?;
 
         self.inner.read(path, args).await
     }
@@ -158,10 +203,89 @@ impl<A: Access> LayeredAccess for CorrectnessAccessor<A> {
                 return Err(err);
             }
         }
+        if args.if_match().is_some() && !capability.write_with_if_match {
+            return Err(new_unsupported_error(
+                self.info.as_ref(),
+                Operation::Write,
+                "if_match",
+            ));
+        }
+        if args.encryption().is_some() && !capability.write_with_encryption {
+            return Err(new_unsupported_error(
+                self.info.as_ref(),
+                Operation::Write,
+                "encryption",
+            ));
+        }
+        if args.user_metadata().is_some_and(|v| !v.is_empty())
+            && !capability.write_with_user_metadata
+        {
+            return Err(new_unsupported_error(
+                self.info.as_ref(),
+                Operation::Write,
+                "user_metadata",
+            ));
+        }
+        if args.storage_class().is_some() && !capability.write_with_storage_class {
+            return Err(new_unsupported_error(
+                self.info.as_ref(),
+                Operation::Write,
+                "storage_class",
+            ));
+        }
+        if args.retention().is_some() && !capability.write_with_retention {
+            return Err(new_unsupported_error(
+                self.info.as_ref(),
+                Operation::Write,
+                "retention",
+            ));
+        }
+        if args.legal_hold().is_some() && !capability.write_with_legal_hold {
+            return Err(new_unsupported_error(
+                self.info.as_ref(),
+                Operation::Write,
+                "legal_hold",
+            ));
+        }
+        check_headers(
+            &self.info,
+            Operation::Write,
+            capability.write_with_headers,
+            args.headers(),
+        )?;
 
         self.inner.write(path, args).await
     }
 
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let capability = self.info.full_capability();
+        if args.metadata_directive() == options::MetadataDirective::Replace
+            && !capability.copy_with_metadata_directive
+        {
+            return Err(new_unsupported_error(
+                self.info.as_ref(),
+                Operation::Copy,
+                "metadata_directive",
+            ));
+        }
+
+        self.inner.copy(from, to, args).await
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        // Rename must be atomic, so it's never emulated by copy and delete.
+        if !self.info.full_capability().rename {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "service doesn't support native rename, use copy and delete instead if non-atomic rename is acceptable",
+            )
+            .with_operation(Operation::Rename)
+            .with_context("service", self.info.scheme()));
+        }
+
+        self.inner.rename(from, to, args).await
+    }
+
     async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
         let capability = self.info.full_capability();
         if !capability.stat_with_version && args.version().is_some() {
@@ -171,6 +295,13 @@ impl<A: Access> LayeredAccess for CorrectnessAccessor<A> {
                 "version",
             ));
         }
+        if !capability.stat_with_tags && args.tags() {
+            return Err(new_unsupported_error(
+                self.info.as_ref(),
+                Operation::Stat,
+                "tags",
+            ));
+        }
         if !capability.stat_with_if_match && args.if_match().is_some() {
             return Err(new_unsupported_error(
                 self.info.as_ref(),
@@ -199,6 +330,12 @@ impl<A: Access> LayeredAccess for CorrectnessAccessor<A> {
                 "if_unmodified_since",
             ));
         }
+        check_headers(
+            &self.info,
+            Operation::Stat,
+            capability.stat_with_headers,
+            args.headers(),
+        )?;
 
         self.inner.stat(path, args).await
     }
@@ -233,6 +370,27 @@ impl<T> CheckWrapper<T> {
                 "version",
             ));
         }
+        if args.recursive() == Some(true) && !self.info.full_capability().delete_with_recursive {
+            return Err(new_unsupported_error(
+                &self.info,
+                Operation::Delete,
+                "recursive",
+            ));
+        }
+        if args.must_exist() && !self.info.full_capability().delete_with_must_exist {
+            return Err(new_unsupported_error(
+                &self.info,
+                Operation::Delete,
+                "must_exist",
+            ));
+        }
+        if args.if_match().is_some() && !self.info.full_capability().delete_with_if_match {
+            return Err(new_unsupported_error(
+                &self.info,
+                Operation::Delete,
+                "if_match",
+            ));
+        }
 
         Ok(())
     }
@@ -251,6 +409,8 @@ impl<T: oio::Delete> oio::Delete for CheckWrapper<T> {
 
 #[cfg(test)]
 mod tests {
+    use chrono::Utc;
+
     use super::*;
     use crate::raw::oio;
     use crate::Capability;
@@ -296,6 +456,10 @@ mod tests {
         async fn delete(&self) -> Result<(RpDelete, Self::Deleter)> {
             Ok((RpDelete::default(), Box::new(MockDeleter)))
         }
+
+        async fn copy(&self, _: &str, _: &str, _: OpCopy) -> Result<RpCopy> {
+            Ok(RpCopy::default())
+        }
     }
 
     struct MockWriter;
@@ -361,6 +525,10 @@ mod tests {
         assert!(res.is_err());
         assert_eq!(res.unwrap_err().kind(), ErrorKind::Unsupported);
 
+        let res = op.stat_with("path").tags(true).await;
+        assert!(res.is_err());
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::Unsupported);
+
         let op = new_test_operator(Capability {
             stat: true,
             stat_with_version: true,
@@ -370,6 +538,37 @@ mod tests {
         assert!(res.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_headers() {
+        let mut headers = http::HeaderMap::new();
+        headers.insert("x-tenant-id", "tenant".parse().unwrap());
+
+        let op = new_test_operator(Capability {
+            read: true,
+            stat: true,
+            ..Default::default()
+        });
+        let res = op.read_with("path").headers(headers.clone()).await;
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::Unsupported);
+        let res = op.stat_with("path").headers(headers.clone()).await;
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::Unsupported);
+
+        let op = new_test_operator(Capability {
+            read: true,
+            read_with_headers: true,
+            stat: true,
+            stat_with_headers: true,
+            ..Default::default()
+        });
+        assert!(op.read_with("path").headers(headers.clone()).await.is_ok());
+        assert!(op.stat_with("path").headers(headers.clone()).await.is_ok());
+
+        // Reserved headers can't be set.
+        headers.insert(http::header::AUTHORIZATION, "token".parse().unwrap());
+        let res = op.read_with("path").headers(headers).await;
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::Unsupported);
+    }
+
     #[tokio::test]
     async fn test_write_with() {
         let op = new_test_operator(Capability {
@@ -402,6 +601,42 @@ mod tests {
              "Unsupported (permanent) at write, context: { hint: use if_not_exists instead } => The service memory does not support the operation write with the arguments if_none_match. Please verify if the relevant flags have been enabled, or submit an issue if you believe this is incorrect."
         );
 
+        let res = op.write_with("path", "".as_bytes()).if_match("etag").await;
+        assert!(res.is_err());
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::Unsupported);
+
+        let res = op
+            .write_with("path", "".as_bytes())
+            .encryption(Encryption::SseS3)
+            .await;
+        assert!(res.is_err());
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::Unsupported);
+
+        let res = op
+            .write_with("path", "".as_bytes())
+            .user_metadata([("key".to_string(), "value".to_string())])
+            .await;
+        assert!(res.is_err());
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::Unsupported);
+
+        let res = op
+            .write_with("path", "".as_bytes())
+            .storage_class("STANDARD_IA")
+            .await;
+        assert!(res.is_err());
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::Unsupported);
+
+        let res = op
+            .write_with("path", "".as_bytes())
+            .retention(Utc::now(), RetentionMode::Compliance)
+            .await;
+        assert!(res.is_err());
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::Unsupported);
+
+        let res = op.write_with("path", "".as_bytes()).legal_hold(true).await;
+        assert!(res.is_err());
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::Unsupported);
+
         let res = op
             .write_with("path", "".as_bytes())
             .if_not_exists(true)
@@ -419,6 +654,37 @@ mod tests {
         assert!(res.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_copy() {
+        let op = new_test_operator(Capability {
+            copy: true,
+            ..Default::default()
+        });
+        let res = op
+            .copy_with("from", "to")
+            .metadata_directive(options::MetadataDirective::Replace)
+            .await;
+        assert!(res.is_err());
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::Unsupported);
+
+        let res = op
+            .copy_with("from", "to")
+            .metadata_directive(options::MetadataDirective::Copy)
+            .await;
+        assert!(res.is_ok());
+
+        let op = new_test_operator(Capability {
+            copy: true,
+            copy_with_metadata_directive: true,
+            ..Default::default()
+        });
+        let res = op
+            .copy_with("from", "to")
+            .metadata_directive(options::MetadataDirective::Replace)
+            .await;
+        assert!(res.is_ok());
+    }
+
     #[tokio::test]
     async fn test_delete() {
         let op = new_test_operator(Capability {
@@ -435,6 +701,65 @@ mod tests {
             ..Default::default()
         });
         let res = op.delete_with("path").version("version").await;
+        assert!(res.is_ok());
+
+        let res = op.delete_with("path/").recursive(true).await;
+        assert!(res.is_err());
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::Unsupported);
+
+        let op = new_test_operator(Capability {
+            delete: true,
+            delete_with_recursive: true,
+            ..Default::default()
+        });
+        let res = op.delete_with("path/").recursive(true).await;
+        assert!(res.is_ok());
+
+        let res = op.delete_with("path").must_exist(true).await;
+        assert!(res.is_err());
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::Unsupported);
+
+        let op = new_test_operator(Capability {
+            delete: true,
+            delete_with_must_exist: true,
+            ..Default::default()
+        });
+        let res = op.delete_with("path").must_exist(true).await;
+        assert!(res.is_ok());
+
+        let res = op.delete_with("path").if_match("etag").await;
+        assert!(res.is_err());
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::Unsupported);
+
+        let op = new_test_operator(Capability {
+            delete: true,
+            delete_with_if_match: true,
+            ..Default::default()
+        });
+        let res = op.delete_with("path").if_match("etag").await;
         assert!(res.is_ok())
     }
+
+    #[cfg(feature = "tests")]
+    #[tokio::test]
+    async fn test_rename_unsupported() -> Result<()> {
+        use crate::raw::tests::MockAccess;
+        use crate::OperatorBuilder;
+
+        let mock = MockAccess::new().with_capability(|cap| Capability {
+            rename: false,
+            ..cap
+        });
+        mock.insert("from", "Hello");
+        let op = OperatorBuilder::new(mock.clone()).finish();
+        assert!(op.info().full_capability().copy);
+
+        // Rename is never emulated by copy and delete.
+        let err = op.rename("from", "to").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        assert_eq!(mock.calls(Operation::Copy, "from"), 0);
+        assert!(op.exists("from").await?);
+        assert!(!op.exists("to").await?);
+        Ok(())
+    }
 }
@@ -0,0 +1,561 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::mem;
+
+use bytes::Buf;
+use bytes::Bytes;
+use miniz_oxide::inflate::stream::inflate;
+use miniz_oxide::inflate::stream::InflateState;
+use miniz_oxide::DataFormat;
+use miniz_oxide::MZError;
+use miniz_oxide::MZFlush;
+use miniz_oxide::MZStatus;
+use zstd::stream::raw::Operation;
+
+use crate::raw::*;
+use crate::*;
+
+/// The size of buffer used to hold decompressed data for every read.
+const DECOMPRESS_BUFFER_SIZE: usize = 64 * 1024;
+
+const GZIP_HEADER_SIZE: usize = 10;
+const GZIP_TRAILER_SIZE: usize = 8;
+const GZIP_FHCRC: u8 = 0x02;
+const GZIP_FEXTRA: u8 = 0x04;
+const GZIP_FNAME: u8 = 0x08;
+const GZIP_FCOMMENT: u8 = 0x10;
+
+/// Compression codecs supported by [`DecompressLayer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DecompressCodec {
+    /// Gzip, possibly with multiple members.
+    Gzip,
+    /// Zstandard, possibly with multiple frames.
+    Zstd,
+}
+
+impl DecompressCodec {
+    /// Detect the codec from the value of `Content-Encoding`.
+    ///
+    /// Returns `None` if the encoding is unknown or `identity`.
+    pub fn from_content_encoding(v: &str) -> Option<Self> {
+        match v.trim().to_ascii_lowercase().as_str() {
+            "gzip" | "x-gzip" => Some(Self::Gzip),
+            "zstd" => Some(Self::Zstd),
+            _ => None,
+        }
+    }
+}
+
+/// Decompress the content of objects on the fly while reading.
+///
+/// # Notes
+///
+/// By default, `DecompressLayer` will stat the object before every read and pick the
+/// codec by its `Content-Encoding`, objects with unknown encoding are returned as is.
+/// Use [`DecompressLayer::with_codec`] to decompress all objects with the given codec
+/// and skip the extra stat.
+///
+/// - The whole object is fetched and decompressed, then the range of read is
+///   applied to the decompressed content.
+/// - Multi-member gzip and multi-frame zstd are supported, a truncated stream
+///   will fail the read instead of returning partial content.
+/// - `stat` still returns the size of the compressed object. Readers with `chunk`
+///   set must be given an explicit range.
+/// - Reading with suffix range is not supported for compressed objects.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use opendal::layers::DecompressCodec;
+/// # use opendal::layers::DecompressLayer;
+/// # use opendal::services;
+/// # use opendal::Operator;
+/// # use opendal::Result;
+///
+/// # fn main() -> Result<()> {
+/// let _ = Operator::new(services::Memory::default())?
+///     .layer(DecompressLayer::new().with_codec(DecompressCodec::Gzip))
+///     .finish();
+/// Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DecompressLayer {
+    codec: Option<DecompressCodec>,
+}
+
+impl DecompressLayer {
+    /// Create a new `DecompressLayer` that detects codec by `Content-Encoding`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Decompress all objects with given codec instead of detecting it.
+    pub fn with_codec(mut self, codec: DecompressCodec) -> Self {
+        self.codec = Some(codec);
+        self
+    }
+}
+
+impl<A: Access> Layer<A> for DecompressLayer {
+    type LayeredAccess = DecompressAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccess {
+        DecompressAccessor {
+            inner,
+            codec: self.codec,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct DecompressAccessor<A: Access> {
+    inner: A,
+    codec: Option<DecompressCodec>,
+}
+
+impl<A: Access> DecompressAccessor<A> {
+    async fn detect_codec(&self, path: &str, args: &OpRead) -> Result<Option<DecompressCodec>> {
+        if self.codec.is_some() {
+            return Ok(self.codec);
+        }
+
+        let mut op_stat = OpStat::new();
+        if let Some(v) = args.version() {
+            op_stat = op_stat.with_version(v);
+        }
+        let meta = self.inner.stat(path, op_stat).await?.into_metadata();
+        Ok(meta
+            .content_encoding()
+            .and_then(DecompressCodec::from_content_encoding))
+    }
+}
+
+impl<A: Access> LayeredAccess for DecompressAccessor<A> {
+    type Inner = A;
+    type Reader = TwoWays<A::Reader, DecompressReader<A::Reader>>;
+    type Writer = A::Writer;
+    type Lister = A::Lister;
+    type Deleter = A::Deleter;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let Some(codec) = self.detect_codec(path, &args).await? else {
+            let (rp, r) = self.inner.read(path, args).await?;
+            return Ok((rp, TwoWays::One(r)));
+        };
+
+        if args.suffix_range().is_some() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "suffix range is not supported for compressed objects",
+            )
+            .with_context("path", path));
+        }
+
+        // The range is applied on decompressed content, so we must fetch the whole object.
+        let range = args.range();
+        let args = args.with_range(BytesRange::default());
+        let (_, r) = self.inner.read(path, args).await?;
+        let r = DecompressReader::new(r, codec, range.offset(), range.size())?;
+        Ok((RpRead::new(), TwoWays::Two(r)))
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.inner.write(path, args).await
+    }
+
+    async fn delete(&self) -> Result<(RpDelete, Self::Deleter)> {
+        self.inner.delete().await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        self.inner.list(path, args).await
+    }
+}
+
+fn invalid_stream(msg: &'static str, codec: DecompressCodec) -> Error {
+    Error::new(ErrorKind::Unexpected, msg).with_context("codec", format!("{codec:?}"))
+}
+
+/// Parse the gzip member header, returns the size of header or `None` if
+/// more data is required.
+fn parse_gzip_header(bs: &[u8]) -> Result<Option<usize>> {
+    if bs.len() < GZIP_HEADER_SIZE {
+        return Ok(None);
+    }
+    if bs[0] != 0x1f || bs[1] != 0x8b || bs[2] != 8 {
+        return Err(invalid_stream(
+            "gzip member header is invalid",
+            DecompressCodec::Gzip,
+        ));
+    }
+
+    let flags = bs[3];
+    let mut pos = GZIP_HEADER_SIZE;
+    if flags & GZIP_FEXTRA != 0 {
+        let Some(xlen) = bs.get(pos..pos + 2) else {
+            return Ok(None);
+        };
+        pos += 2 + u16::from_le_bytes([xlen[0], xlen[1]]) as usize;
+    }
+    for flag in [GZIP_FNAME, GZIP_FCOMMENT] {
+        if flags & flag != 0 {
+            // Skip the zero-terminated string.
+            let Some(end) = bs.get(pos..).and_then(|v| v.iter().position(|b| *b == 0)) else {
+                return Ok(None);
+            };
+            pos += end + 1;
+        }
+    }
+    if flags & GZIP_FHCRC != 0 {
+        pos += 2;
+    }
+
+    Ok((pos <= bs.len()).then_some(pos))
+}
+
+enum GzipState {
+    /// The header bytes of current member collected so far.
+    Header(Vec<u8>),
+    Body {
+        state: Box<InflateState>,
+        crc: crc32fast::Hasher,
+        size: u32,
+    },
+    /// The trailer bytes collected so far with the expected CRC32 and size.
+    Trailer(Vec<u8>, u32, u32),
+    /// A member has been finished, new data means another member.
+    End,
+}
+
+enum Decoder {
+    Gzip(GzipState),
+    Zstd {
+        decoder: Box<zstd::stream::raw::Decoder<'static>>,
+        /// Whether we are at the end of a frame.
+        finished: bool,
+    },
+}
+
+impl Decoder {
+    fn new(codec: DecompressCodec) -> Result<Self> {
+        match codec {
+            DecompressCodec::Gzip => Ok(Self::Gzip(GzipState::Header(vec![]))),
+            DecompressCodec::Zstd => {
+                let decoder = zstd::stream::raw::Decoder::new().map_err(|err| {
+                    Error::new(ErrorKind::Unexpected, "create zstd decoder failed").set_source(err)
+                })?;
+                Ok(Self::Zstd {
+                    decoder: Box::new(decoder),
+                    finished: false,
+                })
+            }
+        }
+    }
+
+    fn codec(&self) -> DecompressCodec {
+        match self {
+            Self::Gzip(_) => DecompressCodec::Gzip,
+            Self::Zstd { .. } => DecompressCodec::Zstd,
+        }
+    }
+
+    /// Decode input into output, returns the bytes consumed and written.
+    fn decode(&mut self, input: &[u8], output: &mut [u8]) -> Result<(usize, usize)> {
+        match self {
+            Self::Gzip(state) => decode_gzip(state, input, output),
+            Self::Zstd { decoder, finished } => {
+                let status = decoder.run_on_buffers(input, output).map_err(|err| {
+                    invalid_stream("zstd stream is corrupted", DecompressCodec::Zstd)
+                        .set_source(err)
+                })?;
+                if status.bytes_read > 0 || status.bytes_written > 0 {
+                    *finished = status.remaining == 0;
+                }
+                Ok((status.bytes_read, status.bytes_written))
+            }
+        }
+    }
+
+    /// Check whether the stream ends at a member or frame boundary.
+    fn finish(&self) -> Result<()> {
+        let finished = match self {
+            Self::Gzip(state) => matches!(state, GzipState::End),
+            Self::Zstd { finished, .. } => *finished,
+        };
+        if finished {
+            Ok(())
+        } else {
+            Err(invalid_stream(
+                "compressed stream is truncated",
+                self.codec(),
+            ))
+        }
+    }
+}
+
+fn decode_gzip(state: &mut GzipState, input: &[u8], output: &mut [u8]) -> Result<(usize, usize)> {
+    loop {
+        match state {
+            GzipState::End if input.is_empty() => return Ok((0, 0)),
+            GzipState::End => *state = GzipState::Header(vec![]),
+            GzipState::Header(header) => {
+                let prev = header.len();
+                header.extend_from_slice(input);
+                let Some(n) = parse_gzip_header(header)? else {
+                    return Ok((input.len(), 0));
+                };
+                *state = GzipState::Body {
+                    state: InflateState::new_boxed(DataFormat::Raw),
+                    crc: crc32fast::Hasher::new(),
+                    size: 0,
+                };
+                return Ok((n - prev, 0));
+            }
+            GzipState::Body {
+                state: st,
+                crc,
+                size,
+            } => {
+                let res = inflate(st, input, output, MZFlush::None);
+                crc.update(&output[..res.bytes_written]);
+                *size = size.wrapping_add(res.bytes_written as u32);
+                match res.status {
+                    Ok(MZStatus::StreamEnd) => {
+                        *state = GzipState::Trailer(vec![], crc.clone().finalize(), *size);
+                    }
+                    Ok(_) | Err(MZError::Buf) => {}
+                    Err(err) => {
+                        return Err(invalid_stream(
+                            "gzip stream is corrupted",
+                            DecompressCodec::Gzip,
+                        )
+                        .with_context("error", format!("{err:?}")));
+                    }
+                }
+                return Ok((res.bytes_consumed, res.bytes_written));
+            }
+            GzipState::Trailer(trailer, crc, size) => {
+                let n = (GZIP_TRAILER_SIZE - trailer.len()).min(input.len());
+                trailer.extend_from_slice(&input[..n]);
+                if trailer.len() == GZIP_TRAILER_SIZE {
+                    let expected_crc = u32::from_le_bytes(trailer[0..4].try_into().unwrap());
+                    let expected_size = u32::from_le_bytes(trailer[4..8].try_into().unwrap());
+                    if expected_crc != *crc || expected_size != *size {
+                        return Err(invalid_stream(
+                            "gzip member checksum mismatch",
+                            DecompressCodec::Gzip,
+                        ));
+                    }
+                    *state = GzipState::End;
+                }
+                return Ok((n, 0));
+            }
+        }
+    }
+}
+
+/// DecompressReader decompresses the whole object and returns the requested
+/// range of decompressed content.
+pub struct DecompressReader<R> {
+    inner: R,
+    decoder: Decoder,
+    /// The compressed data that has not been consumed.
+    input: Buffer,
+    eof: bool,
+    /// The decompressed bytes to skip before the requested range.
+    skip: u64,
+    /// The decompressed bytes left in the requested range, `None` means read to the end.
+    remaining: Option<u64>,
+    /// The decompressed bytes returned so far.
+    read: u64,
+    finished: bool,
+    /// The decompressed data left by `read_into`.
+    pending: Buffer,
+}
+
+impl<R> Debug for DecompressReader<R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DecompressReader")
+            .field("codec", &self.decoder.codec())
+            .field("read", &self.read)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<R> DecompressReader<R> {
+    pub(crate) fn new(
+        inner: R,
+        codec: DecompressCodec,
+        offset: u64,
+        size: Option<u64>,
+    ) -> Result<Self> {
+        Ok(Self {
+            inner,
+            decoder: Decoder::new(codec)?,
+            input: Buffer::new(),
+            eof: false,
+            skip: offset,
+            remaining: size,
+            read: 0,
+            finished: false,
+            pending: Buffer::new(),
+        })
+    }
+}
+
+impl<R: oio::Read> oio::Read for DecompressReader<R> {
+    async fn read(&mut self) -> Result<Buffer> {
+        if !self.pending.is_empty() {
+            return Ok(mem::take(&mut self.pending));
+        }
+
+        loop {
+            if self.finished || self.remaining == Some(0) {
+                return Ok(Buffer::new());
+            }
+
+            if self.input.is_empty() && !self.eof {
+                self.input = self.inner.read().await?;
+                self.eof = self.input.is_empty();
+            }
+
+            let mut output = vec![0; DECOMPRESS_BUFFER_SIZE];
+            let (consumed, written) = self.decoder.decode(self.input.chunk(), &mut output)?;
+            self.input.advance(consumed);
+
+            if consumed == 0 && written == 0 {
+                if !self.eof {
+                    return Err(invalid_stream(
+                        "compressed stream is corrupted",
+                        self.decoder.codec(),
+                    ));
+                }
+
+                self.decoder.finish()?;
+                self.finished = true;
+                if let Some(size) = self.remaining {
+                    return Err(
+                        Error::new(ErrorKind::Unexpected, "reader got too little data")
+                            .with_context("expect", self.read + size)
+                            .with_context("actual", self.read),
+                    );
+                }
+                return Ok(Buffer::new());
+            }
+
+            let skipped = self.skip.min(written as u64);
+            self.skip -= skipped;
+            let start = skipped as usize;
+            let end = match self.remaining {
+                Some(size) => start + size.min((written - start) as u64) as usize,
+                None => written,
+            };
+            if let Some(size) = self.remaining.as_mut() {
+                *size -= (end - start) as u64;
+            }
+            self.read += (end - start) as u64;
+            if end > start {
+                return Ok(Buffer::from(Bytes::from(output).slice(start..end)));
+            }
+        }
+    }
+
+    async fn read_into(&mut self, dst: &mut [u8]) -> Result<usize> {
+        if dst.is_empty() {
+            return Ok(0);
+        }
+
+        // Keep the decompressed data that `dst` can't hold for the next call.
+        if self.pending.is_empty() {
+            self.pending = self.read().await?;
+        }
+        let n = self.pending.len().min(dst.len());
+        self.pending.copy_to_slice(&mut dst[..n]);
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+    use crate::services;
+
+    fn gzip(content: &[u8]) -> Vec<u8> {
+        let mut bs = vec![0x1f, 0x8b, 8, GZIP_FNAME, 0, 0, 0, 0, 0, 0xff];
+        bs.write_all(b"test.txt\0").unwrap();
+        bs.extend(miniz_oxide::deflate::compress_to_vec(content, 6));
+        bs.extend(crc32fast::hash(content).to_le_bytes());
+        bs.extend((content.len() as u32).to_le_bytes());
+        bs
+    }
+
+    #[tokio::test]
+    async fn test_decompress() -> Result<()> {
+        let op = Operator::new(services::Memory::default())?
+            .layer(DecompressLayer::new())
+            .finish();
+
+        // Multi-member gzip.
+        let mut bs = gzip(b"Hello, ");
+        bs.extend(gzip(b"World!"));
+        op.write_with("test.gz", bs.clone())
+            .content_encoding("gzip")
+            .await?;
+        assert_eq!(op.read("test.gz").await?.to_vec(), b"Hello, World!");
+        assert_eq!(
+            op.read_with("test.gz").range(3..9).await?.to_vec(),
+            b"lo, Wo"
+        );
+
+        // Multi-frame zstd.
+        let mut zs = zstd::bulk::compress(b"Hello, ", 3).unwrap();
+        zs.extend(zstd::bulk::compress(b"World!", 3).unwrap());
+        op.write_with("test.zst", zs.clone())
+            .content_encoding("zstd")
+            .await?;
+        assert_eq!(op.read("test.zst").await?.to_vec(), b"Hello, World!");
+
+        // Objects without encoding are returned as is.
+        op.write("test.txt", bs.clone()).await?;
+        assert_eq!(op.read("test.txt").await?.to_vec(), bs);
+
+        // Truncated streams must fail.
+        op.write_with("truncated.gz", bs[..bs.len() - 4].to_vec())
+            .content_encoding("gzip")
+            .await?;
+        let err = op.read("truncated.gz").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        op.write_with("truncated.zst", zs[..zs.len() - 4].to_vec())
+            .content_encoding("zstd")
+            .await?;
+        let err = op.read("truncated.zst").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        Ok(())
+    }
+}
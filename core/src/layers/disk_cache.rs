@@ -0,0 +1,662 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use futures::TryStreamExt;
+use log::warn;
+use md5::Digest;
+
+use crate::raw::*;
+use crate::*;
+
+/// The default max size of a single read to be cached.
+const DEFAULT_MAX_OBJECT_SIZE: u64 = 64 * 1024 * 1024;
+
+/// Add a read-through cache backed by another operator.
+///
+/// # Notes
+///
+/// `DiskCacheLayer` will check the cache operator before reading from the
+/// underlying service, and populate the cache on miss. The cache operator can
+/// be any service, for example, `fs` to cache on local disk, so cached objects
+/// survive process restarts.
+///
+/// - Cached data are keyed by path and range plus the `ETag` returned by `stat`.
+///   If the service doesn't return `ETag`, `content_length` and `last_modified`
+///   will be used instead. Objects without any of them will not be cached.
+/// - `stat` is only sent once per reader, chunked and concurrent reads of the
+///   same reader share the result.
+/// - Only the requested range will be fetched and cached on miss, so reads of
+///   the same range will hit the cache. Reads larger than `max_object_size`
+///   will be passed through without caching, since a missed read is buffered
+///   in memory before being cached.
+/// - Reads with conditions, version, overrides or extra headers will be passed through.
+/// - `write`, `delete`, `copy` and `rename` through the same operator will
+///   invalidate the cached copies of affected paths.
+/// - Failures of the cache operator will be logged and never fail the read.
+///
+/// If `max_size` is set, the sizes of cached objects will be tracked in memory,
+/// and the oldest cached objects will be evicted once the total size exceeds
+/// `max_size`. Objects cached before the process started are loaded by listing
+/// the cache operator once. Eviction runs in background on the executor of the
+/// cache operator, so it never blocks reads.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use opendal::layers::DiskCacheLayer;
+/// # use opendal::services;
+/// # use opendal::Operator;
+/// # use opendal::Result;
+///
+/// # fn main() -> Result<()> {
+/// // Use `services::Fs` to cache on local disk in real world.
+/// let cache = Operator::new(services::Memory::default())?.finish();
+/// let _ = Operator::new(services::Memory::default())?
+///     .layer(DiskCacheLayer::new(cache).with_max_size(1024 * 1024 * 1024))
+///     .finish();
+/// Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct DiskCacheLayer {
+    cache: Arc<DiskCache>,
+}
+
+impl DiskCacheLayer {
+    /// Create a new `DiskCacheLayer` with given cache operator.
+    pub fn new(cache: Operator) -> Self {
+        Self {
+            cache: Arc::new(DiskCache::new(cache, None, DEFAULT_MAX_OBJECT_SIZE)),
+        }
+    }
+
+    /// Set the max total size in bytes of cached objects.
+    pub fn with_max_size(self, max_size: u64) -> Self {
+        let cache = &self.cache;
+        Self {
+            cache: Arc::new(DiskCache::new(
+                cache.op.clone(),
+                Some(max_size),
+                cache.max_object_size,
+            )),
+        }
+    }
+
+    /// Set the max size in bytes of a single read to be cached, larger reads
+    /// will be passed through.
+    ///
+    /// Default to 64 MiB.
+    pub fn with_max_object_size(self, max_object_size: u64) -> Self {
+        let cache = &self.cache;
+        Self {
+            cache: Arc::new(DiskCache::new(
+                cache.op.clone(),
+                cache.max_size,
+                max_object_size,
+            )),
+        }
+    }
+}
+
+impl<A: Access> Layer<A> for DiskCacheLayer {
+    type LayeredAccess = DiskCacheAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccess {
+        DiskCacheAccessor {
+            inner,
+            cache: self.cache.clone(),
+        }
+    }
+}
+
+struct DiskCache {
+    op: Operator,
+    max_size: Option<u64>,
+    max_object_size: u64,
+    /// The sizes of cached objects, only maintained if `max_size` is set.
+    index: Mutex<CacheIndex>,
+    /// Whether the objects cached before have been loaded into the index.
+    loaded: AtomicBool,
+    /// Whether an eviction is running in background.
+    evicting: AtomicBool,
+}
+
+impl Debug for DiskCache {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DiskCache")
+            .field("op", &self.op)
+            .field("max_size", &self.max_size)
+            .field("max_object_size", &self.max_object_size)
+            .finish_non_exhaustive()
+    }
+}
+
+/// CacheIndex tracks the sizes of cached objects in the order of population.
+#[derive(Default)]
+struct CacheIndex {
+    /// The sequence and size of cached objects.
+    entries: BTreeMap<String, (i64, u64)>,
+    /// The keys of cached objects ordered by sequence.
+    order: BTreeMap<i64, String>,
+    next_seq: i64,
+    total: u64,
+}
+
+impl CacheIndex {
+    fn insert(&mut self, key: &str, seq: i64, size: u64) {
+        self.remove(key);
+        self.entries.insert(key.to_string(), (seq, size));
+        self.order.insert(seq, key.to_string());
+        self.total += size;
+    }
+
+    fn push(&mut self, key: &str, size: u64) {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        self.insert(key, seq, size);
+    }
+
+    fn remove(&mut self, key: &str) {
+        if let Some((seq, size)) = self.entries.remove(key) {
+            self.order.remove(&seq);
+            self.total -= size;
+        }
+    }
+
+    fn remove_dir(&mut self, dir: &str) {
+        let keys: Vec<_> = self
+            .entries
+            .range(dir.to_string()..)
+            .take_while(|(k, _)| k.starts_with(dir))
+            .map(|(k, _)| k.clone())
+            .collect();
+        for key in keys {
+            self.remove(&key);
+        }
+    }
+
+    fn pop_oldest(&mut self) -> Option<String> {
+        let (_, key) = self.order.first_key_value()?;
+        let key = key.clone();
+        self.remove(&key);
+        Some(key)
+    }
+}
+
+fn hex_md5(v: &str) -> String {
+    format!("{:x}", md5::Md5::digest(v.as_bytes()))
+}
+
+impl DiskCache {
+    fn new(op: Operator, max_size: Option<u64>, max_object_size: u64) -> Self {
+        Self {
+            op,
+            max_size,
+            max_object_size,
+            index: Mutex::default(),
+            loaded: AtomicBool::new(false),
+            evicting: AtomicBool::new(false),
+        }
+    }
+
+    /// All cached copies of a path are stored under the same dir so that
+    /// they can be invalidated together.
+    fn dir_of(path: &str) -> String {
+        format!("{}/", hex_md5(path))
+    }
+
+    /// Build the cache key of given path, metadata and range, returns `None`
+    /// if the object can't be identified.
+    fn key_of(path: &str, meta: &Metadata, range: BytesRange) -> Option<String> {
+        let version = match (meta.etag(), meta.last_modified()) {
+            (Some(etag), _) => etag.to_string(),
+            (None, Some(lm)) => format!("{}-{}", meta.content_length(), lm.timestamp_micros()),
+            (None, None) => return None,
+        };
+        let size = range.size().map(|v| v.to_string()).unwrap_or_default();
+        Some(format!(
+            "{}{}-{}-{size}",
+            Self::dir_of(path),
+            hex_md5(&version),
+            range.offset()
+        ))
+    }
+
+    async fn populate(self: &Arc<Self>, key: &str, buf: Buffer) {
+        let size = buf.len() as u64;
+        if let Err(err) = self.op.write(key, buf).await {
+            warn!("disk cache failed to populate {key}: {err}");
+            return;
+        }
+
+        let Some(max_size) = self.max_size else {
+            return;
+        };
+        {
+            let mut index = self.index.lock().unwrap();
+            index.push(key, size);
+            if index.total <= max_size && self.loaded.load(Ordering::Acquire) {
+                return;
+            }
+        }
+        // Only one eviction is allowed to run at the same time.
+        if self.evicting.swap(true, Ordering::AcqRel) {
+            return;
+        }
+
+        let cache = self.clone();
+        self.op
+            .executor()
+            .into_inner()
+            .execute(Box::pin(async move {
+                if let Err(err) = cache.evict(max_size).await {
+                    warn!("disk cache failed to evict: {err}");
+                }
+                cache.evicting.store(false, Ordering::Release);
+            }));
+    }
+
+    /// Load the objects cached before into the index, they are older than
+    /// all objects populated by this process.
+    async fn load(&self) -> Result<()> {
+        let mut entries: Vec<Entry> = self
+            .op
+            .lister_with("/")
+            .recursive(true)
+            .metadata(true)
+            .await?
+            .try_collect()
+            .await?;
+        entries.retain(|e| e.metadata().is_file());
+        entries.sort_by_key(|e| e.metadata().last_modified());
+
+        let mut index = self.index.lock().unwrap();
+        let base = -(entries.len() as i64);
+        for (seq, entry) in (base..).zip(entries) {
+            if !index.entries.contains_key(entry.path()) {
+                index.insert(entry.path(), seq, entry.metadata().content_length());
+            }
+        }
+        Ok(())
+    }
+
+    /// Evict the oldest cached objects until the total size is under max_size.
+    async fn evict(&self, max_size: u64) -> Result<()> {
+        if !self.loaded.load(Ordering::Acquire) {
+            self.load().await?;
+            self.loaded.store(true, Ordering::Release);
+        }
+
+        let keys = {
+            let mut index = self.index.lock().unwrap();
+            let mut keys = vec![];
+            while index.total > max_size {
+                match index.pop_oldest() {
+                    Some(key) => keys.push(key),
+                    None => break,
+                }
+            }
+            keys
+        };
+        for key in keys {
+            self.op.delete(&key).await?;
+        }
+        Ok(())
+    }
+
+    async fn invalidate(&self, path: &str) {
+        let dir = Self::dir_of(path);
+        if self.max_size.is_some() {
+            self.index.lock().unwrap().remove_dir(&dir);
+        }
+        if let Err(err) = self.op.remove_all(&dir).await {
+            warn!("disk cache failed to invalidate {path}: {err}");
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct DiskCacheAccessor<A: Access> {
+    inner: A,
+    cache: Arc<DiskCache>,
+}
+
+impl<A: Access> DiskCacheAccessor<A> {
+    /// Stat the file only once for all reads issued by the same reader.
+    async fn stat_once(&self, path: &str, args: &OpRead) -> Result<Metadata> {
+        let stat = || async {
+            self.inner
+                .stat(path, OpStat::new())
+                .await
+                .map(RpStat::into_metadata)
+        };
+        match args.shared_stat() {
+            Some(cell) => cell.get_or_try_init(stat).await.cloned(),
+            None => stat().await,
+        }
+    }
+}
+
+/// Only plain read without conditions, version, overrides and extra headers can be cached.
+fn is_cacheable(args: &OpRead) -> bool {
+    args.suffix_range().is_none()
+        && args.ranges().is_empty()
+        && !args.decompress()
+        && args.if_match().is_none()
+        && args.if_none_match().is_none()
+        && args.if_modified_since().is_none()
+        && args.if_unmodified_since().is_none()
+        && args.override_content_type().is_none()
+        && args.override_cache_control().is_none()
+        && args.override_content_disposition().is_none()
+        && args.version().is_none()
+        && args.headers().is_none()
+}
+
+impl<A: Access> LayeredAccess for DiskCacheAccessor<A> {
+    type Inner = A;
+    type Reader = TwoWays<A::Reader, Buffer>;
+    type Writer = DiskCacheWrapper<A::Writer>;
+    type Lister = A::Lister;
+    type Deleter = DiskCacheWrapper<A::Deleter>;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let key = if is_cacheable(&args) && self.inner.info().full_capability().stat {
+            match self.stat_once(path, &args).await {
+                Ok(meta) => {
+                    let range = args.range();
+                    let size = range
+                        .size()
+                        .unwrap_or_else(|| meta.content_length().saturating_sub(range.offset()));
+                    if size <= self.cache.max_object_size {
+                        DiskCache::key_of(path, &meta, range)
+                    } else {
+                        None
+                    }
+                }
+                Err(_) => None,
+            }
+        } else {
+            None
+        };
+        let Some(key) = key else {
+            let (rp, r) = self.inner.read(path, args).await?;
+            return Ok((rp, TwoWays::One(r)));
+        };
+
+        match self.cache.op.read(&key).await {
+            Ok(buf) => return Ok((RpRead::new(), TwoWays::Two(buf))),
+            Err(err) if err.kind() == ErrorKind::NotFound => {}
+            Err(err) => warn!("disk cache failed to read {key}: {err}"),
+        }
+
+        let (_, mut r) = self.inner.read(path, args).await?;
+        let buf = oio::Read::read_all(&mut r).await?;
+        self.cache.populate(&key, buf.clone()).await;
+        Ok((RpRead::new(), TwoWays::Two(buf)))
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let (rp, w) = self.inner.write(path, args).await?;
+        Ok((
+            rp,
+            DiskCacheWrapper::new(w, self.cache.clone(), Some(path.to_string())),
+        ))
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let res = self.inner.copy(from, to, args).await;
+        self.cache.invalidate(to).await;
+        res
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let res = self.inner.rename(from, to, args).await;
+        self.cache.invalidate(from).await;
+        self.cache.invalidate(to).await;
+        res
+    }
+
+    async fn delete(&self) -> Result<(RpDelete, Self::Deleter)> {
+        let (rp, d) = self.inner.delete().await?;
+        Ok((rp, DiskCacheWrapper::new(d, self.cache.clone(), None)))
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        self.inner.list(path, args).await
+    }
+}
+
+pub struct DiskCacheWrapper<R> {
+    inner: R,
+    cache: Arc<DiskCache>,
+    /// The paths that should be invalidated once the operation is done.
+    paths: Vec<String>,
+}
+
+impl<R> DiskCacheWrapper<R> {
+    fn new(inner: R, cache: Arc<DiskCache>, path: Option<String>) -> Self {
+        Self {
+            inner,
+            cache,
+            paths: path.into_iter().collect(),
+        }
+    }
+
+    async fn invalidate(&self) {
+        for path in &self.paths {
+            self.cache.invalidate(path).await;
+        }
+    }
+}
+
+impl<R: oio::Write> oio::Write for DiskCacheWrapper<R> {
+    async fn write(&mut self, bs: Buffer) -> Result<()> {
+        self.inner.write(bs).await
+    }
+
+    async fn close(&mut self) -> Result<Metadata> {
+        let res = self.inner.close().await;
+        self.invalidate().await;
+        res
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.inner.abort().await
+    }
+}
+
+impl<R: oio::Delete> oio::Delete for DiskCacheWrapper<R> {
+    fn delete(&mut self, path: &str, args: OpDelete) -> Result<()> {
+        self.inner.delete(path, args)?;
+        self.paths.push(path.to_string());
+        Ok(())
+    }
+
+    async fn flush(&mut self) -> Result<usize> {
+        let res = self.inner.flush().await;
+        self.invalidate().await;
+        if res.is_ok() {
+            self.paths.clear();
+        }
+        res
+    }
+}
+
+#[cfg(all(test, feature = "tests"))]
+mod tests {
+    use super::*;
+    use crate::raw::tests::MockAccess;
+    use crate::services;
+
+    /// Build an operator caching `inner` by given layer, the mock service
+    /// returns the md5 of content as etag.
+    fn new_operator(layer: DiskCacheLayer) -> (Operator, Operator, MockAccess) {
+        let mock = MockAccess::new();
+        let inner = OperatorBuilder::new(mock.clone()).finish();
+        let op = Operator::from_inner(inner.clone().into_inner()).layer(layer);
+        (inner, op, mock)
+    }
+
+    #[tokio::test]
+    async fn test_disk_cache() -> Result<()> {
+        let cache = Operator::new(services::Memory::default())?.finish();
+        let (inner, op, _) = new_operator(DiskCacheLayer::new(cache.clone()));
+
+        inner.write("test", "Hello, World!").await?;
+        assert_eq!(op.read("test").await?.to_vec(), b"Hello, World!");
+        let cached = cache.list_with("/").recursive(true).await?;
+        let files: Vec<_> = cached.iter().filter(|e| e.metadata().is_file()).collect();
+        assert_eq!(files.len(), 1);
+
+        // Only the requested range should be fetched and cached.
+        assert_eq!(op.read_with("test").range(7..12).await?.to_vec(), b"World");
+        let cached = cache.list_with("/").recursive(true).await?;
+        let files: Vec<_> = cached.iter().filter(|e| e.metadata().is_file()).collect();
+        assert_eq!(files.len(), 2);
+        let range_file = files
+            .iter()
+            .find(|e| e.path().ends_with("-7-5"))
+            .expect("range must be cached");
+        assert_eq!(cache.read(range_file.path()).await?.to_vec(), b"World");
+
+        // Range reads should be served from the cache.
+        cache.write(range_file.path(), "Cache").await?;
+        assert_eq!(op.read_with("test").range(7..12).await?.to_vec(), b"Cache");
+
+        // Writes through the same operator should invalidate the cache.
+        op.write("test", "Hi").await?;
+        let cached = cache.list_with("/").recursive(true).await?;
+        assert!(cached.iter().all(|e| !e.metadata().is_file()));
+        assert_eq!(op.read("test").await?.to_vec(), b"Hi");
+
+        op.delete("test").await?;
+        let cached = cache.list_with("/").recursive(true).await?;
+        assert!(cached.iter().all(|e| !e.metadata().is_file()));
+
+        Ok(())
+    }
+
+    async fn cached_size(cache: &Operator) -> Result<u64> {
+        let cached = cache.list_with("/").recursive(true).await?;
+        let mut total = 0;
+        for entry in cached.iter().filter(|e| e.metadata().is_file()) {
+            total += cache.stat(entry.path()).await?.content_length();
+        }
+        Ok(total)
+    }
+
+    #[tokio::test]
+    async fn test_disk_cache_eviction() -> Result<()> {
+        let cache = Operator::new(services::Memory::default())?.finish();
+        // Objects cached before should be loaded and evicted first.
+        cache.write("old/0-0-", vec![0; 8]).await?;
+        let layer = DiskCacheLayer::new(cache.clone()).with_max_size(16);
+        let (inner, op, _) = new_operator(layer.clone());
+
+        for i in 0..4 {
+            let path = format!("test-{i}");
+            inner.write(&path, vec![0; 8]).await?;
+            op.read(&path).await?;
+            // Eviction runs in background, wait for it.
+            while layer.cache.evicting.load(Ordering::Acquire) {
+                tokio::task::yield_now().await;
+            }
+            assert!(
+                cached_size(&cache).await? <= 16,
+                "cache size must be bounded"
+            );
+        }
+        assert!(!cache.exists("old/0-0-").await?);
+        assert_eq!(layer.cache.index.lock().unwrap().total, 16);
+
+        // Invalidated objects are removed from the index.
+        op.delete("test-3").await?;
+        assert_eq!(layer.cache.index.lock().unwrap().total, 8);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_disk_cache_max_object_size() -> Result<()> {
+        let cache = Operator::new(services::Memory::default())?.finish();
+        let layer = DiskCacheLayer::new(cache.clone()).with_max_object_size(8);
+        let (inner, op, _) = new_operator(layer);
+
+        inner.write("test", "Hello, World!").await?;
+        assert_eq!(op.read("test").await?.to_vec(), b"Hello, World!");
+        assert_eq!(cached_size(&cache).await?, 0);
+
+        assert_eq!(op.read_with("test").range(7..12).await?.to_vec(), b"World");
+        assert_eq!(cached_size(&cache).await?, 5);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_disk_cache_skip_decompress() -> Result<()> {
+        let cache = Operator::new(services::Memory::default())?.finish();
+        let mock = MockAccess::new().with_capability(|cap| Capability {
+            read_with_decompress: true,
+            ..cap
+        });
+        mock.insert("test", "compressed");
+        mock.insert_decompressed("test", "Hello, World!");
+        mock.inject_stat(
+            "test",
+            Metadata::new(EntryMode::FILE)
+                .with_content_length(10)
+                .with_etag("v1".to_string()),
+        );
+        let op = OperatorBuilder::new(mock)
+            .layer(DiskCacheLayer::new(cache.clone()))
+            .finish();
+
+        let bs = op.read_with("test").range(0..5).decompress(true).await?;
+        assert_eq!(bs.to_vec(), b"Hello");
+        assert_eq!(cached_size(&cache).await?, 0);
+        assert_eq!(op.read_with("test").range(0..5).await?.to_vec(), b"compr");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_disk_cache_stat_once_per_reader() -> Result<()> {
+        let cache = Operator::new(services::Memory::default())?.finish();
+        let (inner, op, mock) = new_operator(DiskCacheLayer::new(cache.clone()));
+
+        inner.write("test", "Hello, World!").await?;
+        let stats = mock.calls(Operation::Stat, "test");
+        let r = op.reader_with("test").chunk(4).await?;
+        assert_eq!(r.read(0..13).await?.to_vec(), b"Hello, World!");
+        assert_eq!(mock.calls(Operation::Stat, "test"), stats + 1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,319 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use log::info;
+
+use crate::raw::*;
+use crate::*;
+
+static DRY_RUN_TARGET: &str = "opendal::layers::dry_run";
+
+/// Validate mutating operations without committing any changes.
+///
+/// # Notes
+///
+/// `DryRunLayer` intercepts `write`, `delete`, `create_dir`, `copy`, `rename`,
+/// `set_tags` and `abort_multipart`, performs the checks that can be done
+/// without side effects, and returns success instead of sending them to the
+/// service. What would have happened will be logged with `Info` level under
+/// the `opendal::layers::dry_run` target.
+///
+/// - `write` will `stat` the path to probe the permission and evaluate
+///   conditions like `if_not_exists` and `if_match`. Written data is counted
+///   and dropped.
+/// - `copy`, `rename` and `set_tags` will `stat` the source path to make sure
+///   it exists.
+/// - `delete`, `create_dir` and `abort_multipart` will only be logged.
+///
+/// Reads, stats and lists will be passed through. Please note that a `stat`
+/// succeeding doesn't guarantee the write permission.
+///
+/// Presigning `stat` and `read` will be passed through too, while presigning
+/// mutating operations like `write` and `delete` returns an `Unsupported`
+/// error since the presigned requests would be sent without the layer.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use opendal::layers::DryRunLayer;
+/// # use opendal::services;
+/// # use opendal::Operator;
+/// # use opendal::Result;
+///
+/// # async fn test() -> Result<()> {
+/// let op = Operator::new(services::Memory::default())?
+///     .layer(DryRunLayer)
+///     .finish();
+///
+/// // Nothing will be written.
+/// op.write("test", "Hello, World!").await?;
+/// assert!(!op.exists("test").await?);
+/// Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DryRunLayer;
+
+impl<A: Access> Layer<A> for DryRunLayer {
+    type LayeredAccess = DryRunAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccess {
+        DryRunAccessor { inner }
+    }
+}
+
+#[derive(Debug)]
+pub struct DryRunAccessor<A: Access> {
+    inner: A,
+}
+
+impl<A: Access> DryRunAccessor<A> {
+    /// Stat the path and make sure it exists.
+    async fn probe(&self, path: &str) -> Result<Metadata> {
+        self.inner
+            .stat(path, OpStat::new())
+            .await
+            .map(|rp| rp.into_metadata())
+    }
+
+    /// Evaluate the conditions of write against the current object.
+    async fn check_write(&self, path: &str, args: &OpWrite) -> Result<()> {
+        let meta = match self.probe(path).await {
+            Ok(meta) => Some(meta),
+            Err(err) if err.kind() == ErrorKind::NotFound => None,
+            Err(err) => return Err(err),
+        };
+
+        let condition_not_match = match &meta {
+            Some(meta) => {
+                args.if_not_exists()
+                    || args
+                        .if_none_match()
+                        .is_some_and(|v| v == "*" || Some(v) == meta.etag())
+                    || args.if_match().is_some_and(|v| Some(v) != meta.etag())
+            }
+            None => args.if_match().is_some(),
+        };
+        if condition_not_match {
+            return Err(Error::new(
+                ErrorKind::ConditionNotMatch,
+                "write condition doesn't match",
+            )
+            .with_operation(Operation::Write)
+            .with_context("path", path));
+        }
+        Ok(())
+    }
+}
+
+impl<A: Access> LayeredAccess for DryRunAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type Writer = DryRunWriter;
+    type Lister = A::Lister;
+    type Deleter = DryRunDeleter;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn create_dir(&self, path: &str, _: OpCreateDir) -> Result<RpCreateDir> {
+        info!(target: DRY_RUN_TARGET, "would create dir {path}");
+        Ok(RpCreateDir::default())
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.inner.read(path, args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.check_write(path, &args).await?;
+        Ok((RpWrite::new(), DryRunWriter::new(path, args.append())))
+    }
+
+    async fn copy(&self, from: &str, to: &str, _: OpCopy) -> Result<RpCopy> {
+        self.probe(from).await?;
+        info!(target: DRY_RUN_TARGET, "would copy {from} to {to}");
+        Ok(RpCopy::default())
+    }
+
+    async fn rename(&self, from: &str, to: &str, _: OpRename) -> Result<RpRename> {
+        self.probe(from).await?;
+        info!(target: DRY_RUN_TARGET, "would rename {from} to {to}");
+        Ok(RpRename::default())
+    }
+
+    async fn delete(&self) -> Result<(RpDelete, Self::Deleter)> {
+        Ok((RpDelete::default(), DryRunDeleter::default()))
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        self.inner.list(path, args).await
+    }
+
+    async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        match args.operation() {
+            PresignOperation::Stat(_) | PresignOperation::Read(_) => {
+                self.inner.presign(path, args).await
+            }
+            _ => Err(Error::new(
+                ErrorKind::Unsupported,
+                "presigning mutating operations is not supported in dry run",
+            )
+            .with_operation(Operation::Presign)
+            .with_context("path", path)),
+        }
+    }
+
+    async fn set_tags(&self, path: &str, args: OpSetTags) -> Result<RpSetTags> {
+        self.probe(path).await?;
+        info!(target: DRY_RUN_TARGET, "would set tags {:?} on {path}", args.tags());
+        Ok(RpSetTags::default())
+    }
+
+    async fn abort_multipart(
+        &self,
+        path: &str,
+        args: OpAbortMultipart,
+    ) -> Result<RpAbortMultipart> {
+        info!(
+            target: DRY_RUN_TARGET,
+            "would abort multipart upload {} of {path}",
+            args.upload_id()
+        );
+        Ok(RpAbortMultipart::new())
+    }
+}
+
+/// DryRunWriter counts the written data and drops it.
+pub struct DryRunWriter {
+    path: String,
+    append: bool,
+    size: u64,
+}
+
+impl DryRunWriter {
+    fn new(path: &str, append: bool) -> Self {
+        Self {
+            path: path.to_string(),
+            append,
+            size: 0,
+        }
+    }
+}
+
+impl oio::Write for DryRunWriter {
+    async fn write(&mut self, bs: Buffer) -> Result<()> {
+        self.size += bs.len() as u64;
+        Ok(())
+    }
+
+    async fn close(&mut self) -> Result<Metadata> {
+        let action = if self.append { "append" } else { "write" };
+        info!(
+            target: DRY_RUN_TARGET,
+            "would {action} {} bytes to {}", self.size, self.path
+        );
+        Ok(Metadata::new(EntryMode::FILE).with_content_length(self.size))
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        info!(target: DRY_RUN_TARGET, "would abort write to {}", self.path);
+        Ok(())
+    }
+}
+
+/// DryRunDeleter logs the queued paths and drops them.
+#[derive(Default)]
+pub struct DryRunDeleter {
+    paths: Vec<String>,
+}
+
+impl oio::Delete for DryRunDeleter {
+    fn delete(&mut self, path: &str, args: OpDelete) -> Result<()> {
+        match args.version() {
+            Some(version) => self.paths.push(format!("{path} (version {version})")),
+            None => self.paths.push(path.to_string()),
+        }
+        Ok(())
+    }
+
+    async fn flush(&mut self) -> Result<usize> {
+        for path in &self.paths {
+            info!(target: DRY_RUN_TARGET, "would delete {path}");
+        }
+        let n = self.paths.len();
+        self.paths.clear();
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::services;
+
+    #[tokio::test]
+    async fn test_dry_run() -> Result<()> {
+        let op = Operator::new(services::Memory::default())?.finish();
+        op.write("exists", "Hello").await?;
+        let dry = op.clone().layer(DryRunLayer);
+
+        let meta = dry.write("test", "Hello, World!").await?;
+        assert_eq!(meta.content_length(), 13);
+        assert!(!op.exists("test").await?);
+
+        let err = dry
+            .write_with("exists", "Hello, World!")
+            .if_not_exists(true)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+
+        dry.delete("exists").await?;
+        dry.copy("exists", "copied").await?;
+        dry.rename("exists", "renamed").await?;
+        assert_eq!(op.read("exists").await?.to_vec(), b"Hello");
+        assert!(!op.exists("copied").await?);
+        assert!(!op.exists("renamed").await?);
+
+        // The source must exist.
+        let err = dry.copy("missing", "copied").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_presign() -> Result<()> {
+        let op = Operator::new(services::Memory::default())?
+            .layer(DryRunLayer)
+            .finish();
+        let expire = Duration::from_secs(60);
+
+        let err = op.presign_write("test", expire).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        assert!(err.to_string().contains("dry run"));
+        let err = op.presign_delete("test", expire).await.unwrap_err();
+        assert!(err.to_string().contains("dry run"));
+        // Presigning read is left to the service.
+        let err = op.presign_read("test", expire).await.unwrap_err();
+        assert!(!err.to_string().contains("dry run"));
+        Ok(())
+    }
+}
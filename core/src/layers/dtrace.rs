@@ -69,6 +69,9 @@ use crate::*;
 /// 7. writer_close_start, arguments: path
 /// 8. writer_close_ok, arguments: path
 /// 9. writer_close_error, arguments: path
+/// 10. writer_flush_start, arguments: path
+/// 11. writer_flush_ok, arguments: path
+/// 12. writer_flush_error, arguments: path
 ///
 /// Example:
 ///
@@ -247,6 +250,21 @@ impl<R: oio::Read> oio::Read for DtraceLayerWrapper<R> {
             }
         }
     }
+
+    async fn read_into(&mut self, dst: &mut [u8]) -> Result<usize> {
+        let c_path = CString::new(self.path.clone()).unwrap();
+        probe_lazy!(opendal, reader_read_start, c_path.as_ptr());
+        match self.inner.read_into(dst).await {
+            Ok(n) => {
+                probe_lazy!(opendal, reader_read_ok, c_path.as_ptr(), n);
+                Ok(n)
+            }
+            Err(e) => {
+                probe_lazy!(opendal, reader_read_error, c_path.as_ptr());
+                Err(e)
+            }
+        }
+    }
 }
 
 impl<R: oio::Write> oio::Write for DtraceLayerWrapper<R> {
@@ -264,6 +282,20 @@ impl<R: oio::Write> oio::Write for DtraceLayerWrapper<R> {
             })
     }
 
+    async fn flush(&mut self) -> Result<()> {
+        let c_path = CString::new(self.path.clone()).unwrap();
+        probe_lazy!(opendal, writer_flush_start, c_path.as_ptr());
+        self.inner
+            .flush()
+            .await
+            .map(|_| {
+                probe_lazy!(opendal, writer_flush_ok, c_path.as_ptr());
+            })
+            .inspect_err(|_| {
+                probe_lazy!(opendal, writer_flush_error, c_path.as_ptr());
+            })
+    }
+
     async fn abort(&mut self) -> Result<()> {
         let c_path = CString::new(self.path.clone()).unwrap();
         probe_lazy!(opendal, writer_poll_abort_start, c_path.as_ptr());
@@ -145,6 +145,43 @@ impl<A: Access> LayeredAccess for ErrorContextAccessor<A> {
         })
     }
 
+    async fn set_tags(&self, path: &str, args: OpSetTags) -> Result<RpSetTags> {
+        self.inner.set_tags(path, args).await.map_err(|err| {
+            err.with_operation(Operation::SetTags)
+                .with_context("service", self.info.scheme())
+                .with_context("path", path)
+        })
+    }
+
+    async fn abort_multipart(
+        &self,
+        path: &str,
+        args: OpAbortMultipart,
+    ) -> Result<RpAbortMultipart> {
+        let upload_id = args.upload_id().to_string();
+        self.inner.abort_multipart(path, args).await.map_err(|err| {
+            err.with_operation(Operation::AbortMultipart)
+                .with_context("service", self.info.scheme())
+                .with_context("path", path)
+                .with_context("upload_id", upload_id)
+        })
+    }
+
+    async fn list_multipart_uploads(
+        &self,
+        path: &str,
+        args: OpListMultipartUploads,
+    ) -> Result<RpListMultipartUploads> {
+        self.inner
+            .list_multipart_uploads(path, args)
+            .await
+            .map_err(|err| {
+                err.with_operation(Operation::ListMultipartUploads)
+                    .with_context("service", self.info.scheme())
+                    .with_context("path", path)
+            })
+    }
+
     async fn delete(&self) -> Result<(RpDelete, Self::Deleter)> {
         self.inner
             .delete()
@@ -228,6 +265,22 @@ impl<T: oio::Read> oio::Read for ErrorContextWrapper<T> {
                     .with_context("read", self.processed.to_string())
             })
     }
+
+    async fn read_into(&mut self, dst: &mut [u8]) -> Result<usize> {
+        self.inner
+            .read_into(dst)
+            .await
+            .inspect(|n| {
+                self.processed += *n as u64;
+            })
+            .map_err(|err| {
+                err.with_operation(Operation::Read)
+                    .with_context("service", self.scheme)
+                    .with_context("path", &self.path)
+                    .with_context("range", self.range.to_string())
+                    .with_context("read", self.processed.to_string())
+            })
+    }
 }
 
 impl<T: oio::Write> oio::Write for ErrorContextWrapper<T> {
@@ -248,6 +301,15 @@ impl<T: oio::Write> oio::Write for ErrorContextWrapper<T> {
             })
     }
 
+    async fn flush(&mut self) -> Result<()> {
+        self.inner.flush().await.map_err(|err| {
+            err.with_operation(Operation::Write)
+                .with_context("service", self.scheme)
+                .with_context("path", &self.path)
+                .with_context("written", self.processed.to_string())
+        })
+    }
+
     async fn close(&mut self) -> Result<Metadata> {
         self.inner.close().await.map_err(|err| {
             err.with_operation(Operation::Write)
@@ -0,0 +1,483 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use bytes::Buf;
+use log::warn;
+
+use crate::raw::*;
+use crate::*;
+
+/// Fall back to secondary operators for reads when the primary fails.
+///
+/// # Notes
+///
+/// `FallbackLayer` is added to the primary operator and takes an ordered list
+/// of secondary operators, like replicas of the primary bucket in other regions.
+///
+/// - `read` and `stat` will try the primary first, then each secondary in order
+///   until one succeeds. The error of the primary will be returned if all of
+///   them fail.
+/// - Only errors with the configured kinds will trigger the fallback, by default
+///   [`ErrorKind::NotFound`] and [`ErrorKind::Unexpected`] which covers
+///   unavailable services.
+/// - `write` and `delete` go to the primary only by default. With
+///   [`FallbackLayer::with_fan_out`] enabled, they will be sent to all secondaries
+///   too, and fail if any of them fails. Written data is chunked for each
+///   secondary by its own multipart capability.
+/// - Other operations like `list`, `copy` and `rename` go to the primary only.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use opendal::layers::FallbackLayer;
+/// # use opendal::services;
+/// # use opendal::Operator;
+/// # use opendal::Result;
+///
+/// # fn main() -> Result<()> {
+/// let replica = Operator::new(services::Memory::default())?.finish();
+/// let _ = Operator::new(services::Memory::default())?
+///     .layer(FallbackLayer::new([replica]))
+///     .finish();
+/// Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct FallbackLayer {
+    secondaries: Vec<Accessor>,
+    error_kinds: Vec<ErrorKind>,
+    fan_out: bool,
+}
+
+impl FallbackLayer {
+    /// Create a new `FallbackLayer` with given secondary operators in order.
+    pub fn new(secondaries: impl IntoIterator<Item = Operator>) -> Self {
+        Self {
+            secondaries: secondaries.into_iter().map(|op| op.into_inner()).collect(),
+            error_kinds: vec![ErrorKind::NotFound, ErrorKind::Unexpected],
+            fan_out: false,
+        }
+    }
+
+    /// Set the error kinds of the primary that trigger the fallback.
+    ///
+    /// Default to `NotFound` and `Unexpected`.
+    pub fn with_error_kinds(mut self, kinds: impl IntoIterator<Item = ErrorKind>) -> Self {
+        self.error_kinds = kinds.into_iter().collect();
+        self
+    }
+
+    /// Send writes and deletes to all secondaries too.
+    ///
+    /// Default to `false`.
+    pub fn with_fan_out(mut self, fan_out: bool) -> Self {
+        self.fan_out = fan_out;
+        self
+    }
+}
+
+impl<A: Access> Layer<A> for FallbackLayer {
+    type LayeredAccess = FallbackAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccess {
+        FallbackAccessor {
+            inner,
+            secondaries: self.secondaries.clone(),
+            error_kinds: self.error_kinds.clone(),
+            fan_out: self.fan_out,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct FallbackAccessor<A: Access> {
+    inner: A,
+    secondaries: Vec<Accessor>,
+    error_kinds: Vec<ErrorKind>,
+    fan_out: bool,
+}
+
+impl<A: Access> FallbackAccessor<A> {
+    fn should_fallback(&self, err: &Error) -> bool {
+        self.error_kinds.contains(&err.kind())
+    }
+}
+
+impl<A: Access> LayeredAccess for FallbackAccessor<A> {
+    type Inner = A;
+    type Reader = TwoWays<A::Reader, oio::Reader>;
+    type Writer = FallbackWriter<A::Writer>;
+    type Lister = A::Lister;
+    type Deleter = FallbackDeleter<A::Deleter>;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let err = match self.inner.read(path, args.clone()).await {
+            Ok((rp, r)) => return Ok((rp, TwoWays::One(r))),
+            Err(err) if self.should_fallback(&err) => err,
+            Err(err) => return Err(err),
+        };
+
+        for acc in &self.secondaries {
+            match acc.read(path, args.clone()).await {
+                Ok((rp, r)) => return Ok((rp, TwoWays::Two(r))),
+                Err(e) => warn!(
+                    "fallback read {path} from {} failed: {e}",
+                    acc.info().scheme()
+                ),
+            }
+        }
+        Err(err)
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let (rp, w) = self.inner.write(path, args.clone()).await?;
+
+        let mut secondaries = Vec::new();
+        if self.fan_out {
+            for acc in &self.secondaries {
+                let ctx =
+                    WriteContext::new(acc.clone(), path.to_string(), args.clone(), OpWriter::new());
+                secondaries.push(WriteGenerator::create(Arc::new(ctx)).await?);
+            }
+        }
+        Ok((
+            rp,
+            FallbackWriter {
+                inner: w,
+                secondaries,
+            },
+        ))
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let err = match self.inner.stat(path, args.clone()).await {
+            Ok(rp) => return Ok(rp),
+            Err(err) if self.should_fallback(&err) => err,
+            Err(err) => return Err(err),
+        };
+
+        for acc in &self.secondaries {
+            match acc.stat(path, args.clone()).await {
+                Ok(rp) => return Ok(rp),
+                Err(e) => warn!(
+                    "fallback stat {path} from {} failed: {e}",
+                    acc.info().scheme()
+                ),
+            }
+        }
+        Err(err)
+    }
+
+    async fn delete(&self) -> Result<(RpDelete, Self::Deleter)> {
+        let (rp, d) = self.inner.delete().await?;
+
+        let mut secondaries = Vec::new();
+        if self.fan_out {
+            for acc in &self.secondaries {
+                secondaries.push(SecondaryDeleter::create(acc.clone()).await?);
+            }
+        }
+        Ok((
+            rp,
+            FallbackDeleter {
+                inner: d,
+                secondaries,
+            },
+        ))
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        self.inner.list(path, args).await
+    }
+}
+
+/// FallbackWriter writes to the primary and all fanned out secondaries.
+///
+/// The buffers are forwarded as is to the primary, which has been chunked by
+/// the caller, but they must be chunked again for secondaries since their part
+/// size requirements could be different.
+pub struct FallbackWriter<W> {
+    inner: W,
+    secondaries: Vec<WriteGenerator<oio::Writer>>,
+}
+
+impl<W: oio::Write> oio::Write for FallbackWriter<W> {
+    async fn write(&mut self, bs: Buffer) -> Result<()> {
+        for w in &mut self.secondaries {
+            let mut bs = bs.clone();
+            while !bs.is_empty() {
+                let n = w.write(bs.clone()).await?;
+                bs.advance(n);
+            }
+        }
+        self.inner.write(bs).await
+    }
+
+    async fn close(&mut self) -> Result<Metadata> {
+        // Commit the primary first so that secondaries never have data
+        // which is missing in the primary.
+        let meta = self.inner.close().await?;
+        for w in &mut self.secondaries {
+            w.close().await?;
+        }
+        Ok(meta)
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        for w in &mut self.secondaries {
+            w.abort().await?;
+        }
+        self.inner.abort().await
+    }
+}
+
+/// FallbackDeleter deletes from the primary and all fanned out secondaries.
+///
+/// Paths are batched by the primary's `delete_max_size`, so they are kept in
+/// the pending queue of each secondary and sent in batches of its own max size
+/// while the primary flushes.
+pub struct FallbackDeleter<D> {
+    inner: D,
+    secondaries: Vec<SecondaryDeleter>,
+}
+
+impl<D: oio::Delete> oio::Delete for FallbackDeleter<D> {
+    fn delete(&mut self, path: &str, args: OpDelete) -> Result<()> {
+        self.inner.delete(path, args.clone())?;
+        for d in &mut self.secondaries {
+            d.pending.push((path.to_string(), args.clone()));
+        }
+        Ok(())
+    }
+
+    async fn flush(&mut self) -> Result<usize> {
+        let deleted = self.inner.flush().await?;
+        for d in &mut self.secondaries {
+            d.flush().await?;
+        }
+        Ok(deleted)
+    }
+}
+
+/// SecondaryDeleter keeps the paths not yet deleted from a secondary.
+///
+/// Paths are only removed from `pending` after the secondary confirms the
+/// whole batch, so that a failed flush can be retried without losing them.
+struct SecondaryDeleter {
+    acc: Accessor,
+    /// The deleter is dropped after a failed flush since paths may be left
+    /// in its queue, a new one will be created while retrying.
+    deleter: Option<oio::Deleter>,
+    max_size: usize,
+    pending: Vec<(String, OpDelete)>,
+}
+
+impl SecondaryDeleter {
+    async fn create(acc: Accessor) -> Result<Self> {
+        let max_size = acc.info().full_capability().delete_max_size.unwrap_or(1);
+        let (_, d) = acc.delete().await?;
+        Ok(Self {
+            acc,
+            deleter: Some(d),
+            max_size: max_size.max(1),
+            pending: Vec::new(),
+        })
+    }
+
+    async fn flush(&mut self) -> Result<()> {
+        // Deleters ignore duplicated paths in the same batch.
+        let mut seen = HashSet::new();
+        self.pending.retain(|v| seen.insert(v.clone()));
+
+        while !self.pending.is_empty() {
+            let size = self.pending.len().min(self.max_size);
+            let d = match &mut self.deleter {
+                Some(d) => d,
+                None => self.deleter.insert(self.acc.delete().await?.1),
+            };
+            if let Err(err) = Self::delete_batch(d, &self.pending[..size]).await {
+                self.deleter = None;
+                return Err(err);
+            }
+            self.pending.drain(..size);
+        }
+        Ok(())
+    }
+
+    /// Flush until the whole batch is deleted so that no path is left in the
+    /// queue of deleter.
+    async fn delete_batch(d: &mut oio::Deleter, batch: &[(String, OpDelete)]) -> Result<()> {
+        for (path, args) in batch {
+            oio::Delete::delete(d, path, args.clone())?;
+        }
+        let mut flushed = 0;
+        while flushed < batch.len() {
+            match oio::Delete::flush(d).await? {
+                0 => {
+                    return Err(Error::new(
+                        ErrorKind::Unexpected,
+                        "secondary deleter didn't make progress",
+                    )
+                    .with_context("pending", batch.len() - flushed))
+                }
+                n => flushed += n,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services;
+
+    #[tokio::test]
+    async fn test_fallback() -> Result<()> {
+        let primary = Operator::new(services::Memory::default())?.finish();
+        let replica = Operator::new(services::Memory::default())?.finish();
+        replica.write("replicated", "Hello").await?;
+        let op = primary.clone().layer(FallbackLayer::new([replica.clone()]));
+
+        assert_eq!(op.read("replicated").await?.to_vec(), b"Hello");
+        assert_eq!(op.stat("replicated").await?.content_length(), 5);
+
+        // Writes go to the primary only by default.
+        op.write("test", "World").await?;
+        assert!(primary.exists("test").await?);
+        assert!(!replica.exists("test").await?);
+
+        let err = op.read("missing").await.expect_err("read must fail");
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        // Only configured error kinds trigger the fallback.
+        let op = primary
+            .clone()
+            .layer(FallbackLayer::new([replica.clone()]).with_error_kinds([]));
+        let err = op.stat("replicated").await.expect_err("stat must fail");
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        let op = primary
+            .clone()
+            .layer(FallbackLayer::new([replica.clone()]).with_fan_out(true));
+        op.write("fan_out", "Hello, World!").await?;
+        assert_eq!(replica.read("fan_out").await?.to_vec(), b"Hello, World!");
+        op.delete("fan_out").await?;
+        assert!(!primary.exists("fan_out").await?);
+        assert!(!replica.exists("fan_out").await?);
+        Ok(())
+    }
+
+    #[cfg(feature = "tests")]
+    #[tokio::test]
+    async fn test_fallback_writer_chunks_for_secondaries() -> Result<()> {
+        use crate::raw::tests::MockAccess;
+
+        let mock = MockAccess::new().with_capability(|cap| Capability {
+            write_multi_min_size: Some(4),
+            ..cap
+        });
+        let replica = OperatorBuilder::new(mock.clone()).finish();
+        let op = Operator::new(services::Memory::default())?
+            .finish()
+            .layer(FallbackLayer::new([replica.clone()]).with_fan_out(true));
+
+        let mut w = op.writer("test").await?;
+        for bs in ["ab", "cd", "ef", "g"] {
+            w.write(bs).await?;
+        }
+        w.close().await?;
+        assert_eq!(op.read("test").await?.to_vec(), b"abcdefg");
+        assert_eq!(replica.read("test").await?.to_vec(), b"abcdefg");
+        assert_eq!(mock.take_written_parts("test"), [4, 3]);
+        Ok(())
+    }
+
+    /// A deleter that deletes all queued paths at once.
+    struct BatchDeleter(Vec<String>);
+
+    impl oio::Delete for BatchDeleter {
+        fn delete(&mut self, path: &str, _: OpDelete) -> Result<()> {
+            self.0.push(path.to_string());
+            Ok(())
+        }
+
+        async fn flush(&mut self) -> Result<usize> {
+            Ok(self.0.drain(..).count())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fallback_deleter_batches_for_secondaries() -> Result<()> {
+        // Memory only deletes one path at once.
+        let replica = Operator::new(services::Memory::default())?.finish();
+        for path in ["a", "b", "c"] {
+            replica.write(path, "Hello").await?;
+        }
+        let mut d = FallbackDeleter {
+            inner: BatchDeleter(vec![]),
+            secondaries: vec![SecondaryDeleter::create(replica.clone().into_inner()).await?],
+        };
+        for path in ["a", "b", "c", "a"] {
+            oio::Delete::delete(&mut d, path, OpDelete::new())?;
+        }
+        assert_eq!(oio::Delete::flush(&mut d).await?, 4);
+        for path in ["a", "b", "c"] {
+            assert!(!replica.exists(path).await?);
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "tests")]
+    #[tokio::test]
+    async fn test_fallback_deleter_retry_secondaries() -> Result<()> {
+        use crate::raw::tests::MockAccess;
+
+        let mock = MockAccess::new();
+        let replica = OperatorBuilder::new(mock.clone()).finish();
+        for path in ["a", "b", "c"] {
+            replica.write(path, "Hello").await?;
+        }
+        mock.inject_error(Operation::Delete, "b", 1, || {
+            Error::new(ErrorKind::Unexpected, "connection reset").set_temporary()
+        });
+
+        let mut d = FallbackDeleter {
+            inner: BatchDeleter(vec![]),
+            secondaries: vec![SecondaryDeleter::create(replica.clone().into_inner()).await?],
+        };
+        for path in ["a", "b", "c"] {
+            oio::Delete::delete(&mut d, path, OpDelete::new())?;
+        }
+        assert!(oio::Delete::flush(&mut d).await.is_err());
+        assert!(replica.exists("b").await?);
+
+        // Paths not confirmed by the secondary are deleted while retrying.
+        oio::Delete::flush(&mut d).await?;
+        for path in ["a", "b", "c"] {
+            assert!(!replica.exists(path).await?);
+        }
+        Ok(())
+    }
+}
@@ -228,6 +228,11 @@ impl<R: oio::Read> oio::Read for FastraceWrapper<R> {
     async fn read(&mut self) -> Result<Buffer> {
         self.inner.read().await
     }
+
+    #[trace(enter_on_poll = true)]
+    async fn read_into(&mut self, dst: &mut [u8]) -> Result<usize> {
+        self.inner.read_into(dst).await
+    }
 }
 
 impl<R: oio::Write> oio::Write for FastraceWrapper<R> {
@@ -237,6 +242,12 @@ impl<R: oio::Write> oio::Write for FastraceWrapper<R> {
         self.inner.write(bs)
     }
 
+    fn flush(&mut self) -> impl Future<Output = Result<()>> + MaybeSend {
+        let _g = self.span.set_local_parent();
+        let _span = LocalSpan::enter_with_local_parent(Operation::Write.into_static());
+        self.inner.flush()
+    }
+
     fn abort(&mut self) -> impl Future<Output = Result<()>> + MaybeSend {
         let _g = self.span.set_local_parent();
         let _span = LocalSpan::enter_with_local_parent(Operation::Write.into_static());
@@ -15,6 +15,7 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::borrow::Cow;
 use std::fmt::Debug;
 use std::fmt::Display;
 use std::sync::Arc;
@@ -71,6 +72,36 @@ use crate::*;
 /// RUST_LOG="info,opendal::services=debug" ./app
 /// ```
 ///
+/// # Path Redaction
+///
+/// Paths will be redacted before passing to the logging interceptor, so that
+/// secrets never reach the log backend:
+///
+/// - By default, values of known credential-bearing query params like
+///   `X-Amz-Signature` will be replaced by [`redact_path_credentials`].
+/// - Users can set their own redactor via [`LoggingLayer::with_path_redactor`], for
+///   example, [`hash_path_components`] to hide the path components.
+/// - The paths in the context of logged errors will be redacted too, credentials
+///   in other contexts and the source error will be replaced by
+///   [`redact_path_credentials`].
+///
+/// Note that error messages are logged as is.
+///
+/// ```no_run
+/// # use opendal::layers::LoggingLayer;
+/// # use opendal::raw::hash_path_components;
+/// # use opendal::services;
+/// # use opendal::Operator;
+/// # use opendal::Result;
+///
+/// # fn main() -> Result<()> {
+/// let _ = Operator::new(services::Memory::default())?
+///     .layer(LoggingLayer::default().with_path_redactor(hash_path_components))
+///     .finish();
+/// Ok(())
+/// # }
+/// ```
+///
 /// # Logging Interceptor
 ///
 /// You can implement your own logging interceptor to customize the logging behavior.
@@ -111,12 +142,14 @@ use crate::*;
 #[derive(Debug)]
 pub struct LoggingLayer<I = DefaultLoggingInterceptor> {
     logger: I,
+    path_redactor: fn(&str) -> String,
 }
 
 impl Default for LoggingLayer {
     fn default() -> Self {
         Self {
             logger: DefaultLoggingInterceptor,
+            path_redactor: redact_path_credentials,
         }
     }
 }
@@ -124,7 +157,21 @@ impl Default for LoggingLayer {
 impl LoggingLayer {
     /// Create the layer with specific logging interceptor.
     pub fn new<I: LoggingInterceptor>(logger: I) -> LoggingLayer<I> {
-        LoggingLayer { logger }
+        LoggingLayer {
+            logger,
+            path_redactor: redact_path_credentials,
+        }
+    }
+}
+
+impl<I: LoggingInterceptor> LoggingLayer<I> {
+    /// Set the redactor for paths in logs.
+    ///
+    /// The redactor will be applied to `path`, `from` and `to` of every log
+    /// entry. Default to [`redact_path_credentials`].
+    pub fn with_path_redactor(mut self, redactor: fn(&str) -> String) -> Self {
+        self.path_redactor = redactor;
+        self
     }
 }
 
@@ -137,11 +184,53 @@ impl<A: Access, I: LoggingInterceptor> Layer<A> for LoggingLayer<I> {
             inner,
 
             info,
-            logger: self.logger.clone(),
+            logger: RedactingLogger {
+                inner: self.logger.clone(),
+                path_redactor: self.path_redactor,
+            },
         }
     }
 }
 
+/// RedactingLogger redacts the paths in context and errors before passing to
+/// the logging interceptor.
+#[derive(Debug, Clone)]
+struct RedactingLogger<I> {
+    inner: I,
+    path_redactor: fn(&str) -> String,
+}
+
+impl<I: LoggingInterceptor> RedactingLogger<I> {
+    fn redact(&self, key: &str, value: &str) -> String {
+        match key {
+            "path" | "from" | "to" => (self.path_redactor)(value),
+            _ => redact_path_credentials(value),
+        }
+    }
+
+    fn log(
+        &self,
+        info: &AccessorInfo,
+        operation: Operation,
+        context: &[(&str, &str)],
+        message: &str,
+        err: Option<&Error>,
+    ) {
+        let redacted: Vec<(&str, Cow<str>)> = context
+            .iter()
+            .map(|(k, v)| match *k {
+                "path" | "from" | "to" => (*k, Cow::Owned((self.path_redactor)(v))),
+                _ => (*k, Cow::Borrowed(*v)),
+            })
+            .collect();
+        let context: Vec<(&str, &str)> = redacted.iter().map(|(k, v)| (*k, v.as_ref())).collect();
+        let err = err.map(|err| err.redact(|k, v| self.redact(k, v)));
+
+        self.inner
+            .log(info, operation, &context, message, err.as_ref())
+    }
+}
+
 /// LoggingInterceptor is used to intercept the log.
 pub trait LoggingInterceptor: Debug + Clone + Send + Sync + Unpin + 'static {
     /// Everytime there is a log, this function will be called.
@@ -237,7 +326,7 @@ pub struct LoggingAccessor<A: Access, I: LoggingInterceptor> {
     inner: A,
 
     info: Arc<AccessorInfo>,
-    logger: I,
+    logger: RedactingLogger<I>,
 }
 
 static LOGGING_TARGET: &str = "opendal::services";
@@ -543,7 +632,7 @@ impl<A: Access, I: LoggingInterceptor> LayeredAccess for LoggingAccessor<A, I> {
 
 pub struct LoggingReader<R, I: LoggingInterceptor> {
     info: Arc<AccessorInfo>,
-    logger: I,
+    logger: RedactingLogger<I>,
     path: String,
 
     read: u64,
@@ -551,7 +640,7 @@ pub struct LoggingReader<R, I: LoggingInterceptor> {
 }
 
 impl<R, I: LoggingInterceptor> LoggingReader<R, I> {
-    fn new(info: Arc<AccessorInfo>, logger: I, path: &str, reader: R) -> Self {
+    fn new(info: Arc<AccessorInfo>, logger: RedactingLogger<I>, path: &str, reader: R) -> Self {
         Self {
             info,
             logger,
@@ -596,11 +685,40 @@ impl<R: oio::Read, I: LoggingInterceptor> oio::Read for LoggingReader<R, I> {
             }
         }
     }
+
+    async fn read_into(&mut self, dst: &mut [u8]) -> Result<usize> {
+        match self.inner.read_into(dst).await {
+            Ok(0) if !dst.is_empty() => {
+                self.logger.log(
+                    &self.info,
+                    Operation::Read,
+                    &[("path", &self.path), ("read", &self.read.to_string())],
+                    "finished",
+                    None,
+                );
+                Ok(0)
+            }
+            Ok(n) => {
+                self.read += n as u64;
+                Ok(n)
+            }
+            Err(err) => {
+                self.logger.log(
+                    &self.info,
+                    Operation::Read,
+                    &[("path", &self.path), ("read", &self.read.to_string())],
+                    "failed",
+                    Some(&err),
+                );
+                Err(err)
+            }
+        }
+    }
 }
 
 pub struct LoggingWriter<W, I> {
     info: Arc<AccessorInfo>,
-    logger: I,
+    logger: RedactingLogger<I>,
     path: String,
 
     written: u64,
@@ -608,7 +726,7 @@ pub struct LoggingWriter<W, I> {
 }
 
 impl<W, I> LoggingWriter<W, I> {
-    fn new(info: Arc<AccessorInfo>, logger: I, path: &str, writer: W) -> Self {
+    fn new(info: Arc<AccessorInfo>, logger: RedactingLogger<I>, path: &str, writer: W) -> Self {
         Self {
             info,
             logger,
@@ -646,6 +764,31 @@ impl<W: oio::Write, I: LoggingInterceptor> oio::Write for LoggingWriter<W, I> {
         }
     }
 
+    async fn flush(&mut self) -> Result<()> {
+        match self.inner.flush().await {
+            Ok(_) => {
+                self.logger.log(
+                    &self.info,
+                    Operation::Write,
+                    &[("path", &self.path), ("written", &self.written.to_string())],
+                    "flush succeeded",
+                    None,
+                );
+                Ok(())
+            }
+            Err(err) => {
+                self.logger.log(
+                    &self.info,
+                    Operation::Write,
+                    &[("path", &self.path), ("written", &self.written.to_string())],
+                    "flush failed",
+                    Some(&err),
+                );
+                Err(err)
+            }
+        }
+    }
+
     async fn abort(&mut self) -> Result<()> {
         match self.inner.abort().await {
             Ok(_) => {
@@ -699,7 +842,7 @@ impl<W: oio::Write, I: LoggingInterceptor> oio::Write for LoggingWriter<W, I> {
 
 pub struct LoggingLister<P, I: LoggingInterceptor> {
     info: Arc<AccessorInfo>,
-    logger: I,
+    logger: RedactingLogger<I>,
     path: String,
 
     listed: usize,
@@ -707,7 +850,7 @@ pub struct LoggingLister<P, I: LoggingInterceptor> {
 }
 
 impl<P, I: LoggingInterceptor> LoggingLister<P, I> {
-    fn new(info: Arc<AccessorInfo>, logger: I, path: &str, inner: P) -> Self {
+    fn new(info: Arc<AccessorInfo>, logger: RedactingLogger<I>, path: &str, inner: P) -> Self {
         Self {
             info,
             logger,
@@ -753,7 +896,7 @@ impl<P: oio::List, I: LoggingInterceptor> oio::List for LoggingLister<P, I> {
 
 pub struct LoggingDeleter<D, I: LoggingInterceptor> {
     info: Arc<AccessorInfo>,
-    logger: I,
+    logger: RedactingLogger<I>,
 
     queued: usize,
     deleted: usize,
@@ -761,7 +904,7 @@ pub struct LoggingDeleter<D, I: LoggingInterceptor> {
 }
 
 impl<D, I: LoggingInterceptor> LoggingDeleter<D, I> {
-    fn new(info: Arc<AccessorInfo>, logger: I, inner: D) -> Self {
+    fn new(info: Arc<AccessorInfo>, logger: RedactingLogger<I>, inner: D) -> Self {
         Self {
             info,
             logger,
@@ -840,3 +983,59 @@ impl<D: oio::Delete, I: LoggingInterceptor> oio::Delete for LoggingDeleter<D, I>
         res
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::services;
+
+    #[derive(Debug, Clone, Default)]
+    struct MockInterceptor {
+        logs: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl LoggingInterceptor for MockInterceptor {
+        fn log(
+            &self,
+            _: &AccessorInfo,
+            operation: Operation,
+            context: &[(&str, &str)],
+            message: &str,
+            err: Option<&Error>,
+        ) {
+            let err = err.map(|err| err.to_string()).unwrap_or_default();
+            self.logs.lock().unwrap().push(format!(
+                "{operation}{} {message} {err}",
+                LoggingContext(context)
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_logging_redacts_errors() -> Result<()> {
+        let logger = MockInterceptor::default();
+        let op = Operator::new(services::Memory::default())?
+            .layer(LoggingLayer::new(logger.clone()))
+            .finish();
+
+        let err = op
+            .stat("abc?X-Amz-Signature=secret")
+            .await
+            .expect_err("stat must fail");
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+        // The error returned to users is not changed.
+        assert!(err.to_string().contains("secret"));
+
+        let logs = logger.logs.lock().unwrap();
+        assert!(logs.iter().any(|v| v.contains("failed")));
+        for log in logs.iter() {
+            assert!(
+                !log.contains("secret"),
+                "credentials must be redacted: {log}"
+            );
+        }
+        Ok(())
+    }
+}
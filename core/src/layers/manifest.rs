@@ -0,0 +1,325 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::raw::*;
+use crate::*;
+
+/// The version of manifest format written by [`ManifestLayer`].
+const MANIFEST_VERSION: u32 = 1;
+
+/// Manifest of an object written by [`ManifestLayer`].
+///
+/// The manifest is serialized as JSON with fields in the declared order, for
+/// example:
+///
+/// ```json
+/// {"version":1,"size":13,"chunks":[{"offset":0,"length":5,"crc32c":2178485787},{"offset":5,"length":8,"crc32c":1611853448}]}
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Manifest {
+    /// The version of manifest format.
+    pub version: u32,
+    /// The total size of the object.
+    pub size: u64,
+    /// The chunks in the order they have been written.
+    pub chunks: Vec<ManifestChunk>,
+}
+
+/// A chunk recorded in [`Manifest`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct ManifestChunk {
+    /// The offset of this chunk in the object.
+    pub offset: u64,
+    /// The length of this chunk.
+    pub length: u64,
+    /// The CRC32C checksum of this chunk.
+    pub crc32c: u32,
+}
+
+impl Manifest {
+    /// Parse a manifest from the content of a sidecar object.
+    pub fn from_slice(bs: &[u8]) -> Result<Self> {
+        serde_json::from_slice(bs)
+            .map_err(|err| Error::new(ErrorKind::Unexpected, "manifest is invalid").set_source(err))
+    }
+
+    /// Serialize the manifest into bytes.
+    pub fn to_vec(&self) -> Result<Vec<u8>> {
+        serde_json::to_vec(self).map_err(|err| {
+            Error::new(ErrorKind::Unexpected, "manifest serialize failed").set_source(err)
+        })
+    }
+}
+
+/// Record a manifest of written chunks for every write.
+///
+/// # Notes
+///
+/// `ManifestLayer` will record the offset, length and CRC32C checksum of every
+/// chunk sent to the service. Once the writer is closed successfully, the
+/// [`Manifest`] will be written as a sidecar object at `{path}{suffix}`, the
+/// suffix is `.manifest` by default.
+///
+/// Downstream consumers can read the sidecar and parse it via
+/// [`Manifest::from_slice`] to verify or resume the upload.
+///
+/// - Append writes will not be recorded since the object offset is unknown.
+/// - The sidecar object will not be removed while deleting the object.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use opendal::layers::Manifest;
+/// # use opendal::layers::ManifestLayer;
+/// # use opendal::services;
+/// # use opendal::Operator;
+/// # use opendal::Result;
+///
+/// # async fn test() -> Result<()> {
+/// let op = Operator::new(services::Memory::default())?
+///     .layer(ManifestLayer::new())
+///     .finish();
+///
+/// op.write("test", "Hello, World!").await?;
+/// let manifest = Manifest::from_slice(&op.read("test.manifest").await?.to_vec())?;
+/// assert_eq!(manifest.size, 13);
+/// Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ManifestLayer {
+    suffix: String,
+}
+
+impl Default for ManifestLayer {
+    fn default() -> Self {
+        Self {
+            suffix: ".manifest".to_string(),
+        }
+    }
+}
+
+impl ManifestLayer {
+    /// Create a new `ManifestLayer`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the suffix of sidecar manifest objects.
+    ///
+    /// Default to `.manifest`.
+    pub fn with_suffix(mut self, suffix: &str) -> Self {
+        self.suffix = suffix.to_string();
+        self
+    }
+}
+
+impl<A: Access> Layer<A> for ManifestLayer {
+    type LayeredAccess = ManifestAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccess {
+        ManifestAccessor {
+            inner: Arc::new(inner),
+            suffix: self.suffix.clone(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ManifestAccessor<A: Access> {
+    inner: Arc<A>,
+    suffix: String,
+}
+
+impl<A: Access> LayeredAccess for ManifestAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type Writer = ManifestWriter<A>;
+    type Lister = A::Lister;
+    type Deleter = A::Deleter;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.inner.read(path, args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let sidecar = if args.append() {
+            None
+        } else {
+            Some((self.inner.clone(), format!("{path}{}", self.suffix)))
+        };
+
+        let (rp, w) = self.inner.write(path, args).await?;
+        Ok((rp, ManifestWriter::new(w, sidecar)))
+    }
+
+    async fn delete(&self) -> Result<(RpDelete, Self::Deleter)> {
+        self.inner.delete().await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        self.inner.list(path, args).await
+    }
+}
+
+pub struct ManifestWriter<A: Access> {
+    inner: A::Writer,
+    /// The accessor and path to write the sidecar manifest.
+    sidecar: Option<(Arc<A>, String)>,
+    manifest: Manifest,
+    /// The metadata returned by inner writer, kept so that a failed sidecar
+    /// write can be retried without closing the inner writer again.
+    closed: Option<Metadata>,
+}
+
+impl<A: Access> ManifestWriter<A> {
+    fn new(inner: A::Writer, sidecar: Option<(Arc<A>, String)>) -> Self {
+        Self {
+            inner,
+            sidecar,
+            manifest: Manifest {
+                version: MANIFEST_VERSION,
+                ..Default::default()
+            },
+            closed: None,
+        }
+    }
+
+    async fn write_sidecar(&self) -> Result<()> {
+        let Some((acc, path)) = &self.sidecar else {
+            return Ok(());
+        };
+
+        let bs = self.manifest.to_vec()?;
+        let write = async {
+            let (_, mut w) = acc.write(path, OpWrite::new()).await?;
+            oio::Write::write(&mut w, Buffer::from(bs)).await?;
+            oio::Write::close(&mut w).await
+        };
+        write
+            .await
+            .map(|_| ())
+            .map_err(|err| err.with_context("manifest", path))
+    }
+}
+
+impl<A: Access> oio::Write for ManifestWriter<A> {
+    async fn write(&mut self, bs: Buffer) -> Result<()> {
+        let length = bs.len() as u64;
+        let mut crc32c = 0;
+        if self.sidecar.is_some() {
+            for chunk in bs.clone() {
+                crc32c = crc32c::crc32c_append(crc32c, &chunk);
+            }
+        }
+
+        self.inner.write(bs).await?;
+
+        if length > 0 {
+            self.manifest.chunks.push(ManifestChunk {
+                offset: self.manifest.size,
+                length,
+                crc32c,
+            });
+            self.manifest.size += length;
+        }
+        Ok(())
+    }
+
+    async fn flush(&mut self) -> Result<()> {
+        self.inner.flush().await
+    }
+
+    async fn close(&mut self) -> Result<Metadata> {
+        let meta = match self.closed.take() {
+            Some(meta) => meta,
+            None => self.inner.close().await?,
+        };
+
+        if let Err(err) = self.write_sidecar().await {
+            self.closed = Some(meta);
+            return Err(err);
+        }
+        Ok(meta)
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.inner.abort().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services;
+
+    #[tokio::test]
+    async fn test_manifest() -> Result<()> {
+        let op = Operator::new(services::Memory::default())?
+            .layer(ManifestLayer::new())
+            .finish();
+
+        let mut w = op.writer("test").await?;
+        w.write("Hello").await?;
+        w.write(", World!").await?;
+        w.close().await?;
+
+        let manifest = Manifest::from_slice(&op.read("test.manifest").await?.to_vec())?;
+        assert_eq!(
+            manifest,
+            Manifest {
+                version: MANIFEST_VERSION,
+                size: 13,
+                chunks: vec![
+                    ManifestChunk {
+                        offset: 0,
+                        length: 5,
+                        crc32c: crc32c::crc32c(b"Hello"),
+                    },
+                    ManifestChunk {
+                        offset: 5,
+                        length: 8,
+                        crc32c: crc32c::crc32c(b", World!"),
+                    },
+                ],
+            }
+        );
+
+        // The format must be stable.
+        assert_eq!(
+            String::from_utf8(manifest.to_vec()?).unwrap(),
+            format!(
+                r#"{{"version":1,"size":13,"chunks":[{{"offset":0,"length":5,"crc32c":{}}},{{"offset":5,"length":8,"crc32c":{}}}]}}"#,
+                crc32c::crc32c(b"Hello"),
+                crc32c::crc32c(b", World!")
+            )
+        );
+        Ok(())
+    }
+}
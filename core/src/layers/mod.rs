@@ -24,10 +24,31 @@ mod error_context;
 pub(crate) use error_context::ErrorContextLayer;
 
 mod complete;
-pub(crate) use complete::CompleteLayer;
+pub(crate) use complete::check_max_size;
+pub use complete::CompleteLayer;
+pub use complete::DirMarker;
 
 mod concurrent_limit;
 pub use concurrent_limit::ConcurrentLimitLayer;
+pub use concurrent_limit::DrainSummary;
+pub(crate) use concurrent_limit::InFlightTracker;
+
+#[cfg(feature = "layers-dry-run")]
+mod dry_run;
+#[cfg(feature = "layers-dry-run")]
+pub use dry_run::DryRunLayer;
+
+#[cfg(feature = "layers-fallback")]
+mod fallback;
+#[cfg(feature = "layers-fallback")]
+pub use fallback::FallbackLayer;
+
+#[cfg(feature = "layers-path-rewrite")]
+mod path_rewrite;
+#[cfg(feature = "layers-path-rewrite")]
+pub use path_rewrite::PathRewriteLayer;
+#[cfg(feature = "layers-path-rewrite")]
+pub use path_rewrite::PathRewriter;
 
 mod immutable_index;
 pub use immutable_index::ImmutableIndexLayer;
@@ -39,6 +60,56 @@ pub use logging::LoggingLayer;
 mod timeout;
 pub use timeout::TimeoutLayer;
 
+#[cfg(feature = "layers-read-coalesce")]
+mod read_coalesce;
+#[cfg(feature = "layers-read-coalesce")]
+pub use read_coalesce::ReadCoalesceLayer;
+
+#[cfg(feature = "layers-single-flight")]
+mod single_flight;
+#[cfg(feature = "layers-single-flight")]
+pub use single_flight::SingleFlightLayer;
+
+#[cfg(feature = "layers-disk-cache")]
+mod disk_cache;
+#[cfg(feature = "layers-disk-cache")]
+pub use disk_cache::DiskCacheLayer;
+
+#[cfg(feature = "layers-stat-cache")]
+mod stat_cache;
+#[cfg(feature = "layers-stat-cache")]
+pub use stat_cache::StatCacheLayer;
+
+#[cfg(feature = "layers-checksum")]
+mod checksum;
+#[cfg(feature = "layers-checksum")]
+pub use checksum::ChecksumAlgorithm;
+#[cfg(feature = "layers-checksum")]
+pub use checksum::ChecksumLayer;
+
+#[cfg(feature = "layers-manifest")]
+mod manifest;
+#[cfg(feature = "layers-manifest")]
+pub use manifest::Manifest;
+#[cfg(feature = "layers-manifest")]
+pub use manifest::ManifestChunk;
+#[cfg(feature = "layers-manifest")]
+pub use manifest::ManifestLayer;
+
+#[cfg(feature = "layers-archive")]
+mod archive;
+#[cfg(feature = "layers-archive")]
+pub use archive::ArchiveLayer;
+
+#[cfg(feature = "layers-decompress")]
+mod decompress;
+#[cfg(feature = "layers-decompress")]
+pub use decompress::DecompressCodec;
+#[cfg(feature = "layers-decompress")]
+pub use decompress::DecompressLayer;
+#[cfg(feature = "layers-decompress")]
+pub(crate) use decompress::DecompressReader;
+
 #[cfg(feature = "layers-chaos")]
 mod chaos;
 #[cfg(feature = "layers-chaos")]
@@ -76,8 +147,11 @@ pub use self::fastmetrics::FastmetricsLayer;
 pub use self::fastmetrics::FastmetricsLayerBuilder;
 
 mod retry;
+pub use self::retry::RetryBackoff;
 pub use self::retry::RetryInterceptor;
 pub use self::retry::RetryLayer;
+pub use self::retry::RetryPolicy;
+pub use self::retry::RetryPredicate;
 
 #[cfg(feature = "layers-tracing")]
 mod tracing;
@@ -854,6 +854,21 @@ impl<R: oio::Read, I: MetricsIntercept> oio::Read for MetricsWrapper<R, I> {
                 );
             })
     }
+
+    async fn read_into(&mut self, dst: &mut [u8]) -> Result<usize> {
+        self.inner
+            .read_into(dst)
+            .await
+            .inspect(|n| {
+                self.size += *n as u64;
+            })
+            .inspect_err(|err| {
+                self.interceptor.observe(
+                    self.labels.clone().with_error(err.kind()),
+                    MetricValue::OperationErrorsTotal,
+                );
+            })
+    }
 }
 
 impl<R: oio::Write, I: MetricsIntercept> oio::Write for MetricsWrapper<R, I> {
@@ -874,6 +889,15 @@ impl<R: oio::Write, I: MetricsIntercept> oio::Write for MetricsWrapper<R, I> {
             })
     }
 
+    async fn flush(&mut self) -> Result<()> {
+        self.inner.flush().await.inspect_err(|err| {
+            self.interceptor.observe(
+                self.labels.clone().with_error(err.kind()),
+                MetricValue::OperationErrorsTotal,
+            );
+        })
+    }
+
     async fn close(&mut self) -> Result<Metadata> {
         self.inner.close().await.inspect_err(|err| {
             self.interceptor.observe(
@@ -934,3 +958,76 @@ impl<R: oio::Delete, I: MetricsIntercept> oio::Delete for MetricsWrapper<R, I> {
         })
     }
 }
+
+#[cfg(all(test, feature = "tests"))]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::raw::tests::MockAccess;
+
+    /// RecordInterceptor records all observed metrics, like a bridge to a
+    /// custom metrics lib.
+    #[derive(Debug, Clone, Default)]
+    struct RecordInterceptor {
+        records: Arc<Mutex<Vec<(MetricLabels, MetricValue)>>>,
+    }
+
+    impl MetricsIntercept for RecordInterceptor {
+        fn observe(&self, labels: MetricLabels, value: MetricValue) {
+            self.records.lock().unwrap().push((labels, value));
+        }
+    }
+
+    impl RecordInterceptor {
+        fn bytes(&self, op: Operation) -> Vec<u64> {
+            let records = self.records.lock().unwrap();
+            records
+                .iter()
+                .filter(|(labels, _)| {
+                    labels.scheme == "mock" && labels.operation == op.into_static()
+                })
+                .filter_map(|(_, value)| match value {
+                    MetricValue::OperationBytes(v) => Some(*v),
+                    _ => None,
+                })
+                .collect()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_metrics_throughput() -> Result<()> {
+        let interceptor = RecordInterceptor::default();
+        let mock = MockAccess::new();
+        let op = OperatorBuilder::new(mock.clone())
+            .layer(MetricsLayer::new(interceptor.clone()))
+            .finish();
+
+        let mut w = op.writer("test").await?;
+        w.write("Hello, ").await?;
+        w.write("World!").await?;
+        w.close().await?;
+        drop(w);
+        assert_eq!(interceptor.bytes(Operation::Write), [13]);
+
+        // The bytes actually read are observed once the reader is dropped.
+        let r = op.reader("test").await?;
+        r.read(0..5).await?;
+        r.read(7..13).await?;
+        drop(r);
+        assert_eq!(interceptor.bytes(Operation::Read).iter().sum::<u64>(), 11);
+
+        // Errors are labeled by their kinds.
+        mock.inject_error(Operation::Stat, "test", 1, || {
+            Error::new(ErrorKind::RateLimited, "mock error")
+        });
+        op.stat("test").await.unwrap_err();
+        let records = interceptor.records.lock().unwrap();
+        assert!(records.iter().any(|(labels, value)| {
+            matches!(value, MetricValue::OperationErrorsTotal)
+                && labels.operation == Operation::Stat.into_static()
+                && labels.error == Some(ErrorKind::RateLimited)
+        }));
+        Ok(())
+    }
+}
@@ -71,6 +71,18 @@
 //! * **Histogram**: Distribution of values with configurable buckets, includes count, sum and quantiles
 //! * **Counter**: Cumulative metric that only increases over time (resets on restart)
 //! * **Gauge**: Point-in-time metric that can increase and decrease
+//!
+//! ## Throughput
+//!
+//! `operation_bytes` is observed once a reader or writer has been dropped, with
+//! the bytes actually read or written through it. So the total bytes in/out can
+//! be derived from the sum of this histogram, and the number of operations from
+//! its count, like `rate(opendal_operation_bytes_sum{operation="read"}[1m])`.
+//!
+//! ## Custom Metrics Impls
+//!
+//! Metrics libs not provided by OpenDAL, like statsd, can be bridged by
+//! implementing [`MetricsIntercept`] and applying it with [`MetricsLayer`].
 
 mod metrics;
 
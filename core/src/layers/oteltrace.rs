@@ -15,13 +15,13 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use std::future::Future;
+use std::fmt::Debug;
 use std::sync::Arc;
 
 use opentelemetry::global;
-use opentelemetry::global::BoxedSpan;
 use opentelemetry::trace::FutureExt as TraceFutureExt;
 use opentelemetry::trace::Span;
+use opentelemetry::trace::Status;
 use opentelemetry::trace::TraceContextExt;
 use opentelemetry::trace::Tracer;
 use opentelemetry::Context as TraceContext;
@@ -32,7 +32,23 @@ use crate::*;
 
 /// Add [opentelemetry::trace](https://docs.rs/opentelemetry/latest/opentelemetry/trace/index.html) for every operation.
 ///
-/// Examples
+/// # Notes
+///
+/// `OtelTraceLayer` opens a span for every operation as a child of the current
+/// context, with the following attributes:
+///
+/// - `operation`: the operation name like `read`.
+/// - `path`, `from` and `to`: the redacted paths, see [`OtelTraceLayer::with_path_redactor`].
+/// - `args`: the debug string of operation args.
+/// - `bytes`: the bytes read or written by the reader or writer.
+///
+/// The span status will be set from the result of the operation. Spans of
+/// readers, writers and listers will end once they have been dropped.
+///
+/// Attributes will not be recorded if the span is not recording, for example,
+/// no tracer provider has been registered.
+///
+/// # Examples
 ///
 /// ## Basic Setup
 ///
@@ -44,24 +60,89 @@ use crate::*;
 ///
 /// # fn main() -> Result<()> {
 /// let _ = Operator::new(services::Memory::default())?
-///     .layer(OtelTraceLayer)
+///     .layer(OtelTraceLayer::new())
 ///     .finish();
 /// Ok(())
 /// # }
 /// ```
-pub struct OtelTraceLayer;
+#[derive(Clone)]
+pub struct OtelTraceLayer {
+    path_redactor: fn(&str) -> String,
+}
+
+impl Default for OtelTraceLayer {
+    fn default() -> Self {
+        Self {
+            path_redactor: redact_path_credentials,
+        }
+    }
+}
+
+impl OtelTraceLayer {
+    /// Create a new `OtelTraceLayer`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the redactor for paths recorded in spans.
+    ///
+    /// The redactor will be applied to `path`, `from` and `to` attributes.
+    /// Default to [`redact_path_credentials`](crate::raw::redact_path_credentials).
+    pub fn with_path_redactor(mut self, redactor: fn(&str) -> String) -> Self {
+        self.path_redactor = redactor;
+        self
+    }
+}
 
 impl<A: Access> Layer<A> for OtelTraceLayer {
     type LayeredAccess = OtelTraceAccessor<A>;
 
     fn layer(&self, inner: A) -> Self::LayeredAccess {
-        OtelTraceAccessor { inner }
+        OtelTraceAccessor {
+            inner,
+            path_redactor: self.path_redactor,
+        }
     }
 }
 
 #[derive(Debug)]
 pub struct OtelTraceAccessor<A> {
     inner: A,
+    path_redactor: fn(&str) -> String,
+}
+
+impl<A> OtelTraceAccessor<A> {
+    /// Start a span with given paths and args as a child of current context.
+    fn start(
+        &self,
+        name: &'static str,
+        op: Operation,
+        paths: &[(&'static str, &str)],
+        args: &impl Debug,
+    ) -> TraceContext {
+        let tracer = global::tracer("opendal");
+        let mut span = tracer.start(name);
+        if span.is_recording() {
+            span.set_attribute(KeyValue::new("operation", op.into_static()));
+            for (key, path) in paths {
+                span.set_attribute(KeyValue::new(*key, (self.path_redactor)(path)));
+            }
+            span.set_attribute(KeyValue::new("args", format!("{args:?}")));
+        }
+        TraceContext::current_with_span(span)
+    }
+}
+
+/// Set the span status from the result.
+fn set_status<T>(cx: &TraceContext, res: &Result<T>) {
+    let span = cx.span();
+    if !span.is_recording() {
+        return;
+    }
+    match res {
+        Ok(_) => span.set_status(Status::Ok),
+        Err(err) => span.set_status(Status::error(err.to_string())),
+    }
 }
 
 impl<A: Access> LayeredAccess for OtelTraceAccessor<A> {
@@ -81,63 +162,79 @@ impl<A: Access> LayeredAccess for OtelTraceAccessor<A> {
     }
 
     async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
-        let tracer = global::tracer("opendal");
-        let mut span = tracer.start("create");
-        span.set_attribute(KeyValue::new("path", path.to_string()));
-        span.set_attribute(KeyValue::new("args", format!("{args:?}")));
-        let cx = TraceContext::current_with_span(span);
-        self.inner.create_dir(path, args).with_context(cx).await
+        let cx = self.start("create", Operation::CreateDir, &[("path", path)], &args);
+        let res = self
+            .inner
+            .create_dir(path, args)
+            .with_context(cx.clone())
+            .await;
+        set_status(&cx, &res);
+        res
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
-        let tracer = global::tracer("opendal");
-        let mut span = tracer.start("read");
-        span.set_attribute(KeyValue::new("path", path.to_string()));
-        span.set_attribute(KeyValue::new("args", format!("{args:?}")));
-        self.inner
-            .read(path, args)
-            .await
-            .map(|(rp, r)| (rp, OtelTraceWrapper::new(span, r)))
+        let cx = self.start("read", Operation::Read, &[("path", path)], &args);
+        let res = self.inner.read(path, args).with_context(cx.clone()).await;
+        match res {
+            Ok((rp, r)) => Ok((rp, OtelTraceWrapper::new(cx, r))),
+            Err(err) => {
+                let res = Err(err);
+                set_status(&cx, &res);
+                res
+            }
+        }
     }
 
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
-        let tracer = global::tracer("opendal");
-        let mut span = tracer.start("write");
-        span.set_attribute(KeyValue::new("path", path.to_string()));
-        span.set_attribute(KeyValue::new("args", format!("{args:?}")));
-        self.inner
-            .write(path, args)
-            .await
-            .map(|(rp, r)| (rp, OtelTraceWrapper::new(span, r)))
+        let cx = self.start("write", Operation::Write, &[("path", path)], &args);
+        let res = self.inner.write(path, args).with_context(cx.clone()).await;
+        match res {
+            Ok((rp, w)) => Ok((rp, OtelTraceWrapper::new(cx, w))),
+            Err(err) => {
+                let res = Err(err);
+                set_status(&cx, &res);
+                res
+            }
+        }
     }
 
     async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
-        let tracer = global::tracer("opendal");
-        let mut span = tracer.start("copy");
-        span.set_attribute(KeyValue::new("from", from.to_string()));
-        span.set_attribute(KeyValue::new("to", to.to_string()));
-        span.set_attribute(KeyValue::new("args", format!("{args:?}")));
-        let cx = TraceContext::current_with_span(span);
-        self.inner().copy(from, to, args).with_context(cx).await
+        let cx = self.start(
+            "copy",
+            Operation::Copy,
+            &[("from", from), ("to", to)],
+            &args,
+        );
+        let res = self
+            .inner()
+            .copy(from, to, args)
+            .with_context(cx.clone())
+            .await;
+        set_status(&cx, &res);
+        res
     }
 
     async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
-        let tracer = global::tracer("opendal");
-        let mut span = tracer.start("rename");
-        span.set_attribute(KeyValue::new("from", from.to_string()));
-        span.set_attribute(KeyValue::new("to", to.to_string()));
-        span.set_attribute(KeyValue::new("args", format!("{args:?}")));
-        let cx = TraceContext::current_with_span(span);
-        self.inner().rename(from, to, args).with_context(cx).await
+        let cx = self.start(
+            "rename",
+            Operation::Rename,
+            &[("from", from), ("to", to)],
+            &args,
+        );
+        let res = self
+            .inner()
+            .rename(from, to, args)
+            .with_context(cx.clone())
+            .await;
+        set_status(&cx, &res);
+        res
     }
 
     async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
-        let tracer = global::tracer("opendal");
-        let mut span = tracer.start("stat");
-        span.set_attribute(KeyValue::new("path", path.to_string()));
-        span.set_attribute(KeyValue::new("args", format!("{args:?}")));
-        let cx = TraceContext::current_with_span(span);
-        self.inner().stat(path, args).with_context(cx).await
+        let cx = self.start("stat", Operation::Stat, &[("path", path)], &args);
+        let res = self.inner().stat(path, args).with_context(cx.clone()).await;
+        set_status(&cx, &res);
+        res
     }
 
     async fn delete(&self) -> Result<(RpDelete, Self::Deleter)> {
@@ -145,59 +242,233 @@ impl<A: Access> LayeredAccess for OtelTraceAccessor<A> {
     }
 
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
-        let tracer = global::tracer("opendal");
-        let mut span = tracer.start("list");
-        span.set_attribute(KeyValue::new("path", path.to_string()));
-        span.set_attribute(KeyValue::new("args", format!("{args:?}")));
-        self.inner
-            .list(path, args)
-            .await
-            .map(|(rp, s)| (rp, OtelTraceWrapper::new(span, s)))
+        let cx = self.start("list", Operation::List, &[("path", path)], &args);
+        let res = self.inner.list(path, args).with_context(cx.clone()).await;
+        match res {
+            Ok((rp, l)) => Ok((rp, OtelTraceWrapper::new(cx, l))),
+            Err(err) => {
+                let res = Err(err);
+                set_status(&cx, &res);
+                res
+            }
+        }
     }
 
     async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
-        let tracer = global::tracer("opendal");
-        let mut span = tracer.start("presign");
-        span.set_attribute(KeyValue::new("path", path.to_string()));
-        span.set_attribute(KeyValue::new("args", format!("{args:?}")));
-        let cx = TraceContext::current_with_span(span);
-        self.inner().presign(path, args).with_context(cx).await
+        let cx = self.start("presign", Operation::Presign, &[("path", path)], &args);
+        let res = self
+            .inner()
+            .presign(path, args)
+            .with_context(cx.clone())
+            .await;
+        set_status(&cx, &res);
+        res
     }
 }
 
 pub struct OtelTraceWrapper<R> {
-    _span: BoxedSpan,
+    cx: TraceContext,
     inner: R,
+    bytes: u64,
 }
 
 impl<R> OtelTraceWrapper<R> {
-    fn new(_span: BoxedSpan, inner: R) -> Self {
-        Self { _span, inner }
+    fn new(cx: TraceContext, inner: R) -> Self {
+        Self {
+            cx,
+            inner,
+            bytes: 0,
+        }
+    }
+
+    /// Set the span status if the result is an error or the IO is finished.
+    fn finish<T>(&self, res: &Result<T>, finished: impl FnOnce(&T) -> bool) {
+        if matches!(res, Ok(v) if !finished(v)) {
+            return;
+        }
+        set_status(&self.cx, res);
+    }
+}
+
+impl<R> Drop for OtelTraceWrapper<R> {
+    fn drop(&mut self) {
+        let span = self.cx.span();
+        if span.is_recording() {
+            span.set_attribute(KeyValue::new("bytes", self.bytes as i64));
+        }
     }
 }
 
 impl<R: oio::Read> oio::Read for OtelTraceWrapper<R> {
     async fn read(&mut self) -> Result<Buffer> {
-        self.inner.read().await
+        let res = self.inner.read().with_context(self.cx.clone()).await;
+        if let Ok(bs) = &res {
+            self.bytes += bs.len() as u64;
+        }
+        self.finish(&res, |bs| bs.is_empty());
+        res
+    }
+
+    async fn read_into(&mut self, dst: &mut [u8]) -> Result<usize> {
+        let empty = dst.is_empty();
+        let res = self
+            .inner
+            .read_into(dst)
+            .with_context(self.cx.clone())
+            .await;
+        if let Ok(n) = &res {
+            self.bytes += *n as u64;
+        }
+        self.finish(&res, |n| *n == 0 && !empty);
+        res
     }
 }
 
 impl<R: oio::Write> oio::Write for OtelTraceWrapper<R> {
-    fn write(&mut self, bs: Buffer) -> impl Future<Output = Result<()>> + MaybeSend {
-        self.inner.write(bs)
+    async fn write(&mut self, bs: Buffer) -> Result<()> {
+        let size = bs.len() as u64;
+        let res = self.inner.write(bs).with_context(self.cx.clone()).await;
+        if res.is_ok() {
+            self.bytes += size;
+        }
+        self.finish(&res, |_| false);
+        res
+    }
+
+    async fn flush(&mut self) -> Result<()> {
+        let res = self.inner.flush().with_context(self.cx.clone()).await;
+        self.finish(&res, |_| false);
+        res
     }
 
-    fn abort(&mut self) -> impl Future<Output = Result<()>> + MaybeSend {
-        self.inner.abort()
+    async fn abort(&mut self) -> Result<()> {
+        let res = self.inner.abort().with_context(self.cx.clone()).await;
+        self.finish(&res, |_| false);
+        res
     }
 
-    fn close(&mut self) -> impl Future<Output = Result<Metadata>> + MaybeSend {
-        self.inner.close()
+    async fn close(&mut self) -> Result<Metadata> {
+        let res = self.inner.close().with_context(self.cx.clone()).await;
+        self.finish(&res, |_| true);
+        res
     }
 }
 
 impl<R: oio::List> oio::List for OtelTraceWrapper<R> {
     async fn next(&mut self) -> Result<Option<oio::Entry>> {
-        self.inner.next().await
+        let res = self.inner.next().with_context(self.cx.clone()).await;
+        self.finish(&res, |entry| entry.is_none());
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+    use std::sync::OnceLock;
+
+    use opentelemetry::Value;
+    use opentelemetry_sdk::error::OTelSdkResult;
+    use opentelemetry_sdk::trace::SdkTracerProvider;
+    use opentelemetry_sdk::trace::SpanData;
+    use opentelemetry_sdk::trace::SpanExporter;
+
+    use super::*;
+    use crate::services;
+
+    /// Collect all ended spans in memory.
+    #[derive(Clone, Debug, Default)]
+    struct TestExporter {
+        spans: Arc<Mutex<Vec<SpanData>>>,
+    }
+
+    impl SpanExporter for TestExporter {
+        async fn export(&self, batch: Vec<SpanData>) -> OTelSdkResult {
+            self.spans.lock().unwrap().extend(batch);
+            Ok(())
+        }
+    }
+
+    /// The tracer provider is global, so it's registered only once for all tests.
+    fn exporter() -> &'static TestExporter {
+        static EXPORTER: OnceLock<TestExporter> = OnceLock::new();
+        EXPORTER.get_or_init(|| {
+            let exporter = TestExporter::default();
+            let provider = SdkTracerProvider::builder()
+                .with_simple_exporter(exporter.clone())
+                .build();
+            global::set_tracer_provider(provider);
+            exporter
+        })
+    }
+
+    fn attribute(span: &SpanData, key: &str) -> Option<Value> {
+        span.attributes
+            .iter()
+            .find(|kv| kv.key.as_str() == key)
+            .map(|kv| kv.value.clone())
+    }
+
+    /// Find the span of given name with given attribute.
+    fn find_span(name: &str, key: &str, value: &str) -> SpanData {
+        exporter()
+            .spans
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|span| {
+                span.name == name && attribute(span, key) == Some(value.to_string().into())
+            })
+            .cloned()
+            .unwrap_or_else(|| panic!("span {name} with {key}={value} must be exported"))
+    }
+
+    #[tokio::test]
+    async fn test_otel_trace_spans() -> Result<()> {
+        exporter();
+        let op = Operator::new(services::Memory::default())?
+            .layer(OtelTraceLayer::new())
+            .finish();
+
+        op.write("otel/file", "Hello, World!").await?;
+        let span = find_span("write", "path", "otel/file");
+        assert_eq!(attribute(&span, "operation"), Some("write".into()));
+        assert_eq!(attribute(&span, "bytes"), Some(Value::I64(13)));
+        assert_eq!(span.status, Status::Ok);
+
+        op.read_with("otel/file").range(0..5).await?;
+        let span = find_span("read", "path", "otel/file");
+        assert_eq!(attribute(&span, "bytes"), Some(Value::I64(5)));
+        assert_eq!(span.status, Status::Ok);
+
+        // Spans of listers end once the lister has been dropped.
+        let entries = op.list("otel/").await?;
+        assert_eq!(entries.len(), 1);
+        let span = find_span("list", "path", "otel/");
+        assert_eq!(attribute(&span, "operation"), Some("list".into()));
+        assert_eq!(span.status, Status::Ok);
+
+        let err = op.stat("otel/not_exist").await.unwrap_err();
+        let span = find_span("stat", "path", "otel/not_exist");
+        assert_eq!(span.status, Status::error(err.to_string()));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_otel_trace_path_redactor() -> Result<()> {
+        exporter();
+        let op = Operator::new(services::Memory::default())?
+            .layer(OtelTraceLayer::new().with_path_redactor(|path| path.replace("secret", "***")))
+            .finish();
+
+        op.create_dir("redact/secret/").await?;
+        find_span("create", "path", "redact/***/");
+        let spans = exporter().spans.lock().unwrap();
+        assert!(spans
+            .iter()
+            .all(|span| attribute(span, "path") != Some("redact/secret/".into())));
+
+        Ok(())
     }
 }
@@ -0,0 +1,337 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+use crate::raw::*;
+use crate::*;
+
+/// PathRewriter maps the path used by callers to the path stored in the service.
+pub type PathRewriter = Arc<dyn Fn(&str) -> String + Send + Sync>;
+
+/// Rewrite paths via a mapping function before sending them to the service.
+///
+/// # Notes
+///
+/// The mapping function receives the normalized path relative to the root,
+/// like `dir/file` or `dir/`. The root itself is passed as `/`.
+///
+/// All operations that take paths will be rewritten, including `read`,
+/// `write`, `stat`, `list`, `delete`, `copy`, `rename` and `presign`.
+///
+/// Entries returned by `list` will be mapped back by replacing the rewritten
+/// list path with the path passed by callers, so callers always see their own
+/// namespace. Entries outside the rewritten list path are returned as is.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::sync::Arc;
+/// # use opendal::layers::PathRewriteLayer;
+/// # use opendal::services;
+/// # use opendal::Operator;
+/// # use opendal::Result;
+///
+/// # fn main() -> Result<()> {
+/// // Files are still stored under the legacy `v1/` prefix.
+/// let _ = Operator::new(services::Memory::default())?
+///     .layer(PathRewriteLayer::new(Arc::new(|path| match path {
+///         "/" => "v1/".to_string(),
+///         _ => format!("v1/{path}"),
+///     })))
+///     .finish();
+/// Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct PathRewriteLayer {
+    rewrite: PathRewriter,
+}
+
+impl Debug for PathRewriteLayer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PathRewriteLayer").finish_non_exhaustive()
+    }
+}
+
+impl PathRewriteLayer {
+    /// Create a new `PathRewriteLayer` with given mapping function.
+    pub fn new(rewrite: PathRewriter) -> Self {
+        Self { rewrite }
+    }
+
+    /// Create a new `PathRewriteLayer` that resolves `.` and `..` segments
+    /// like a filesystem does, for example `abc/../def` will be `def`.
+    ///
+    /// `..` never goes beyond the root. See [`crate::raw::resolve_dot_segments`] for details.
+    pub fn resolve_dot_segments() -> Self {
+        Self::new(Arc::new(resolve_dot_segments))
+    }
+}
+
+impl<A: Access> Layer<A> for PathRewriteLayer {
+    type LayeredAccess = PathRewriteAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccess {
+        PathRewriteAccessor {
+            inner,
+            rewrite: self.rewrite.clone(),
+        }
+    }
+}
+
+pub struct PathRewriteAccessor<A: Access> {
+    inner: A,
+    rewrite: PathRewriter,
+}
+
+impl<A: Access> Debug for PathRewriteAccessor<A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PathRewriteAccessor")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<A: Access> PathRewriteAccessor<A> {
+    fn rewrite(&self, path: &str) -> String {
+        (self.rewrite)(path)
+    }
+}
+
+impl<A: Access> LayeredAccess for PathRewriteAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type Writer = A::Writer;
+    type Lister = PathRewriteLister<A::Lister>;
+    type Deleter = PathRewriteDeleter<A::Deleter>;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        self.inner.create_dir(&self.rewrite(path), args).await
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.inner.read(&self.rewrite(path), args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.inner.write(&self.rewrite(path), args).await
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.inner
+            .copy(&self.rewrite(from), &self.rewrite(to), args)
+            .await
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.inner
+            .rename(&self.rewrite(from), &self.rewrite(to), args)
+            .await
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.inner.stat(&self.rewrite(path), args).await
+    }
+
+    async fn delete(&self) -> Result<(RpDelete, Self::Deleter)> {
+        let (rp, deleter) = self.inner.delete().await?;
+        let deleter = PathRewriteDeleter {
+            inner: deleter,
+            rewrite: self.rewrite.clone(),
+        };
+        Ok((rp, deleter))
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        let target = self.rewrite(path);
+        let args = match args.start_after() {
+            Some(v) => {
+                let start_after = self.rewrite(v);
+                args.with_start_after(&start_after)
+            }
+            None => args,
+        };
+
+        let (rp, lister) = self.inner.list(&target, args).await?;
+        let lister = PathRewriteLister {
+            inner: lister,
+            source: path.to_string(),
+            target,
+        };
+        Ok((rp, lister))
+    }
+
+    async fn set_tags(&self, path: &str, args: OpSetTags) -> Result<RpSetTags> {
+        self.inner.set_tags(&self.rewrite(path), args).await
+    }
+
+    async fn abort_multipart(
+        &self,
+        path: &str,
+        args: OpAbortMultipart,
+    ) -> Result<RpAbortMultipart> {
+        self.inner.abort_multipart(&self.rewrite(path), args).await
+    }
+
+    async fn list_multipart_uploads(
+        &self,
+        path: &str,
+        args: OpListMultipartUploads,
+    ) -> Result<RpListMultipartUploads> {
+        let target = self.rewrite(path);
+        let rp = self.inner.list_multipart_uploads(&target, args).await?;
+        let uploads = rp
+            .into_uploads()
+            .into_iter()
+            .map(|upload| {
+                let mapped = MultipartUpload::new(
+                    &reverse_path(path, &target, upload.path()),
+                    upload.upload_id(),
+                );
+                match upload.initiated() {
+                    Some(v) => mapped.with_initiated(v),
+                    None => mapped,
+                }
+            })
+            .collect();
+        Ok(RpListMultipartUploads::new(uploads))
+    }
+
+    async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        self.inner.presign(&self.rewrite(path), args).await
+    }
+}
+
+/// Map the path returned by service back to the namespace of callers.
+///
+/// `source` is the path passed by callers and `target` is the rewritten one.
+fn reverse_path(source: &str, target: &str, path: &str) -> String {
+    let target = if target == "/" { "" } else { target };
+    let source = if source == "/" { "" } else { source };
+    match path.strip_prefix(target) {
+        Some(rest) => {
+            let path = format!("{source}{rest}");
+            if path.is_empty() {
+                "/".to_string()
+            } else {
+                path
+            }
+        }
+        None => path.to_string(),
+    }
+}
+
+pub struct PathRewriteLister<L> {
+    inner: L,
+    source: String,
+    target: String,
+}
+
+impl<L: oio::List> oio::List for PathRewriteLister<L> {
+    async fn next(&mut self) -> Result<Option<oio::Entry>> {
+        let Some(mut entry) = self.inner.next().await? else {
+            return Ok(None);
+        };
+        let path = reverse_path(&self.source, &self.target, entry.path());
+        entry.set_path(&path);
+        Ok(Some(entry))
+    }
+}
+
+pub struct PathRewriteDeleter<D> {
+    inner: D,
+    rewrite: PathRewriter,
+}
+
+impl<D: oio::Delete> oio::Delete for PathRewriteDeleter<D> {
+    fn delete(&mut self, path: &str, args: OpDelete) -> Result<()> {
+        self.inner.delete(&(self.rewrite)(path), args)
+    }
+
+    async fn flush(&mut self) -> Result<usize> {
+        self.inner.flush().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::TryStreamExt;
+
+    use super::*;
+    use crate::services;
+
+    #[tokio::test]
+    async fn test_path_rewrite() -> Result<()> {
+        let op = Operator::new(services::Memory::default())?.finish();
+        op.write("v1/dir/file", "Hello").await?;
+        let rewritten = op
+            .clone()
+            .layer(PathRewriteLayer::new(Arc::new(|path| match path {
+                "/" => "v1/".to_string(),
+                _ => format!("v1/{path}"),
+            })));
+
+        assert_eq!(rewritten.read("dir/file").await?.to_vec(), b"Hello");
+        rewritten.write("dir/new", "World").await?;
+        assert_eq!(op.read("v1/dir/new").await?.to_vec(), b"World");
+
+        let mut paths: Vec<String> = rewritten
+            .lister_with("/")
+            .recursive(true)
+            .await?
+            .map_ok(|e| e.path().to_string())
+            .try_collect()
+            .await?;
+        paths.sort();
+        assert_eq!(paths, ["dir/file", "dir/new"]);
+
+        let mut paths: Vec<String> = rewritten
+            .list("dir/")
+            .await?
+            .into_iter()
+            .map(|e| e.path().to_string())
+            .filter(|p| p != "dir/")
+            .collect();
+        paths.sort();
+        assert_eq!(paths, ["dir/file", "dir/new"]);
+
+        rewritten.delete("dir/file").await?;
+        assert!(!op.exists("v1/dir/file").await?);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_path_rewrite_resolve_dot_segments() -> Result<()> {
+        let op = Operator::new(services::Memory::default())?.finish();
+        let resolved = op.clone().layer(PathRewriteLayer::resolve_dot_segments());
+
+        resolved.write("a/./b/../c", "Hello").await?;
+        assert_eq!(op.read("a/c").await?.to_vec(), b"Hello");
+        assert_eq!(resolved.read("../../a/c").await?.to_vec(), b"Hello");
+
+        // Without the layer, `.` and `..` are kept as is.
+        assert!(!op.exists("a/./b/../c").await?);
+        Ok(())
+    }
+}
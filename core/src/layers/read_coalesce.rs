@@ -0,0 +1,430 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::task::Context;
+use std::task::Poll;
+
+use futures::channel::oneshot;
+use futures::future::Shared;
+use futures::FutureExt;
+
+use crate::raw::*;
+use crate::*;
+
+/// Coalesce nearby small range reads on the same path into one read.
+///
+/// # Notes
+///
+/// `ReadCoalesceLayer` collects range reads issued around the same time, for
+/// example, via `futures::future::join_all` or from concurrent tasks, and sends
+/// a single read that covers all of them to the underlying service. The merged
+/// result will be sliced back to every caller.
+///
+/// A read joins a pending batch only if:
+///
+/// - the gap between it and the batch is not larger than `gap`.
+/// - the merged range is not larger than `max_size`.
+/// - the bytes fetched but not requested by anyone are not larger than `max_waste`.
+///
+/// Reads without a bounded range, or with conditions, version, overrides or
+/// extra headers, will be passed through. If the merged read fails, every
+/// caller will fall back to read its own range.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use opendal::layers::ReadCoalesceLayer;
+/// # use opendal::services;
+/// # use opendal::Operator;
+/// # use opendal::Result;
+///
+/// # fn main() -> Result<()> {
+/// let _ = Operator::new(services::Memory::default())?
+///     .layer(
+///         ReadCoalesceLayer::new()
+///             .with_gap(64 * 1024)
+///             .with_max_size(4 * 1024 * 1024),
+///     )
+///     .finish();
+/// Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ReadCoalesceLayer {
+    gap: u64,
+    max_size: u64,
+    max_waste: u64,
+}
+
+impl Default for ReadCoalesceLayer {
+    fn default() -> Self {
+        Self {
+            gap: 64 * 1024,
+            max_size: 4 * 1024 * 1024,
+            max_waste: 1024 * 1024,
+        }
+    }
+}
+
+impl ReadCoalesceLayer {
+    /// Create a new `ReadCoalesceLayer` with default settings.
+    ///
+    /// - gap: 64 KiB
+    /// - max_size: 4 MiB
+    /// - max_waste: 1 MiB
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the max gap in bytes between two ranges that can be merged.
+    pub fn with_gap(mut self, gap: u64) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// Set the max size in bytes of a merged read.
+    ///
+    /// Reads larger than this will never be coalesced.
+    pub fn with_max_size(mut self, max_size: u64) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// Set the max bytes a merged read could fetch that are not requested
+    /// by any caller.
+    pub fn with_max_waste(mut self, max_waste: u64) -> Self {
+        self.max_waste = max_waste;
+        self
+    }
+}
+
+impl<A: Access> Layer<A> for ReadCoalesceLayer {
+    type LayeredAccess = ReadCoalesceAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccess {
+        ReadCoalesceAccessor {
+            inner: Arc::new(inner),
+            config: *self,
+            batches: Arc::default(),
+        }
+    }
+}
+
+/// The result of a merged read, `None` means the read failed and callers
+/// should read by themselves.
+type BatchFuture = Shared<BoxedStaticFuture<Option<(u64, Buffer)>>>;
+
+type Batches = Arc<Mutex<HashMap<String, Vec<Batch>>>>;
+
+#[derive(Clone)]
+struct Batch {
+    state: Arc<Mutex<BatchState>>,
+    fut: BatchFuture,
+}
+
+struct BatchState {
+    start: u64,
+    end: u64,
+    /// The total size requested by callers in this batch.
+    requested: u64,
+    sealed: bool,
+}
+
+impl BatchState {
+    /// Try to join the range into this batch, returns `false` if the range
+    /// can't be merged.
+    fn try_join(&mut self, cfg: &ReadCoalesceLayer, offset: u64, size: u64) -> bool {
+        let end = offset + size;
+        if self.sealed || offset > self.end + cfg.gap || end + cfg.gap < self.start {
+            return false;
+        }
+
+        let start = self.start.min(offset);
+        let merged_end = self.end.max(end);
+        let span = merged_end - start;
+        if span > cfg.max_size || span.saturating_sub(self.requested + size) > cfg.max_waste {
+            return false;
+        }
+
+        self.start = start;
+        self.end = merged_end;
+        self.requested += size;
+        true
+    }
+}
+
+pub struct ReadCoalesceAccessor<A: Access> {
+    inner: Arc<A>,
+    config: ReadCoalesceLayer,
+    batches: Batches,
+}
+
+impl<A: Access> Debug for ReadCoalesceAccessor<A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReadCoalesceAccessor")
+            .field("inner", &self.inner)
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Only plain bounded range reads without conditions, version, overrides and
+/// extra headers can be coalesced.
+fn is_coalescable(args: &OpRead) -> bool {
+    args.range().size().is_some()
+        && args.suffix_range().is_none()
+        && args.ranges().is_empty()
+        && !args.decompress()
+        && args.if_match().is_none()
+        && args.if_none_match().is_none()
+        && args.if_modified_since().is_none()
+        && args.if_unmodified_since().is_none()
+        && args.override_content_type().is_none()
+        && args.override_cache_control().is_none()
+        && args.override_content_disposition().is_none()
+        && args.version().is_none()
+        && args.headers().is_none()
+}
+
+impl<A: Access> ReadCoalesceAccessor<A> {
+    /// Join a pending batch of path or start a new one.
+    ///
+    /// The sender will be returned if a new batch is started, the batch will
+    /// be sealed and sent once the sender is consumed or dropped.
+    fn join(
+        &self,
+        path: &str,
+        offset: u64,
+        size: u64,
+    ) -> (BatchFuture, Option<oneshot::Sender<()>>) {
+        let mut batches = self.batches.lock().unwrap();
+        let pending = batches.entry(path.to_string()).or_default();
+        for batch in pending.iter() {
+            if batch
+                .state
+                .lock()
+                .unwrap()
+                .try_join(&self.config, offset, size)
+            {
+                return (batch.fut.clone(), None);
+            }
+        }
+
+        let state = Arc::new(Mutex::new(BatchState {
+            start: offset,
+            end: offset + size,
+            requested: size,
+            sealed: false,
+        }));
+        let (tx, rx) = oneshot::channel();
+        let fut = Box::pin(read_batch(
+            self.inner.clone(),
+            self.batches.clone(),
+            path.to_string(),
+            state.clone(),
+            rx,
+        ));
+        let batch = Batch {
+            state,
+            fut: (fut as BoxedStaticFuture<_>).shared(),
+        };
+        pending.push(batch.clone());
+        (batch.fut, Some(tx))
+    }
+}
+
+async fn read_batch<A: Access>(
+    inner: Arc<A>,
+    batches: Batches,
+    path: String,
+    state: Arc<Mutex<BatchState>>,
+    start_rx: oneshot::Receiver<()>,
+) -> Option<(u64, Buffer)> {
+    let _ = start_rx.await;
+
+    let (start, end) = {
+        let mut state = state.lock().unwrap();
+        state.sealed = true;
+        (state.start, state.end)
+    };
+    {
+        let mut batches = batches.lock().unwrap();
+        if let Some(pending) = batches.get_mut(&path) {
+            pending.retain(|b| !Arc::ptr_eq(&b.state, &state));
+            if pending.is_empty() {
+                batches.remove(&path);
+            }
+        }
+    }
+
+    let args = OpRead::new().with_range(BytesRange::new(start, Some(end - start)));
+    let (_, mut r) = inner.read(&path, args).await.ok()?;
+    let buf = oio::Read::read_all(&mut r).await.ok()?;
+    Some((start, buf))
+}
+
+/// Yield to the executor once.
+struct YieldNow(bool);
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            return Poll::Ready(());
+        }
+        self.0 = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+impl<A: Access> LayeredAccess for ReadCoalesceAccessor<A> {
+    type Inner = A;
+    type Reader = TwoWays<A::Reader, Buffer>;
+    type Writer = A::Writer;
+    type Lister = A::Lister;
+    type Deleter = A::Deleter;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let range = args.range();
+        let (offset, size) = match range.size() {
+            Some(size) if is_coalescable(&args) && size <= self.config.max_size => {
+                (range.offset(), size)
+            }
+            _ => {
+                let (rp, r) = self.inner.read(path, args).await?;
+                return Ok((rp, TwoWays::One(r)));
+            }
+        };
+
+        let (fut, start_tx) = self.join(path, offset, size);
+        if let Some(tx) = start_tx {
+            // Give other reads issued at the same time a chance to join.
+            YieldNow(false).await;
+            let _ = tx.send(());
+        }
+        let Some((start, buf)) = fut.await else {
+            let (rp, r) = self.inner.read(path, args).await?;
+            return Ok((rp, TwoWays::One(r)));
+        };
+
+        // The merged read could return less data if it reaches the end of file.
+        let begin = ((offset - start) as usize).min(buf.len());
+        let end = ((offset - start + size) as usize).min(buf.len());
+        Ok((RpRead::new(), TwoWays::Two(buf.slice(begin..end))))
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.inner.write(path, args).await
+    }
+
+    async fn delete(&self) -> Result<(RpDelete, Self::Deleter)> {
+        self.inner.delete().await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        self.inner.list(path, args).await
+    }
+}
+
+#[cfg(all(test, feature = "tests"))]
+mod tests {
+    use std::future::IntoFuture;
+
+    use futures::future::join_all;
+
+    use super::*;
+    use crate::raw::tests::MockAccess;
+
+    const CONTENT: &[u8] = b"Hello, World! Hello, OpenDAL!";
+
+    fn new_operator(layer: ReadCoalesceLayer) -> (Operator, MockAccess) {
+        let mock = MockAccess::new();
+        mock.insert("test", CONTENT);
+        let op = OperatorBuilder::new(mock.clone()).finish().layer(layer);
+        (op, mock)
+    }
+
+    #[tokio::test]
+    async fn test_read_coalesce() {
+        let (op, mock) = new_operator(ReadCoalesceLayer::new().with_gap(2));
+
+        let ranges = [0..5u64, 7..12, 14..19, 24..29];
+        let bs = join_all(
+            ranges
+                .iter()
+                .map(|r| op.read_with("test").range(r.clone()).into_future()),
+        )
+        .await;
+        assert_eq!(mock.calls(Operation::Read, "test"), 2);
+        for (r, bs) in ranges.iter().zip(bs) {
+            assert_eq!(
+                bs.unwrap().to_vec(),
+                &CONTENT[r.start as usize..r.end as usize]
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_coalesce_limits() {
+        // Ranges exceed the waste budget should not be merged.
+        let (op, mock) = new_operator(ReadCoalesceLayer::new().with_gap(16).with_max_waste(4));
+        let bs =
+            join_all([0..5u64, 14..19].map(|r| op.read_with("test").range(r).into_future())).await;
+        assert_eq!(mock.calls(Operation::Read, "test"), 2);
+        assert_eq!(bs[1].as_ref().unwrap().to_vec(), &CONTENT[14..19]);
+
+        // Ranges exceed the max size should not be merged.
+        let (op, mock) = new_operator(ReadCoalesceLayer::new().with_max_size(8));
+        let _ =
+            join_all([0..5u64, 5..10].map(|r| op.read_with("test").range(r).into_future())).await;
+        assert_eq!(mock.calls(Operation::Read, "test"), 2);
+    }
+
+    #[tokio::test]
+    async fn test_read_coalesce_skip_decompress() -> Result<()> {
+        let mock = MockAccess::new().with_capability(|cap| Capability {
+            read_with_decompress: true,
+            ..cap
+        });
+        mock.insert("test", "compressed");
+        mock.insert_decompressed("test", "Hello, World!");
+        let op = OperatorBuilder::new(mock)
+            .layer(ReadCoalesceLayer::new())
+            .finish();
+
+        let bs = join_all(
+            [0..5u64, 7..12].map(|r| op.read_with("test").range(r).decompress(true).into_future()),
+        )
+        .await;
+        assert_eq!(bs[0].as_ref().unwrap().to_vec(), b"Hello");
+        assert_eq!(bs[1].as_ref().unwrap().to_vec(), b"World");
+        Ok(())
+    }
+}
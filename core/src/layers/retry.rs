@@ -18,11 +18,13 @@
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::sync::Arc;
+use std::sync::Mutex;
 use std::time::Duration;
 
 use backon::BlockingRetryable;
 use backon::ExponentialBuilder;
 use backon::Retryable;
+use backon::Sleeper;
 use log::warn;
 
 use crate::raw::*;
@@ -118,14 +120,22 @@ use crate::*;
 /// ```
 pub struct RetryLayer<I: RetryInterceptor = DefaultRetryInterceptor> {
     builder: ExponentialBuilder,
+    delete_builder: Option<ExponentialBuilder>,
     notify: Arc<I>,
+    budget: Arc<RetryBudget>,
+    policy: RetryPolicy,
+    clock: Arc<dyn Clock>,
 }
 
 impl<I: RetryInterceptor> Clone for RetryLayer<I> {
     fn clone(&self) -> Self {
         Self {
             builder: self.builder,
+            delete_builder: self.delete_builder,
             notify: self.notify.clone(),
+            budget: self.budget.clone(),
+            policy: self.policy.clone(),
+            clock: self.clock.clone(),
         }
     }
 }
@@ -134,7 +144,11 @@ impl Default for RetryLayer {
     fn default() -> Self {
         Self {
             builder: ExponentialBuilder::default(),
+            delete_builder: None,
             notify: Arc::new(DefaultRetryInterceptor),
+            budget: Arc::default(),
+            policy: RetryPolicy::default(),
+            clock: Arc::new(SystemClock),
         }
     }
 }
@@ -177,7 +191,11 @@ impl<I: RetryInterceptor> RetryLayer<I> {
     pub fn with_notify<NI: RetryInterceptor>(self, notify: NI) -> RetryLayer<NI> {
         RetryLayer {
             builder: self.builder,
+            delete_builder: self.delete_builder,
             notify: Arc::new(notify),
+            budget: self.budget,
+            policy: self.policy,
+            clock: self.clock,
         }
     }
 
@@ -221,20 +239,334 @@ impl<I: RetryInterceptor> RetryLayer<I> {
         self.builder = self.builder.with_max_times(max_times);
         self
     }
+
+    /// Set the backoff for delete operations.
+    ///
+    /// This allows deletes against rate-limited services to back off more
+    /// aggressively than other operations. Deletes use the same backoff as other operations if not set.
+    ///
+    /// Deleting a path that doesn't exist is treated as success by services, so
+    /// retrying a delete that has already taken effect is safe.
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// use opendal::layers::RetryBackoff;
+    /// use opendal::layers::RetryLayer;
+    /// use opendal::services;
+    /// use opendal::Operator;
+    ///
+    /// let _ = Operator::new(services::Memory::default())
+    ///     .expect("must init")
+    ///     .layer(
+    ///         RetryLayer::new().with_delete_backoff(
+    ///             RetryBackoff::new()
+    ///                 .with_jitter()
+    ///                 .with_factor(4.0)
+    ///                 .with_min_delay(Duration::from_secs(1))
+    ///                 .with_max_times(10),
+    ///         ),
+    ///     )
+    ///     .finish();
+    /// ```
+    pub fn with_delete_backoff(mut self, backoff: RetryBackoff) -> Self {
+        self.delete_builder = Some(backoff.builder);
+        self
+    }
+
+    /// Set the retry budget shared by all operations of this layer.
+    ///
+    /// The budget is a token bucket that starts with `capacity` tokens. Every retry
+    /// takes 5 tokens from the bucket and every successful operation returns 1 token,
+    /// so retries will be suppressed and the original error will be returned
+    /// immediately once failures dominate. The first attempt of an operation is never
+    /// suppressed.
+    ///
+    /// Operators built with clones of this layer share the same budget. Retries are not
+    /// limited by default.
+    ///
+    /// ```no_run
+    /// use opendal::layers::RetryLayer;
+    /// use opendal::services;
+    /// use opendal::Operator;
+    ///
+    /// let _ = Operator::new(services::Memory::default())
+    ///     .expect("must init")
+    ///     .layer(RetryLayer::new().with_retry_budget(500))
+    ///     .finish();
+    /// ```
+    pub fn with_retry_budget(mut self, capacity: usize) -> Self {
+        self.budget = Arc::new(RetryBudget::new(capacity));
+        self
+    }
+
+    /// Set the clock used to sleep between retries.
+    ///
+    /// It's useful to verify the backoff in tests without real sleeps, see [`Clock`]
+    /// for more details. Sync deletes always sleep with the system clock.
+    pub fn with_clock(mut self, clock: impl Clock) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Retry errors of given kind even if they are not temporary.
+    ///
+    /// It's useful for services that return errors like [`ErrorKind::NotFound`]
+    /// for a while after the object has been written. Errors that have already
+    /// been retried by an inner `RetryLayer` will not be retried again.
+    ///
+    /// ```no_run
+    /// use opendal::layers::RetryLayer;
+    /// use opendal::services;
+    /// use opendal::ErrorKind;
+    /// use opendal::Operator;
+    ///
+    /// let _ = Operator::new(services::Memory::default())
+    ///     .expect("must init")
+    ///     .layer(RetryLayer::new().with_retryable_kind(ErrorKind::NotFound))
+    ///     .finish();
+    /// ```
+    pub fn with_retryable_kind(mut self, kind: ErrorKind) -> Self {
+        self.policy = self.policy.with_retryable_kind(kind);
+        self
+    }
+
+    /// Retry errors matched by the given predicate even if they are not temporary.
+    ///
+    /// The predicate augments the default classification: errors are retried if
+    /// they are temporary, of a retryable kind, or matched by any predicate.
+    /// Errors that have already been retried by an inner `RetryLayer` will not
+    /// be retried again.
+    ///
+    /// ```no_run
+    /// use std::sync::Arc;
+    ///
+    /// use opendal::layers::RetryLayer;
+    /// use opendal::services;
+    /// use opendal::Operator;
+    ///
+    /// let _ = Operator::new(services::Memory::default())
+    ///     .expect("must init")
+    ///     .layer(
+    ///         RetryLayer::new()
+    ///             .with_retry_predicate(Arc::new(|err| err.context("status") == Some("409"))),
+    ///     )
+    ///     .finish();
+    /// ```
+    pub fn with_retry_predicate(mut self, predicate: RetryPredicate) -> Self {
+        self.policy = self.policy.with_retry_predicate(predicate);
+        self
+    }
+
+    /// Get the [`RetryPolicy`] of this layer.
+    pub fn policy(&self) -> RetryPolicy {
+        self.policy.clone()
+    }
 }
 
 impl<A: Access, I: RetryInterceptor> Layer<A> for RetryLayer<I> {
     type LayeredAccess = RetryAccessor<A, I>;
 
     fn layer(&self, inner: A) -> Self::LayeredAccess {
+        inner.info().set_retry_policy(self.policy.clone());
+
         RetryAccessor {
             inner: Arc::new(inner),
             builder: self.builder,
+            delete_builder: self.delete_builder.unwrap_or(self.builder),
             notify: self.notify.clone(),
+            budget: self.budget.clone(),
+            policy: Arc::new(self.policy.clone()),
+            clock: self.clock.clone(),
         }
     }
 }
 
+/// ClockSleeper sleeps between retries with the clock of the layer.
+struct ClockSleeper(Arc<dyn Clock>);
+
+impl Sleeper for ClockSleeper {
+    type Sleep = BoxedStaticFuture<()>;
+
+    fn sleep(&self, dur: Duration) -> Self::Sleep {
+        self.0.sleep(dur)
+    }
+}
+
+/// RetryPolicy decides whether an error will be retried by [`RetryLayer`].
+///
+/// The policy of the outermost `RetryLayer` can be fetched by
+/// [`OperatorInfo::retry_policy`], which allows users to classify errors as
+/// transient or fatal in the same way as the layer.
+///
+/// ```no_run
+/// use opendal::layers::RetryLayer;
+/// use opendal::services;
+/// use opendal::Error;
+/// use opendal::Operator;
+///
+/// fn is_transient(op: &Operator, err: &Error) -> bool {
+///     op.info()
+///         .retry_policy()
+///         .is_some_and(|policy| policy.is_retryable(err))
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct RetryPolicy {
+    retryable_kinds: Vec<ErrorKind>,
+    predicates: Vec<RetryPredicate>,
+}
+
+/// RetryPredicate decides whether an error should be retried in addition to
+/// the default classification of [`RetryPolicy`].
+pub type RetryPredicate = Arc<dyn Fn(&Error) -> bool + Send + Sync>;
+
+impl Debug for RetryPolicy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("retryable_kinds", &self.retryable_kinds)
+            .field("predicates", &self.predicates.len())
+            .finish()
+    }
+}
+
+impl RetryPolicy {
+    /// Retry errors of given kind even if they are not temporary.
+    pub fn with_retryable_kind(mut self, kind: ErrorKind) -> Self {
+        if !self.retryable_kinds.contains(&kind) {
+            self.retryable_kinds.push(kind);
+        }
+        self
+    }
+
+    /// Retry errors matched by the given predicate even if they are not temporary.
+    pub fn with_retry_predicate(mut self, predicate: RetryPredicate) -> Self {
+        self.predicates.push(predicate);
+        self
+    }
+
+    /// Check if the error is retryable under this policy.
+    ///
+    /// Temporary errors, errors of the configured retryable kinds and errors
+    /// matched by the retry predicates are retryable, unless they have already
+    /// been retried and marked as persistent.
+    pub fn is_retryable(&self, err: &Error) -> bool {
+        if err.is_persistent() {
+            return false;
+        }
+        err.is_temporary()
+            || self.retryable_kinds.contains(&err.kind())
+            || self.predicates.iter().any(|f| f(err))
+    }
+}
+
+/// The tokens taken from the budget by every retry.
+const RETRY_COST: usize = 5;
+/// The tokens returned to the budget by every successful operation.
+const SUCCESS_REWARD: usize = 1;
+
+/// RetryBudget is a token bucket that limits retries while failures dominate.
+#[derive(Debug, Default)]
+struct RetryBudget {
+    /// The max tokens of the bucket, retries are not limited if it's `None`.
+    capacity: Option<usize>,
+    tokens: Mutex<usize>,
+}
+
+impl RetryBudget {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity: Some(capacity),
+            tokens: Mutex::new(capacity),
+        }
+    }
+
+    /// Check if the error should be retried, tokens will be taken from the
+    /// budget for the retry.
+    fn should_retry(&self, policy: &RetryPolicy, err: &Error) -> bool {
+        if !policy.is_retryable(err) {
+            return false;
+        }
+        if self.capacity.is_none() {
+            return true;
+        }
+
+        let mut tokens = self.tokens.lock().unwrap();
+        match tokens.checked_sub(RETRY_COST) {
+            Some(v) => {
+                *tokens = v;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Return tokens to the budget after an operation succeeded.
+    fn on_success(&self) {
+        if let Some(capacity) = self.capacity {
+            let mut tokens = self.tokens.lock().unwrap();
+            *tokens = (*tokens + SUCCESS_REWARD).min(capacity);
+        }
+    }
+}
+
+/// RetryBackoff is the exponential backoff used to retry a kind of operations,
+/// see [`RetryLayer::with_delete_backoff`].
+///
+/// The defaults are the same as [`RetryLayer`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RetryBackoff {
+    builder: ExponentialBuilder,
+}
+
+impl RetryBackoff {
+    /// Create a new backoff with default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set jitter of current backoff.
+    ///
+    /// If jitter is enabled, ExponentialBackoff will add a random jitter in `[0, min_delay)
+    /// to current delay.
+    pub fn with_jitter(mut self) -> Self {
+        self.builder = self.builder.with_jitter();
+        self
+    }
+
+    /// Set factor of current backoff.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if input factor smaller than `1.0`.
+    pub fn with_factor(mut self, factor: f32) -> Self {
+        self.builder = self.builder.with_factor(factor);
+        self
+    }
+
+    /// Set min_delay of current backoff.
+    pub fn with_min_delay(mut self, min_delay: Duration) -> Self {
+        self.builder = self.builder.with_min_delay(min_delay);
+        self
+    }
+
+    /// Set max_delay of current backoff.
+    ///
+    /// Delay will not increase if current delay is larger than max_delay.
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.builder = self.builder.with_max_delay(max_delay);
+        self
+    }
+
+    /// Set max_times of current backoff.
+    ///
+    /// Backoff will return `None` if max times is reaching.
+    pub fn with_max_times(mut self, max_times: usize) -> Self {
+        self.builder = self.builder.with_max_times(max_times);
+        self
+    }
+}
+
 /// RetryInterceptor is used to intercept while retry happened.
 pub trait RetryInterceptor: Send + Sync + 'static {
     /// Everytime RetryLayer is retrying, this function will be called.
@@ -279,7 +611,11 @@ impl RetryInterceptor for DefaultRetryInterceptor {
 pub struct RetryAccessor<A: Access, I: RetryInterceptor> {
     inner: Arc<A>,
     builder: ExponentialBuilder,
+    delete_builder: ExponentialBuilder,
     notify: Arc<I>,
+    budget: Arc<RetryBudget>,
+    policy: Arc<RetryPolicy>,
+    clock: Arc<dyn Clock>,
 }
 
 impl<A: Access, I: RetryInterceptor> Debug for RetryAccessor<A, I> {
@@ -304,22 +640,33 @@ impl<A: Access, I: RetryInterceptor> LayeredAccess for RetryAccessor<A, I> {
     async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
         { || self.inner.create_dir(path, args.clone()) }
             .retry(self.builder)
-            .when(|e| e.is_temporary())
+            .sleep(ClockSleeper(self.clock.clone()))
+            .when(|e| self.budget.should_retry(&self.policy, e))
             .notify(|err, dur: Duration| self.notify.intercept(err, dur))
             .await
+            .inspect(|_| self.budget.on_success())
             .map_err(|e| e.set_persistent())
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
         let (rp, reader) = { || self.inner.read(path, args.clone()) }
             .retry(self.builder)
-            .when(|e| e.is_temporary())
+            .sleep(ClockSleeper(self.clock.clone()))
+            .when(|e| self.budget.should_retry(&self.policy, e))
             .notify(|err, dur| self.notify.intercept(err, dur))
             .await
+            .inspect(|_| self.budget.on_success())
             .map_err(|e| e.set_persistent())?;
 
         let retry_reader = RetryReader::new(self.inner.clone(), path.to_string(), args, reader);
-        let retry_wrapper = RetryWrapper::new(retry_reader, self.notify.clone(), self.builder);
+        let retry_wrapper = RetryWrapper::new(
+            retry_reader,
+            self.notify.clone(),
+            self.builder,
+            self.budget.clone(),
+            self.policy.clone(),
+            self.clock.clone(),
+        );
 
         Ok((rp, retry_wrapper))
     }
@@ -327,57 +674,146 @@ impl<A: Access, I: RetryInterceptor> LayeredAccess for RetryAccessor<A, I> {
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
         { || self.inner.write(path, args.clone()) }
             .retry(self.builder)
-            .when(|e| e.is_temporary())
+            .sleep(ClockSleeper(self.clock.clone()))
+            .when(|e| self.budget.should_retry(&self.policy, e))
             .notify(|err, dur| self.notify.intercept(err, dur))
             .await
-            .map(|(rp, r)| (rp, RetryWrapper::new(r, self.notify.clone(), self.builder)))
+            .inspect(|_| self.budget.on_success())
+            .map(|(rp, r)| {
+                (
+                    rp,
+                    RetryWrapper::new(
+                        r,
+                        self.notify.clone(),
+                        self.builder,
+                        self.budget.clone(),
+                        self.policy.clone(),
+                        self.clock.clone(),
+                    ),
+                )
+            })
             .map_err(|e| e.set_persistent())
     }
 
     async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
         { || self.inner.stat(path, args.clone()) }
             .retry(self.builder)
-            .when(|e| e.is_temporary())
+            .sleep(ClockSleeper(self.clock.clone()))
+            .when(|e| self.budget.should_retry(&self.policy, e))
             .notify(|err, dur| self.notify.intercept(err, dur))
             .await
+            .inspect(|_| self.budget.on_success())
             .map_err(|e| e.set_persistent())
     }
 
     async fn delete(&self) -> Result<(RpDelete, Self::Deleter)> {
         { || self.inner.delete() }
-            .retry(self.builder)
-            .when(|e| e.is_temporary())
+            .retry(self.delete_builder)
+            .sleep(ClockSleeper(self.clock.clone()))
+            .when(|e| self.budget.should_retry(&self.policy, e))
             .notify(|err, dur| self.notify.intercept(err, dur))
             .await
-            .map(|(rp, r)| (rp, RetryWrapper::new(r, self.notify.clone(), self.builder)))
+            .inspect(|_| self.budget.on_success())
+            .map(|(rp, r)| {
+                (
+                    rp,
+                    RetryWrapper::new(
+                        r,
+                        self.notify.clone(),
+                        self.delete_builder,
+                        self.budget.clone(),
+                        self.policy.clone(),
+                        self.clock.clone(),
+                    ),
+                )
+            })
             .map_err(|e| e.set_persistent())
     }
 
     async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
         { || self.inner.copy(from, to, args.clone()) }
             .retry(self.builder)
-            .when(|e| e.is_temporary())
+            .sleep(ClockSleeper(self.clock.clone()))
+            .when(|e| self.budget.should_retry(&self.policy, e))
             .notify(|err, dur| self.notify.intercept(err, dur))
             .await
+            .inspect(|_| self.budget.on_success())
+            .map_err(|e| e.set_persistent())
+    }
+
+    async fn set_tags(&self, path: &str, args: OpSetTags) -> Result<RpSetTags> {
+        { || self.inner.set_tags(path, args.clone()) }
+            .retry(self.builder)
+            .sleep(ClockSleeper(self.clock.clone()))
+            .when(|e| self.budget.should_retry(&self.policy, e))
+            .notify(|err, dur| self.notify.intercept(err, dur))
+            .await
+            .inspect(|_| self.budget.on_success())
+            .map_err(|e| e.set_persistent())
+    }
+
+    async fn abort_multipart(
+        &self,
+        path: &str,
+        args: OpAbortMultipart,
+    ) -> Result<RpAbortMultipart> {
+        { || self.inner.abort_multipart(path, args.clone()) }
+            .retry(self.builder)
+            .sleep(ClockSleeper(self.clock.clone()))
+            .when(|e| self.budget.should_retry(&self.policy, e))
+            .notify(|err, dur| self.notify.intercept(err, dur))
+            .await
+            .inspect(|_| self.budget.on_success())
+            .map_err(|e| e.set_persistent())
+    }
+
+    async fn list_multipart_uploads(
+        &self,
+        path: &str,
+        args: OpListMultipartUploads,
+    ) -> Result<RpListMultipartUploads> {
+        { || self.inner.list_multipart_uploads(path, args.clone()) }
+            .retry(self.builder)
+            .sleep(ClockSleeper(self.clock.clone()))
+            .when(|e| self.budget.should_retry(&self.policy, e))
+            .notify(|err, dur| self.notify.intercept(err, dur))
+            .await
+            .inspect(|_| self.budget.on_success())
             .map_err(|e| e.set_persistent())
     }
 
     async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
         { || self.inner.rename(from, to, args.clone()) }
             .retry(self.builder)
-            .when(|e| e.is_temporary())
+            .sleep(ClockSleeper(self.clock.clone()))
+            .when(|e| self.budget.should_retry(&self.policy, e))
             .notify(|err, dur| self.notify.intercept(err, dur))
             .await
+            .inspect(|_| self.budget.on_success())
             .map_err(|e| e.set_persistent())
     }
 
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
         { || self.inner.list(path, args.clone()) }
             .retry(self.builder)
-            .when(|e| e.is_temporary())
+            .sleep(ClockSleeper(self.clock.clone()))
+            .when(|e| self.budget.should_retry(&self.policy, e))
             .notify(|err, dur| self.notify.intercept(err, dur))
             .await
-            .map(|(rp, r)| (rp, RetryWrapper::new(r, self.notify.clone(), self.builder)))
+            .inspect(|_| self.budget.on_success())
+            .map(|(rp, r)| {
+                (
+                    rp,
+                    RetryWrapper::new(
+                        r,
+                        self.notify.clone(),
+                        self.builder,
+                        self.budget.clone(),
+                        self.policy.clone(),
+                        self.clock.clone(),
+                    ),
+                )
+            })
             .map_err(|e| e.set_persistent())
     }
 }
@@ -420,6 +856,24 @@ impl<A: Access> oio::Read for RetryReader<A, A::Reader> {
             }
         }
     }
+
+    async fn read_into(&mut self, dst: &mut [u8]) -> Result<usize> {
+        loop {
+            match self.reader.take() {
+                None => {
+                    let (_, r) = self.inner.read(&self.path, self.args.clone()).await?;
+                    self.reader = Some(r);
+                    continue;
+                }
+                Some(mut reader) => {
+                    let n = reader.read_into(dst).await?;
+                    self.reader = Some(reader);
+                    self.args.range_mut().advance(n as u64);
+                    return Ok(n);
+                }
+            }
+        }
+    }
 }
 
 pub struct RetryWrapper<R, I> {
@@ -427,14 +881,27 @@ pub struct RetryWrapper<R, I> {
     notify: Arc<I>,
 
     builder: ExponentialBuilder,
+    budget: Arc<RetryBudget>,
+    policy: Arc<RetryPolicy>,
+    clock: Arc<dyn Clock>,
 }
 
 impl<R, I> RetryWrapper<R, I> {
-    fn new(inner: R, notify: Arc<I>, backoff: ExponentialBuilder) -> Self {
+    fn new(
+        inner: R,
+        notify: Arc<I>,
+        backoff: ExponentialBuilder,
+        budget: Arc<RetryBudget>,
+        policy: Arc<RetryPolicy>,
+        clock: Arc<dyn Clock>,
+    ) -> Self {
         Self {
             inner: Some(inner),
             notify,
             builder: backoff,
+            budget,
+            policy,
+            clock,
         }
     }
 
@@ -462,13 +929,42 @@ impl<R: oio::Read, I: RetryInterceptor> oio::Read for RetryWrapper<R, I> {
             }
         }
         .retry(self.builder)
-        .when(|e| e.is_temporary())
+        .sleep(ClockSleeper(self.clock.clone()))
+        .when(|e| self.budget.should_retry(&self.policy, e))
         .context(inner)
         .notify(|err, dur| self.notify.intercept(err, dur))
         .await;
 
         self.inner = Some(inner);
-        res.map_err(|err| err.set_persistent())
+        res.inspect(|_| self.budget.on_success())
+            .map_err(|err| err.set_persistent())
+    }
+
+    async fn read_into(&mut self, dst: &mut [u8]) -> Result<usize> {
+        use backon::RetryableWithContext;
+
+        let inner = self.take_inner()?;
+
+        // Use fn instead of closure to make sure `dst` could be returned back.
+        async fn read_into<R: oio::Read>(
+            (mut r, dst): (R, &mut [u8]),
+        ) -> ((R, &mut [u8]), Result<usize>) {
+            let res = r.read_into(&mut *dst).await;
+
+            ((r, dst), res)
+        }
+
+        let ((inner, _), res) = read_into
+            .retry(self.builder)
+            .sleep(ClockSleeper(self.clock.clone()))
+            .when(|e| self.budget.should_retry(&self.policy, e))
+            .context((inner, dst))
+            .notify(|err, dur| self.notify.intercept(err, dur))
+            .await;
+
+        self.inner = Some(inner);
+        res.inspect(|_| self.budget.on_success())
+            .map_err(|err| err.set_persistent())
     }
 }
 
@@ -486,13 +982,39 @@ impl<R: oio::Write, I: RetryInterceptor> oio::Write for RetryWrapper<R, I> {
             }
         }
         .retry(self.builder)
-        .when(|e| e.is_temporary())
+        .sleep(ClockSleeper(self.clock.clone()))
+        .when(|e| self.budget.should_retry(&self.policy, e))
         .context((inner, bs))
         .notify(|err, dur| self.notify.intercept(err, dur))
         .await;
 
         self.inner = Some(inner);
-        res.map_err(|err| err.set_persistent())
+        res.inspect(|_| self.budget.on_success())
+            .map_err(|err| err.set_persistent())
+    }
+
+    async fn flush(&mut self) -> Result<()> {
+        use backon::RetryableWithContext;
+
+        let inner = self.take_inner()?;
+
+        let (inner, res) = {
+            |mut r: R| async move {
+                let res = r.flush().await;
+
+                (r, res)
+            }
+        }
+        .retry(self.builder)
+        .sleep(ClockSleeper(self.clock.clone()))
+        .when(|e| self.budget.should_retry(&self.policy, e))
+        .context(inner)
+        .notify(|err, dur| self.notify.intercept(err, dur))
+        .await;
+
+        self.inner = Some(inner);
+        res.inspect(|_| self.budget.on_success())
+            .map_err(|err| err.set_persistent())
     }
 
     async fn abort(&mut self) -> Result<()> {
@@ -508,13 +1030,15 @@ impl<R: oio::Write, I: RetryInterceptor> oio::Write for RetryWrapper<R, I> {
             }
         }
         .retry(self.builder)
-        .when(|e| e.is_temporary())
+        .sleep(ClockSleeper(self.clock.clone()))
+        .when(|e| self.budget.should_retry(&self.policy, e))
         .context(inner)
         .notify(|err, dur| self.notify.intercept(err, dur))
         .await;
 
         self.inner = Some(inner);
-        res.map_err(|err| err.set_persistent())
+        res.inspect(|_| self.budget.on_success())
+            .map_err(|err| err.set_persistent())
     }
 
     async fn close(&mut self) -> Result<Metadata> {
@@ -530,13 +1054,15 @@ impl<R: oio::Write, I: RetryInterceptor> oio::Write for RetryWrapper<R, I> {
             }
         }
         .retry(self.builder)
-        .when(|e| e.is_temporary())
+        .sleep(ClockSleeper(self.clock.clone()))
+        .when(|e| self.budget.should_retry(&self.policy, e))
         .context(inner)
         .notify(|err, dur| self.notify.intercept(err, dur))
         .await;
 
         self.inner = Some(inner);
-        res.map_err(|err| err.set_persistent())
+        res.inspect(|_| self.budget.on_success())
+            .map_err(|err| err.set_persistent())
     }
 }
 
@@ -554,25 +1080,28 @@ impl<P: oio::List, I: RetryInterceptor> oio::List for RetryWrapper<P, I> {
             }
         }
         .retry(self.builder)
-        .when(|e| e.is_temporary())
+        .sleep(ClockSleeper(self.clock.clone()))
+        .when(|e| self.budget.should_retry(&self.policy, e))
         .context(inner)
         .notify(|err, dur| self.notify.intercept(err, dur))
         .await;
 
         self.inner = Some(inner);
-        res.map_err(|err| err.set_persistent())
+        res.inspect(|_| self.budget.on_success())
+            .map_err(|err| err.set_persistent())
     }
 }
 
 impl<P: oio::Delete, I: RetryInterceptor> oio::Delete for RetryWrapper<P, I> {
     fn delete(&mut self, path: &str, args: OpDelete) -> Result<()> {
-        { || self.inner.as_mut().unwrap().delete(path, args.clone()) }
+        let res = { || self.inner.as_mut().unwrap().delete(path, args.clone()) }
             .retry(self.builder)
-            .when(|e| e.is_temporary())
+            .when(|e| self.budget.should_retry(&self.policy, e))
             .notify(|err, dur| {
                 self.notify.intercept(err, dur);
             })
-            .call()
+            .call();
+        res.inspect(|_| self.budget.on_success())
             .map_err(|e| e.set_persistent())
     }
 
@@ -589,13 +1118,15 @@ impl<P: oio::Delete, I: RetryInterceptor> oio::Delete for RetryWrapper<P, I> {
             }
         }
         .retry(self.builder)
-        .when(|e| e.is_temporary())
+        .sleep(ClockSleeper(self.clock.clone()))
+        .when(|e| self.budget.should_retry(&self.policy, e))
         .context(inner)
         .notify(|err, dur| self.notify.intercept(err, dur))
         .await;
 
         self.inner = Some(inner);
-        res.map_err(|err| err.set_persistent())
+        res.inspect(|_| self.budget.on_success())
+            .map_err(|err| err.set_persistent())
     }
 }
 
@@ -612,6 +1143,7 @@ mod tests {
 
     use super::*;
     use crate::layers::LoggingLayer;
+    use crate::services;
 
     #[derive(Default, Clone)]
     struct MockBuilder {
@@ -715,13 +1247,17 @@ mod tests {
                         .set_temporary(),
                 ),
                 // Should read out all data.
-                3 => Ok(self.buf.slice(self.range.to_range_as_usize())),
+                3 => Ok(self
+                    .buf
+                    .slice(self.range.to_range_as_usize_within(self.buf.len()))),
                 4 => Err(
                     Error::new(ErrorKind::Unexpected, "retryable_error from reader")
                         .set_temporary(),
                 ),
                 // Should be empty.
-                5 => Ok(self.buf.slice(self.range.to_range_as_usize())),
+                5 => Ok(self
+                    .buf
+                    .slice(self.range.to_range_as_usize_within(self.buf.len()))),
                 _ => unreachable!(),
             }
         }
@@ -930,4 +1466,140 @@ mod tests {
         op.delete_stream(stream::iter(paths)).await.unwrap();
         assert_eq!(*builder.attempt.lock().unwrap(), 5);
     }
+    #[tokio::test]
+    async fn test_retry_batch_with_delete_backoff() {
+        let _ = tracing_subscriber::fmt().with_test_writer().try_init();
+
+        let builder = MockBuilder::default();
+        // Other operations will not be retried.
+        let op = Operator::new(builder.clone())
+            .unwrap()
+            .layer(
+                RetryLayer::new().with_max_times(0).with_delete_backoff(
+                    RetryBackoff::new()
+                        .with_min_delay(Duration::from_secs_f32(0.1))
+                        .with_max_times(5),
+                ),
+            )
+            .finish();
+
+        let paths = vec!["hello", "world", "test", "batch"];
+        op.delete_stream(stream::iter(paths)).await.unwrap();
+        assert_eq!(*builder.attempt.lock().unwrap(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_retry_policy() {
+        let temporary = Error::new(ErrorKind::Unexpected, "retryable error").set_temporary();
+        let not_found = Error::new(ErrorKind::NotFound, "not found");
+        assert!(!RetryPolicy::default().is_retryable(&not_found));
+
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        assert!(op.info().retry_policy().is_none());
+
+        let retries = Arc::new(Mutex::new(0));
+        let counter = retries.clone();
+        let op = op.layer(
+            RetryLayer::new()
+                .with_retryable_kind(ErrorKind::NotFound)
+                .with_min_delay(Duration::from_millis(1))
+                .with_max_times(3)
+                .with_notify(move |_: &Error, _: Duration| *counter.lock().unwrap() += 1),
+        );
+        let policy = op.info().retry_policy().expect("retry policy must be set");
+        assert!(policy.is_retryable(&temporary));
+        assert!(policy.is_retryable(&not_found));
+        assert!(!policy.is_retryable(&not_found.set_persistent()));
+
+        let err = op.stat("not_exist").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+        assert!(err.is_persistent());
+        assert_eq!(*retries.lock().unwrap(), 3);
+    }
+
+    #[test]
+    fn test_retry_predicate() {
+        let conflict = Error::new(ErrorKind::Unexpected, "conflict").with_context("status", 409);
+        let not_found = Error::new(ErrorKind::NotFound, "not found");
+        let temporary = Error::new(ErrorKind::Unexpected, "retryable error").set_temporary();
+
+        let policy = RetryLayer::new()
+            .with_retryable_kind(ErrorKind::NotFound)
+            .with_retry_predicate(Arc::new(|err| err.context("status") == Some("409")))
+            .policy();
+        assert!(policy.is_retryable(&conflict));
+        // The predicate composes with the default classification.
+        assert!(policy.is_retryable(&not_found));
+        assert!(policy.is_retryable(&temporary));
+        assert!(!policy.is_retryable(&conflict.set_persistent()));
+        assert!(!policy.is_retryable(&Error::new(ErrorKind::Unexpected, "other")));
+    }
+
+    #[test]
+    fn test_retry_budget() {
+        let err = Error::new(ErrorKind::Unexpected, "retryable error").set_temporary();
+        let policy = RetryPolicy::default();
+
+        let budget = RetryBudget::default();
+        for _ in 0..100 {
+            assert!(
+                budget.should_retry(&policy, &err),
+                "retries are not limited by default"
+            );
+        }
+
+        let budget = RetryBudget::new(2 * RETRY_COST);
+        assert!(!budget.should_retry(&policy, &Error::new(ErrorKind::NotFound, "not found")));
+        assert!(budget.should_retry(&policy, &err));
+        assert!(budget.should_retry(&policy, &err));
+        assert!(
+            !budget.should_retry(&policy, &err),
+            "budget must be exhausted"
+        );
+
+        for _ in 0..RETRY_COST {
+            budget.on_success();
+        }
+        assert!(
+            budget.should_retry(&policy, &err),
+            "successes must refill the budget"
+        );
+        assert!(!budget.should_retry(&policy, &err));
+
+        // Tokens never exceed the capacity.
+        for _ in 0..100 {
+            budget.on_success();
+        }
+        assert_eq!(*budget.tokens.lock().unwrap(), 2 * RETRY_COST);
+    }
+
+    #[cfg(feature = "tests")]
+    #[tokio::test]
+    async fn test_retry_with_clock() -> Result<()> {
+        use std::pin::pin;
+
+        use crate::raw::tests::MockAccess;
+        use crate::raw::tests::MockClock;
+
+        let clock = MockClock::new();
+        let mock = MockAccess::new();
+        mock.insert("hello", "Hello, World!");
+        mock.inject_error(Operation::Stat, "hello", 1, || {
+            Error::new(ErrorKind::RateLimited, "mock error").set_temporary()
+        });
+        let op = OperatorBuilder::new(mock).finish().layer(
+            RetryLayer::new()
+                .with_min_delay(Duration::from_secs(60))
+                .with_clock(clock.clone()),
+        );
+
+        let mut fut = pin!(op.stat("hello"));
+        assert!(futures::poll!(fut.as_mut()).is_pending());
+        clock.advance(Duration::from_secs(59));
+        assert!(futures::poll!(fut.as_mut()).is_pending());
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(fut.await?.content_length(), 13);
+
+        Ok(())
+    }
 }
@@ -0,0 +1,385 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::hash::Hash;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use futures::future::Shared;
+use futures::FutureExt;
+
+use crate::raw::*;
+use crate::*;
+
+/// Deduplicate identical in-flight `read` and `stat` requests.
+///
+/// # Notes
+///
+/// `SingleFlightLayer` collapses concurrent identical requests into one call to
+/// the underlying service, and the result will be shared by all waiters:
+///
+/// - `read` requests are identical if they have the same path and range. The
+///   whole range will be loaded into memory and shared as a cheap-clone [`Buffer`].
+/// - `stat` requests are identical if they have the same path.
+///
+/// Only bounded reads no larger than `max_size` (4 MiB by default, see
+/// [`SingleFlightLayer::with_max_size`]) are shared, other reads are streamed
+/// from the underlying service as usual.
+///
+/// Requests with conditions, version, overrides or extra headers will be passed through.
+/// Results are not cached, the entry is removed once the request completes.
+///
+/// Waiters other than the one that drives the request will get an error with
+/// the same kind and status, while the original error is kept as source.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use opendal::layers::SingleFlightLayer;
+/// # use opendal::services;
+/// # use opendal::Operator;
+/// # use opendal::Result;
+///
+/// # fn main() -> Result<()> {
+/// let _ = Operator::new(services::Memory::default())?
+///     .layer(SingleFlightLayer::new())
+///     .finish();
+/// Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct SingleFlightLayer {
+    max_size: u64,
+}
+
+impl Default for SingleFlightLayer {
+    fn default() -> Self {
+        Self {
+            max_size: 4 * 1024 * 1024,
+        }
+    }
+}
+
+impl SingleFlightLayer {
+    /// Create a new `SingleFlightLayer`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the max size of reads that can be shared.
+    ///
+    /// Shared reads are buffered in memory, reads without a size or larger
+    /// than `max_size` will be passed through.
+    pub fn with_max_size(mut self, max_size: u64) -> Self {
+        self.max_size = max_size;
+        self
+    }
+}
+
+impl<A: Access> Layer<A> for SingleFlightLayer {
+    type LayeredAccess = SingleFlightAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccess {
+        SingleFlightAccessor {
+            inner: Arc::new(inner),
+            max_size: self.max_size,
+            reads: Flights::default(),
+            stats: Flights::default(),
+        }
+    }
+}
+
+type FlightFuture<T> = Shared<BoxedStaticFuture<std::result::Result<T, Arc<Error>>>>;
+
+/// The in-flight requests indexed by key.
+struct Flights<K, T: Clone> {
+    entries: Arc<Mutex<HashMap<K, FlightFuture<T>>>>,
+}
+
+impl<K, T: Clone> Default for Flights<K, T> {
+    fn default() -> Self {
+        Self {
+            entries: Arc::default(),
+        }
+    }
+}
+
+impl<K, T> Flights<K, T>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    T: Clone + Send + Sync + 'static,
+{
+    /// Join the in-flight request of key or start a new one with given future.
+    async fn run<F>(&self, key: K, f: impl FnOnce() -> F) -> Result<T>
+    where
+        F: MaybeSend + std::future::Future<Output = Result<T>> + 'static,
+    {
+        let fut = {
+            let mut entries = self.entries.lock().unwrap();
+            match entries.get(&key) {
+                Some(fut) => fut.clone(),
+                None => {
+                    let fut = f();
+                    let entries_ref = self.entries.clone();
+                    let flight_key = key.clone();
+                    let flight: BoxedStaticFuture<_> = Box::pin(async move {
+                        let res = fut.await.map_err(Arc::new);
+                        // Remove the entry once completed so that later requests
+                        // will not see a stale result.
+                        entries_ref.lock().unwrap().remove(&flight_key);
+                        res
+                    });
+                    let fut = flight.shared();
+                    entries.insert(key, fut.clone());
+                    fut
+                }
+            }
+        };
+
+        fut.await.map_err(|err| match Arc::try_unwrap(err) {
+            Ok(err) => err,
+            Err(err) => shared_error(&err),
+        })
+    }
+}
+
+/// Build an error for waiters since [`Error`] can't be cloned.
+fn shared_error(err: &Error) -> Error {
+    Error::new(err.kind(), "shared in-flight request failed")
+        .with_temporary(err.is_temporary())
+        .with_persistent(err.is_persistent())
+        .set_source(anyhow::anyhow!("{err}"))
+}
+
+pub struct SingleFlightAccessor<A: Access> {
+    inner: Arc<A>,
+    max_size: u64,
+    /// Keyed by path, offset and size.
+    reads: Flights<(String, u64, Option<u64>), Buffer>,
+    stats: Flights<String, RpStat>,
+}
+
+impl<A: Access> Debug for SingleFlightAccessor<A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SingleFlightAccessor")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Only bounded read without conditions, version, overrides and extra headers can be shared.
+fn is_shareable_read(args: &OpRead, max_size: u64) -> bool {
+    args.range().size().is_some_and(|size| size <= max_size)
+        && args.suffix_range().is_none()
+        && args.ranges().is_empty()
+        && !args.decompress()
+        && args.if_match().is_none()
+        && args.if_none_match().is_none()
+        && args.if_modified_since().is_none()
+        && args.if_unmodified_since().is_none()
+        && args.override_content_type().is_none()
+        && args.override_cache_control().is_none()
+        && args.override_content_disposition().is_none()
+        && args.version().is_none()
+        && args.headers().is_none()
+}
+
+/// Only stat without conditions, version, overrides, tags and extra headers can be shared.
+fn is_shareable_stat(args: &OpStat) -> bool {
+    args.if_match().is_none()
+        && args.if_none_match().is_none()
+        && args.if_modified_since().is_none()
+        && args.if_unmodified_since().is_none()
+        && args.override_content_type().is_none()
+        && args.override_cache_control().is_none()
+        && args.override_content_disposition().is_none()
+        && args.version().is_none()
+        && !args.tags()
+        && args.headers().is_none()
+}
+
+impl<A: Access> LayeredAccess for SingleFlightAccessor<A> {
+    type Inner = A;
+    type Reader = TwoWays<A::Reader, Buffer>;
+    type Writer = A::Writer;
+    type Lister = A::Lister;
+    type Deleter = A::Deleter;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        if !is_shareable_read(&args, self.max_size) {
+            let (rp, r) = self.inner.read(path, args).await?;
+            return Ok((rp, TwoWays::One(r)));
+        }
+
+        let range = args.range();
+        let key = (path.to_string(), range.offset(), range.size());
+        let buf = self
+            .reads
+            .run(key, || {
+                let inner = self.inner.clone();
+                let path = path.to_string();
+                async move {
+                    let (_, mut r) = inner.read(&path, args).await?;
+                    oio::Read::read_all(&mut r).await
+                }
+            })
+            .await?;
+        Ok((RpRead::new(), TwoWays::Two(buf)))
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.inner.write(path, args).await
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        if !is_shareable_stat(&args) {
+            return self.inner.stat(path, args).await;
+        }
+
+        self.stats
+            .run(path.to_string(), || {
+                let inner = self.inner.clone();
+                let path = path.to_string();
+                async move { inner.stat(&path, args).await }
+            })
+            .await
+    }
+
+    async fn delete(&self) -> Result<(RpDelete, Self::Deleter)> {
+        self.inner.delete().await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        self.inner.list(path, args).await
+    }
+}
+
+#[cfg(all(test, feature = "tests"))]
+mod tests {
+    use std::future::Future;
+    use std::pin::pin;
+    use std::time::Duration;
+
+    use futures::future::join_all;
+
+    use super::*;
+    use crate::raw::tests::MockAccess;
+    use crate::raw::tests::MockClock;
+
+    const CONTENT: &[u8] = b"Hello, World!";
+    const LATENCY: Duration = Duration::from_millis(10);
+
+    fn new_accessor(
+        layer: SingleFlightLayer,
+    ) -> (SingleFlightAccessor<MockAccess>, MockAccess, MockClock) {
+        // Requests must be pending to be shared.
+        let clock = MockClock::new();
+        let mock = MockAccess::new().with_latency(clock.clone(), LATENCY);
+        mock.insert("test", CONTENT);
+        (layer.layer(mock.clone()), mock, clock)
+    }
+
+    /// Start all requests in `fut` before the service responds.
+    async fn respond<F: Future>(clock: &MockClock, fut: F) -> F::Output {
+        let mut fut = pin!(fut);
+        assert!(futures::poll!(fut.as_mut()).is_pending());
+        clock.advance(LATENCY);
+        fut.await
+    }
+
+    async fn read(acc: &SingleFlightAccessor<MockAccess>, range: BytesRange) -> Result<Buffer> {
+        let (_, mut r) = Access::read(acc, "test", OpRead::new().with_range(range)).await?;
+        oio::Read::read_all(&mut r).await
+    }
+
+    #[tokio::test]
+    async fn test_single_flight() {
+        let (acc, mock, clock) = new_accessor(SingleFlightLayer::new());
+
+        let ranges = [0..5u64, 0..5, 0..5, 7..12];
+        let bs = respond(
+            &clock,
+            join_all(ranges.iter().map(|r| read(&acc, r.clone().into()))),
+        )
+        .await;
+        // Reads with different ranges must not be shared.
+        assert_eq!(mock.calls(Operation::Read, "test"), 2);
+        for (r, bs) in ranges.iter().zip(bs) {
+            assert_eq!(
+                bs.unwrap().to_vec(),
+                &CONTENT[r.start as usize..r.end as usize]
+            );
+        }
+
+        let metas = respond(
+            &clock,
+            join_all((0..4).map(|_| Access::stat(&acc, "test", OpStat::new()))),
+        )
+        .await;
+        assert_eq!(mock.calls(Operation::Stat, "test"), 1);
+        for meta in metas {
+            assert_eq!(
+                meta.unwrap().into_metadata().content_length(),
+                CONTENT.len() as u64
+            );
+        }
+
+        // Entries are removed once the request completes.
+        respond(&clock, Access::stat(&acc, "test", OpStat::new()))
+            .await
+            .unwrap();
+        assert_eq!(mock.calls(Operation::Stat, "test"), 2);
+    }
+
+    #[tokio::test]
+    async fn test_single_flight_bounded_reads_only() {
+        let (acc, mock, clock) = new_accessor(SingleFlightLayer::new().with_max_size(5));
+        let reads = || mock.calls(Operation::Read, "test");
+
+        // Unbounded reads are not shared.
+        let bs = respond(
+            &clock,
+            join_all((0..3).map(|_| read(&acc, BytesRange::default()))),
+        )
+        .await;
+        assert_eq!(reads(), 3);
+        for bs in bs {
+            assert_eq!(bs.unwrap().to_vec(), CONTENT);
+        }
+
+        // Reads larger than max_size are not shared.
+        let bs = respond(&clock, join_all((0..3).map(|_| read(&acc, (0..6).into())))).await;
+        assert_eq!(reads(), 6);
+        for bs in bs {
+            assert_eq!(bs.unwrap().to_vec(), &CONTENT[..6]);
+        }
+
+        // Reads within max_size are shared.
+        let bs = respond(&clock, join_all((0..3).map(|_| read(&acc, (0..5).into())))).await;
+        assert_eq!(reads(), 7);
+        for bs in bs {
+            assert_eq!(bs.unwrap().to_vec(), &CONTENT[..5]);
+        }
+    }
+}
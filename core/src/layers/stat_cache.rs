@@ -0,0 +1,456 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use lru::LruCache;
+
+use crate::raw::*;
+use crate::*;
+
+/// Add stat cache for the underlying services.
+///
+/// # Notes
+///
+/// `StatCacheLayer` memoizes the metadata returned by `stat` for the given ttl,
+/// and evicts the least recently used entries once the capacity is reached.
+///
+/// - `stat` with conditions, version, tags or extra headers will not be cached.
+/// - `write`, `delete`, `copy`, `rename` and `create_dir` through the same
+///   operator will invalidate the cached entries of affected paths.
+/// - Mutations made by other clients will not be visible until entries expire.
+/// - The cache can be shared by multiple services, entries are keyed by the
+///   scheme, name and root of the service along with the path.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::time::Duration;
+///
+/// # use opendal::layers::StatCacheLayer;
+/// # use opendal::services;
+/// # use opendal::Operator;
+/// # use opendal::Result;
+///
+/// # fn main() -> Result<()> {
+/// let _ = Operator::new(services::Memory::default())?
+///     .layer(StatCacheLayer::new(Duration::from_secs(60), 1024))
+///     .finish();
+/// Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct StatCacheLayer {
+    cache: Arc<StatCache>,
+}
+
+impl StatCacheLayer {
+    /// Create a new `StatCacheLayer` with given ttl and capacity.
+    ///
+    /// The capacity will be set to 1 if 0 is given.
+    pub fn new(ttl: Duration, capacity: usize) -> Self {
+        let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN);
+        Self {
+            cache: Arc::new(StatCache {
+                ttl,
+                clock: Arc::new(SystemClock),
+                entries: Mutex::new(LruCache::new(capacity)),
+            }),
+        }
+    }
+
+    /// Set the clock used to check the ttl of cached entries.
+    ///
+    /// It's useful to verify the expiry in tests, see [`Clock`] for more details.
+    /// The cache will be reset, so it should be set before the layer is used.
+    pub fn with_clock(self, clock: impl Clock) -> Self {
+        let capacity = self.cache.entries.lock().unwrap().cap();
+        Self {
+            cache: Arc::new(StatCache {
+                ttl: self.cache.ttl,
+                clock: Arc::new(clock),
+                entries: Mutex::new(LruCache::new(capacity)),
+            }),
+        }
+    }
+
+    /// Clear all cached entries.
+    pub fn clear(&self) {
+        self.cache.entries.lock().unwrap().clear();
+    }
+}
+
+impl<A: Access> Layer<A> for StatCacheLayer {
+    type LayeredAccess = StatCacheAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccess {
+        let info = inner.info();
+        let prefix = format!("{}://{}{}", info.scheme(), info.name(), info.root());
+        StatCacheAccessor {
+            inner,
+            cache: self.cache.clone(),
+            prefix: prefix.into(),
+        }
+    }
+}
+
+struct StatCache {
+    ttl: Duration,
+    clock: Arc<dyn Clock>,
+    /// The cached metadata and the instant it was fetched, keyed by the
+    /// service prefix and path.
+    entries: Mutex<LruCache<String, (Instant, Metadata)>>,
+}
+
+impl Debug for StatCache {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StatCache")
+            .field("ttl", &self.ttl)
+            .finish_non_exhaustive()
+    }
+}
+
+impl StatCache {
+    fn get(&self, path: &str) -> Option<Metadata> {
+        let mut entries = self.entries.lock().unwrap();
+        let (fetched_at, meta) = entries.get(path)?;
+        if self.clock.now().saturating_duration_since(*fetched_at) < self.ttl {
+            return Some(meta.clone());
+        }
+
+        entries.pop(path);
+        None
+    }
+
+    fn insert(&self, path: &str, meta: Metadata) {
+        self.entries
+            .lock()
+            .unwrap()
+            .put(path.to_string(), (self.clock.now(), meta));
+    }
+
+    /// Invalidate the cached entry of path, all entries under it will also be
+    /// invalidated if path is a dir.
+    fn invalidate(&self, path: &str) {
+        let mut entries = self.entries.lock().unwrap();
+        if !path.ends_with('/') {
+            entries.pop(path);
+            return;
+        }
+
+        let keys: Vec<String> = entries
+            .iter()
+            .filter(|(k, _)| k.starts_with(path))
+            .map(|(k, _)| k.clone())
+            .collect();
+        for k in keys {
+            entries.pop(&k);
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct StatCacheAccessor<A: Access> {
+    inner: A,
+    cache: Arc<StatCache>,
+    /// The prefix of cache keys to distinguish services sharing the cache.
+    prefix: Arc<str>,
+}
+
+impl<A: Access> StatCacheAccessor<A> {
+    fn key(&self, path: &str) -> String {
+        format!("{}{path}", self.prefix)
+    }
+}
+
+/// Only stat without conditions, version, tags and extra headers can be cached.
+fn is_cacheable(args: &OpStat) -> bool {
+    args.if_match().is_none()
+        && args.if_none_match().is_none()
+        && args.if_modified_since().is_none()
+        && args.if_unmodified_since().is_none()
+        && args.override_content_type().is_none()
+        && args.override_cache_control().is_none()
+        && args.override_content_disposition().is_none()
+        && args.version().is_none()
+        && !args.tags()
+        && args.headers().is_none()
+}
+
+impl<A: Access> LayeredAccess for StatCacheAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type Writer = StatCacheWrapper<A::Writer>;
+    type Lister = A::Lister;
+    type Deleter = StatCacheWrapper<A::Deleter>;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        let res = self.inner.create_dir(path, args).await;
+        self.cache.invalidate(&self.key(path));
+        res
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.inner.read(path, args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let (rp, w) = self.inner.write(path, args).await?;
+        self.cache.invalidate(&self.key(path));
+        Ok((
+            rp,
+            StatCacheWrapper::new(w, self.cache.clone(), self.prefix.clone(), Some(path)),
+        ))
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let res = self.inner.copy(from, to, args).await;
+        self.cache.invalidate(&self.key(to));
+        res
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let res = self.inner.rename(from, to, args).await;
+        self.cache.invalidate(&self.key(from));
+        self.cache.invalidate(&self.key(to));
+        res
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        if !is_cacheable(&args) {
+            return self.inner.stat(path, args).await;
+        }
+
+        let key = self.key(path);
+        if let Some(meta) = self.cache.get(&key) {
+            return Ok(RpStat::new(meta));
+        }
+
+        let rp = self.inner.stat(path, args).await?;
+        self.cache.insert(&key, rp.clone().into_metadata());
+        Ok(rp)
+    }
+
+    async fn delete(&self) -> Result<(RpDelete, Self::Deleter)> {
+        let (rp, d) = self.inner.delete().await?;
+        Ok((
+            rp,
+            StatCacheWrapper::new(d, self.cache.clone(), self.prefix.clone(), None),
+        ))
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        self.inner.list(path, args).await
+    }
+
+    async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        self.inner.presign(path, args).await
+    }
+}
+
+pub struct StatCacheWrapper<R> {
+    inner: R,
+    cache: Arc<StatCache>,
+    prefix: Arc<str>,
+    /// The cache keys that should be invalidated once the operation is done.
+    paths: Vec<String>,
+}
+
+impl<R> StatCacheWrapper<R> {
+    fn new(inner: R, cache: Arc<StatCache>, prefix: Arc<str>, path: Option<&str>) -> Self {
+        let paths = path.map(|p| format!("{prefix}{p}")).into_iter().collect();
+        Self {
+            inner,
+            cache,
+            prefix,
+            paths,
+        }
+    }
+
+    fn invalidate(&self) {
+        for path in &self.paths {
+            self.cache.invalidate(path);
+        }
+    }
+}
+
+impl<R: oio::Write> oio::Write for StatCacheWrapper<R> {
+    async fn write(&mut self, bs: Buffer) -> Result<()> {
+        self.inner.write(bs).await
+    }
+
+    async fn flush(&mut self) -> Result<()> {
+        self.inner.flush().await
+    }
+
+    async fn close(&mut self) -> Result<Metadata> {
+        let res = self.inner.close().await;
+        self.invalidate();
+        res
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        let res = self.inner.abort().await;
+        self.invalidate();
+        res
+    }
+}
+
+impl<R: oio::Delete> oio::Delete for StatCacheWrapper<R> {
+    fn delete(&mut self, path: &str, args: OpDelete) -> Result<()> {
+        self.inner.delete(path, args)?;
+        let key = format!("{}{path}", self.prefix);
+        self.cache.invalidate(&key);
+        self.paths.push(key);
+        Ok(())
+    }
+
+    async fn flush(&mut self) -> Result<usize> {
+        let res = self.inner.flush().await;
+        // Entries could be stat again before flushed.
+        self.invalidate();
+        if res.is_ok() {
+            self.paths.clear();
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services;
+
+    #[tokio::test]
+    async fn test_stat_cache() -> Result<()> {
+        let inner = Operator::new(services::Memory::default())?.finish();
+        let layer = StatCacheLayer::new(Duration::from_secs(60), 16);
+        let op = Operator::from_inner(inner.clone().into_inner()).layer(layer.clone());
+
+        op.write("test", "Hello").await?;
+        assert_eq!(op.stat("test").await?.content_length(), 5);
+
+        // Writes bypass the cache will not be visible.
+        inner.write("test", "Hello, World!").await?;
+        assert_eq!(op.stat("test").await?.content_length(), 5);
+
+        layer.clear();
+        assert_eq!(op.stat("test").await?.content_length(), 13);
+
+        // Writes through the same operator should invalidate the cache.
+        op.write("test", "Hi").await?;
+        assert_eq!(op.stat("test").await?.content_length(), 2);
+
+        op.delete("test").await?;
+        let err = op.stat("test").await.expect_err("stat must fail");
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "tests")]
+    fn new_mock_operator() -> (Operator, crate::raw::tests::MockAccess) {
+        let mock = crate::raw::tests::MockAccess::new().with_capability(|cap| Capability {
+            stat_with_tags: true,
+            stat_with_headers: true,
+            ..cap
+        });
+        mock.insert("test", "Hello");
+        let op = OperatorBuilder::new(mock.clone())
+            .finish()
+            .layer(StatCacheLayer::new(Duration::from_secs(60), 16));
+        (op, mock)
+    }
+
+    #[cfg(feature = "tests")]
+    #[tokio::test]
+    async fn test_stat_cache_skip_tags() -> Result<()> {
+        let (op, mock) = new_mock_operator();
+
+        op.stat("test").await?;
+        op.stat_with("test").tags(true).await?;
+        assert_eq!(mock.calls(Operation::Stat, "test"), 2);
+        op.stat("test").await?;
+        assert_eq!(mock.calls(Operation::Stat, "test"), 2);
+        Ok(())
+    }
+
+    #[cfg(feature = "tests")]
+    #[tokio::test]
+    async fn test_stat_cache_skip_headers() -> Result<()> {
+        let (op, mock) = new_mock_operator();
+
+        let mut headers = http::HeaderMap::new();
+        headers.insert("x-request-payer", "requester".parse().unwrap());
+        op.stat("test").await?;
+        op.stat_with("test").headers(headers).await?;
+        assert_eq!(mock.calls(Operation::Stat, "test"), 2);
+        op.stat("test").await?;
+        assert_eq!(mock.calls(Operation::Stat, "test"), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_stat_cache_shared_by_services() -> Result<()> {
+        let layer = StatCacheLayer::new(Duration::from_secs(60), 16);
+        let a = Operator::new(services::Memory::default().root("/a"))?
+            .finish()
+            .layer(layer.clone());
+        let b = Operator::new(services::Memory::default().root("/b"))?
+            .finish()
+            .layer(layer.clone());
+
+        a.write("test", "Hello").await?;
+        b.write("test", "Hello, World!").await?;
+        assert_eq!(a.stat("test").await?.content_length(), 5);
+        assert_eq!(b.stat("test").await?.content_length(), 13);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "tests")]
+    #[tokio::test]
+    async fn test_stat_cache_ttl() -> Result<()> {
+        use crate::raw::tests::MockClock;
+
+        let clock = MockClock::new();
+        let inner = Operator::new(services::Memory::default())?.finish();
+        let op = Operator::from_inner(inner.clone().into_inner())
+            .layer(StatCacheLayer::new(Duration::from_secs(60), 16).with_clock(clock.clone()));
+
+        op.write("test", "Hello").await?;
+        assert_eq!(op.stat("test").await?.content_length(), 5);
+
+        inner.write("test", "Hello, World!").await?;
+        clock.advance(Duration::from_secs(59));
+        assert_eq!(op.stat("test").await?.content_length(), 5);
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(op.stat("test").await?.content_length(), 13);
+
+        Ok(())
+    }
+}
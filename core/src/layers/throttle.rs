@@ -15,10 +15,12 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
 use std::num::NonZeroU32;
 use std::sync::Arc;
+use std::time::Instant;
 
-use governor::clock::Clock;
+use governor::clock::Clock as _;
 use governor::clock::DefaultClock;
 use governor::middleware::NoOpMiddleware;
 use governor::state::InMemoryState;
@@ -29,7 +31,7 @@ use governor::RateLimiter;
 use crate::raw::*;
 use crate::*;
 
-/// Add a bandwidth rate limiter to the underlying services.
+/// Add a bandwidth and request rate limiter to the underlying services.
 ///
 /// # Throttle
 ///
@@ -38,6 +40,17 @@ use crate::*;
 /// [Governor](https://docs.rs/governor/latest/governor/index.html).
 /// By setting the `bandwidth` and `burst`, we can control the byte flow rate of underlying services.
 ///
+/// # Request Rate
+///
+/// Unlike [`ConcurrentLimitLayer`](crate::layers::ConcurrentLimitLayer) which caps parallelism,
+/// [`ThrottleLayer::with_operation_rate`] caps the number of requests per second for each
+/// operation independently. Operations will wait until a token is available before being
+/// dispatched to the underlying services. Dropping the waiting future will not consume any token.
+///
+/// - `read`, `write`, `stat`, `list`, `create_dir`, `copy`, `rename` and `presign` take one
+///   token per call.
+/// - `delete` takes one token every time the deleter flushes.
+///
 /// # Note
 ///
 /// When setting the ThrottleLayer, always consider the largest possible operation size as the burst size,
@@ -64,10 +77,42 @@ use crate::*;
 /// Ok(())
 /// # }
 /// ```
+///
+/// This example limits stat to 100 requests/s and write to 20 requests/s without limiting bandwidth.
+///
+/// ```no_run
+/// # use opendal::layers::ThrottleLayer;
+/// # use opendal::raw::Operation;
+/// # use opendal::services;
+/// # use opendal::Operator;
+/// # use opendal::Result;
+///
+/// # fn main() -> Result<()> {
+/// let _ = Operator::new(services::Memory::default())?
+///     .layer(
+///         ThrottleLayer::default()
+///             .with_operation_rate(Operation::Stat, 100, 100)
+///             .with_operation_rate(Operation::Write, 20, 20),
+///     )
+///     .finish();
+/// Ok(())
+/// # }
+/// ```
 #[derive(Clone)]
 pub struct ThrottleLayer {
-    bandwidth: NonZeroU32,
-    burst: NonZeroU32,
+    bandwidth: Option<Quota>,
+    operations: HashMap<Operation, Quota>,
+    clock: Arc<dyn Clock>,
+}
+
+impl Default for ThrottleLayer {
+    fn default() -> Self {
+        Self {
+            bandwidth: None,
+            operations: HashMap::new(),
+            clock: Arc::new(SystemClock),
+        }
+    }
 }
 
 impl ThrottleLayer {
@@ -79,22 +124,58 @@ impl ThrottleLayer {
         assert!(bandwidth > 0);
         assert!(burst > 0);
         Self {
-            bandwidth: NonZeroU32::new(bandwidth).unwrap(),
-            burst: NonZeroU32::new(burst).unwrap(),
+            bandwidth: Some(
+                Quota::per_second(NonZeroU32::new(bandwidth).unwrap())
+                    .allow_burst(NonZeroU32::new(burst).unwrap()),
+            ),
+            ..Default::default()
         }
     }
+
+    /// Limit the request rate of given operation.
+    ///
+    /// - rate: the maximum number of requests allowed per second.
+    /// - burst: the maximum number of requests allowed to pass through at once.
+    pub fn with_operation_rate(mut self, op: Operation, rate: u32, burst: u32) -> Self {
+        assert!(rate > 0);
+        assert!(burst > 0);
+        self.operations.insert(
+            op,
+            Quota::per_second(NonZeroU32::new(rate).unwrap())
+                .allow_burst(NonZeroU32::new(burst).unwrap()),
+        );
+        self
+    }
+
+    /// Set the clock used to limit the request rate of operations.
+    ///
+    /// It's useful to verify the request rate in tests without real sleeps, see
+    /// [`Clock`] for more details.
+    pub fn with_clock(mut self, clock: impl Clock) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
 }
 
 impl<A: Access> Layer<A> for ThrottleLayer {
     type LayeredAccess = ThrottleAccessor<A>;
 
     fn layer(&self, accessor: A) -> Self::LayeredAccess {
-        let rate_limiter = Arc::new(RateLimiter::direct(
-            Quota::per_second(self.bandwidth).allow_burst(self.burst),
-        ));
+        let rate_limiter = self
+            .bandwidth
+            .map(|quota| Arc::new(RateLimiter::direct(quota)));
+        let operation_limiters = self
+            .operations
+            .iter()
+            .map(|(op, quota)| {
+                let limiter = OperationLimiter::new(*quota, self.clock.clone());
+                (*op, Arc::new(limiter))
+            })
+            .collect();
         ThrottleAccessor {
             inner: accessor,
             rate_limiter,
+            operation_limiters: Arc::new(operation_limiters),
         }
     }
 }
@@ -104,10 +185,56 @@ impl<A: Access> Layer<A> for ThrottleLayer {
 /// Read more about [Middleware](https://docs.rs/governor/latest/governor/middleware/index.html)
 type SharedRateLimiter = Arc<RateLimiter<NotKeyed, InMemoryState, DefaultClock, NoOpMiddleware>>;
 
+/// LimiterClock drives the rate limiters of operations with [`Clock`].
+#[derive(Debug, Clone)]
+struct LimiterClock(Arc<dyn Clock>);
+
+impl governor::clock::Clock for LimiterClock {
+    type Instant = Instant;
+
+    fn now(&self) -> Instant {
+        self.0.now()
+    }
+}
+
+/// OperationLimiter limits the request rate of an operation.
+#[derive(Debug)]
+struct OperationLimiter {
+    limiter: RateLimiter<NotKeyed, InMemoryState, LimiterClock, NoOpMiddleware<Instant>>,
+    clock: Arc<dyn Clock>,
+}
+
+impl OperationLimiter {
+    fn new(quota: Quota, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            limiter: RateLimiter::direct_with_clock(quota, LimiterClock(clock.clone())),
+            clock,
+        }
+    }
+
+    /// Wait until the next request is allowed.
+    async fn until_ready(&self) {
+        while let Err(not_until) = self.limiter.check() {
+            let wait = not_until.wait_time_from(self.clock.now());
+            self.clock.sleep(wait).await;
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ThrottleAccessor<A: Access> {
     inner: A,
-    rate_limiter: SharedRateLimiter,
+    rate_limiter: Option<SharedRateLimiter>,
+    operation_limiters: Arc<HashMap<Operation, Arc<OperationLimiter>>>,
+}
+
+impl<A: Access> ThrottleAccessor<A> {
+    /// Wait until the request of given operation is allowed.
+    async fn acquire(&self, op: Operation) {
+        if let Some(limiter) = self.operation_limiters.get(&op) {
+            limiter.until_ready().await;
+        }
+    }
 }
 
 impl<A: Access> LayeredAccess for ThrottleAccessor<A> {
@@ -115,13 +242,19 @@ impl<A: Access> LayeredAccess for ThrottleAccessor<A> {
     type Reader = ThrottleWrapper<A::Reader>;
     type Writer = ThrottleWrapper<A::Writer>;
     type Lister = A::Lister;
-    type Deleter = A::Deleter;
+    type Deleter = ThrottleDeleter<A::Deleter>;
 
     fn inner(&self) -> &Self::Inner {
         &self.inner
     }
 
+    async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        self.acquire(Operation::CreateDir).await;
+        self.inner.create_dir(path, args).await
+    }
+
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.acquire(Operation::Read).await;
         let limiter = self.rate_limiter.clone();
 
         self.inner
@@ -131,6 +264,7 @@ impl<A: Access> LayeredAccess for ThrottleAccessor<A> {
     }
 
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.acquire(Operation::Write).await;
         let limiter = self.rate_limiter.clone();
 
         self.inner
@@ -139,22 +273,48 @@ impl<A: Access> LayeredAccess for ThrottleAccessor<A> {
             .map(|(rp, w)| (rp, ThrottleWrapper::new(w, limiter)))
     }
 
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.acquire(Operation::Copy).await;
+        self.inner.copy(from, to, args).await
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.acquire(Operation::Rename).await;
+        self.inner.rename(from, to, args).await
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.acquire(Operation::Stat).await;
+        self.inner.stat(path, args).await
+    }
+
     async fn delete(&self) -> Result<(RpDelete, Self::Deleter)> {
-        self.inner.delete().await
+        let limiter = self.operation_limiters.get(&Operation::Delete).cloned();
+
+        self.inner
+            .delete()
+            .await
+            .map(|(rp, d)| (rp, ThrottleDeleter { inner: d, limiter }))
     }
 
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        self.acquire(Operation::List).await;
         self.inner.list(path, args).await
     }
+
+    async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        self.acquire(Operation::Presign).await;
+        self.inner.presign(path, args).await
+    }
 }
 
 pub struct ThrottleWrapper<R> {
     inner: R,
-    limiter: SharedRateLimiter,
+    limiter: Option<SharedRateLimiter>,
 }
 
 impl<R> ThrottleWrapper<R> {
-    pub fn new(inner: R, rate_limiter: SharedRateLimiter) -> Self {
+    pub fn new(inner: R, rate_limiter: Option<SharedRateLimiter>) -> Self {
         Self {
             inner,
             limiter: rate_limiter,
@@ -166,14 +326,21 @@ impl<R: oio::Read> oio::Read for ThrottleWrapper<R> {
     async fn read(&mut self) -> Result<Buffer> {
         self.inner.read().await
     }
+
+    async fn read_into(&mut self, dst: &mut [u8]) -> Result<usize> {
+        self.inner.read_into(dst).await
+    }
 }
 
 impl<R: oio::Write> oio::Write for ThrottleWrapper<R> {
     async fn write(&mut self, bs: Buffer) -> Result<()> {
+        let Some(limiter) = self.limiter.as_ref() else {
+            return self.inner.write(bs).await;
+        };
         let buf_length = NonZeroU32::new(bs.len() as u32).unwrap();
 
         loop {
-            match self.limiter.check_n(buf_length) {
+            match limiter.check_n(buf_length) {
                 Ok(res) => match res {
                     Ok(_) => return self.inner.write(bs).await,
                     // the query is valid but the Decider can not accommodate them.
@@ -194,6 +361,10 @@ impl<R: oio::Write> oio::Write for ThrottleWrapper<R> {
         }
     }
 
+    async fn flush(&mut self) -> Result<()> {
+        self.inner.flush().await
+    }
+
     async fn abort(&mut self) -> Result<()> {
         self.inner.abort().await
     }
@@ -202,3 +373,60 @@ impl<R: oio::Write> oio::Write for ThrottleWrapper<R> {
         self.inner.close().await
     }
 }
+
+/// ThrottleDeleter limits the request rate of flush.
+pub struct ThrottleDeleter<D> {
+    inner: D,
+    limiter: Option<Arc<OperationLimiter>>,
+}
+
+impl<D: oio::Delete> oio::Delete for ThrottleDeleter<D> {
+    fn delete(&mut self, path: &str, args: OpDelete) -> Result<()> {
+        self.inner.delete(path, args)
+    }
+
+    async fn flush(&mut self) -> Result<usize> {
+        if let Some(limiter) = self.limiter.as_ref() {
+            limiter.until_ready().await;
+        }
+        self.inner.flush().await
+    }
+}
+
+#[cfg(all(test, feature = "tests"))]
+mod tests {
+    use super::*;
+    use crate::services;
+
+    #[tokio::test]
+    async fn test_throttle_operation_rate() -> Result<()> {
+        use std::pin::pin;
+        use std::time::Duration;
+
+        use crate::raw::tests::MockClock;
+
+        let clock = MockClock::new();
+        let op = Operator::new(services::Memory::default())?
+            .layer(
+                ThrottleLayer::default()
+                    .with_operation_rate(Operation::Stat, 10, 1)
+                    .with_clock(clock.clone()),
+            )
+            .finish();
+
+        // Operations without rate limit should not be throttled.
+        for _ in 0..5 {
+            op.write("test", "Hello").await?;
+        }
+
+        op.stat("test").await?;
+        let mut fut = pin!(op.stat("test"));
+        assert!(futures::poll!(fut.as_mut()).is_pending());
+        clock.advance(Duration::from_millis(99));
+        assert!(futures::poll!(fut.as_mut()).is_pending());
+        clock.advance(Duration::from_millis(1));
+        fut.await?;
+
+        Ok(())
+    }
+}
@@ -16,8 +16,13 @@
 // under the License.
 
 use std::future::Future;
+use std::pin::pin;
 use std::sync::Arc;
 use std::time::Duration;
+use std::time::Instant;
+
+use futures::future;
+use futures::future::Either;
 
 use crate::raw::*;
 use crate::*;
@@ -37,10 +42,14 @@ use crate::*;
 /// - IO Operation like `read`, `Reader::read` and `Writer::write`, they operate on data directly, we
 ///   control them by setting `io_timeout`.
 ///
+/// Besides, reads can be controlled by `idle_timeout` which fails the read if no bytes arrive
+/// for the given duration. It's independent of `io_timeout` and the read specific timeout.
+///
 /// # Default
 ///
 /// - timeout: 60 seconds
 /// - io_timeout: 10 seconds
+/// - idle_timeout: disabled
 ///
 /// # Panics
 ///
@@ -100,9 +109,9 @@ use crate::*;
 ///
 /// # Implementation Notes
 ///
-/// TimeoutLayer is using [`tokio::time::timeout`] to implement timeout for operations. And IO
-/// Operations insides `reader`, `writer` will use `Pin<Box<tokio::time::Sleep>>` to track the
-/// timeout.
+/// TimeoutLayer races operations against the sleep of its [`Clock`], which is
+/// [`SystemClock`] by default. And IO Operations insides `reader`, `writer` will use a
+/// boxed sleep future to track the timeout.
 ///
 /// This might introduce a bit overhead for IO operations, but it's the only way to implement
 /// timeout correctly. We used to implement timeout layer in zero cost way that only stores
@@ -116,6 +125,8 @@ use crate::*;
 pub struct TimeoutLayer {
     timeout: Duration,
     io_timeout: Duration,
+    idle_timeout: Option<Duration>,
+    clock: Arc<dyn Clock>,
 }
 
 impl Default for TimeoutLayer {
@@ -123,6 +134,8 @@ impl Default for TimeoutLayer {
         Self {
             timeout: Duration::from_secs(60),
             io_timeout: Duration::from_secs(10),
+            idle_timeout: None,
+            clock: Arc::new(SystemClock),
         }
     }
 }
@@ -149,6 +162,27 @@ impl TimeoutLayer {
         self
     }
 
+    /// Set idle timeout for reads with given value.
+    ///
+    /// Reads will fail if no bytes arrive for the given duration. The timer is
+    /// reset on every non-empty buffer, so half-dead connections trickling data
+    /// slowly can be detected even if every single read call is fast enough.
+    ///
+    /// Time spent outside of read calls will not be counted.
+    pub fn with_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the clock used to track timeouts.
+    ///
+    /// It's useful to verify timeouts in tests without real sleeps, see [`Clock`]
+    /// for more details.
+    pub fn with_clock(mut self, clock: impl Clock) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
     /// Set speed for TimeoutLayer with given value.
     ///
     /// # Notes
@@ -171,7 +205,11 @@ impl<A: Access> Layer<A> for TimeoutLayer {
     fn layer(&self, inner: A) -> Self::LayeredAccess {
         let info = inner.info();
         info.update_executor(|exec| {
-            Executor::with(TimeoutExecutor::new(exec.into_inner(), self.io_timeout))
+            Executor::with(TimeoutExecutor::new(
+                exec.into_inner(),
+                self.io_timeout,
+                self.clock.clone(),
+            ))
         });
 
         TimeoutAccessor {
@@ -179,6 +217,8 @@ impl<A: Access> Layer<A> for TimeoutLayer {
 
             timeout: self.timeout,
             io_timeout: self.io_timeout,
+            idle_timeout: self.idle_timeout,
+            clock: self.clock.clone(),
         }
     }
 }
@@ -189,16 +229,20 @@ pub struct TimeoutAccessor<A: Access> {
 
     timeout: Duration,
     io_timeout: Duration,
+    idle_timeout: Option<Duration>,
+    clock: Arc<dyn Clock>,
 }
 
 impl<A: Access> TimeoutAccessor<A> {
     async fn timeout<F: Future<Output = Result<T>>, T>(&self, op: Operation, fut: F) -> Result<T> {
-        tokio::time::timeout(self.timeout, fut).await.map_err(|_| {
-            Error::new(ErrorKind::Unexpected, "operation timeout reached")
-                .with_operation(op)
-                .with_context("timeout", self.timeout.as_secs_f64().to_string())
-                .set_temporary()
-        })?
+        self::timeout(&*self.clock, self.timeout, fut)
+            .await
+            .ok_or_else(|| {
+                Error::new(ErrorKind::Unexpected, "operation timeout reached")
+                    .with_operation(op)
+                    .with_context("timeout", self.timeout.as_secs_f64().to_string())
+                    .set_temporary()
+            })?
     }
 
     async fn io_timeout<F: Future<Output = Result<T>>, T>(
@@ -206,12 +250,21 @@ impl<A: Access> TimeoutAccessor<A> {
         op: Operation,
         fut: F,
     ) -> Result<T> {
-        tokio::time::timeout(self.io_timeout, fut)
+        self.io_timeout_with(self.io_timeout, op, fut).await
+    }
+
+    async fn io_timeout_with<F: Future<Output = Result<T>>, T>(
+        &self,
+        timeout: Duration,
+        op: Operation,
+        fut: F,
+    ) -> Result<T> {
+        self::timeout(&*self.clock, timeout, fut)
             .await
-            .map_err(|_| {
+            .ok_or_else(|| {
                 Error::new(ErrorKind::Unexpected, "io timeout reached")
                     .with_operation(op)
-                    .with_context("timeout", self.io_timeout.as_secs_f64().to_string())
+                    .with_context("timeout", timeout.as_secs_f64().to_string())
                     .set_temporary()
             })?
     }
@@ -234,15 +287,21 @@ impl<A: Access> LayeredAccess for TimeoutAccessor<A> {
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
-        self.io_timeout(Operation::Read, self.inner.read(path, args))
+        let timeout = args.timeout().unwrap_or(self.io_timeout);
+        self.io_timeout_with(timeout, Operation::Read, self.inner.read(path, args))
             .await
-            .map(|(rp, r)| (rp, TimeoutWrapper::new(r, self.io_timeout)))
+            .map(|(rp, r)| {
+                let r = TimeoutWrapper::new(r, timeout, self.clock.clone())
+                    .with_idle_timeout(self.idle_timeout);
+                (rp, r)
+            })
     }
 
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
-        self.io_timeout(Operation::Write, self.inner.write(path, args))
+        let timeout = args.timeout().unwrap_or(self.io_timeout);
+        self.io_timeout_with(timeout, Operation::Write, self.inner.write(path, args))
             .await
-            .map(|(rp, r)| (rp, TimeoutWrapper::new(r, self.io_timeout)))
+            .map(|(rp, r)| (rp, TimeoutWrapper::new(r, timeout, self.clock.clone())))
     }
 
     async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
@@ -260,16 +319,55 @@ impl<A: Access> LayeredAccess for TimeoutAccessor<A> {
             .await
     }
 
+    async fn set_tags(&self, path: &str, args: OpSetTags) -> Result<RpSetTags> {
+        self.timeout(Operation::SetTags, self.inner.set_tags(path, args))
+            .await
+    }
+
+    async fn abort_multipart(
+        &self,
+        path: &str,
+        args: OpAbortMultipart,
+    ) -> Result<RpAbortMultipart> {
+        self.timeout(
+            Operation::AbortMultipart,
+            self.inner.abort_multipart(path, args),
+        )
+        .await
+    }
+
+    async fn list_multipart_uploads(
+        &self,
+        path: &str,
+        args: OpListMultipartUploads,
+    ) -> Result<RpListMultipartUploads> {
+        self.timeout(
+            Operation::ListMultipartUploads,
+            self.inner.list_multipart_uploads(path, args),
+        )
+        .await
+    }
+
     async fn delete(&self) -> Result<(RpDelete, Self::Deleter)> {
         self.timeout(Operation::Delete, self.inner.delete())
             .await
-            .map(|(rp, r)| (rp, TimeoutWrapper::new(r, self.io_timeout)))
+            .map(|(rp, r)| {
+                (
+                    rp,
+                    TimeoutWrapper::new(r, self.io_timeout, self.clock.clone()),
+                )
+            })
     }
 
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
         self.io_timeout(Operation::List, self.inner.list(path, args))
             .await
-            .map(|(rp, r)| (rp, TimeoutWrapper::new(r, self.io_timeout)))
+            .map(|(rp, r)| {
+                (
+                    rp,
+                    TimeoutWrapper::new(r, self.io_timeout, self.clock.clone()),
+                )
+            })
     }
 
     async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
@@ -278,14 +376,30 @@ impl<A: Access> LayeredAccess for TimeoutAccessor<A> {
     }
 }
 
+/// Run the future until it's ready or the duration elapsed on the clock.
+///
+/// Returns `None` if the duration elapsed first.
+async fn timeout<F: Future>(clock: &dyn Clock, dur: Duration, fut: F) -> Option<F::Output> {
+    let sleep = clock.sleep(dur);
+    match future::select(pin!(fut), sleep).await {
+        Either::Left((v, _)) => Some(v),
+        Either::Right(_) => None,
+    }
+}
+
 pub struct TimeoutExecutor {
     exec: Arc<dyn Execute>,
     timeout: Duration,
+    clock: Arc<dyn Clock>,
 }
 
 impl TimeoutExecutor {
-    pub fn new(exec: Arc<dyn Execute>, timeout: Duration) -> Self {
-        Self { exec, timeout }
+    pub fn new(exec: Arc<dyn Execute>, timeout: Duration, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            exec,
+            timeout,
+            clock,
+        }
     }
 }
 
@@ -295,28 +409,78 @@ impl Execute for TimeoutExecutor {
     }
 
     fn timeout(&self) -> Option<BoxedStaticFuture<()>> {
-        Some(Box::pin(tokio::time::sleep(self.timeout)))
+        Some(self.clock.sleep(self.timeout))
     }
 }
 
 pub struct TimeoutWrapper<R> {
     inner: R,
+    clock: Arc<dyn Clock>,
 
     timeout: Duration,
+    idle_timeout: Option<Duration>,
+    /// The instant since when no bytes have been returned, `None` if the
+    /// last read call made progress.
+    idle_since: Option<Instant>,
 }
 
 impl<R> TimeoutWrapper<R> {
-    fn new(inner: R, timeout: Duration) -> Self {
-        Self { inner, timeout }
+    fn new(inner: R, timeout: Duration, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            inner,
+            clock,
+            timeout,
+            idle_timeout: None,
+            idle_since: None,
+        }
+    }
+
+    fn with_idle_timeout(mut self, idle_timeout: Option<Duration>) -> Self {
+        self.idle_timeout = idle_timeout;
+        self
+    }
+
+    /// Run a read future with both io timeout and idle timeout.
+    ///
+    /// The idle timer will be reset if `progressed` returns true.
+    async fn read_timeout<F: Future<Output = Result<T>>, T>(
+        clock: &dyn Clock,
+        timeout: Duration,
+        idle: Option<(Duration, &mut Option<Instant>)>,
+        fut: F,
+        progressed: impl FnOnce(&T) -> bool,
+    ) -> Result<T> {
+        let Some((idle_timeout, idle_since)) = idle else {
+            return Self::io_timeout(clock, timeout, Operation::Read.into_static(), fut).await;
+        };
+
+        let now = clock.now();
+        let since = *idle_since.get_or_insert(now);
+        let remaining = idle_timeout.saturating_sub(now.saturating_duration_since(since));
+        let res = if remaining >= timeout {
+            Self::io_timeout(clock, timeout, Operation::Read.into_static(), fut).await
+        } else {
+            self::timeout(clock, remaining, fut).await.ok_or_else(|| {
+                Error::new(ErrorKind::Unexpected, "io idle timeout reached")
+                    .with_operation(Operation::Read.into_static())
+                    .with_context("idle_timeout", idle_timeout.as_secs_f64().to_string())
+                    .set_temporary()
+            })?
+        };
+        if matches!(&res, Ok(v) if progressed(v)) {
+            *idle_since = None;
+        }
+        res
     }
 
     #[inline]
     async fn io_timeout<F: Future<Output = Result<T>>, T>(
+        clock: &dyn Clock,
         timeout: Duration,
         op: &'static str,
         fut: F,
     ) -> Result<T> {
-        tokio::time::timeout(timeout, fut).await.map_err(|_| {
+        self::timeout(clock, timeout, fut).await.ok_or_else(|| {
             Error::new(ErrorKind::Unexpected, "io operation timeout reached")
                 .with_operation(op)
                 .with_context("timeout", timeout.as_secs_f64().to_string())
@@ -327,32 +491,74 @@ impl<R> TimeoutWrapper<R> {
 
 impl<R: oio::Read> oio::Read for TimeoutWrapper<R> {
     async fn read(&mut self) -> Result<Buffer> {
+        let idle = self.idle_timeout.map(|v| (v, &mut self.idle_since));
         let fut = self.inner.read();
-        Self::io_timeout(self.timeout, Operation::Read.into_static(), fut).await
+        Self::read_timeout(&*self.clock, self.timeout, idle, fut, |bs| !bs.is_empty()).await
+    }
+
+    async fn read_into(&mut self, dst: &mut [u8]) -> Result<usize> {
+        let idle = self.idle_timeout.map(|v| (v, &mut self.idle_since));
+        let fut = self.inner.read_into(dst);
+        Self::read_timeout(&*self.clock, self.timeout, idle, fut, |n| *n > 0).await
     }
 }
 
 impl<R: oio::Write> oio::Write for TimeoutWrapper<R> {
     async fn write(&mut self, bs: Buffer) -> Result<()> {
         let fut = self.inner.write(bs);
-        Self::io_timeout(self.timeout, Operation::Write.into_static(), fut).await
+        Self::io_timeout(
+            &*self.clock,
+            self.timeout,
+            Operation::Write.into_static(),
+            fut,
+        )
+        .await
+    }
+
+    async fn flush(&mut self) -> Result<()> {
+        let fut = self.inner.flush();
+        Self::io_timeout(
+            &*self.clock,
+            self.timeout,
+            Operation::Write.into_static(),
+            fut,
+        )
+        .await
     }
 
     async fn close(&mut self) -> Result<Metadata> {
         let fut = self.inner.close();
-        Self::io_timeout(self.timeout, Operation::Write.into_static(), fut).await
+        Self::io_timeout(
+            &*self.clock,
+            self.timeout,
+            Operation::Write.into_static(),
+            fut,
+        )
+        .await
     }
 
     async fn abort(&mut self) -> Result<()> {
         let fut = self.inner.abort();
-        Self::io_timeout(self.timeout, Operation::Write.into_static(), fut).await
+        Self::io_timeout(
+            &*self.clock,
+            self.timeout,
+            Operation::Write.into_static(),
+            fut,
+        )
+        .await
     }
 }
 
 impl<R: oio::List> oio::List for TimeoutWrapper<R> {
     async fn next(&mut self) -> Result<Option<oio::Entry>> {
         let fut = self.inner.next();
-        Self::io_timeout(self.timeout, Operation::List.into_static(), fut).await
+        Self::io_timeout(
+            &*self.clock,
+            self.timeout,
+            Operation::List.into_static(),
+            fut,
+        )
+        .await
     }
 }
 
@@ -363,7 +569,13 @@ impl<R: oio::Delete> oio::Delete for TimeoutWrapper<R> {
 
     async fn flush(&mut self) -> Result<usize> {
         let fut = self.inner.flush();
-        Self::io_timeout(self.timeout, Operation::Delete.into_static(), fut).await
+        Self::io_timeout(
+            &*self.clock,
+            self.timeout,
+            Operation::Delete.into_static(),
+            fut,
+        )
+        .await
     }
 }
 
@@ -472,6 +684,62 @@ mod tests {
         assert!(err.to_string().contains("timeout"))
     }
 
+    #[cfg(feature = "tests")]
+    #[tokio::test]
+    async fn test_io_timeout_override() {
+        use std::pin::pin;
+
+        use crate::raw::tests::MockClock;
+
+        let clock = MockClock::new();
+        let acc = Arc::new(TypeEraseLayer.layer(MockService)) as Accessor;
+        let op = Operator::from_inner(acc).layer(
+            TimeoutLayer::new()
+                .with_io_timeout(Duration::from_secs(60))
+                .with_clock(clock.clone()),
+        );
+
+        let reader = op
+            .reader_with("test")
+            .timeout(Duration::from_secs(1))
+            .await
+            .unwrap();
+
+        let mut fut = pin!(reader.read(0..4));
+        assert!(futures::poll!(fut.as_mut()).is_pending());
+        clock.advance(Duration::from_secs(1));
+        let err = fut.await.expect_err("read must timeout");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        assert!(err.to_string().contains("timeout"))
+    }
+
+    #[cfg(feature = "tests")]
+    #[tokio::test]
+    async fn test_io_idle_timeout() {
+        use std::pin::pin;
+
+        use crate::raw::tests::MockClock;
+
+        let clock = MockClock::new();
+        let acc = Arc::new(TypeEraseLayer.layer(MockService)) as Accessor;
+        let op = Operator::from_inner(acc).layer(
+            TimeoutLayer::new()
+                .with_io_timeout(Duration::from_secs(60))
+                .with_idle_timeout(Duration::from_secs(1))
+                .with_clock(clock.clone()),
+        );
+
+        let reader = op.reader("test").await.unwrap();
+
+        let mut fut = pin!(reader.read(0..4));
+        assert!(futures::poll!(fut.as_mut()).is_pending());
+        clock.advance(Duration::from_secs(1));
+        let err = fut.await.expect_err("read must timeout");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        assert!(err.is_temporary());
+        assert!(err.to_string().contains("idle timeout"))
+    }
+
     #[tokio::test]
     async fn test_list_timeout() {
         let acc = Arc::new(TypeEraseLayer.layer(MockService)) as Accessor;
@@ -510,4 +778,29 @@ mod tests {
         assert_eq!(err.kind(), ErrorKind::Unexpected);
         assert!(err.to_string().contains("timeout"));
     }
+
+    #[cfg(feature = "tests")]
+    #[tokio::test]
+    async fn test_operation_timeout_with_clock() {
+        use std::pin::pin;
+
+        use crate::raw::tests::MockClock;
+
+        let clock = MockClock::new();
+        let acc = Arc::new(TypeEraseLayer.layer(MockService)) as Accessor;
+        let op = Operator::from_inner(acc).layer(
+            TimeoutLayer::new()
+                .with_timeout(Duration::from_secs(60))
+                .with_clock(clock.clone()),
+        );
+
+        let mut fut = pin!(op.delete("test"));
+        assert!(futures::poll!(fut.as_mut()).is_pending());
+        clock.advance(Duration::from_secs(59));
+        assert!(futures::poll!(fut.as_mut()).is_pending());
+        clock.advance(Duration::from_secs(1));
+        let err = fut.await.expect_err("delete must timeout");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        assert!(err.to_string().contains("timeout"));
+    }
 }
@@ -304,6 +304,12 @@ impl<R: oio::Read> oio::Read for TracingWrapper<R> {
 
         self.inner.read().await
     }
+
+    async fn read_into(&mut self, dst: &mut [u8]) -> Result<usize> {
+        let _enter = self.span.enter();
+
+        self.inner.read_into(dst).await
+    }
 }
 
 impl<R: oio::Write> oio::Write for TracingWrapper<R> {
@@ -313,6 +319,12 @@ impl<R: oio::Write> oio::Write for TracingWrapper<R> {
         self.inner.write(bs).await
     }
 
+    async fn flush(&mut self) -> Result<()> {
+        let _enter = self.span.enter();
+
+        self.inner.flush().await
+    }
+
     async fn abort(&mut self) -> Result<()> {
         let _enter = self.span.enter();
 
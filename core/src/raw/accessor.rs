@@ -24,6 +24,8 @@ use std::sync::Arc;
 
 use futures::Future;
 
+use crate::layers::InFlightTracker;
+use crate::layers::RetryPolicy;
 use crate::raw::*;
 use crate::*;
 
@@ -238,6 +240,68 @@ pub trait Access: Send + Sync + Debug + Unpin + 'static {
         )))
     }
 
+    /// Invoke the `set_tags` operation on the specified path.
+    ///
+    /// Require [`Capability::set_tags`]
+    ///
+    /// # Behavior
+    ///
+    /// - Existing tags of the file SHOULD be replaced by the given tags.
+    fn set_tags(
+        &self,
+        path: &str,
+        args: OpSetTags,
+    ) -> impl Future<Output = Result<RpSetTags>> + MaybeSend {
+        let (_, _) = (path, args);
+
+        ready(Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        )))
+    }
+
+    /// Invoke the `abort_multipart` operation on the specified path.
+    ///
+    /// Require [`Capability::abort_multipart`]
+    ///
+    /// # Behavior
+    ///
+    /// - The multipart upload with given upload id SHOULD be aborted and its
+    ///   uploaded parts SHOULD be removed.
+    fn abort_multipart(
+        &self,
+        path: &str,
+        args: OpAbortMultipart,
+    ) -> impl Future<Output = Result<RpAbortMultipart>> + MaybeSend {
+        let (_, _) = (path, args);
+
+        ready(Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        )))
+    }
+
+    /// Invoke the `list_multipart_uploads` operation on the specified path.
+    ///
+    /// Require [`Capability::list_multipart_uploads`]
+    ///
+    /// # Behavior
+    ///
+    /// - Path is a prefix, all in-progress multipart uploads of files starting
+    ///   with the prefix SHOULD be returned.
+    fn list_multipart_uploads(
+        &self,
+        path: &str,
+        args: OpListMultipartUploads,
+    ) -> impl Future<Output = Result<RpListMultipartUploads>> + MaybeSend {
+        let (_, _) = (path, args);
+
+        ready(Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        )))
+    }
+
     /// Invoke the `presign` operation on the specified path.
     ///
     /// Require [`Capability::presign`]
@@ -306,6 +370,24 @@ pub trait AccessDyn: Send + Sync + Debug + Unpin {
         to: &'a str,
         args: OpRename,
     ) -> BoxedFuture<'a, Result<RpRename>>;
+    /// Dyn version of [`Accessor::set_tags`]
+    fn set_tags_dyn<'a>(
+        &'a self,
+        path: &'a str,
+        args: OpSetTags,
+    ) -> BoxedFuture<'a, Result<RpSetTags>>;
+    /// Dyn version of [`Accessor::abort_multipart`]
+    fn abort_multipart_dyn<'a>(
+        &'a self,
+        path: &'a str,
+        args: OpAbortMultipart,
+    ) -> BoxedFuture<'a, Result<RpAbortMultipart>>;
+    /// Dyn version of [`Accessor::list_multipart_uploads`]
+    fn list_multipart_uploads_dyn<'a>(
+        &'a self,
+        path: &'a str,
+        args: OpListMultipartUploads,
+    ) -> BoxedFuture<'a, Result<RpListMultipartUploads>>;
     /// Dyn version of [`Accessor::presign`]
     fn presign_dyn<'a>(
         &'a self,
@@ -385,6 +467,30 @@ where
         Box::pin(self.rename(from, to, args))
     }
 
+    fn set_tags_dyn<'a>(
+        &'a self,
+        path: &'a str,
+        args: OpSetTags,
+    ) -> BoxedFuture<'a, Result<RpSetTags>> {
+        Box::pin(self.set_tags(path, args))
+    }
+
+    fn abort_multipart_dyn<'a>(
+        &'a self,
+        path: &'a str,
+        args: OpAbortMultipart,
+    ) -> BoxedFuture<'a, Result<RpAbortMultipart>> {
+        Box::pin(self.abort_multipart(path, args))
+    }
+
+    fn list_multipart_uploads_dyn<'a>(
+        &'a self,
+        path: &'a str,
+        args: OpListMultipartUploads,
+    ) -> BoxedFuture<'a, Result<RpListMultipartUploads>> {
+        Box::pin(self.list_multipart_uploads(path, args))
+    }
+
     fn presign_dyn<'a>(
         &'a self,
         path: &'a str,
@@ -436,6 +542,26 @@ impl Access for dyn AccessDyn {
         self.rename_dyn(from, to, args).await
     }
 
+    async fn set_tags(&self, path: &str, args: OpSetTags) -> Result<RpSetTags> {
+        self.set_tags_dyn(path, args).await
+    }
+
+    async fn abort_multipart(
+        &self,
+        path: &str,
+        args: OpAbortMultipart,
+    ) -> Result<RpAbortMultipart> {
+        self.abort_multipart_dyn(path, args).await
+    }
+
+    async fn list_multipart_uploads(
+        &self,
+        path: &str,
+        args: OpListMultipartUploads,
+    ) -> Result<RpListMultipartUploads> {
+        self.list_multipart_uploads_dyn(path, args).await
+    }
+
     async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
         self.presign_dyn(path, args).await
     }
@@ -531,6 +657,30 @@ impl<T: Access + ?Sized> Access for Arc<T> {
         async move { self.as_ref().rename(from, to, args).await }
     }
 
+    fn set_tags(
+        &self,
+        path: &str,
+        args: OpSetTags,
+    ) -> impl Future<Output = Result<RpSetTags>> + MaybeSend {
+        async move { self.as_ref().set_tags(path, args).await }
+    }
+
+    fn abort_multipart(
+        &self,
+        path: &str,
+        args: OpAbortMultipart,
+    ) -> impl Future<Output = Result<RpAbortMultipart>> + MaybeSend {
+        async move { self.as_ref().abort_multipart(path, args).await }
+    }
+
+    fn list_multipart_uploads(
+        &self,
+        path: &str,
+        args: OpListMultipartUploads,
+    ) -> impl Future<Output = Result<RpListMultipartUploads>> + MaybeSend {
+        async move { self.as_ref().list_multipart_uploads(path, args).await }
+    }
+
     fn presign(
         &self,
         path: &str,
@@ -554,6 +704,9 @@ struct AccessorInfoInner {
 
     http_client: HttpClient,
     executor: Executor,
+
+    retry_policy: Option<RetryPolicy>,
+    in_flight_tracker: Option<Arc<InFlightTracker>>,
 }
 
 impl Default for AccessorInfoInner {
@@ -566,6 +719,8 @@ impl Default for AccessorInfoInner {
             full_capability: Capability::default(),
             http_client: HttpClient::default(),
             executor: Executor::default(),
+            retry_policy: None,
+            in_flight_tracker: None,
         }
     }
 }
@@ -853,4 +1008,51 @@ impl AccessorInfo {
 
         self
     }
+
+    /// Get the retry policy of the outermost [`RetryLayer`](crate::layers::RetryLayer).
+    ///
+    /// Returns `None` if no `RetryLayer` has been applied.
+    ///
+    /// # Panic Safety
+    ///
+    /// This method safely handles lock poisoning scenarios. If the inner `RwLock` is poisoned,
+    /// this method will gracefully continue execution by simply returning the current retry policy.
+    pub fn retry_policy(&self) -> Option<RetryPolicy> {
+        match self.inner.read() {
+            Ok(v) => v.retry_policy.clone(),
+            Err(err) => err.get_ref().retry_policy.clone(),
+        }
+    }
+
+    /// Set the retry policy for the context.
+    ///
+    /// # Panic Safety
+    ///
+    /// This method safely handles lock poisoning scenarios. If the inner `RwLock` is poisoned,
+    /// this method will gracefully continue execution by simply skipping the update operation.
+    pub fn set_retry_policy(&self, policy: RetryPolicy) -> &Self {
+        if let Ok(mut v) = self.inner.write() {
+            v.retry_policy = Some(policy);
+        }
+
+        self
+    }
+
+    /// Get the in-flight tracker of the outermost
+    /// [`ConcurrentLimitLayer`](crate::layers::ConcurrentLimitLayer).
+    pub(crate) fn in_flight_tracker(&self) -> Option<Arc<InFlightTracker>> {
+        match self.inner.read() {
+            Ok(v) => v.in_flight_tracker.clone(),
+            Err(err) => err.get_ref().in_flight_tracker.clone(),
+        }
+    }
+
+    /// Set the in-flight tracker for the context.
+    pub(crate) fn set_in_flight_tracker(&self, tracker: Arc<InFlightTracker>) -> &Self {
+        if let Ok(mut v) = self.inner.write() {
+            v.in_flight_tracker = Some(tracker);
+        }
+
+        self
+    }
 }
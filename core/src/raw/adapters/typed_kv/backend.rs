@@ -123,7 +123,8 @@ impl<S: Adapter> Access for Backend<S> {
             None => return Err(Error::new(ErrorKind::NotFound, "kv doesn't have this path")),
         };
 
-        Ok((RpRead::new(), bs.slice(args.range().to_range_as_usize())))
+        let range = args.range().to_range_as_usize_within(bs.len());
+        Ok((RpRead::new(), bs.slice(range)))
     }
 
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
@@ -0,0 +1,54 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::time::Duration;
+use std::time::Instant;
+
+use backon::DefaultSleeper;
+use backon::Sleeper;
+
+use crate::raw::*;
+
+/// Clock is the source of time used by layers like `TimeoutLayer`, `RetryLayer`
+/// and `StatCacheLayer`.
+///
+/// Layers use [`SystemClock`] by default. Tests could inject a clock that can be
+/// advanced manually to verify expiry and timeout deterministically without
+/// real sleeps.
+pub trait Clock: Debug + Send + Sync + 'static {
+    /// Returns the current instant of this clock.
+    fn now(&self) -> Instant;
+
+    /// Returns a future that completes after the given duration elapsed on this clock.
+    fn sleep(&self, dur: Duration) -> BoxedStaticFuture<()>;
+}
+
+/// SystemClock reads the time from the system and sleeps with backon's default
+/// sleeper, which is tokio on native targets and `gloo-timers` on wasm.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn sleep(&self, dur: Duration) -> BoxedStaticFuture<()> {
+        Box::pin(DefaultSleeper::default().sleep(dur))
+    }
+}
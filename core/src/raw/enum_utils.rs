@@ -58,6 +58,13 @@ impl<ONE: oio::Read, TWO: oio::Read> oio::Read for TwoWays<ONE, TWO> {
             TwoWays::Two(v) => v.read().await,
         }
     }
+
+    async fn read_into(&mut self, dst: &mut [u8]) -> Result<usize> {
+        match self {
+            TwoWays::One(v) => v.read_into(dst).await,
+            TwoWays::Two(v) => v.read_into(dst).await,
+        }
+    }
 }
 
 impl<ONE: oio::Write, TWO: oio::Write> oio::Write for TwoWays<ONE, TWO> {
@@ -68,6 +75,13 @@ impl<ONE: oio::Write, TWO: oio::Write> oio::Write for TwoWays<ONE, TWO> {
         }
     }
 
+    async fn flush(&mut self) -> Result<()> {
+        match self {
+            Self::One(v) => v.flush().await,
+            Self::Two(v) => v.flush().await,
+        }
+    }
+
     async fn close(&mut self) -> Result<Metadata> {
         match self {
             Self::One(v) => v.close().await,
@@ -112,6 +126,14 @@ impl<ONE: oio::Read, TWO: oio::Read, THREE: oio::Read> oio::Read for ThreeWays<O
             ThreeWays::Three(v) => v.read().await,
         }
     }
+
+    async fn read_into(&mut self, dst: &mut [u8]) -> Result<usize> {
+        match self {
+            ThreeWays::One(v) => v.read_into(dst).await,
+            ThreeWays::Two(v) => v.read_into(dst).await,
+            ThreeWays::Three(v) => v.read_into(dst).await,
+        }
+    }
 }
 
 impl<ONE: oio::Write, TWO: oio::Write, THREE: oio::Write> oio::Write
@@ -125,6 +147,14 @@ impl<ONE: oio::Write, TWO: oio::Write, THREE: oio::Write> oio::Write
         }
     }
 
+    async fn flush(&mut self) -> Result<()> {
+        match self {
+            Self::One(v) => v.flush().await,
+            Self::Two(v) => v.flush().await,
+            Self::Three(v) => v.flush().await,
+        }
+    }
+
     async fn close(&mut self) -> Result<Metadata> {
         match self {
             Self::One(v) => v.close().await,
@@ -181,6 +211,15 @@ where
             FourWays::Four(v) => v.read().await,
         }
     }
+
+    async fn read_into(&mut self, dst: &mut [u8]) -> Result<usize> {
+        match self {
+            FourWays::One(v) => v.read_into(dst).await,
+            FourWays::Two(v) => v.read_into(dst).await,
+            FourWays::Three(v) => v.read_into(dst).await,
+            FourWays::Four(v) => v.read_into(dst).await,
+        }
+    }
 }
 
 impl<ONE, TWO, THREE, FOUR> oio::List for FourWays<ONE, TWO, THREE, FOUR>
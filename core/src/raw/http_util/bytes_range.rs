@@ -19,6 +19,7 @@ use std::fmt::Debug;
 use std::fmt::Display;
 use std::fmt::Formatter;
 use std::ops::Bound;
+use std::ops::Range;
 use std::ops::RangeBounds;
 use std::str::FromStr;
 
@@ -104,15 +105,18 @@ impl BytesRange {
         )
     }
 
-    /// Convert bytes range into rust range with usize.
-    pub(crate) fn to_range_as_usize(self) -> impl RangeBounds<usize> {
-        (
-            Bound::Included(self.0 as usize),
-            match self.1 {
-                Some(size) => Bound::Excluded((self.0 + size) as usize),
-                None => Bound::Unbounded,
-            },
-        )
+    /// Convert bytes range into rust range with usize that fits in content
+    /// of `len` bytes.
+    ///
+    /// The range will be truncated to the end of content, just like services
+    /// return less data if the range exceeds the end of the file.
+    pub(crate) fn to_range_as_usize_within(self, len: usize) -> Range<usize> {
+        let start = (self.0 as usize).min(len);
+        let end = match self.1 {
+            Some(size) => start.saturating_add(size as usize).min(len),
+            None => len,
+        };
+        start..end
     }
 }
 
@@ -46,15 +46,44 @@ pub fn new_request_sign_error(err: anyhow::Error) -> Error {
         .set_source(err)
 }
 
+/// Headers used by services to carry the id of request.
+///
+/// The first one found in response will be used.
+const REQUEST_ID_HEADERS: &[&str] = &[
+    // s3 and s3 compatible services.
+    "x-amz-request-id",
+    // azblob, azdls and azfile.
+    "x-ms-request-id",
+    "x-oss-request-id",
+    "x-cos-request-id",
+    "x-obs-request-id",
+    "x-guploader-uploadid",
+    "x-request-id",
+];
+
 /// Add response context to error.
 ///
 /// This helper function will:
 ///
 /// - remove sensitive or useless headers from parts.
-/// - fetch uri if parts extensions contains `Uri`.
+/// - fetch uri and endpoint if parts extensions contains `Uri`.
+/// - add http status and the request id returned by service.
 pub fn with_error_response_context(mut err: Error, mut parts: Parts) -> Error {
     if let Some(uri) = parts.extensions.get::<Uri>() {
         err = err.with_context("uri", uri.to_string());
+        if let (Some(scheme), Some(authority)) = (uri.scheme(), uri.authority()) {
+            err = err.with_context("endpoint", format!("{scheme}://{authority}"));
+        }
+    }
+
+    err = err.with_context("status", parts.status.as_u16());
+
+    if let Some(request_id) = REQUEST_ID_HEADERS
+        .iter()
+        .find_map(|name| parts.headers.get(*name))
+        .and_then(|v| v.to_str().ok())
+    {
+        err = err.with_context("request_id", request_id);
     }
 
     // The following headers may contains sensitive information.
@@ -66,3 +95,37 @@ pub fn with_error_response_context(mut err: Error, mut parts: Parts) -> Error {
 
     err
 }
+
+#[cfg(test)]
+mod tests {
+    use http::Response;
+
+    use super::*;
+
+    #[test]
+    fn test_with_error_response_context() {
+        let mut resp = Response::builder()
+            .status(503)
+            .header("x-amz-request-id", "4442587FB7D0A2F9")
+            .header("Set-Cookie", "secret")
+            .body(())
+            .unwrap();
+        resp.extensions_mut().insert(
+            "https://bucket.s3.amazonaws.com/path/to/file?x-id=GetObject"
+                .parse::<Uri>()
+                .unwrap(),
+        );
+        let (parts, _) = resp.into_parts();
+
+        let err =
+            with_error_response_context(Error::new(ErrorKind::Unexpected, "slow down"), parts);
+        let s = err.to_string();
+        assert!(
+            s.contains("endpoint: https://bucket.s3.amazonaws.com"),
+            "{s}"
+        );
+        assert!(s.contains("status: 503"), "{s}");
+        assert!(s.contains("request_id: 4442587FB7D0A2F9"), "{s}");
+        assert!(!s.contains("secret"), "{s}");
+    }
+}
@@ -33,6 +33,7 @@ use http::header::LOCATION;
 use http::HeaderMap;
 use http::HeaderName;
 use http::HeaderValue;
+use http::Request;
 use md5::Digest;
 
 use crate::raw::*;
@@ -200,6 +201,26 @@ pub fn parse_into_metadata(path: &str, headers: &HeaderMap) -> Result<Metadata>
     Ok(m)
 }
 
+/// Parse the metadata of the whole file from the headers of read response.
+///
+/// Returns `None` if the size of file can't be known from the headers, for
+/// example, the response is chunked or the total size of content range is unknown.
+pub fn parse_read_metadata(path: &str, headers: &HeaderMap) -> Result<Option<Metadata>> {
+    let mut m = parse_into_metadata(path, headers)?;
+
+    match m.content_range() {
+        Some(range) => match range.size() {
+            Some(size) => {
+                m.set_content_length(size);
+            }
+            None => return Ok(None),
+        },
+        None if parse_content_length(headers)?.is_none() => return Ok(None),
+        None => {}
+    }
+    Ok(Some(m))
+}
+
 /// Parse prefixed headers and return a map with the prefix of each header removed.
 pub fn parse_prefixed_headers(headers: &HeaderMap, prefix: &str) -> HashMap<String, String> {
     headers
@@ -269,10 +290,77 @@ pub fn build_header_value(v: &str) -> Result<HeaderValue> {
     })
 }
 
+/// Headers that can't be set as extra headers, since they are managed by the
+/// http client or covered by request signing.
+const RESERVED_HEADERS: &[&str] = &[
+    "host",
+    "content-length",
+    "transfer-encoding",
+    "authorization",
+    "date",
+    "x-amz-date",
+    "x-amz-content-sha256",
+    "x-amz-security-token",
+    "x-goog-date",
+    "x-ms-date",
+    "x-ms-version",
+];
+
+/// Check if the header is reserved and can't be set as extra headers.
+pub fn is_reserved_header(name: &HeaderName) -> bool {
+    RESERVED_HEADERS.contains(&name.as_str())
+}
+
+/// Merge extra headers into the request.
+///
+/// This should be called before the request is signed so that extra headers
+/// like `x-amz-*` are covered by the signature. Headers that have been set in
+/// the request will not be overridden.
+pub fn merge_extra_headers<T>(req: &mut Request<T>, headers: Option<&HeaderMap>) {
+    let Some(headers) = headers else {
+        return;
+    };
+
+    let dst = req.headers_mut();
+    for name in headers.keys() {
+        if is_reserved_header(name) || dst.contains_key(name) {
+            continue;
+        }
+        for value in headers.get_all(name) {
+            dst.append(name.clone(), value.clone());
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use http::header::AUTHORIZATION;
+
     use super::*;
 
+    #[test]
+    fn test_merge_extra_headers() {
+        let mut req = Request::get("https://example.com")
+            .header(AUTHORIZATION, "signed")
+            .header(CONTENT_TYPE, "text/plain")
+            .body(())
+            .unwrap();
+
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, HeaderValue::from_static("override"));
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("override"));
+        headers.append("x-tenant", HeaderValue::from_static("a"));
+        headers.append("x-tenant", HeaderValue::from_static("b"));
+        merge_extra_headers(&mut req, Some(&headers));
+
+        assert_eq!(req.headers()[AUTHORIZATION], "signed");
+        assert_eq!(req.headers()[CONTENT_TYPE], "text/plain");
+        assert_eq!(
+            req.headers().get_all("x-tenant").iter().collect::<Vec<_>>(),
+            ["a", "b"]
+        );
+    }
+
     /// Test cases is from https://docs.aws.amazon.com/AmazonS3/latest/API/API_DeleteObjects.html
     #[test]
     fn test_format_content_md5() {
@@ -334,6 +422,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_read_metadata() {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_LENGTH, HeaderValue::from_static("4"));
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("image/png"));
+        let meta = parse_read_metadata("file", &headers).unwrap().unwrap();
+        assert_eq!(meta.content_length(), 4);
+        assert_eq!(meta.content_type(), Some("image/png"));
+
+        // The size of whole file is taken from the content range.
+        headers.insert(CONTENT_RANGE, HeaderValue::from_static("bytes 0-3/1024"));
+        let meta = parse_read_metadata("file", &headers).unwrap().unwrap();
+        assert_eq!(meta.content_length(), 1024);
+
+        headers.insert(CONTENT_RANGE, HeaderValue::from_static("bytes 0-3/*"));
+        assert!(parse_read_metadata("file", &headers).unwrap().is_none());
+
+        // Chunked response doesn't carry the size.
+        let headers = HeaderMap::new();
+        assert!(parse_read_metadata("file", &headers).unwrap().is_none());
+    }
+
     #[test]
     fn test_parse_multipart_boundary() {
         let cases = vec![
@@ -38,6 +38,8 @@ pub use header::build_header_value;
 pub use header::format_authorization_by_basic;
 pub use header::format_authorization_by_bearer;
 pub use header::format_content_md5;
+pub use header::is_reserved_header;
+pub use header::merge_extra_headers;
 pub use header::parse_content_disposition;
 pub use header::parse_content_encoding;
 pub use header::parse_content_length;
@@ -51,6 +53,7 @@ pub use header::parse_last_modified;
 pub use header::parse_location;
 pub use header::parse_multipart_boundary;
 pub use header::parse_prefixed_headers;
+pub use header::parse_read_metadata;
 
 mod uri;
 pub use uri::new_http_uri_invalid_error;
@@ -64,6 +67,9 @@ pub use error::new_request_credential_error;
 pub use error::new_request_sign_error;
 pub use error::with_error_response_context;
 
+mod signer;
+pub use signer::RequestSigner;
+
 mod bytes_range;
 pub use bytes_range::BytesRange;
 
@@ -71,6 +77,7 @@ mod bytes_content_range;
 pub use bytes_content_range::BytesContentRange;
 
 mod multipart;
+pub use multipart::parse_multipart_byteranges;
 pub use multipart::FormDataPart;
 pub use multipart::MixedPart;
 pub use multipart::Multipart;
@@ -35,6 +35,7 @@ use http::Uri;
 use http::Version;
 
 use super::new_request_build_error;
+use super::BytesContentRange;
 use crate::*;
 
 /// Multipart is a builder for multipart/form-data.
@@ -628,6 +629,59 @@ impl Part for RelatedPart {
     }
 }
 
+/// Parse a `multipart/byteranges` response body into the content range
+/// and content of each part.
+///
+/// Unlike [`Multipart::parse`], the body is parsed as raw bytes so binary
+/// content is kept as is without extra copy.
+pub fn parse_multipart_byteranges(
+    boundary: &str,
+    body: Bytes,
+) -> Result<Vec<(BytesContentRange, Bytes)>> {
+    let invalid = |msg: &'static str| {
+        Error::new(ErrorKind::Unexpected, msg).with_operation("parse_multipart_byteranges")
+    };
+    let find = |from: usize, pat: &[u8]| {
+        body[from..]
+            .windows(pat.len())
+            .position(|w| w == pat)
+            .map(|pos| from + pos)
+    };
+
+    let delimiter = format!("--{}", boundary.trim_matches('"'));
+    let delimiter = delimiter.as_bytes();
+    let mut offset = find(0, delimiter)
+        .ok_or_else(|| invalid("multipart byteranges response has no boundary"))?
+        + delimiter.len();
+
+    let mut parts = Vec::new();
+    // The close delimiter is the boundary followed by `--`.
+    while !body[offset..].starts_with(b"--") {
+        let header_end = find(offset, b"\r\n\r\n")
+            .ok_or_else(|| invalid("multipart byteranges part has no headers end"))?;
+        let headers = std::str::from_utf8(&body[offset..header_end]).map_err(|err| {
+            invalid("multipart byteranges part headers are invalid").set_source(err)
+        })?;
+        let range = headers
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .find(|(k, _)| k.trim().eq_ignore_ascii_case("content-range"))
+            .ok_or_else(|| invalid("multipart byteranges part has no content range"))?
+            .1
+            .trim()
+            .parse::<BytesContentRange>()?;
+
+        let content_start = header_end + 4;
+        let content_end = find(content_start, &[b"\r\n", delimiter].concat())
+            .ok_or_else(|| invalid("multipart byteranges part is not terminated"))?;
+        parts.push((range, body.slice(content_start..content_end)));
+
+        offset = content_end + 2 + delimiter.len();
+    }
+
+    Ok(parts)
+}
+
 #[cfg(test)]
 mod tests {
     use http::header::CONTENT_TYPE;
@@ -1177,4 +1231,28 @@ This is a text file.
 
         assert_eq!(output, expected);
     }
+
+    #[test]
+    fn test_parse_multipart_byteranges() -> Result<()> {
+        let body = Bytes::from_static(
+            b"--3d6b6a416f9b5\r\n\
+            Content-Type: application/octet-stream\r\n\
+            Content-Range: bytes 0-3/1270\r\n\
+            \r\n\
+            \x00\r\n\x01\r\n\
+            --3d6b6a416f9b5\r\n\
+            content-range: bytes 100-104/1270\r\n\
+            \r\n\
+            hello\r\n\
+            --3d6b6a416f9b5--\r\n",
+        );
+
+        let parts = parse_multipart_byteranges("3d6b6a416f9b5", body)?;
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].0.range(), Some(0..4));
+        assert_eq!(parts[0].1, Bytes::from_static(b"\x00\r\n\x01"));
+        assert_eq!(parts[1].0.range(), Some(100..105));
+        assert_eq!(parts[1].1, Bytes::from_static(b"hello"));
+        Ok(())
+    }
 }
@@ -0,0 +1,60 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::fmt::Debug;
+use std::time::Duration;
+
+use http::Request;
+
+use crate::*;
+
+/// RequestSigner signs the outgoing http requests for services that speak a
+/// known protocol with a custom authentication scheme, like S3-compatible
+/// gateways with their own HMAC signing.
+///
+/// Services that accept a `RequestSigner` will call it instead of their
+/// built-in signer for every request, including presign.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use http::header::AUTHORIZATION;
+/// use http::Request;
+/// use opendal::raw::RequestSigner;
+/// use opendal::Buffer;
+/// use opendal::Result;
+///
+/// #[derive(Debug)]
+/// struct TokenSigner(String);
+///
+/// impl RequestSigner for TokenSigner {
+///     fn sign(&self, req: &mut Request<Buffer>, expire: Option<Duration>) -> Result<()> {
+///         let value = format!("Token {}", self.0);
+///         req.headers_mut()
+///             .insert(AUTHORIZATION, value.parse().expect("must be valid header"));
+///         Ok(())
+///     }
+/// }
+/// ```
+pub trait RequestSigner: Debug + Send + Sync + 'static {
+    /// Sign the request in place.
+    ///
+    /// `expire` is set while presigning, the signature should be carried in the
+    /// query of request and be valid for the given duration.
+    fn sign(&self, req: &mut Request<Buffer>, expire: Option<Duration>) -> Result<()>;
+}
@@ -170,6 +170,30 @@ pub trait LayeredAccess: Send + Sync + Debug + Unpin + 'static {
         args: OpList,
     ) -> impl Future<Output = Result<(RpList, Self::Lister)>> + MaybeSend;
 
+    fn set_tags(
+        &self,
+        path: &str,
+        args: OpSetTags,
+    ) -> impl Future<Output = Result<RpSetTags>> + MaybeSend {
+        self.inner().set_tags(path, args)
+    }
+
+    fn abort_multipart(
+        &self,
+        path: &str,
+        args: OpAbortMultipart,
+    ) -> impl Future<Output = Result<RpAbortMultipart>> + MaybeSend {
+        self.inner().abort_multipart(path, args)
+    }
+
+    fn list_multipart_uploads(
+        &self,
+        path: &str,
+        args: OpListMultipartUploads,
+    ) -> impl Future<Output = Result<RpListMultipartUploads>> + MaybeSend {
+        self.inner().list_multipart_uploads(path, args)
+    }
+
     fn presign(
         &self,
         path: &str,
@@ -221,6 +245,26 @@ impl<L: LayeredAccess> Access for L {
         LayeredAccess::list(self, path, args).await
     }
 
+    async fn set_tags(&self, path: &str, args: OpSetTags) -> Result<RpSetTags> {
+        LayeredAccess::set_tags(self, path, args).await
+    }
+
+    async fn abort_multipart(
+        &self,
+        path: &str,
+        args: OpAbortMultipart,
+    ) -> Result<RpAbortMultipart> {
+        LayeredAccess::abort_multipart(self, path, args).await
+    }
+
+    async fn list_multipart_uploads(
+        &self,
+        path: &str,
+        args: OpListMultipartUploads,
+    ) -> Result<RpListMultipartUploads> {
+        LayeredAccess::list_multipart_uploads(self, path, args).await
+    }
+
     async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
         LayeredAccess::presign(self, path, args).await
     }
@@ -48,6 +48,9 @@ pub use layer::*;
 mod path;
 pub use path::*;
 
+mod redact;
+pub use redact::*;
+
 #[cfg(feature = "internal-path-cache")]
 mod path_cache;
 #[cfg(feature = "internal-path-cache")]
@@ -74,6 +77,10 @@ pub use serde_util::*;
 mod chrono_util;
 pub use chrono_util::*;
 
+mod clock;
+pub use clock::Clock;
+pub use clock::SystemClock;
+
 #[cfg(feature = "internal-tokio-rt")]
 mod tokio_util;
 #[cfg(feature = "internal-tokio-rt")]
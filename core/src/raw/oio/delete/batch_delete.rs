@@ -111,13 +111,29 @@ impl<D: BatchDelete> oio::Delete for BatchDeleter<D> {
             self.buffer.remove(&i);
         }
 
-        // Return directly if there are non-temporary errors.
+        // Non-temporary errors will not be fixed by retrying, drop them from the
+        // buffer and report all failed paths of this batch together.
+        let mut failed = vec![];
         for (path, op, err) in result.failed {
-            if !err.is_temporary() {
-                return Err(err
-                    .with_context("path", path)
-                    .with_context("version", op.version().unwrap_or("<latest>")));
+            if err.is_temporary() {
+                continue;
             }
+            self.buffer.remove(&(path.clone(), op.clone()));
+            failed.push(DeleteFailure::new(
+                path,
+                op.version().map(|v| v.to_string()),
+                err,
+            ));
+        }
+        if let Some(first) = failed.first() {
+            let kind = first.error().kind();
+            let failed_count = failed.len();
+            return Err(
+                Error::new(kind, "some paths failed to delete permanently in batch")
+                    .with_context("deleted", deleted)
+                    .with_context("failed", failed_count)
+                    .set_source(DeleteFailures::new(deleted, failed)),
+            );
         }
 
         // Return the number of succeeded operations to allow users to decide whether
@@ -125,3 +141,51 @@ impl<D: BatchDelete> oio::Delete for BatchDeleter<D> {
         Ok(deleted)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct MockDeleter;
+
+    impl BatchDelete for MockDeleter {
+        async fn delete_once(&self, _: String, _: OpDelete) -> Result<()> {
+            Ok(())
+        }
+
+        async fn delete_batch(&self, batch: Vec<(String, OpDelete)>) -> Result<BatchDeleteResult> {
+            let mut result = BatchDeleteResult::default();
+            for (path, args) in batch {
+                if path.starts_with("denied") {
+                    let err = Error::new(ErrorKind::PermissionDenied, "access denied");
+                    result.failed.push((path, args, err));
+                } else {
+                    result.succeeded.push((path, args));
+                }
+            }
+            Ok(result)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_batch_deleter_reports_failed_paths() {
+        let mut d = BatchDeleter::new(MockDeleter);
+        for path in ["a", "denied-1", "b", "denied-2"] {
+            oio::Delete::delete(&mut d, path, OpDelete::default()).unwrap();
+        }
+
+        let err = oio::Delete::flush(&mut d)
+            .await
+            .expect_err("flush must fail");
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        let failures = DeleteFailures::from_error(&err).expect("failures must be attached");
+        assert_eq!(failures.deleted(), 2);
+        let mut failed: Vec<_> = failures.failed().iter().map(|v| v.path()).collect();
+        failed.sort();
+        assert_eq!(failed, ["denied-1", "denied-2"]);
+
+        // Failed paths should not be retried.
+        assert!(d.buffer.is_empty());
+        assert_eq!(oio::Delete::flush(&mut d).await.unwrap(), 0);
+    }
+}
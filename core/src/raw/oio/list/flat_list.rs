@@ -15,6 +15,8 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::VecDeque;
+
 use crate::raw::*;
 use crate::*;
 
@@ -54,13 +56,71 @@ use crate::*;
 /// Especially, for storage services that can't return dirs first, ToFlatLister
 /// may output parent dirs' files before nested dirs, this is expected because files
 /// always output directly while listing.
+///
+/// # Concurrent
+///
+/// If [`FlatLister::with_concurrent`] is set to larger than 1, FlatLister will list
+/// up to `concurrent` dirs at the same time and output entries in breadth-first
+/// order instead:
+///
+/// ```txt
+/// file_a
+/// dir_x/file_b
+/// dir_x/
+/// dir_x/dir_y/file_c
+/// dir_x/dir_y/
+/// dir_x/dir_y/dir_z/
+/// ```
+///
+/// The order is still deterministic: dirs are output in the order they are found,
+/// and each dir will be output right after its direct children. But nested dirs are
+/// no longer guaranteed to show up before parent dirs.
+///
+/// To keep memory bounded, at most `concurrent * 8` found dirs are queued. Dirs found
+/// while the queue is full will be walked in place one by one like the non-concurrent
+/// mode, before the remaining entries of their parent dir.
+///
+/// # Sorted
+///
+/// If [`FlatLister::with_sorted`] is enabled, FlatLister will walk dir in top down
+/// way and output all entries in lexicographic order:
+///
+/// ```txt
+/// dir_x/
+/// dir_x/dir_y/
+/// dir_x/dir_y/dir_z/
+/// dir_x/dir_y/dir_z/file_c
+/// dir_x/dir_y/file_b
+/// dir_x/file_a
+/// ```
+///
+/// Every dir will be listed and sorted in memory before its entries are output, so
+/// only entries of the dirs along the current path are buffered at the same time.
+/// `concurrent` will be ignored in this mode.
 pub struct FlatLister<A: Access, L> {
     acc: A,
 
     next_dir: Option<oio::Entry>,
     active_lister: Vec<(Option<oio::Entry>, L)>,
+
+    /// The tasks to list dirs concurrently, only available if concurrent is larger than 1.
+    tasks: Option<ListDirTasks<A>>,
+    pending_dirs: VecDeque<oio::Entry>,
+    max_pending_dirs: usize,
+    /// The listed dir, it will be output after its entries.
+    ready_dir: Option<oio::Entry>,
+    ready_entries: VecDeque<oio::Entry>,
+    /// The lister to walk dirs that can't be queued.
+    inline_lister: Option<Box<FlatLister<A, L>>>,
+
+    /// The sorted entries of dirs along the current path, only used if sorted is enabled.
+    sorted: bool,
+    sorted_entries: Vec<VecDeque<oio::Entry>>,
 }
 
+/// The tasks to list dirs, output the listed dir and all its entries.
+type ListDirTasks<A> = ConcurrentTasks<(A, oio::Entry), (oio::Entry, Vec<oio::Entry>)>;
+
 /// # Safety
 ///
 /// wasm32 is a special target that we only have one event-loop for this FlatLister.
@@ -80,16 +140,172 @@ where
             acc,
             next_dir: Some(oio::Entry::new(path, Metadata::new(EntryMode::DIR))),
             active_lister: vec![],
+
+            tasks: None,
+            pending_dirs: VecDeque::new(),
+            max_pending_dirs: 0,
+            ready_dir: None,
+            ready_entries: VecDeque::new(),
+            inline_lister: None,
+
+            sorted: false,
+            sorted_entries: vec![],
         }
     }
+
+    /// Output entries in lexicographic order of their paths.
+    pub fn with_sorted(mut self, sorted: bool) -> Self {
+        self.sorted = sorted;
+        self
+    }
+
+    /// Set the number of dirs that can be listed concurrently.
+    ///
+    /// At most `concurrent` dirs will be listed at the same time, and dirs will be
+    /// listed one by one if `concurrent` is less than or equal to 1.
+    pub fn with_concurrent(mut self, concurrent: usize) -> Self {
+        if concurrent <= 1 {
+            self.tasks = None;
+            return self;
+        }
+
+        self.max_pending_dirs = concurrent * 8;
+        let executor = self.acc.info().executor();
+        self.tasks = Some(ConcurrentTasks::new(
+            executor,
+            concurrent,
+            0,
+            |(acc, de): (A, oio::Entry)| {
+                Box::pin(async move {
+                    let res = list_dir(&acc, de.path()).await;
+                    let res = res.map(|entries| (de.clone(), entries));
+                    ((acc, de), res)
+                })
+            },
+        ));
+        self
+    }
 }
 
-impl<A, L> oio::List for FlatLister<A, L>
+impl<A, L> FlatLister<A, L>
 where
     A: Access<Lister = L>,
     L: oio::List,
+{
+    async fn next_sorted(&mut self) -> Result<Option<oio::Entry>> {
+        loop {
+            if let Some(de) = self.next_dir.take() {
+                let mut entries = list_dir(&self.acc, de.path()).await?;
+                // Keep the same behavior with other modes: dirs that returns
+                // nothing will not be output.
+                if entries.is_empty() {
+                    continue;
+                }
+                // should not loop itself again
+                entries.retain(|v| v.path() != de.path());
+                entries.sort_by(|a, b| a.path().cmp(b.path()));
+                self.sorted_entries.push(entries.into());
+                // Dir is always smaller than its children, output it first.
+                return Ok(Some(de));
+            }
+
+            let Some(entries) = self.sorted_entries.last_mut() else {
+                return Ok(None);
+            };
+            match entries.pop_front() {
+                Some(v) if v.mode().is_dir() => self.next_dir = Some(v),
+                Some(v) => return Ok(Some(v)),
+                None => {
+                    self.sorted_entries.pop();
+                }
+            }
+        }
+    }
+}
+
+/// List all entries in given dir.
+async fn list_dir<A: Access>(acc: &A, path: &str) -> Result<Vec<oio::Entry>> {
+    let (_, mut l) = acc.list(path, OpList::new()).await?;
+    let mut entries = vec![];
+    while let Some(de) = oio::List::next(&mut l).await? {
+        entries.push(de);
+    }
+    Ok(entries)
+}
+
+impl<A, L> oio::List for FlatLister<A, L>
+where
+    A: Access<Lister = L> + Clone,
+    L: oio::List,
 {
     async fn next(&mut self) -> Result<Option<oio::Entry>> {
+        if self.sorted {
+            return self.next_sorted().await;
+        }
+
+        if let Some(tasks) = self.tasks.as_mut() {
+            if let Some(de) = self.next_dir.take() {
+                self.pending_dirs.push_back(de);
+            }
+
+            loop {
+                if let Some(lister) = self.inline_lister.as_mut() {
+                    match lister.next_flat().await? {
+                        Some(v) => return Ok(Some(v)),
+                        None => self.inline_lister = None,
+                    }
+                }
+
+                if let Some(v) = self.ready_entries.pop_front() {
+                    if !v.mode().is_dir() {
+                        return Ok(Some(v));
+                    }
+                    if self.pending_dirs.len() < self.max_pending_dirs {
+                        self.pending_dirs.push_back(v);
+                    } else {
+                        self.inline_lister =
+                            Some(Box::new(FlatLister::new(self.acc.clone(), v.path())));
+                    }
+                    continue;
+                }
+                if let Some(de) = self.ready_dir.take() {
+                    return Ok(Some(de));
+                }
+
+                while tasks.has_remaining() {
+                    let Some(de) = self.pending_dirs.pop_front() else {
+                        break;
+                    };
+                    tasks.execute((self.acc.clone(), de)).await?;
+                }
+
+                let Some(res) = tasks.next().await else {
+                    return Ok(None);
+                };
+                let (de, mut entries) = res?;
+                // Keep the same behavior with non-concurrent mode: dirs that
+                // returns nothing will not be output.
+                if entries.is_empty() {
+                    continue;
+                }
+                // should not loop itself again
+                entries.retain(|v| v.path() != de.path());
+                self.ready_entries = entries.into();
+                self.ready_dir = Some(de);
+            }
+        }
+
+        self.next_flat().await
+    }
+}
+
+impl<A, L> FlatLister<A, L>
+where
+    A: Access<Lister = L>,
+    L: oio::List,
+{
+    /// Walk dirs one by one in bottom up way.
+    async fn next_flat(&mut self) -> Result<Option<oio::Entry>> {
         loop {
             if let Some(de) = self.next_dir.take() {
                 let (_, mut l) = self.acc.list(de.path(), OpList::new()).await?;
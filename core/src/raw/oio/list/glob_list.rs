@@ -0,0 +1,191 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+#[cfg(feature = "list-glob")]
+use glob::MatchOptions;
+#[cfg(feature = "list-glob")]
+use glob::Pattern;
+
+use crate::raw::*;
+use crate::*;
+
+/// GlobLister is used to filter entries by a glob pattern.
+///
+/// The pattern is matched against the entry path relative to the listed dir
+/// with the trailing slash of dirs trimmed. For example, listing `data/` with
+/// pattern `**/*.parquet` will return `data/a.parquet` and `data/2024/b.parquet`.
+///
+/// - `*` and `?` never match `/`.
+/// - `**` matches any number of path segments, users should list recursively
+///   to reach nested entries.
+///
+/// Patterns are only supported with the `list-glob` feature enabled, all
+/// entries will be returned as is otherwise.
+pub struct GlobLister<L> {
+    lister: L,
+    #[cfg(feature = "list-glob")]
+    root: String,
+    #[cfg(feature = "list-glob")]
+    pattern: Option<Pattern>,
+}
+
+/// # Safety
+///
+/// We will only take `&mut Self` reference for GlobLister.
+unsafe impl<L> Sync for GlobLister<L> {}
+
+impl<L> GlobLister<L> {
+    /// Create a new glob lister that matches entries relative to `root`.
+    ///
+    /// All entries will be returned if `pattern` is `None`.
+    #[cfg(feature = "list-glob")]
+    pub fn new(lister: L, root: &str, pattern: Option<&str>) -> Result<GlobLister<L>> {
+        let pattern = pattern
+            .map(|v| {
+                Pattern::new(v).map_err(|err| {
+                    Error::new(ErrorKind::ConfigInvalid, "list glob pattern is invalid")
+                        .with_context("pattern", v)
+                        .set_source(err)
+                })
+            })
+            .transpose()?;
+
+        Ok(GlobLister {
+            lister,
+            // Entries under root dir don't have the leading slash.
+            root: root.trim_start_matches('/').to_string(),
+            pattern,
+        })
+    }
+
+    /// Create a new glob lister that matches entries relative to `root`.
+    ///
+    /// Returns an error if `pattern` is given since the `list-glob` feature
+    /// is not enabled.
+    #[cfg(not(feature = "list-glob"))]
+    pub fn new(lister: L, _: &str, pattern: Option<&str>) -> Result<GlobLister<L>> {
+        if let Some(pattern) = pattern {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "list with glob requires the list-glob feature",
+            )
+            .with_context("pattern", pattern));
+        }
+
+        Ok(GlobLister { lister })
+    }
+
+    /// Get the literal prefix of given glob pattern that can be used to narrow
+    /// the list request.
+    pub fn literal_prefix(pattern: &str) -> &str {
+        let idx = pattern.find(['*', '?', '[', ']']).unwrap_or(pattern.len());
+        &pattern[..idx]
+    }
+
+    #[cfg(feature = "list-glob")]
+    fn is_match(&self, path: &str) -> bool {
+        let Some(pattern) = &self.pattern else {
+            return true;
+        };
+        let Some(path) = path.strip_prefix(&self.root) else {
+            return false;
+        };
+        let path = path.trim_end_matches('/');
+        if path.is_empty() {
+            return false;
+        }
+
+        pattern.matches_with(
+            path,
+            MatchOptions {
+                case_sensitive: true,
+                require_literal_separator: true,
+                require_literal_leading_dot: false,
+            },
+        )
+    }
+
+    #[cfg(not(feature = "list-glob"))]
+    fn is_match(&self, _: &str) -> bool {
+        true
+    }
+}
+
+impl<L> oio::List for GlobLister<L>
+where
+    L: oio::List,
+{
+    async fn next(&mut self) -> Result<Option<oio::Entry>> {
+        loop {
+            match self.lister.next().await {
+                Ok(Some(e)) if !self.is_match(e.path()) => continue,
+                v => return v,
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "list-glob"))]
+mod tests {
+    use super::*;
+
+    fn new_lister(pattern: &str) -> GlobLister<()> {
+        GlobLister::new((), "data/", Some(pattern)).expect("pattern must be valid")
+    }
+
+    #[test]
+    fn test_glob_match() {
+        let cases = [
+            ("*.parquet", "data/a.parquet", true),
+            ("*.parquet", "data/2024/a.parquet", false),
+            ("*.parquet", "data/a.csv", false),
+            ("**/*.parquet", "data/a.parquet", true),
+            ("**/*.parquet", "data/2024/01/a.parquet", true),
+            ("2024/**", "data/2024/01/", true),
+            ("2024/*", "data/2024/01/a.parquet", false),
+            ("*", "data/", false),
+            ("*", "other/a.parquet", false),
+        ];
+
+        for (pattern, path, expected) in cases {
+            assert_eq!(
+                new_lister(pattern).is_match(path),
+                expected,
+                "{pattern} {path}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_glob_invalid_pattern() {
+        let err = GlobLister::new((), "data/", Some("[a"))
+            .map(|_| ())
+            .expect_err("pattern must be invalid");
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+    }
+
+    #[test]
+    fn test_glob_literal_prefix() {
+        assert_eq!(
+            GlobLister::<()>::literal_prefix("2024/01/*.parquet"),
+            "2024/01/"
+        );
+        assert_eq!(GlobLister::<()>::literal_prefix("2024/a?c"), "2024/a");
+        assert_eq!(GlobLister::<()>::literal_prefix("**/*.parquet"), "");
+        assert_eq!(GlobLister::<()>::literal_prefix("a.parquet"), "a.parquet");
+    }
+}
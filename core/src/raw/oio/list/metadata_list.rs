@@ -0,0 +1,104 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::raw::*;
+use crate::*;
+
+/// MetadataLister fills the metadata of listed entries by stat them.
+///
+/// Only files will be stat, dirs will be returned directly. Up to `concurrent`
+/// entries will be stat at the same time while the order of entries is kept.
+///
+/// Entries that have been removed before stat will be returned with the
+/// metadata from list.
+pub struct MetadataLister<A: Access, L> {
+    acc: A,
+    inner: L,
+    done: bool,
+
+    /// The tasks to stat entries, entries will be returned directly if it's `None`.
+    tasks: Option<ConcurrentTasks<(A, oio::Entry), oio::Entry>>,
+}
+
+/// # Safety
+///
+/// wasm32 is a special target that we only have one event-loop for this MetadataLister.
+unsafe impl<A: Access, L> Send for MetadataLister<A, L> {}
+/// # Safety
+///
+/// We will only take `&mut Self` reference for MetadataLister.
+unsafe impl<A: Access, L> Sync for MetadataLister<A, L> {}
+
+impl<A: Access, L> MetadataLister<A, L> {
+    /// Create a new metadata lister, entries will be stat only if `enabled` is true.
+    pub fn new(acc: A, inner: L, concurrent: usize, enabled: bool) -> Self {
+        let executor = acc.info().executor();
+        let tasks = enabled.then(|| {
+            ConcurrentTasks::new(executor, concurrent, 0, |(acc, de): (A, oio::Entry)| {
+                Box::pin(async move {
+                    let res = stat_entry(&acc, &de).await;
+                    ((acc, de), res)
+                })
+            })
+        });
+
+        Self {
+            acc,
+            inner,
+            done: false,
+            tasks,
+        }
+    }
+}
+
+/// Stat the entry to fetch its full metadata.
+async fn stat_entry<A: Access>(acc: &A, de: &oio::Entry) -> Result<oio::Entry> {
+    if de.mode().is_dir() {
+        return Ok(de.clone());
+    }
+
+    let mut args = OpStat::new();
+    if let Some(version) = de.metadata().version() {
+        args = args.with_version(version);
+    }
+    match acc.stat(de.path(), args).await {
+        Ok(rp) => Ok(oio::Entry::new(de.path(), rp.into_metadata())),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(de.clone()),
+        Err(err) => Err(err),
+    }
+}
+
+impl<A, L> oio::List for MetadataLister<A, L>
+where
+    A: Access + Clone,
+    L: oio::List,
+{
+    async fn next(&mut self) -> Result<Option<oio::Entry>> {
+        let Some(tasks) = self.tasks.as_mut() else {
+            return self.inner.next().await;
+        };
+
+        while !self.done && tasks.has_remaining() && !tasks.has_result() {
+            match self.inner.next().await? {
+                Some(de) => tasks.execute((self.acc.clone(), de)).await?,
+                None => self.done = true,
+            }
+        }
+
+        tasks.next().await.transpose()
+    }
+}
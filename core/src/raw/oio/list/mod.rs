@@ -27,8 +27,14 @@ pub use page_list::PageLister;
 mod flat_list;
 pub use flat_list::FlatLister;
 
+mod metadata_list;
+pub use metadata_list::MetadataLister;
+
 mod hierarchy_list;
 pub use hierarchy_list::HierarchyLister;
 
 mod prefix_list;
 pub use prefix_list::PrefixLister;
+
+mod glob_list;
+pub use glob_list::GlobLister;
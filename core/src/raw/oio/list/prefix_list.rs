@@ -29,9 +29,13 @@ use crate::*;
 /// ```
 ///
 /// We can use `PrefixLister` to filter entries with prefix `file_`.
+///
+/// Use [`PrefixLister::new_relative`] to get entry paths relative to the prefix,
+/// for example `a` and `b` instead of `file_a` and `file_b`.
 pub struct PrefixLister<L> {
     lister: L,
     prefix: String,
+    relative: bool,
 }
 
 /// # Safety
@@ -45,6 +49,18 @@ impl<L> PrefixLister<L> {
         PrefixLister {
             lister,
             prefix: prefix.to_string(),
+            relative: false,
+        }
+    }
+
+    /// Create a new prefix lister that returns entry paths relative to the prefix.
+    ///
+    /// The entry that equals the prefix will be skipped, and the trailing slash of
+    /// dirs will be preserved.
+    pub fn new_relative(lister: L, prefix: &str) -> PrefixLister<L> {
+        PrefixLister {
+            relative: true,
+            ..Self::new(lister, prefix)
         }
     }
 }
@@ -57,8 +73,79 @@ where
         loop {
             match self.lister.next().await {
                 Ok(Some(e)) if !e.path().starts_with(&self.prefix) => continue,
+                Ok(Some(mut e)) if self.relative => {
+                    let path = &e.path()[self.prefix.len()..];
+                    if path.is_empty() {
+                        continue;
+                    }
+                    let path = path.to_string();
+                    e.set_path(&path);
+                    return Ok(Some(e));
+                }
                 v => return v,
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use super::*;
+
+    struct MockLister(VecDeque<oio::Entry>);
+
+    impl MockLister {
+        fn new(paths: &[&str]) -> Self {
+            Self(
+                paths
+                    .iter()
+                    .map(|p| {
+                        let mode = if p.ends_with('/') {
+                            EntryMode::DIR
+                        } else {
+                            EntryMode::FILE
+                        };
+                        oio::Entry::new(p, Metadata::new(mode))
+                    })
+                    .collect(),
+            )
+        }
+    }
+
+    impl oio::List for MockLister {
+        async fn next(&mut self) -> Result<Option<oio::Entry>> {
+            Ok(self.0.pop_front())
+        }
+    }
+
+    async fn collect(mut l: impl oio::List) -> Vec<String> {
+        let mut paths = vec![];
+        while let Some(e) = l.next().await.unwrap() {
+            paths.push(e.path().to_string());
+        }
+        paths
+    }
+
+    #[tokio::test]
+    async fn test_prefix_lister_keeps_full_path() {
+        let l = MockLister::new(&["dir/", "dir/file_a", "dir/other", "dir/file_b/"]);
+        let paths = collect(PrefixLister::new(l, "dir/file_")).await;
+        assert_eq!(paths, ["dir/file_a", "dir/file_b/"]);
+    }
+
+    #[tokio::test]
+    async fn test_prefix_lister_relative_strips_prefix() {
+        let l = MockLister::new(&["dir/", "dir/a", "dir/sub/", "dir/sub/b"]);
+        let paths = collect(PrefixLister::new_relative(l, "dir/")).await;
+        assert_eq!(paths, ["a", "sub/", "sub/b"]);
+    }
+
+    #[tokio::test]
+    async fn test_prefix_lister_relative_skips_unmatched() {
+        let l = MockLister::new(&["abc", "dir/a", "dirx/b", "di", "dir/c/"]);
+        let paths = collect(PrefixLister::new_relative(l, "dir/")).await;
+        assert_eq!(paths, ["a", "c/"]);
+    }
+}
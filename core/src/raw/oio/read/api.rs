@@ -18,6 +18,7 @@
 use std::mem;
 use std::ops::DerefMut;
 
+use bytes::Buf;
 use bytes::Bytes;
 use futures::Future;
 
@@ -59,6 +60,24 @@ pub trait Read: Unpin + Send + Sync {
             Ok(bufs.into_iter().flatten().collect())
         }
     }
+
+    /// Read data into the given buffer, returns the number of bytes read.
+    ///
+    /// `Ok(0)` means all data has been read or `dst` is empty.
+    ///
+    /// Services should override it to read into `dst` directly to avoid allocations.
+    /// The default implementation returns [`ErrorKind::Unsupported`] without consuming
+    /// any data, so that callers like `CompleteLayer` could fallback to [`Read::read`]
+    /// and keep the data that `dst` can't hold for the next call.
+    fn read_into(&mut self, dst: &mut [u8]) -> impl Future<Output = Result<usize>> + MaybeSend {
+        let _ = dst;
+        async {
+            Err(Error::new(
+                ErrorKind::Unsupported,
+                "reader doesn't support read_into",
+            ))
+        }
+    }
 }
 
 impl Read for () {
@@ -74,12 +93,24 @@ impl Read for Bytes {
     async fn read(&mut self) -> Result<Buffer> {
         Ok(Buffer::from(self.split_off(0)))
     }
+
+    async fn read_into(&mut self, dst: &mut [u8]) -> Result<usize> {
+        let n = self.len().min(dst.len());
+        self.copy_to_slice(&mut dst[..n]);
+        Ok(n)
+    }
 }
 
 impl Read for Buffer {
     async fn read(&mut self) -> Result<Buffer> {
         Ok(mem::take(self))
     }
+
+    async fn read_into(&mut self, dst: &mut [u8]) -> Result<usize> {
+        let n = self.len().min(dst.len());
+        self.copy_to_slice(&mut dst[..n]);
+        Ok(n)
+    }
 }
 
 /// ReadDyn is the dyn version of [`Read`] make it possible to use as
@@ -92,6 +123,9 @@ pub trait ReadDyn: Unpin + Send + Sync {
 
     /// The dyn version of [`Read::read_all`]
     fn read_all_dyn(&mut self) -> BoxedFuture<'_, Result<Buffer>>;
+
+    /// The dyn version of [`Read::read_into`]
+    fn read_into_dyn<'a>(&'a mut self, dst: &'a mut [u8]) -> BoxedFuture<'a, Result<usize>>;
 }
 
 impl<T: Read + ?Sized> ReadDyn for T {
@@ -102,6 +136,10 @@ impl<T: Read + ?Sized> ReadDyn for T {
     fn read_all_dyn(&mut self) -> BoxedFuture<'_, Result<Buffer>> {
         Box::pin(self.read_all())
     }
+
+    fn read_into_dyn<'a>(&'a mut self, dst: &'a mut [u8]) -> BoxedFuture<'a, Result<usize>> {
+        Box::pin(self.read_into(dst))
+    }
 }
 
 /// # NOTE
@@ -116,4 +154,63 @@ impl<T: ReadDyn + ?Sized> Read for Box<T> {
     async fn read_all(&mut self) -> Result<Buffer> {
         self.deref_mut().read_all_dyn().await
     }
+
+    async fn read_into(&mut self, dst: &mut [u8]) -> Result<usize> {
+        self.deref_mut().read_into_dyn(dst).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use super::*;
+    use crate::services;
+
+    /// A reader that only implements `read`.
+    struct ChunkedReader(VecDeque<Buffer>);
+
+    impl Read for ChunkedReader {
+        async fn read(&mut self) -> Result<Buffer> {
+            Ok(self.0.pop_front().unwrap_or_default())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_into() -> Result<()> {
+        // The default implementation doesn't consume any data.
+        let mut r = ChunkedReader(VecDeque::from([Buffer::from("Hello, World!")]));
+        let mut dst = [0; 8];
+        let err = r.read_into(&mut dst).await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        assert_eq!(r.read().await?.to_vec(), b"Hello, World!");
+
+        // Buffer could be read into small buffers.
+        let mut r = Buffer::from("Hello, World!");
+        assert_eq!(r.read_into(&mut dst).await?, 8);
+        assert_eq!(&dst, b"Hello, W");
+        assert_eq!(r.read_into(&mut dst).await?, 5);
+        assert_eq!(&dst[..5], b"orld!");
+        assert_eq!(r.read_into(&mut dst).await?, 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_read_into_with_operator() -> Result<()> {
+        let op = Operator::new(services::Memory::default())?.finish();
+        op.write("test", "Hello, World!").await?;
+
+        let (_, mut r) = op.into_inner().read("test", OpRead::new()).await?;
+        let mut dst = [0; 4];
+        let mut bs = vec![];
+        loop {
+            let n = r.read_into(&mut dst).await?;
+            if n == 0 {
+                break;
+            }
+            bs.extend_from_slice(&dst[..n]);
+        }
+        assert_eq!(bs, b"Hello, World!");
+        Ok(())
+    }
 }
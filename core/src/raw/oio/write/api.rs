@@ -34,6 +34,18 @@ pub trait Write: Unpin + Send + Sync {
     /// - `Err(err)` means error happens and no bytes has been written.
     fn write(&mut self, bs: Buffer) -> impl Future<Output = Result<()>> + MaybeSend;
 
+    /// Flush the written data to the service without closing the writer.
+    ///
+    /// # Behavior
+    ///
+    /// - `Ok(())` means all data sent to the writer before has been accepted by
+    ///   the service, it's not guaranteed to be visible or durable until close.
+    /// - The default implementation does nothing, services that commit data in
+    ///   parts or appends should override it.
+    fn flush(&mut self) -> impl Future<Output = Result<()>> + MaybeSend {
+        async { Ok(()) }
+    }
+
     /// Close the writer and make sure all data has been flushed.
     fn close(&mut self) -> impl Future<Output = Result<Metadata>> + MaybeSend;
 
@@ -64,6 +76,8 @@ impl Write for () {
 pub trait WriteDyn: Unpin + Send + Sync {
     fn write_dyn(&mut self, bs: Buffer) -> BoxedFuture<'_, Result<()>>;
 
+    fn flush_dyn(&mut self) -> BoxedFuture<'_, Result<()>>;
+
     fn close_dyn(&mut self) -> BoxedFuture<'_, Result<Metadata>>;
 
     fn abort_dyn(&mut self) -> BoxedFuture<'_, Result<()>>;
@@ -74,6 +88,10 @@ impl<T: Write + ?Sized> WriteDyn for T {
         Box::pin(self.write(bs))
     }
 
+    fn flush_dyn(&mut self) -> BoxedFuture<'_, Result<()>> {
+        Box::pin(self.flush())
+    }
+
     fn close_dyn(&mut self) -> BoxedFuture<'_, Result<Metadata>> {
         Box::pin(self.close())
     }
@@ -88,6 +106,10 @@ impl<T: WriteDyn + ?Sized> Write for Box<T> {
         self.deref_mut().write_dyn(bs).await
     }
 
+    async fn flush(&mut self) -> Result<()> {
+        self.deref_mut().flush_dyn().await
+    }
+
     async fn close(&mut self) -> Result<Metadata> {
         self.deref_mut().close_dyn().await
     }
@@ -172,7 +172,8 @@ where
     W: BlockWrite,
 {
     async fn write(&mut self, bs: Buffer) -> Result<()> {
-        if !self.started && self.cache.is_none() {
+        // The cache could be empty after flush.
+        if self.cache.is_none() {
             self.fill_cache(bs);
             return Ok(());
         }
@@ -194,6 +195,27 @@ where
         Ok(())
     }
 
+    /// Stage the cached block and wait for all in-flight blocks to be uploaded.
+    async fn flush(&mut self) -> Result<()> {
+        if let Some(cache) = self.cache.clone() {
+            self.started = true;
+            self.tasks
+                .execute(WriteInput {
+                    w: self.w.clone(),
+                    executor: self.executor.clone(),
+                    block_id: Uuid::new_v4(),
+                    bytes: cache,
+                })
+                .await?;
+            self.cache = None;
+        }
+
+        while let Some(block_id) = self.tasks.next().await.transpose()? {
+            self.block_ids.push(block_id);
+        }
+        Ok(())
+    }
+
     async fn close(&mut self) -> Result<Metadata> {
         if !self.started {
             let (size, body) = match self.cache.clone() {
@@ -367,6 +389,57 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_block_writer_with_flush() {
+        let mut rng = thread_rng();
+
+        let mut w = BlockWriter::new(Arc::default(), TestWrite::new(), 8);
+        let mut expected_content = Vec::new();
+
+        for i in 0..100 {
+            let size = rng.gen_range(1..1024);
+            let mut bs = vec![0; size];
+            rng.fill_bytes(&mut bs);
+            expected_content.extend_from_slice(&bs);
+
+            loop {
+                match w.write(bs.clone().into()).await {
+                    Ok(_) => break,
+                    Err(_) => continue,
+                }
+            }
+
+            if i % 10 == 0 {
+                loop {
+                    match w.flush().await {
+                        Ok(_) => break,
+                        Err(_) => continue,
+                    }
+                }
+                assert!(w.cache.is_none(), "cache must be staged after flush");
+                assert_eq!(
+                    w.w.lock().unwrap().length,
+                    expected_content.len() as u64,
+                    "all data must be staged after flush"
+                );
+            }
+        }
+
+        loop {
+            match w.close().await {
+                Ok(_) => break,
+                Err(_) => continue,
+            }
+        }
+
+        let inner = w.w.lock().unwrap();
+        assert_eq!(
+            expected_content,
+            inner.content.clone().unwrap().to_bytes(),
+            "content must be the same"
+        );
+    }
+
     #[tokio::test]
     async fn test_block_writer_with_retry_when_write_once_error() {
         let mut rng = thread_rng();
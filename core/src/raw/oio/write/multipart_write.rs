@@ -130,6 +130,13 @@ struct WriteInput<W: MultipartWrite> {
 
 /// MultipartWriter will implement [`oio::Write`] based on multipart
 /// uploads.
+///
+/// Up to `concurrent` parts will be uploaded at the same time, so the memory
+/// used by the writer is bounded to roughly `(concurrent + 1) * part_size`.
+/// Parts are always completed in the order of part numbers.
+///
+/// The upload will be aborted as soon as a part failed with an error that is
+/// not temporary, and the error will be returned to the caller.
 pub struct MultipartWriter<W: MultipartWrite> {
     w: Arc<W>,
     executor: Executor,
@@ -138,6 +145,7 @@ pub struct MultipartWriter<W: MultipartWrite> {
     parts: Vec<MultipartPart>,
     cache: Option<Buffer>,
     next_part_number: usize,
+    aborted: bool,
 
     tasks: ConcurrentTasks<WriteInput<W>, MultipartPart>,
 }
@@ -157,8 +165,11 @@ impl<W: MultipartWrite> MultipartWriter<W> {
             parts: Vec::new(),
             cache: None,
             next_part_number: 0,
+            aborted: false,
 
-            tasks: ConcurrentTasks::new(executor, concurrent, 8192, |input| {
+            // Completed tasks still hold the part content, so don't buffer
+            // them to keep the memory bounded by `concurrent`.
+            tasks: ConcurrentTasks::new(executor, concurrent, 0, |input| {
                 Box::pin({
                     async move {
                         let fut = input.w.write_part(
@@ -200,6 +211,38 @@ impl<W: MultipartWrite> MultipartWriter<W> {
         self.cache = Some(bs);
         size
     }
+
+    fn check_aborted(&self) -> Result<()> {
+        if self.aborted {
+            return Err(Error::new(
+                ErrorKind::Unexpected,
+                "multipart upload has been aborted because of a failed part",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Abort the upload if the part failed with an error that is not
+    /// temporary, so that the uploaded parts won't be left behind.
+    ///
+    /// Temporary errors will be returned directly since the caller could
+    /// retry them.
+    async fn abort_on_error(&mut self, err: Error) -> Error {
+        if err.is_temporary() {
+            return err;
+        }
+        let Some(upload_id) = self.upload_id.clone() else {
+            return err;
+        };
+
+        self.tasks.clear();
+        self.cache = None;
+        self.aborted = true;
+        match self.w.abort_part(&upload_id).await {
+            Ok(()) => err,
+            Err(abort_err) => err.with_context("abort_error", abort_err),
+        }
+    }
 }
 
 impl<W> oio::Write for MultipartWriter<W>
@@ -207,6 +250,8 @@ where
     W: MultipartWrite,
 {
     async fn write(&mut self, bs: Buffer) -> Result<()> {
+        self.check_aborted()?;
+
         let upload_id = match self.upload_id.clone() {
             Some(v) => v,
             None => {
@@ -226,7 +271,8 @@ where
         let bytes = self.cache.clone().expect("pending write must exist");
         let part_number = self.next_part_number;
 
-        self.tasks
+        if let Err(err) = self
+            .tasks
             .execute(WriteInput {
                 w: self.w.clone(),
                 executor: self.executor.clone(),
@@ -234,14 +280,35 @@ where
                 part_number,
                 bytes,
             })
-            .await?;
+            .await
+        {
+            return Err(self.abort_on_error(err).await);
+        }
         self.cache = None;
         self.next_part_number += 1;
         self.fill_cache(bs);
         Ok(())
     }
 
+    /// Wait for all in-flight parts to be uploaded.
+    ///
+    /// The cached part will be kept since services like s3 require all parts
+    /// except the last one to be larger than the minimum part size.
+    async fn flush(&mut self) -> Result<()> {
+        self.check_aborted()?;
+
+        while let Some(part) = self.tasks.next().await {
+            match part {
+                Ok(part) => self.parts.push(part),
+                Err(err) => return Err(self.abort_on_error(err).await),
+            }
+        }
+        Ok(())
+    }
+
     async fn close(&mut self) -> Result<Metadata> {
+        self.check_aborted()?;
+
         let upload_id = match self.upload_id.clone() {
             Some(v) => v,
             None => {
@@ -261,7 +328,8 @@ where
         if let Some(cache) = self.cache.clone() {
             let part_number = self.next_part_number;
 
-            self.tasks
+            if let Err(err) = self
+                .tasks
                 .execute(WriteInput {
                     w: self.w.clone(),
                     executor: self.executor.clone(),
@@ -269,16 +337,19 @@ where
                     part_number,
                     bytes: cache,
                 })
-                .await?;
+                .await
+            {
+                return Err(self.abort_on_error(err).await);
+            }
             self.cache = None;
             self.next_part_number += 1;
         }
 
-        loop {
-            let Some(result) = self.tasks.next().await.transpose()? else {
-                break;
-            };
-            self.parts.push(result)
+        while let Some(result) = self.tasks.next().await {
+            match result {
+                Ok(part) => self.parts.push(part),
+                Err(err) => return Err(self.abort_on_error(err).await),
+            }
         }
 
         if self.parts.len() != self.next_part_number {
@@ -294,6 +365,10 @@ where
     }
 
     async fn abort(&mut self) -> Result<()> {
+        // The upload has been aborted already.
+        if self.aborted {
+            return Ok(());
+        }
         let Some(upload_id) = self.upload_id.clone() else {
             return Ok(());
         };
@@ -325,6 +400,8 @@ mod tests {
         part_numbers: Vec<usize>,
         length: u64,
         content: Option<Buffer>,
+        fail_part: Option<usize>,
+        aborted: bool,
     }
 
     impl TestWrite {
@@ -334,6 +411,8 @@ mod tests {
                 part_numbers: Vec::new(),
                 length: 0,
                 content: None,
+                fail_part: None,
+                aborted: false,
             };
 
             Arc::new(Mutex::new(v))
@@ -371,6 +450,9 @@ mod tests {
             {
                 let test = self.lock().await;
                 assert_eq!(upload_id, test.upload_id);
+                if test.fail_part == Some(part_number) {
+                    return Err(Error::new(ErrorKind::PermissionDenied, "part is denied"));
+                }
             }
 
             // Add an async sleep here to enforce some pending.
@@ -409,8 +491,9 @@ mod tests {
         }
 
         async fn abort_part(&self, upload_id: &str) -> Result<()> {
-            let test = self.lock().await;
+            let mut test = self.lock().await;
             assert_eq!(upload_id, test.upload_id);
+            test.aborted = true;
 
             Ok(())
         }
@@ -485,6 +568,28 @@ mod tests {
         assert_eq!(actual_size, total_size);
     }
 
+    #[tokio::test]
+    async fn test_multipart_writer_abort_on_part_error() {
+        let test = TestWrite::new();
+        test.lock().await.fail_part = Some(10);
+
+        let mut w = MultipartWriter::new(Arc::default(), test.clone(), 4);
+        let err = loop {
+            match w.write(vec![0; 1024].into()).await {
+                Ok(_) => continue,
+                // Temporary errors could be retried by callers.
+                Err(err) if err.is_temporary() => continue,
+                Err(err) => break err,
+            }
+        };
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        assert!(test.lock().await.aborted, "upload must be aborted");
+
+        assert!(w.write(vec![0; 1024].into()).await.is_err());
+        assert!(w.close().await.is_err());
+        assert!(w.abort().await.is_ok());
+    }
+
     #[tokio::test]
     async fn test_multipart_writer_with_retry_when_write_once_error() {
         let mut rng = thread_rng();
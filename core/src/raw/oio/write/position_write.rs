@@ -149,6 +149,19 @@ impl<W: PositionWrite> oio::Write for PositionWriter<W> {
         Ok(())
     }
 
+    async fn flush(&mut self) -> Result<()> {
+        while self.tasks.next().await.transpose()?.is_some() {}
+
+        if let Some(buffer) = self.cache.clone() {
+            let length = buffer.len() as u64;
+            let offset = self.next_offset;
+            self.w.write_all_at(offset, buffer).await?;
+            self.cache = None;
+            self.next_offset += length;
+        }
+        Ok(())
+    }
+
     async fn close(&mut self) -> Result<Metadata> {
         // Make sure all tasks are finished.
         while self.tasks.next().await.transpose()?.is_some() {}
@@ -49,6 +49,12 @@ pub enum Operation {
     List,
     /// Operation to generate a presigned URL.
     Presign,
+    /// Operation to set tags of a file.
+    SetTags,
+    /// Operation to abort a multipart upload.
+    AbortMultipart,
+    /// Operation to list in-progress multipart uploads.
+    ListMultipartUploads,
 }
 
 impl Operation {
@@ -77,6 +83,9 @@ impl From<Operation> for &'static str {
             Operation::Delete => "delete",
             Operation::List => "list",
             Operation::Presign => "presign",
+            Operation::SetTags => "set_tags",
+            Operation::AbortMultipart => "abort_multipart",
+            Operation::ListMultipartUploads => "list_multipart_uploads",
         }
     }
 }
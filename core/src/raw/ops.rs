@@ -20,13 +20,23 @@
 //! By using ops, users can add more context for operation.
 
 use std::collections::HashMap;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
 use std::time::Duration;
 
 use chrono::DateTime;
 use chrono::Utc;
+use http::HeaderMap;
+use tokio::sync::OnceCell;
 
 use crate::options;
 use crate::raw::*;
+use crate::BufferPool;
+use crate::Encryption;
+use crate::Metadata;
+use crate::Retention;
+use crate::RetentionMode;
 
 /// Args for `create` operation.
 ///
@@ -47,6 +57,9 @@ impl OpCreateDir {
 #[derive(Debug, Clone, Default, Eq, Hash, PartialEq)]
 pub struct OpDelete {
     version: Option<String>,
+    recursive: Option<bool>,
+    must_exist: bool,
+    if_match: Option<String>,
 }
 
 impl OpDelete {
@@ -67,12 +80,51 @@ impl OpDelete {
     pub fn version(&self) -> Option<&str> {
         self.version.as_deref()
     }
+
+    /// Change the recursive flag of this delete operation.
+    pub fn with_recursive(mut self, recursive: bool) -> Self {
+        self.recursive = Some(recursive);
+        self
+    }
+
+    /// Get the recursive flag of this delete operation.
+    ///
+    /// Returns `None` if the caller didn't set it, services should fall back
+    /// to their own default in this case.
+    pub fn recursive(&self) -> Option<bool> {
+        self.recursive
+    }
+
+    /// Change the must_exist flag of this delete operation.
+    pub fn with_must_exist(mut self, must_exist: bool) -> Self {
+        self.must_exist = must_exist;
+        self
+    }
+
+    /// Get the must_exist flag of this delete operation.
+    pub fn must_exist(&self) -> bool {
+        self.must_exist
+    }
+
+    /// Only delete the path if its current etag matches the given one.
+    pub fn with_if_match(mut self, etag: &str) -> Self {
+        self.if_match = Some(etag.into());
+        self
+    }
+
+    /// Get the if_match of this delete operation.
+    pub fn if_match(&self) -> Option<&str> {
+        self.if_match.as_deref()
+    }
 }
 
 impl From<options::DeleteOptions> for OpDelete {
     fn from(value: options::DeleteOptions) -> Self {
         Self {
             version: value.version,
+            recursive: value.recursive,
+            must_exist: value.must_exist,
+            if_match: value.if_match,
         }
     }
 }
@@ -124,6 +176,25 @@ pub struct OpList {
     ///
     /// Default to `false`
     deleted: bool,
+    /// The glob pattern used to filter entries relative to the listed dir.
+    glob: Option<String>,
+    /// The number of dirs that can be listed concurrently while simulating
+    /// recursive list.
+    concurrent: usize,
+    /// Whether only dirs should be returned.
+    dirs_only: bool,
+    /// Whether entries should be returned in lexicographic order.
+    sorted: bool,
+    /// Whether entries should be returned with full metadata.
+    metadata: bool,
+    /// Only files modified after this time should be returned.
+    modified_after: Option<DateTime<Utc>>,
+    /// Only files modified before this time should be returned.
+    modified_before: Option<DateTime<Utc>>,
+    /// The max number of entries to return in total.
+    max_entries: Option<usize>,
+    /// Whether duplicated entries should be skipped.
+    dedup: bool,
 }
 
 impl OpList {
@@ -172,17 +243,18 @@ impl OpList {
 
     /// Change the concurrent of this list operation.
     ///
-    /// The default concurrent is 1.
-    #[deprecated(since = "0.53.2", note = "concurrent in list is no-op")]
-    pub fn with_concurrent(self, concurrent: usize) -> Self {
-        let _ = concurrent;
+    /// The concurrent only takes effect while recursive list is simulated by
+    /// listing dirs one by one. The default concurrent is 1.
+    ///
+    /// This is no longer a no-op since v0.55, see the upgrade guide.
+    pub fn with_concurrent(mut self, concurrent: usize) -> Self {
+        self.concurrent = concurrent.max(1);
         self
     }
 
     /// Get the concurrent of list operation.
-    #[deprecated(since = "0.53.2", note = "concurrent in list is no-op")]
     pub fn concurrent(&self) -> usize {
-        0
+        self.concurrent.max(1)
     }
 
     /// Change the version of this list operation
@@ -219,6 +291,99 @@ impl OpList {
     pub fn deleted(&self) -> bool {
         self.deleted
     }
+
+    /// Change the glob pattern of this list operation.
+    pub fn with_glob(mut self, pattern: &str) -> Self {
+        self.glob = Some(pattern.to_string());
+        self
+    }
+
+    /// Get the glob pattern of this list operation.
+    pub fn glob(&self) -> Option<&str> {
+        self.glob.as_deref()
+    }
+
+    /// Change the dirs_only of this list operation.
+    pub fn with_dirs_only(mut self, dirs_only: bool) -> Self {
+        self.dirs_only = dirs_only;
+        self
+    }
+
+    /// Get the dirs_only of this list operation.
+    pub fn dirs_only(&self) -> bool {
+        self.dirs_only
+    }
+
+    /// Change the sorted of this list operation.
+    pub fn with_sorted(mut self, sorted: bool) -> Self {
+        self.sorted = sorted;
+        self
+    }
+
+    /// Get the sorted of this list operation.
+    pub fn sorted(&self) -> bool {
+        self.sorted
+    }
+
+    /// Change the metadata of this list operation.
+    ///
+    /// If `true`, entries will be returned with full metadata like `content_length`
+    /// and `last_modified`.
+    pub fn with_metadata(mut self, metadata: bool) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Get the metadata of this list operation.
+    pub fn metadata(&self) -> bool {
+        self.metadata
+    }
+
+    /// Only return files whose `last_modified` is after the given time.
+    pub fn with_modified_after(mut self, v: DateTime<Utc>) -> Self {
+        self.modified_after = Some(v);
+        self
+    }
+
+    /// Get the modified_after of this list operation.
+    pub fn modified_after(&self) -> Option<DateTime<Utc>> {
+        self.modified_after
+    }
+
+    /// Only return files whose `last_modified` is before the given time.
+    pub fn with_modified_before(mut self, v: DateTime<Utc>) -> Self {
+        self.modified_before = Some(v);
+        self
+    }
+
+    /// Get the modified_before of this list operation.
+    pub fn modified_before(&self) -> Option<DateTime<Utc>> {
+        self.modified_before
+    }
+
+    /// Stop listing once the given number of entries have been returned.
+    pub fn with_max_entries(mut self, v: usize) -> Self {
+        self.max_entries = Some(v);
+        self
+    }
+
+    /// Get the max_entries of this list operation.
+    pub fn max_entries(&self) -> Option<usize> {
+        self.max_entries
+    }
+
+    /// Change the dedup of this list operation.
+    ///
+    /// If `true`, entries that have been returned already will be skipped.
+    pub fn with_dedup(mut self, dedup: bool) -> Self {
+        self.dedup = dedup;
+        self
+    }
+
+    /// Get the dedup of this list operation.
+    pub fn dedup(&self) -> bool {
+        self.dedup
+    }
 }
 
 impl From<options::ListOptions> for OpList {
@@ -229,6 +394,15 @@ impl From<options::ListOptions> for OpList {
             recursive: value.recursive,
             versions: value.versions,
             deleted: value.deleted,
+            glob: value.glob,
+            concurrent: value.concurrent.max(1),
+            dirs_only: value.dirs_only,
+            sorted: value.sorted,
+            metadata: value.metadata,
+            modified_after: value.modified_after,
+            modified_before: value.modified_before,
+            max_entries: value.max_entries,
+            dedup: value.dedup,
         }
     }
 }
@@ -280,6 +454,25 @@ pub enum PresignOperation {
     Write(OpWrite),
     /// Presign a delete operation.
     Delete(OpDelete),
+    /// Presign a create multipart upload operation.
+    ///
+    /// The returned upload id should be used by `UploadPart` and `CompleteMultipart`.
+    CreateMultipart(OpWrite),
+    /// Presign an upload part operation of given multipart upload.
+    UploadPart {
+        /// The id of the multipart upload.
+        upload_id: String,
+        /// The part number that will be sent to the service as is, for example,
+        /// S3 requires it to be between 1 and 10000.
+        part_number: usize,
+    },
+    /// Presign a complete multipart upload operation.
+    ///
+    /// The uploaded parts should be sent by the client in request body.
+    CompleteMultipart {
+        /// The id of the multipart upload.
+        upload_id: String,
+    },
 }
 
 impl From<OpStat> for PresignOperation {
@@ -318,6 +511,17 @@ pub struct OpRead {
     override_cache_control: Option<String>,
     override_content_disposition: Option<String>,
     version: Option<String>,
+    timeout: Option<Duration>,
+    skip_checksum: bool,
+    suffix_range: Option<u64>,
+    headers: Option<HeaderMap>,
+    max_size: Option<u64>,
+    lenient: bool,
+    ranges: Vec<BytesRange>,
+    read_after_write_retry: Option<(usize, Duration)>,
+    decompress: bool,
+    shared_stat: Option<Arc<OnceCell<Metadata>>>,
+    reader_id: Option<u64>,
 }
 
 impl OpRead {
@@ -430,6 +634,150 @@ impl OpRead {
     pub fn version(&self) -> Option<&str> {
         self.version.as_deref()
     }
+
+    /// Set the timeout of the option.
+    ///
+    /// The timeout will override the io timeout of `TimeoutLayer` for this read.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Get timeout from option
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Set whether to skip checksum verification for this read.
+    ///
+    /// This only takes effect when `ChecksumLayer` is enabled.
+    pub fn with_skip_checksum(mut self, skip_checksum: bool) -> Self {
+        self.skip_checksum = skip_checksum;
+        self
+    }
+
+    /// Get skip_checksum from option
+    pub fn skip_checksum(&self) -> bool {
+        self.skip_checksum
+    }
+
+    /// Set the suffix range to read the last `n` bytes of the file.
+    ///
+    /// Services with `read_with_suffix_range` should send it as `Range: bytes=-n`
+    /// and ignore the `range` of this op.
+    pub fn with_suffix_range(mut self, n: u64) -> Self {
+        self.suffix_range = Some(n);
+        self
+    }
+
+    /// Get suffix_range from option
+    pub fn suffix_range(&self) -> Option<u64> {
+        self.suffix_range
+    }
+
+    /// Set the extra headers to send with the read request.
+    pub fn with_headers(mut self, headers: HeaderMap) -> Self {
+        self.headers = Some(headers);
+        self
+    }
+
+    /// Get the extra headers from option
+    pub fn headers(&self) -> Option<&HeaderMap> {
+        self.headers.as_ref()
+    }
+
+    /// Set the max size of data that can be read.
+    pub fn with_max_size(mut self, n: u64) -> Self {
+        self.max_size = Some(n);
+        self
+    }
+
+    /// Get max_size from option
+    pub fn max_size(&self) -> Option<u64> {
+        self.max_size
+    }
+
+    /// Allow the reader to return less data than the size of range.
+    ///
+    /// This is used while reading a capped range like the first n bytes of
+    /// a file that could be smaller than n.
+    pub fn with_lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    /// Get lenient from option
+    pub fn lenient(&self) -> bool {
+        self.lenient
+    }
+
+    /// Set multiple ranges to read in one request.
+    ///
+    /// Services with `read_with_multi_range` should ignore the `range` of
+    /// this op, and return the content of all ranges concatenated in the
+    /// given order.
+    pub fn with_ranges(mut self, ranges: Vec<BytesRange>) -> Self {
+        self.ranges = ranges;
+        self
+    }
+
+    /// Get ranges from option
+    pub fn ranges(&self) -> &[BytesRange] {
+        &self.ranges
+    }
+
+    /// Retry `NotFound` up to `max` times with `backoff` between attempts.
+    ///
+    /// This is used to read a file right after it has been written on
+    /// eventually consistent services.
+    pub fn with_read_after_write_retry(mut self, max: usize, backoff: Duration) -> Self {
+        self.read_after_write_retry = Some((max, backoff));
+        self
+    }
+
+    /// Get read_after_write_retry from option
+    pub fn read_after_write_retry(&self) -> Option<(usize, Duration)> {
+        self.read_after_write_retry
+    }
+
+    /// Decompress the content-encoded response transparently.
+    pub fn with_decompress(mut self, decompress: bool) -> Self {
+        self.decompress = decompress;
+        self
+    }
+
+    /// Get decompress from option
+    pub fn decompress(&self) -> bool {
+        self.decompress
+    }
+
+    /// Share the stat result among all reads issued by the same reader, and
+    /// assign an unique id to the reader.
+    pub(crate) fn with_shared_stat(mut self) -> Self {
+        static NEXT_READER_ID: AtomicU64 = AtomicU64::new(0);
+
+        self.shared_stat = Some(Arc::default());
+        self.reader_id = Some(NEXT_READER_ID.fetch_add(1, Ordering::Relaxed));
+        self
+    }
+
+    /// Get the stat result shared among all reads issued by the same reader.
+    ///
+    /// Layers that need the metadata of the file for every read can use it to
+    /// stat only once for chunked or concurrent reads.
+    #[allow(dead_code)]
+    pub(crate) fn shared_stat(&self) -> Option<&Arc<OnceCell<Metadata>>> {
+        self.shared_stat.as_ref()
+    }
+
+    /// Get the unique id of the reader that issued this read.
+    ///
+    /// Layers that keep states across reads of the same reader can use it as
+    /// the key.
+    #[allow(dead_code)]
+    pub(crate) fn reader_id(&self) -> Option<u64> {
+        self.reader_id
+    }
 }
 
 /// Args for reader operation.
@@ -520,6 +868,17 @@ impl From<options::ReadOptions> for (OpRead, OpReader) {
                 override_cache_control: value.override_cache_control,
                 override_content_disposition: value.override_content_disposition,
                 version: value.version,
+                timeout: value.timeout,
+                skip_checksum: value.skip_checksum,
+                suffix_range: value.suffix_range,
+                headers: value.headers,
+                max_size: value.max_size,
+                lenient: false,
+                ranges: Vec::new(),
+                read_after_write_retry: value.read_after_write_retry,
+                decompress: value.decompress,
+                shared_stat: None,
+                reader_id: None,
             },
             OpReader {
                 // Ensure concurrent is at least 1
@@ -545,6 +904,17 @@ impl From<options::ReaderOptions> for (OpRead, OpReader) {
                 override_cache_control: None,
                 override_content_disposition: None,
                 version: value.version,
+                timeout: value.timeout,
+                skip_checksum: value.skip_checksum,
+                suffix_range: None,
+                headers: value.headers,
+                max_size: None,
+                lenient: false,
+                ranges: Vec::new(),
+                read_after_write_retry: value.read_after_write_retry,
+                decompress: value.decompress,
+                shared_stat: None,
+                reader_id: None,
             },
             OpReader {
                 // Ensure concurrent is at least 1
@@ -568,6 +938,9 @@ pub struct OpStat {
     override_cache_control: Option<String>,
     override_content_disposition: Option<String>,
     version: Option<String>,
+    tags: bool,
+    headers: Option<HeaderMap>,
+    read_after_write_retry: Option<(usize, Duration)>,
 }
 
 impl OpStat {
@@ -664,6 +1037,42 @@ impl OpStat {
     pub fn version(&self) -> Option<&str> {
         self.version.as_deref()
     }
+
+    /// Set whether to fetch tags of the file.
+    pub fn with_tags(mut self, tags: bool) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    /// Get whether to fetch tags of the file.
+    pub fn tags(&self) -> bool {
+        self.tags
+    }
+
+    /// Set the extra headers to send with the stat request.
+    pub fn with_headers(mut self, headers: HeaderMap) -> Self {
+        self.headers = Some(headers);
+        self
+    }
+
+    /// Get the extra headers from option
+    pub fn headers(&self) -> Option<&HeaderMap> {
+        self.headers.as_ref()
+    }
+
+    /// Retry `NotFound` up to `max` times with `backoff` between attempts.
+    ///
+    /// This is used by reads that need to stat a file right after it has
+    /// been written, see [`OpRead::with_read_after_write_retry`].
+    pub fn with_read_after_write_retry(mut self, max: usize, backoff: Duration) -> Self {
+        self.read_after_write_retry = Some((max, backoff));
+        self
+    }
+
+    /// Get read_after_write_retry from option
+    pub fn read_after_write_retry(&self) -> Option<(usize, Duration)> {
+        self.read_after_write_retry
+    }
 }
 
 impl From<options::StatOptions> for OpStat {
@@ -677,10 +1086,62 @@ impl From<options::StatOptions> for OpStat {
             override_cache_control: value.override_cache_control,
             override_content_disposition: value.override_content_disposition,
             version: value.version,
+            tags: value.tags,
+            headers: value.headers,
+            read_after_write_retry: None,
         }
     }
 }
 
+/// Args for `set_tags` operation.
+#[derive(Debug, Clone, Default)]
+pub struct OpSetTags {
+    tags: HashMap<String, String>,
+}
+
+impl OpSetTags {
+    /// Create a new `OpSetTags` with given tags.
+    pub fn new(tags: HashMap<String, String>) -> Self {
+        Self { tags }
+    }
+
+    /// Get the tags to set.
+    pub fn tags(&self) -> &HashMap<String, String> {
+        &self.tags
+    }
+}
+
+/// Args for `abort_multipart` operation.
+#[derive(Debug, Clone, Default)]
+pub struct OpAbortMultipart {
+    upload_id: String,
+}
+
+impl OpAbortMultipart {
+    /// Create a new `OpAbortMultipart` with given upload id.
+    pub fn new(upload_id: &str) -> Self {
+        Self {
+            upload_id: upload_id.to_string(),
+        }
+    }
+
+    /// Get the id of the multipart upload to abort.
+    pub fn upload_id(&self) -> &str {
+        &self.upload_id
+    }
+}
+
+/// Args for `list_multipart_uploads` operation.
+#[derive(Debug, Clone, Default)]
+pub struct OpListMultipartUploads {}
+
+impl OpListMultipartUploads {
+    /// Create a new `OpListMultipartUploads`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
 /// Args for `write` operation.
 #[derive(Debug, Clone, Default)]
 pub struct OpWrite {
@@ -694,6 +1155,18 @@ pub struct OpWrite {
     if_none_match: Option<String>,
     if_not_exists: bool,
     user_metadata: Option<HashMap<String, String>>,
+    timeout: Option<Duration>,
+    content_type_detection: bool,
+    encryption: Option<Encryption>,
+    replay_buffer: Option<usize>,
+    headers: Option<HeaderMap>,
+    content_length: Option<u64>,
+    verify: bool,
+    mode: Option<u32>,
+    stat_after_close: bool,
+    storage_class: Option<String>,
+    retention: Option<Retention>,
+    legal_hold: Option<bool>,
 }
 
 impl OpWrite {
@@ -821,12 +1294,155 @@ impl OpWrite {
     pub fn user_metadata(&self) -> Option<&HashMap<String, String>> {
         self.user_metadata.as_ref()
     }
+
+    /// Set the extra headers to send with the write requests.
+    pub fn with_headers(mut self, headers: HeaderMap) -> Self {
+        self.headers = Some(headers);
+        self
+    }
+
+    /// Get the extra headers from the op
+    pub fn headers(&self) -> Option<&HeaderMap> {
+        self.headers.as_ref()
+    }
+
+    /// Set the expected size of data to write.
+    pub fn with_content_length(mut self, n: u64) -> Self {
+        self.content_length = Some(n);
+        self
+    }
+
+    /// Get the expected size of data to write from the op
+    pub fn content_length(&self) -> Option<u64> {
+        self.content_length
+    }
+
+    /// Set the timeout of the option.
+    ///
+    /// The timeout will override the io timeout of `TimeoutLayer` for this write.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Get timeout from option
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Set whether to detect content type from the first written buffer.
+    ///
+    /// The detected content type will not override the content type set by users.
+    pub fn with_content_type_detection(mut self, v: bool) -> Self {
+        self.content_type_detection = v;
+        self
+    }
+
+    /// Get content_type_detection from option
+    pub fn content_type_detection(&self) -> bool {
+        self.content_type_detection
+    }
+
+    /// Set the server side encryption of the option.
+    ///
+    /// The encryption will override the default one configured in services.
+    pub fn with_encryption(mut self, encryption: Encryption) -> Self {
+        self.encryption = Some(encryption);
+        self
+    }
+
+    /// Get the server side encryption from option
+    pub fn encryption(&self) -> Option<&Encryption> {
+        self.encryption.as_ref()
+    }
+
+    /// Set the max size of written data to retain for replaying the write.
+    ///
+    /// The retained data will be written again if a retried close finds the
+    /// previous close failed with temporary errors.
+    pub fn with_replay_buffer(mut self, size: usize) -> Self {
+        self.replay_buffer = Some(size);
+        self
+    }
+
+    /// Get replay_buffer from option
+    pub fn replay_buffer(&self) -> Option<usize> {
+        self.replay_buffer
+    }
+
+    /// Set whether to verify the written object by stat after close.
+    pub fn with_verify(mut self, verify: bool) -> Self {
+        self.verify = verify;
+        self
+    }
+
+    /// Get verify from option
+    pub fn verify(&self) -> bool {
+        self.verify
+    }
+
+    /// Set the permission bits of the written file, like `0o755`.
+    pub fn with_mode(mut self, mode: u32) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    /// Get the permission bits of the written file from option
+    pub fn mode(&self) -> Option<u32> {
+        self.mode
+    }
+
+    /// Set whether to stat the written object after close to fill the
+    /// metadata that hasn't been returned by the service.
+    pub fn with_stat_after_close(mut self, v: bool) -> Self {
+        self.stat_after_close = v;
+        self
+    }
+
+    /// Get stat_after_close from option
+    pub fn stat_after_close(&self) -> bool {
+        self.stat_after_close
+    }
+
+    /// Set the storage class of the written object.
+    pub fn with_storage_class(mut self, storage_class: &str) -> Self {
+        self.storage_class = Some(storage_class.to_string());
+        self
+    }
+
+    /// Get the storage class from option
+    pub fn storage_class(&self) -> Option<&str> {
+        self.storage_class.as_deref()
+    }
+
+    /// Set the object lock retention of the written object.
+    pub fn with_retention(mut self, retain_until: DateTime<Utc>, mode: RetentionMode) -> Self {
+        self.retention = Some(Retention { mode, retain_until });
+        self
+    }
+
+    /// Get the object lock retention from option
+    pub fn retention(&self) -> Option<&Retention> {
+        self.retention.as_ref()
+    }
+
+    /// Set the legal hold status of the written object.
+    pub fn with_legal_hold(mut self, legal_hold: bool) -> Self {
+        self.legal_hold = Some(legal_hold);
+        self
+    }
+
+    /// Get the legal hold status from option
+    pub fn legal_hold(&self) -> Option<bool> {
+        self.legal_hold
+    }
 }
 
 /// Args for `writer` operation.
 #[derive(Debug, Clone, Default)]
 pub struct OpWriter {
     chunk: Option<usize>,
+    buffer_pool: Option<BufferPool>,
 }
 
 impl OpWriter {
@@ -855,6 +1471,19 @@ impl OpWriter {
         self.chunk = Some(chunk);
         self
     }
+
+    /// Get the buffer pool from op.
+    pub fn buffer_pool(&self) -> Option<&BufferPool> {
+        self.buffer_pool.as_ref()
+    }
+
+    /// Set the buffer pool of op.
+    ///
+    /// Chunks buffered by the writer will be reserved from the pool.
+    pub fn with_buffer_pool(mut self, pool: BufferPool) -> Self {
+        self.buffer_pool = Some(pool);
+        self
+    }
 }
 
 impl From<options::WriteOptions> for (OpWrite, OpWriter) {
@@ -872,8 +1501,23 @@ impl From<options::WriteOptions> for (OpWrite, OpWriter) {
                 if_none_match: value.if_none_match,
                 if_not_exists: value.if_not_exists,
                 user_metadata: value.user_metadata,
+                timeout: value.timeout,
+                content_type_detection: value.content_type_detection,
+                encryption: value.encryption,
+                replay_buffer: value.replay_buffer,
+                headers: value.headers,
+                content_length: value.content_length,
+                verify: value.verify,
+                mode: value.mode,
+                stat_after_close: value.stat_after_close,
+                storage_class: value.storage_class,
+                retention: value.retention,
+                legal_hold: value.legal_hold,
+            },
+            OpWriter {
+                chunk: value.chunk,
+                buffer_pool: value.buffer_pool,
             },
-            OpWriter { chunk: value.chunk },
         )
     }
 }
@@ -882,6 +1526,11 @@ impl From<options::WriteOptions> for (OpWrite, OpWriter) {
 #[derive(Debug, Clone, Default)]
 pub struct OpCopy {
     if_not_exists: bool,
+    metadata_directive: options::MetadataDirective,
+    content_type: Option<String>,
+    cache_control: Option<String>,
+    user_metadata: Option<HashMap<String, String>>,
+    preserve_mode: bool,
 }
 
 impl OpCopy {
@@ -903,6 +1552,74 @@ impl OpCopy {
     pub fn if_not_exists(&self) -> bool {
         self.if_not_exists
     }
+
+    /// Set the metadata directive for the operation.
+    pub fn with_metadata_directive(mut self, directive: options::MetadataDirective) -> Self {
+        self.metadata_directive = directive;
+        self
+    }
+
+    /// Get the metadata directive.
+    pub fn metadata_directive(&self) -> options::MetadataDirective {
+        self.metadata_directive
+    }
+
+    /// Set the content type of target, only used while replacing metadata.
+    pub fn with_content_type(mut self, content_type: &str) -> Self {
+        self.content_type = Some(content_type.to_string());
+        self
+    }
+
+    /// Get the content type of target.
+    pub fn content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
+
+    /// Set the cache control of target, only used while replacing metadata.
+    pub fn with_cache_control(mut self, cache_control: &str) -> Self {
+        self.cache_control = Some(cache_control.to_string());
+        self
+    }
+
+    /// Get the cache control of target.
+    pub fn cache_control(&self) -> Option<&str> {
+        self.cache_control.as_deref()
+    }
+
+    /// Set the user metadata of target, only used while replacing metadata.
+    pub fn with_user_metadata(mut self, metadata: HashMap<String, String>) -> Self {
+        self.user_metadata = Some(metadata);
+        self
+    }
+
+    /// Get the user metadata of target.
+    pub fn user_metadata(&self) -> Option<&HashMap<String, String>> {
+        self.user_metadata.as_ref()
+    }
+
+    /// Set whether the permission bits of source should be preserved.
+    pub fn with_preserve_mode(mut self, preserve_mode: bool) -> Self {
+        self.preserve_mode = preserve_mode;
+        self
+    }
+
+    /// Get whether the permission bits of source should be preserved.
+    pub fn preserve_mode(&self) -> bool {
+        self.preserve_mode
+    }
+}
+
+impl From<options::CopyOptions> for OpCopy {
+    fn from(value: options::CopyOptions) -> Self {
+        Self {
+            if_not_exists: value.if_not_exists,
+            metadata_directive: value.metadata_directive,
+            content_type: value.content_type,
+            cache_control: value.cache_control,
+            user_metadata: value.user_metadata,
+            preserve_mode: value.preserve_mode,
+        }
+    }
 }
 
 /// Args for `rename` operation.
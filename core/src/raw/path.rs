@@ -94,6 +94,9 @@ pub fn build_rel_path(root: &str, path: &str) -> String {
 /// - All leading / will be trimmed: `///abc` => `abc`
 /// - Internal // will be replaced by /: `abc///def` => `abc/def`
 /// - Empty path will be `/`: `` => `/`
+///
+/// `.` and `..` are kept as is since they are valid object names for most
+/// services. Use [`resolve_dot_segments`] to resolve them.
 pub fn normalize_path(path: &str) -> String {
     // - all whitespace has been trimmed.
     // - all leading `/` has been trimmed.
@@ -117,6 +120,61 @@ pub fn normalize_path(path: &str) -> String {
         p.push('/');
     }
 
+    // Segments could expose leading whitespace after `/` is trimmed, like
+    // `/ abc`, normalize again to keep the result stable.
+    if p.trim().len() != p.len() {
+        return normalize_path(&p);
+    }
+    p
+}
+
+/// Resolve `.` and `..` segments in path like a filesystem does.
+///
+/// The returned path is normalized by the same rules of [`normalize_path`].
+///
+/// # Resolve Rules
+///
+/// - `.` segments will be removed: `./abc/./def` => `abc/def`
+/// - `..` segments will remove the previous segment: `abc/../def` => `def`
+/// - `..` will never go beyond the root: `../abc` => `abc`
+/// - Path ends with `.` or `..` is a dir path: `abc/def/..` => `abc/`
+///
+/// Resolving a resolved path will always return the same path.
+pub fn resolve_dot_segments(path: &str) -> String {
+    let path = path.trim().trim_start_matches('/');
+
+    if path.is_empty() {
+        return "/".to_string();
+    }
+
+    let mut segments = Vec::new();
+    let mut is_dir = false;
+    for seg in path.split('/') {
+        // Path endswith `/`, `.` or `..` must be a dir.
+        is_dir = matches!(seg, "" | "." | "..");
+        match seg {
+            "" | "." => {}
+            ".." => {
+                segments.pop();
+            }
+            v => segments.push(v),
+        }
+    }
+
+    if segments.is_empty() {
+        return "/".to_string();
+    }
+
+    let mut p = segments.join("/");
+    if is_dir {
+        p.push('/');
+    }
+
+    // Segments could expose leading or trailing whitespace after `..` is
+    // resolved, resolve again to keep the result stable.
+    if p.trim().len() != p.len() {
+        return resolve_dot_segments(&p);
+    }
     p
 }
 
@@ -211,6 +269,18 @@ pub fn get_parent(path: &str) -> &str {
     }
 }
 
+/// Get the dir that contains given path.
+///
+/// - Dir path will be returned as is: `abc/def/` => `abc/def/`
+/// - File path will return its parent: `abc/def` => `abc/`
+pub fn get_dir(path: &str) -> &str {
+    if path.ends_with('/') {
+        path
+    } else {
+        get_parent(path)
+    }
+}
+
 // Sets the size of random generated postfix for random file names
 const RANDOM_TMP_PATH_POSTFIX_LENGTH: usize = 8;
 // Allowed characters for choices in a random-generated char
@@ -285,6 +355,8 @@ mod tests {
             ("file path contains ///", "abc///def", "abc/def"),
             ("dir path contains ///", "abc///def///", "abc/def/"),
             ("file with whitespace", "abc/def   ", "abc/def"),
+            ("abs file with whitespace", "/ abc", "abc"),
+            ("file path contains ..", "abc/../def", "abc/../def"),
         ];
 
         for (name, input, expect) in cases {
@@ -292,6 +364,67 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_resolve_dot_segments() {
+        let cases = vec![
+            ("file path", "abc", "abc"),
+            ("dir path", "abc/", "abc/"),
+            ("empty path", "", "/"),
+            ("root path", "/", "/"),
+            ("dir path contains ///", "abc///def///", "abc/def/"),
+            ("file path contains .", "./abc/./def", "abc/def"),
+            ("file path contains ..", "abc/../def", "def"),
+            ("dir path contains ..", "abc/def/../ghi/", "abc/ghi/"),
+            ("path ends with .", "abc/def/.", "abc/def/"),
+            ("path ends with ..", "abc/def/..", "abc/"),
+            ("path beyond root", "../../abc", "abc"),
+            ("root path with ..", "abc/..", "/"),
+            ("file path like ..", "abc/..def/...", "abc/..def/..."),
+            ("whitespace exposed by ..", "abc/../ def", "def"),
+        ];
+
+        for (name, input, expect) in cases {
+            assert_eq!(resolve_dot_segments(input), expect, "{name}")
+        }
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_normalize_path_idempotent(path in "[a./ ]{0,16}") {
+            let p = normalize_path(&path);
+            proptest::prop_assert_eq!(normalize_path(&p), p);
+        }
+
+        #[test]
+        fn test_resolve_dot_segments_idempotent(path in "[a./ ]{0,16}") {
+            let p = resolve_dot_segments(&path);
+            proptest::prop_assert_eq!(resolve_dot_segments(&p), p.clone());
+            proptest::prop_assert_eq!(normalize_path(&p), p);
+        }
+
+        #[test]
+        fn test_resolve_dot_segments_never_escapes_root(path in "[a./ ]{0,16}") {
+            let p = resolve_dot_segments(&path);
+            proptest::prop_assert!(p == "/" || !p.starts_with('/'));
+            proptest::prop_assert!(!p.split('/').any(|v| v == "." || v == ".."));
+        }
+    }
+
+    #[test]
+    fn test_get_dir() {
+        let cases = vec![
+            ("root path", "/", "/"),
+            ("file path", "abc", "/"),
+            ("dir path", "abc/", "abc/"),
+            ("nested file path", "abc/def", "abc/"),
+            ("nested dir path", "abc/def/", "abc/def/"),
+        ];
+
+        for (name, input, expect) in cases {
+            assert_eq!(get_dir(input), expect, "{name}")
+        }
+    }
+
     #[test]
     fn test_normalize_root() {
         let cases = vec![
@@ -0,0 +1,103 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use md5::Digest;
+
+/// The query params that may carry credentials, compared case-insensitively.
+const CREDENTIAL_QUERY_PARAMS: &[&str] = &[
+    "x-amz-credential",
+    "x-amz-security-token",
+    "x-amz-signature",
+    "x-goog-credential",
+    "x-goog-signature",
+    "x-oss-credential",
+    "x-oss-signature",
+    "ossaccesskeyid",
+    "signature",
+    "sig",
+    "q-signature",
+    "token",
+    "access_token",
+    "api_key",
+];
+
+/// Replace the values of known credential-bearing query params in path with `REDACTED`.
+///
+/// This is the default path redactor of [`LoggingLayer`](crate::layers::LoggingLayer).
+pub fn redact_path_credentials(path: &str) -> String {
+    let Some((base, query)) = path.split_once('?') else {
+        return path.to_string();
+    };
+
+    let query = query
+        .split('&')
+        .map(|pair| match pair.split_once('=') {
+            Some((k, _)) if CREDENTIAL_QUERY_PARAMS.contains(&k.to_lowercase().as_str()) => {
+                format!("{k}=REDACTED")
+            }
+            _ => pair.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("&");
+    format!("{base}?{query}")
+}
+
+/// Replace every component of path with its hash while keeping the structure.
+///
+/// For example, `abc/def/` will be redacted as `900150983cd24fb0/4ed9407630eb1000/`.
+pub fn hash_path_components(path: &str) -> String {
+    path.split('/')
+        .map(|v| {
+            if v.is_empty() {
+                return String::new();
+            }
+            let digest = md5::Md5::digest(v.as_bytes());
+            digest[..8].iter().map(|b| format!("{b:02x}")).collect()
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_path_credentials() {
+        let cases = vec![
+            ("plain path", "abc/def", "abc/def"),
+            (
+                "path with credentials",
+                "abc?X-Amz-Signature=secret&part=1&sig=secret",
+                "abc?X-Amz-Signature=REDACTED&part=1&sig=REDACTED",
+            ),
+        ];
+
+        for (name, input, expect) in cases {
+            assert_eq!(redact_path_credentials(input), expect, "{name}")
+        }
+    }
+
+    #[test]
+    fn test_hash_path_components() {
+        assert_eq!(
+            hash_path_components("abc/def/"),
+            "900150983cd24fb0/4ed9407630eb1000/"
+        );
+        assert_eq!(hash_path_components("/"), "/");
+    }
+}
@@ -116,6 +116,11 @@ pub struct RpRead {
     /// It's ok to leave range as empty, but it's recommended to set range if possible. We will use
     /// this range as hint to do some optimization like avoid an extra stat or read.
     range: Option<BytesContentRange>,
+    /// Metadata is the metadata of the whole file returned along with the read.
+    ///
+    /// It's ok to leave metadata as empty, services that return full metadata
+    /// in read responses could set it to save an extra stat.
+    metadata: Option<Metadata>,
 }
 
 impl RpRead {
@@ -151,6 +156,17 @@ impl RpRead {
         self.range = range;
         self
     }
+
+    /// Got the metadata of the whole file returned by this read operation.
+    pub fn metadata(&self) -> Option<&Metadata> {
+        self.metadata.as_ref()
+    }
+
+    /// Set the metadata of the whole file returned by this read operation.
+    pub fn with_metadata(mut self, metadata: Option<Metadata>) -> Self {
+        self.metadata = metadata;
+        self
+    }
 }
 
 /// Reply for `stat` operation.
@@ -210,6 +226,46 @@ impl RpRename {
     }
 }
 
+/// Reply for `set_tags` operation.
+#[derive(Debug, Clone, Default)]
+pub struct RpSetTags {}
+
+impl RpSetTags {
+    /// Create a new reply for `set_tags`.
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+/// Reply for `abort_multipart` operation.
+#[derive(Debug, Clone, Default)]
+pub struct RpAbortMultipart {}
+
+impl RpAbortMultipart {
+    /// Create a new reply for `abort_multipart`.
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+/// Reply for `list_multipart_uploads` operation.
+#[derive(Debug, Clone, Default)]
+pub struct RpListMultipartUploads {
+    uploads: Vec<MultipartUpload>,
+}
+
+impl RpListMultipartUploads {
+    /// Create a new reply for `list_multipart_uploads`.
+    pub fn new(uploads: Vec<MultipartUpload>) -> Self {
+        Self { uploads }
+    }
+
+    /// Consume reply to get the in-progress multipart uploads.
+    pub fn into_uploads(self) -> Vec<MultipartUpload> {
+        self.uploads
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use anyhow::Result;
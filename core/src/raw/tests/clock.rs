@@ -0,0 +1,119 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use futures::channel::oneshot;
+
+use crate::raw::*;
+
+#[derive(Debug)]
+struct MockClockState {
+    now: Instant,
+    /// The pending sleeps with their deadlines.
+    sleeps: Vec<(Instant, oneshot::Sender<()>)>,
+}
+
+/// MockClock is a [`Clock`] that only moves forward while being advanced.
+///
+/// All clones of `MockClock` share the same time, so it can be advanced after
+/// being injected into layers. Sleeps will complete once the clock has been
+/// advanced past their deadlines.
+///
+/// # Examples
+///
+/// ```
+/// # use std::time::Duration;
+/// # use opendal::layers::TimeoutLayer;
+/// # use opendal::raw::tests::MockClock;
+/// # use opendal::raw::*;
+/// # use opendal::*;
+/// # fn test() -> Result<()> {
+/// let clock = MockClock::new();
+/// let _ = Operator::new(services::Memory::default())?
+///     .layer(TimeoutLayer::new().with_clock(clock.clone()))
+///     .finish();
+///
+/// let start = clock.now();
+/// // Operations will timeout once the clock is advanced past the timeout.
+/// clock.advance(Duration::from_secs(60));
+/// assert_eq!(clock.now() - start, Duration::from_secs(60));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct MockClock {
+    state: Arc<Mutex<MockClockState>>,
+}
+
+impl Default for MockClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MockClock {
+    /// Create a new `MockClock` starting at the current instant.
+    pub fn new() -> Self {
+        Self {
+            state: Arc::new(Mutex::new(MockClockState {
+                now: Instant::now(),
+                sleeps: Vec::new(),
+            })),
+        }
+    }
+
+    /// Advance the clock by the given duration and wake up the sleeps that
+    /// have reached their deadlines.
+    pub fn advance(&self, dur: Duration) {
+        let mut state = self.state.lock().unwrap();
+        state.now += dur;
+
+        let now = state.now;
+        let (ready, pending) = state
+            .sleeps
+            .drain(..)
+            .partition::<Vec<_>, _>(|(deadline, _)| *deadline <= now);
+        state.sleeps = pending;
+        for (_, tx) in ready {
+            let _ = tx.send(());
+        }
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> Instant {
+        self.state.lock().unwrap().now
+    }
+
+    fn sleep(&self, dur: Duration) -> BoxedStaticFuture<()> {
+        if dur.is_zero() {
+            return Box::pin(futures::future::ready(()));
+        }
+
+        let mut state = self.state.lock().unwrap();
+        let (tx, rx) = oneshot::channel();
+        let deadline = state.now + dur;
+        state.sleeps.push((deadline, tx));
+        Box::pin(async move {
+            let _ = rx.await;
+        })
+    }
+}
@@ -0,0 +1,651 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use md5::Digest;
+
+use crate::raw::*;
+use crate::*;
+
+type ErrorFn = Box<dyn Fn() -> Error + Send + Sync>;
+type CorruptFn = Box<dyn Fn(Buffer) -> Buffer + Send + Sync>;
+
+/// A fault that will be injected into the operation on given path.
+struct Fault {
+    op: Operation,
+    path: String,
+    /// The remaining times this fault will be injected.
+    times: usize,
+    error: ErrorFn,
+}
+
+#[derive(Default)]
+struct MockState {
+    /// Files are stored with their path, dirs are stored with a trailing `/`
+    /// and empty content.
+    entries: BTreeMap<String, Buffer>,
+    faults: Vec<Fault>,
+    stats: HashMap<String, Metadata>,
+    /// The content returned while reading with `decompress`.
+    decompressed: HashMap<String, Buffer>,
+    lists: HashMap<String, Vec<oio::Entry>>,
+    corruptions: HashMap<(Operation, String), CorruptFn>,
+    /// The number of calls of each operation on each path.
+    calls: HashMap<(Operation, String), usize>,
+    /// The args of every list call.
+    list_args: Vec<(String, OpList)>,
+    /// The size of every buffer written to writers, by path.
+    parts: HashMap<String, Vec<usize>>,
+    read_metadata: bool,
+    latency: Option<(Arc<dyn Clock>, Duration)>,
+    transfer_latency: Option<(Arc<dyn Clock>, Duration)>,
+}
+
+/// MockAccess is an in-memory [`Access`] that allows injecting faults per path.
+///
+/// It's designed for testing layers deterministically without real services.
+/// All clones of `MockAccess` share the same state, so faults can be injected
+/// after it has been turned into an [`Operator`] via [`OperatorBuilder`].
+///
+/// # Examples
+///
+/// ```
+/// # use opendal::raw::tests::MockAccess;
+/// # use opendal::raw::*;
+/// # use opendal::*;
+/// # async fn test() -> Result<()> {
+/// let mock = MockAccess::new();
+/// mock.insert("hello", "Hello, World!");
+/// // Return `RateLimited` the first two times `hello` is read.
+/// mock.inject_error(Operation::Read, "hello", 2, || {
+///     Error::new(ErrorKind::RateLimited, "mock error").set_temporary()
+/// });
+///
+/// let op = OperatorBuilder::new(mock.clone()).finish();
+/// assert!(op.read("hello").await.is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct MockAccess {
+    info: Arc<AccessorInfo>,
+    state: Arc<Mutex<MockState>>,
+}
+
+impl Debug for MockAccess {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MockAccess").finish_non_exhaustive()
+    }
+}
+
+impl Default for MockAccess {
+    fn default() -> Self {
+        let info = AccessorInfo::default();
+        info.set_scheme("mock")
+            .set_root("/")
+            .set_native_capability(Capability {
+                stat: true,
+                read: true,
+                write: true,
+                write_can_empty: true,
+                write_can_multi: true,
+                write_can_append: true,
+                create_dir: true,
+                delete: true,
+                copy: true,
+                rename: true,
+                list: true,
+                list_with_recursive: true,
+                shared: true,
+                ..Default::default()
+            });
+
+        Self {
+            info: info.into(),
+            state: Arc::default(),
+        }
+    }
+}
+
+impl MockAccess {
+    /// Create a new empty `MockAccess`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Update the native capability of this `MockAccess`.
+    ///
+    /// It must be called before building operators since layers read the
+    /// capability while being applied.
+    pub fn with_capability(self, f: impl FnOnce(Capability) -> Capability) -> Self {
+        self.info
+            .set_native_capability(f(self.info.native_capability()));
+        self
+    }
+
+    /// Return the metadata of the whole file in `RpRead`, like services that
+    /// carry it in the headers of read responses.
+    pub fn with_read_metadata(self) -> Self {
+        self.state.lock().unwrap().read_metadata = true;
+        self
+    }
+
+    /// Make every operation sleep `latency` on `clock` before it's served,
+    /// so that concurrent operations overlap deterministically with a
+    /// [`MockClock`](super::MockClock).
+    pub fn with_latency(self, clock: impl Clock, latency: Duration) -> Self {
+        self.state.lock().unwrap().latency = Some((Arc::new(clock), latency));
+        self
+    }
+
+    /// Make every reader sleep `latency` on `clock` before returning its
+    /// content, like transferring the body of responses.
+    pub fn with_transfer_latency(self, clock: impl Clock, latency: Duration) -> Self {
+        self.state.lock().unwrap().transfer_latency = Some((Arc::new(clock), latency));
+        self
+    }
+
+    /// Insert a file with given content.
+    pub fn insert(&self, path: &str, content: impl Into<Buffer>) {
+        let mut state = self.state.lock().unwrap();
+        state.entries.insert(path.to_string(), content.into());
+    }
+
+    /// Inject an error returned by `err` into the next `times` calls of
+    /// `op` on `path`. The operation will succeed after that.
+    ///
+    /// For `Write`, the error is returned while closing the writer and the
+    /// written content is lost, like streaming uploads. For `Delete`, the
+    /// error is returned while flushing the deleted path.
+    pub fn inject_error(
+        &self,
+        op: Operation,
+        path: &str,
+        times: usize,
+        err: impl Fn() -> Error + Send + Sync + 'static,
+    ) {
+        let mut state = self.state.lock().unwrap();
+        state.faults.push(Fault {
+            op,
+            path: path.to_string(),
+            times,
+            error: Box::new(err),
+        });
+    }
+
+    /// Make `stat` on `path` return given metadata regardless of the stored
+    /// entries, for example, returning a file while a dir is expected.
+    pub fn inject_stat(&self, path: &str, meta: Metadata) {
+        let mut state = self.state.lock().unwrap();
+        state.stats.insert(path.to_string(), meta);
+    }
+
+    /// Make `list` on `path` return given entries regardless of the stored
+    /// entries, for example, unsorted or duplicated entries.
+    pub fn inject_list(&self, path: &str, entries: Vec<oio::Entry>) {
+        let mut state = self.state.lock().unwrap();
+        state.lists.insert(path.to_string(), entries);
+    }
+
+    /// Corrupt the content on `path` silently by `f`.
+    ///
+    /// For `Read`, it's applied to the content returned by reads. For `Write`,
+    /// it's applied to the content stored while closing writers.
+    pub fn inject_corruption(
+        &self,
+        op: Operation,
+        path: &str,
+        f: impl Fn(Buffer) -> Buffer + Send + Sync + 'static,
+    ) {
+        let mut state = self.state.lock().unwrap();
+        state
+            .corruptions
+            .insert((op, path.to_string()), Box::new(f));
+    }
+
+    /// Returns the number of calls of `op` on `path`, including the failed ones.
+    pub fn calls(&self, op: Operation, path: &str) -> usize {
+        let state = self.state.lock().unwrap();
+        state
+            .calls
+            .get(&(op, path.to_string()))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Returns the args of every `list` call on `path`.
+    pub fn list_args(&self, path: &str) -> Vec<OpList> {
+        let state = self.state.lock().unwrap();
+        state
+            .list_args
+            .iter()
+            .filter(|(p, _)| p == path)
+            .map(|(_, args)| args.clone())
+            .collect()
+    }
+
+    /// Take the size of every buffer written to writers on `path` since the
+    /// last call.
+    pub fn take_written_parts(&self, path: &str) -> Vec<usize> {
+        let mut state = self.state.lock().unwrap();
+        state.parts.remove(path).unwrap_or_default()
+    }
+
+    /// Make reads on `path` with `decompress` return given content instead
+    /// of the stored one.
+    pub fn insert_decompressed(&self, path: &str, content: impl Into<Buffer>) {
+        let mut state = self.state.lock().unwrap();
+        state.decompressed.insert(path.to_string(), content.into());
+    }
+
+    /// Check if there is a fault for given operation and path.
+    fn check(&self, op: Operation, path: &str) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        *state.calls.entry((op, path.to_string())).or_default() += 1;
+        let Some(fault) = state
+            .faults
+            .iter_mut()
+            .find(|f| f.op == op && f.path == path && f.times > 0)
+        else {
+            return Ok(());
+        };
+
+        fault.times -= 1;
+        Err((fault.error)())
+    }
+
+    /// Wait for the latency before serving the operation if required.
+    async fn pending(&self) {
+        let sleep = match &self.state.lock().unwrap().latency {
+            Some((clock, latency)) => clock.sleep(*latency),
+            None => return,
+        };
+        sleep.await
+    }
+
+    fn corrupt(&self, op: Operation, path: &str, bs: Buffer) -> Buffer {
+        let state = self.state.lock().unwrap();
+        match state.corruptions.get(&(op, path.to_string())) {
+            Some(f) => f(bs),
+            None => bs,
+        }
+    }
+
+    fn get(&self, path: &str) -> Option<Buffer> {
+        self.state.lock().unwrap().entries.get(path).cloned()
+    }
+
+    /// Build the metadata of a file, the etag is the quoted hex md5 of content.
+    fn file_metadata(bs: &Buffer) -> Metadata {
+        let content = bs.to_bytes();
+        Metadata::new(EntryMode::FILE)
+            .with_content_length(content.len() as u64)
+            .with_content_md5(format_content_md5(&content))
+            .with_etag(format!("\"{:x}\"", md5::Md5::digest(&content)))
+    }
+
+    fn stat_file(&self, path: &str) -> Result<Metadata> {
+        if let Some(meta) = self.state.lock().unwrap().stats.get(path) {
+            return Ok(meta.clone());
+        }
+        match self.get(path) {
+            Some(bs) => Ok(Self::file_metadata(&bs)),
+            None => Err(Error::new(ErrorKind::NotFound, "mock file not found")),
+        }
+    }
+
+    fn is_dir(&self, path: &str) -> bool {
+        let state = self.state.lock().unwrap();
+        path == "/"
+            || state
+                .entries
+                .range(path.to_string()..)
+                .next()
+                .is_some_and(|(k, _)| k.starts_with(path))
+    }
+}
+
+impl Access for MockAccess {
+    type Reader = MockReader;
+    type Writer = MockWriter;
+    type Lister = MockLister;
+    type Deleter = MockDeleter;
+
+    fn info(&self) -> Arc<AccessorInfo> {
+        self.info.clone()
+    }
+
+    async fn create_dir(&self, path: &str, _: OpCreateDir) -> Result<RpCreateDir> {
+        self.pending().await;
+        self.check(Operation::CreateDir, path)?;
+        self.insert(path, Buffer::new());
+        Ok(RpCreateDir::default())
+    }
+
+    async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
+        self.pending().await;
+        self.check(Operation::Stat, path)?;
+
+        if path.ends_with('/') && !self.state.lock().unwrap().stats.contains_key(path) {
+            return if self.is_dir(path) {
+                Ok(RpStat::new(Metadata::new(EntryMode::DIR)))
+            } else {
+                Err(Error::new(ErrorKind::NotFound, "mock dir not found"))
+            };
+        }
+        self.stat_file(path).map(RpStat::new)
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.pending().await;
+        self.check(Operation::Read, path)?;
+
+        let decompressed = match args.decompress() {
+            true => self.state.lock().unwrap().decompressed.get(path).cloned(),
+            false => None,
+        };
+        let bs = decompressed
+            .or_else(|| self.get(path))
+            .ok_or_else(|| Error::new(ErrorKind::NotFound, "mock file not found"))?;
+        let (start, end) = match args.suffix_range() {
+            Some(n) => (bs.len().saturating_sub(n as usize), bs.len()),
+            None => {
+                let range = args.range();
+                let start = (range.offset() as usize).min(bs.len());
+                let end = range
+                    .size()
+                    .map_or(bs.len(), |size| (start + size as usize).min(bs.len()));
+                (start, end)
+            }
+        };
+        let mut rp = RpRead::new();
+        if self.state.lock().unwrap().read_metadata {
+            rp = rp.with_metadata(Some(self.stat_file(path)?));
+        }
+        let bs = self.corrupt(Operation::Read, path, bs.slice(start..end));
+        let latency = self.state.lock().unwrap().transfer_latency.clone();
+        Ok((
+            rp,
+            MockReader {
+                bs: Some(bs),
+                latency,
+            },
+        ))
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.pending().await;
+
+        let mut buf = oio::QueueBuf::new();
+        if args.append() {
+            if let Some(bs) = self.get(path) {
+                buf.push(bs);
+            }
+        }
+        Ok((
+            RpWrite::new(),
+            MockWriter {
+                acc: self.clone(),
+                path: path.to_string(),
+                append: args.append(),
+                buf,
+            },
+        ))
+    }
+
+    async fn delete(&self) -> Result<(RpDelete, Self::Deleter)> {
+        Ok((
+            RpDelete::default(),
+            MockDeleter {
+                acc: self.clone(),
+                queue: VecDeque::new(),
+            },
+        ))
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        self.pending().await;
+        self.check(Operation::List, path)?;
+
+        let mut state = self.state.lock().unwrap();
+        state.list_args.push((path.to_string(), args.clone()));
+        if let Some(entries) = state.lists.get(path) {
+            let entries = entries.iter().cloned().collect();
+            return Ok((RpList::default(), MockLister { entries }));
+        }
+
+        let mut entries = VecDeque::new();
+        for (key, bs) in state.entries.range(path.to_string()..) {
+            if !key.starts_with(path) {
+                break;
+            }
+
+            let entry = if args.recursive() {
+                match key.ends_with('/') {
+                    true => oio::Entry::new(key, Metadata::new(EntryMode::DIR)),
+                    false => oio::Entry::new(key, Self::file_metadata(bs)),
+                }
+            } else {
+                // Collapse the nested entries into their first level dir.
+                match key[path.len()..].find('/') {
+                    Some(idx) => {
+                        oio::Entry::new(&key[..path.len() + idx + 1], Metadata::new(EntryMode::DIR))
+                    }
+                    None => oio::Entry::new(key, Self::file_metadata(bs)),
+                }
+            };
+
+            if entries
+                .back()
+                .is_some_and(|e: &oio::Entry| e.path() == entry.path())
+            {
+                continue;
+            }
+            entries.push_back(entry);
+        }
+
+        Ok((RpList::default(), MockLister { entries }))
+    }
+
+    async fn copy(&self, from: &str, to: &str, _: OpCopy) -> Result<RpCopy> {
+        self.pending().await;
+        self.check(Operation::Copy, from)?;
+
+        let bs = self
+            .get(from)
+            .ok_or_else(|| Error::new(ErrorKind::NotFound, "mock file not found"))?;
+        self.insert(to, bs);
+        Ok(RpCopy::default())
+    }
+
+    async fn rename(&self, from: &str, to: &str, _: OpRename) -> Result<RpRename> {
+        self.pending().await;
+        self.check(Operation::Rename, from)?;
+
+        let mut state = self.state.lock().unwrap();
+        let bs = state
+            .entries
+            .remove(from)
+            .ok_or_else(|| Error::new(ErrorKind::NotFound, "mock file not found"))?;
+        state.entries.insert(to.to_string(), bs);
+        Ok(RpRename::default())
+    }
+}
+
+/// The reader of [`MockAccess`].
+pub struct MockReader {
+    bs: Option<Buffer>,
+    latency: Option<(Arc<dyn Clock>, Duration)>,
+}
+
+impl oio::Read for MockReader {
+    async fn read(&mut self) -> Result<Buffer> {
+        if let Some((clock, latency)) = self.latency.take() {
+            clock.sleep(latency).await;
+        }
+        Ok(self.bs.take().unwrap_or_default())
+    }
+}
+
+/// The writer of [`MockAccess`], content will be visible after `close`.
+///
+/// The size returned by `close` is the size of written data, and it's left
+/// unknown for appends like services that don't return the size of object.
+pub struct MockWriter {
+    acc: MockAccess,
+    path: String,
+    append: bool,
+    buf: oio::QueueBuf,
+}
+
+impl oio::Write for MockWriter {
+    async fn write(&mut self, bs: Buffer) -> Result<()> {
+        let mut state = self.acc.state.lock().unwrap();
+        state
+            .parts
+            .entry(self.path.clone())
+            .or_default()
+            .push(bs.len());
+        drop(state);
+
+        self.buf.push(bs);
+        Ok(())
+    }
+
+    async fn close(&mut self) -> Result<Metadata> {
+        let bs = self.buf.clone().collect();
+        self.buf.clear();
+        self.acc.check(Operation::Write, &self.path)?;
+
+        let mut meta = Metadata::new(EntryMode::FILE);
+        if !self.append {
+            meta.set_content_length(bs.len() as u64);
+        }
+        let bs = self.acc.corrupt(Operation::Write, &self.path, bs);
+        self.acc.insert(&self.path, bs);
+        Ok(meta)
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.buf.clear();
+        Ok(())
+    }
+}
+
+/// The lister of [`MockAccess`].
+pub struct MockLister {
+    entries: VecDeque<oio::Entry>,
+}
+
+impl oio::List for MockLister {
+    async fn next(&mut self) -> Result<Option<oio::Entry>> {
+        Ok(self.entries.pop_front())
+    }
+}
+
+/// The deleter of [`MockAccess`].
+pub struct MockDeleter {
+    acc: MockAccess,
+    queue: VecDeque<String>,
+}
+
+impl oio::Delete for MockDeleter {
+    fn delete(&mut self, path: &str, _: OpDelete) -> Result<()> {
+        self.queue.push_back(path.to_string());
+        Ok(())
+    }
+
+    async fn flush(&mut self) -> Result<usize> {
+        let mut deleted = 0;
+        while let Some(path) = self.queue.front() {
+            self.acc.check(Operation::Delete, path)?;
+            self.acc.state.lock().unwrap().entries.remove(path);
+            self.queue.pop_front();
+            deleted += 1;
+        }
+        Ok(deleted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layers::RetryLayer;
+
+    #[tokio::test]
+    async fn test_mock_access() -> Result<()> {
+        let mock = MockAccess::new();
+        let op = OperatorBuilder::new(mock.clone()).finish();
+
+        op.write("a/b/c", "Hello").await?;
+        op.write("a/d", "World").await?;
+        assert_eq!(op.read("a/d").await?.to_vec(), b"World");
+        assert!(op.stat("a/b/").await?.is_dir());
+
+        let paths: Vec<String> = op
+            .list("a/")
+            .await?
+            .into_iter()
+            .map(|e| e.path().to_string())
+            .collect();
+        assert_eq!(paths, vec!["a/b/", "a/d"]);
+
+        let paths: Vec<String> = op
+            .list_with("a/")
+            .recursive(true)
+            .await?
+            .into_iter()
+            .map(|e| e.path().to_string())
+            .collect();
+        assert_eq!(paths, vec!["a/b/c", "a/d"]);
+
+        mock.inject_stat("a/d", Metadata::new(EntryMode::DIR));
+        assert!(op.stat("a/d").await?.is_dir());
+
+        op.delete("a/b/c").await?;
+        assert!(!op.exists("a/b/c").await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_mock_access_inject_error() -> Result<()> {
+        let mock = MockAccess::new();
+        mock.insert("hello", "Hello, World!");
+        mock.inject_error(Operation::Read, "hello", 2, || {
+            Error::new(ErrorKind::RateLimited, "mock error").set_temporary()
+        });
+
+        let op = OperatorBuilder::new(mock.clone()).finish();
+        let err = op.read("hello").await.expect_err("read must fail");
+        assert_eq!(err.kind(), ErrorKind::RateLimited);
+
+        // The left fault will be consumed by retry.
+        let op = op.layer(RetryLayer::new().with_min_delay(std::time::Duration::ZERO));
+        assert_eq!(op.read("hello").await?.to_vec(), b"Hello, World!");
+
+        Ok(())
+    }
+}
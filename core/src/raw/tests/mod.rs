@@ -25,6 +25,16 @@ mod write;
 pub use write::WriteAction;
 pub use write::WriteChecker;
 
+mod mock;
+pub use mock::MockAccess;
+pub use mock::MockDeleter;
+pub use mock::MockLister;
+pub use mock::MockReader;
+pub use mock::MockWriter;
+
+mod clock;
+pub use clock::MockClock;
+
 mod utils;
 pub use utils::init_test_service;
 pub use utils::TEST_RUNTIME;
@@ -19,6 +19,7 @@ use bytes::Buf;
 use http::Response;
 use serde::Deserialize;
 
+use crate::raw::*;
 use crate::*;
 
 #[derive(Default, Debug, Deserialize)]
@@ -49,6 +50,9 @@ pub(super) fn parse_error(res: Response<Buffer>) -> Error {
         _ => (ErrorKind::Unexpected, false),
     };
     let mut err = Error::new(kind, message);
+
+    err = with_error_response_context(err, parts);
+
     if retryable {
         err = err.set_temporary();
     }
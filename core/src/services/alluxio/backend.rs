@@ -141,6 +141,7 @@ impl Builder for AlluxioBuilder {
 
                             create_dir: true,
                             delete: true,
+                            rename: true,
 
                             list: true,
 
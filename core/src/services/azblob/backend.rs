@@ -18,6 +18,7 @@
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::sync::Arc;
+use std::time::Duration;
 
 use base64::prelude::BASE64_STANDARD;
 use base64::Engine;
@@ -30,6 +31,7 @@ use reqsign::AzureStorageSigner;
 use sha2::Digest;
 use sha2::Sha256;
 
+use super::core::constants::X_MS_COPY_STATUS;
 use super::core::constants::X_MS_META_PREFIX;
 use super::core::constants::X_MS_VERSION_ID;
 use super::core::AzblobCore;
@@ -39,6 +41,7 @@ use super::lister::AzblobLister;
 use super::writer::AzblobWriter;
 use super::writer::AzblobWriters;
 use super::DEFAULT_SCHEME;
+use crate::options::MetadataDirective;
 use crate::raw::*;
 use crate::services::AzblobConfig;
 use crate::*;
@@ -418,6 +421,7 @@ impl Builder for AzblobBuilder {
                             write_with_content_type: true,
                             write_with_if_not_exists: true,
                             write_with_if_none_match: true,
+                            write_with_if_match: true,
                             write_with_user_metadata: true,
 
                             delete: true,
@@ -425,9 +429,12 @@ impl Builder for AzblobBuilder {
 
                             copy: true,
                             copy_with_if_not_exists: true,
+                            copy_with_metadata_directive: true,
 
                             list: true,
                             list_with_recursive: true,
+                            list_with_sorted: true,
+                            list_has_metadata: true,
 
                             presign: self.config.sas_token.is_some(),
                             presign_stat: self.config.sas_token.is_some(),
@@ -549,14 +556,58 @@ impl Access for AzblobBackend {
     }
 
     async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
-        let resp = self.core.azblob_copy_blob(from, to, args).await?;
+        let resp = self.core.azblob_copy_blob(from, to, &args).await?;
 
         let status = resp.status();
+        if status != StatusCode::ACCEPTED {
+            return Err(parse_error(resp));
+        }
+        if args.metadata_directive() != MetadataDirective::Replace {
+            return Ok(RpCopy::default());
+        }
 
-        match status {
-            StatusCode::ACCEPTED => Ok(RpCopy::default()),
-            _ => Err(parse_error(resp)),
+        // Copy Blob can't set properties, so we replace them after the copy
+        // finished and carry over the properties that are not given.
+        let mut backoff = Duration::from_millis(100);
+        let existing = loop {
+            let resp = self
+                .core
+                .azblob_get_blob_properties(to, &OpStat::new())
+                .await?;
+            if resp.status() != StatusCode::OK {
+                return Err(parse_error(resp));
+            }
+            match parse_header_to_str(resp.headers(), X_MS_COPY_STATUS)? {
+                None | Some("success") => break resp.into_parts().0.headers,
+                Some("pending") => {
+                    SystemClock.sleep(backoff).await;
+                    backoff = (backoff * 2).min(Duration::from_secs(1));
+                }
+                Some(v) => {
+                    return Err(
+                        Error::new(ErrorKind::Unexpected, "copy blob is not succeeded")
+                            .with_context("service", Scheme::Azblob)
+                            .with_context("copy_status", v),
+                    )
+                }
+            }
+        };
+        let resp = self
+            .core
+            .azblob_set_blob_properties(to, &args, &existing)
+            .await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp));
+        }
+
+        // Copy Blob copies metadata from source if none is given.
+        if args.user_metadata().is_none_or(|v| v.is_empty()) {
+            let resp = self.core.azblob_clear_blob_metadata(to).await?;
+            if resp.status() != StatusCode::OK {
+                return Err(parse_error(resp));
+            }
         }
+        Ok(RpCopy::default())
     }
 
     async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
@@ -570,7 +621,7 @@ impl Access for AzblobBackend {
                 self.core
                     .azblob_put_blob_request(path, None, &OpWrite::default(), Buffer::new())
             }
-            PresignOperation::Delete(_) => Err(Error::new(
+            _ => Err(Error::new(
                 ErrorKind::Unsupported,
                 "operation is not supported",
             )),
@@ -26,12 +26,16 @@ use base64::Engine;
 use bytes::Bytes;
 use constants::X_MS_META_PREFIX;
 use http::header::HeaderName;
+use http::header::CONTENT_DISPOSITION;
+use http::header::CONTENT_ENCODING;
+use http::header::CONTENT_LANGUAGE;
 use http::header::CONTENT_LENGTH;
 use http::header::CONTENT_TYPE;
 use http::header::IF_MATCH;
 use http::header::IF_MODIFIED_SINCE;
 use http::header::IF_NONE_MATCH;
 use http::header::IF_UNMODIFIED_SINCE;
+use http::HeaderMap;
 use http::HeaderValue;
 use http::Request;
 use http::Response;
@@ -42,6 +46,7 @@ use serde::Deserialize;
 use serde::Serialize;
 use uuid::Uuid;
 
+use crate::options::MetadataDirective;
 use crate::raw::*;
 use crate::*;
 
@@ -51,7 +56,13 @@ pub mod constants {
 
     pub const X_MS_BLOB_TYPE: &str = "x-ms-blob-type";
     pub const X_MS_COPY_SOURCE: &str = "x-ms-copy-source";
+    pub const X_MS_COPY_STATUS: &str = "x-ms-copy-status";
     pub const X_MS_BLOB_CACHE_CONTROL: &str = "x-ms-blob-cache-control";
+    pub const X_MS_BLOB_CONTENT_TYPE: &str = "x-ms-blob-content-type";
+    pub const X_MS_BLOB_CONTENT_ENCODING: &str = "x-ms-blob-content-encoding";
+    pub const X_MS_BLOB_CONTENT_DISPOSITION: &str = "x-ms-blob-content-disposition";
+    pub const X_MS_BLOB_CONTENT_LANGUAGE: &str = "x-ms-blob-content-language";
+    pub const X_MS_BLOB_CONTENT_MD5: &str = "x-ms-blob-content-md5";
     pub const X_MS_BLOB_CONDITION_APPENDPOS: &str = "x-ms-blob-condition-appendpos";
     pub const X_MS_META_PREFIX: &str = "x-ms-meta-";
 
@@ -278,6 +289,10 @@ impl AzblobCore {
             req = req.header(IF_NONE_MATCH, v);
         }
 
+        if let Some(v) = args.if_match() {
+            req = req.header(IF_MATCH, v);
+        }
+
         if let Some(cache_control) = args.cache_control() {
             req = req.header(constants::X_MS_BLOB_CACHE_CONTROL, cache_control);
         }
@@ -493,6 +508,17 @@ impl AzblobCore {
             req = req.header(constants::X_MS_BLOB_CACHE_CONTROL, cache_control);
         }
 
+        // Conditions are checked while committing the block list.
+        if args.if_not_exists() {
+            req = req.header(IF_NONE_MATCH, "*");
+        }
+        if let Some(v) = args.if_none_match() {
+            req = req.header(IF_NONE_MATCH, v);
+        }
+        if let Some(v) = args.if_match() {
+            req = req.header(IF_MATCH, v);
+        }
+
         let content = quick_xml::se::to_string(&PutBlockListRequest {
             latest: block_ids
                 .into_iter()
@@ -579,7 +605,7 @@ impl AzblobCore {
         &self,
         from: &str,
         to: &str,
-        args: OpCopy,
+        args: &OpCopy,
     ) -> Result<Response<Buffer>> {
         let source = self.build_path_url(from);
         let target = self.build_path_url(to);
@@ -593,6 +619,15 @@ impl AzblobCore {
             req = req.header(IF_NONE_MATCH, "*");
         }
 
+        // Copy Blob replaces the user metadata of target if any is given.
+        if args.metadata_directive() == MetadataDirective::Replace {
+            if let Some(user_metadata) = args.user_metadata() {
+                for (key, value) in user_metadata {
+                    req = req.header(format!("{X_MS_META_PREFIX}{key}"), value)
+                }
+            }
+        }
+
         let mut req = req
             .extension(Operation::Copy)
             .body(Buffer::new())
@@ -602,6 +637,65 @@ impl AzblobCore {
         self.send(req).await
     }
 
+    /// Replace the properties of blob, properties that are not set will be cleared.
+    ///
+    /// Properties that can't be given by [`OpCopy`] are carried over from the
+    /// `existing` headers returned by Get Blob Properties.
+    pub async fn azblob_set_blob_properties(
+        &self,
+        path: &str,
+        args: &OpCopy,
+        existing: &HeaderMap,
+    ) -> Result<Response<Buffer>> {
+        let url = format!("{}?comp=properties", self.build_path_url(path));
+
+        let mut req = Request::put(&url).header(CONTENT_LENGTH, 0);
+        if let Some(v) = args.content_type() {
+            req = req.header(constants::X_MS_BLOB_CONTENT_TYPE, v);
+        }
+        if let Some(v) = args.cache_control() {
+            req = req.header(constants::X_MS_BLOB_CACHE_CONTROL, v);
+        }
+        for (name, header) in [
+            (CONTENT_ENCODING, constants::X_MS_BLOB_CONTENT_ENCODING),
+            (
+                CONTENT_DISPOSITION,
+                constants::X_MS_BLOB_CONTENT_DISPOSITION,
+            ),
+            (CONTENT_LANGUAGE, constants::X_MS_BLOB_CONTENT_LANGUAGE),
+            (
+                HeaderName::from_static("content-md5"),
+                constants::X_MS_BLOB_CONTENT_MD5,
+            ),
+        ] {
+            if let Some(v) = existing.get(name) {
+                req = req.header(header, v);
+            }
+        }
+
+        let mut req = req
+            .extension(Operation::Copy)
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
+    /// Clear the user metadata of blob.
+    pub async fn azblob_clear_blob_metadata(&self, path: &str) -> Result<Response<Buffer>> {
+        let url = format!("{}?comp=metadata", self.build_path_url(path));
+
+        let mut req = Request::put(&url)
+            .header(CONTENT_LENGTH, 0)
+            .extension(Operation::Copy)
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
     pub async fn azblob_list_blobs(
         &self,
         path: &str,
@@ -18,6 +18,7 @@
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::sync::Arc;
+use std::time::Duration;
 
 use http::Request;
 use http::Response;
@@ -226,6 +227,8 @@ impl Builder for B2Builder {
                             presign: true,
                             presign_read: true,
                             presign_write: true,
+                            // Download authorizations are valid for at most 7 days.
+                            presign_max_expiry: Some(Duration::from_secs(7 * 24 * 60 * 60)),
                             presign_stat: true,
 
                             shared: true,
@@ -425,7 +428,7 @@ impl Access for B2Backend {
                     parts.headers,
                 )))
             }
-            PresignOperation::Delete(_) => Err(Error::new(
+            _ => Err(Error::new(
                 ErrorKind::Unsupported,
                 "operation is not supported",
             )),
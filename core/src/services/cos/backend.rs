@@ -422,7 +422,7 @@ impl Access for CosBackend {
                 self.core
                     .cos_put_object_request(path, None, v, Buffer::new())
             }
-            PresignOperation::Delete(_) => Err(Error::new(
+            _ => Err(Error::new(
                 ErrorKind::Unsupported,
                 "operation is not supported",
             )),
@@ -131,7 +131,7 @@ impl DashmapAccessor {
 impl Access for DashmapAccessor {
     type Reader = Buffer;
     type Writer = DashmapWriter;
-    type Lister = oio::HierarchyLister<DashmapLister>;
+    type Lister = oio::HierarchyLister<oio::PrefixLister<DashmapLister>>;
     type Deleter = oio::OneShotDeleter<DashmapDeleter>;
 
     fn info(&self) -> Arc<AccessorInfo> {
@@ -196,7 +196,8 @@ impl Access for DashmapAccessor {
     }
 
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
-        let lister = DashmapLister::new(self.core.clone(), self.root.clone(), path.to_string());
+        let lister = DashmapLister::new(self.core.clone(), &self.root, path);
+        let lister = oio::PrefixLister::new_relative(lister, &self.root[1..]);
         let lister = oio::HierarchyLister::new(lister, path, args.recursive());
         Ok((RpList::default(), lister))
     }
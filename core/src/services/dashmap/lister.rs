@@ -17,24 +17,24 @@
 
 use super::core::DashmapCore;
 use crate::raw::oio::Entry;
-use crate::raw::{build_abs_path, build_rel_path, oio};
+use crate::raw::{build_abs_path, oio};
 use crate::*;
 use std::sync::Arc;
 use std::vec::IntoIter;
 
+/// DashmapLister yields the absolute keys under `path`, callers are expected to
+/// strip the root via [`oio::PrefixLister::new_relative`].
 pub struct DashmapLister {
-    root: String,
     path: String,
     iter: IntoIter<String>,
 }
 
 impl DashmapLister {
-    pub fn new(core: Arc<DashmapCore>, root: String, path: String) -> Self {
+    pub fn new(core: Arc<DashmapCore>, root: &str, path: &str) -> Self {
         let entries: Vec<_> = core.cache.iter().map(|item| item.key().clone()).collect();
-        let path = build_abs_path(&root, &path);
+        let path = build_abs_path(root, path);
 
         Self {
-            root,
             path,
             iter: entries.into_iter(),
         }
@@ -45,15 +45,13 @@ impl oio::List for DashmapLister {
     async fn next(&mut self) -> Result<Option<Entry>> {
         for key in self.iter.by_ref() {
             if key.starts_with(&self.path) {
-                let path = build_rel_path(&self.root, &key);
-
                 // Determine if it's a file or directory based on trailing slash
                 let mode = if key.ends_with('/') {
                     EntryMode::DIR
                 } else {
                     EntryMode::FILE
                 };
-                let entry = Entry::new(&path, Metadata::new(mode));
+                let entry = Entry::new(&key, Metadata::new(mode));
                 return Ok(Some(entry));
             }
         }
@@ -143,8 +143,8 @@ pub struct DbfsBackend {
 
 impl Access for DbfsBackend {
     type Reader = ();
-    type Writer = oio::OneShotWriter<DbfsWriter>;
-    type Lister = oio::PageLister<DbfsLister>;
+    type Writer = DbfsWriter;
+    type Lister = DbfsLister;
     type Deleter = oio::OneShotDeleter<DbfsDeleter>;
 
     fn info(&self) -> Arc<AccessorInfo> {
@@ -155,11 +155,15 @@ impl Access for DbfsBackend {
                 stat: true,
 
                 write: true,
+                write_can_multi: true,
                 create_dir: true,
                 delete: true,
+                delete_with_recursive: true,
+                delete_with_must_exist: true,
                 rename: true,
 
                 list: true,
+                list_with_recursive: true,
 
                 shared: true,
 
@@ -217,7 +221,7 @@ impl Access for DbfsBackend {
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
         Ok((
             RpWrite::default(),
-            oio::OneShotWriter::new(DbfsWriter::new(self.core.clone(), args, path.to_string())),
+            DbfsWriter::new(self.core.clone(), args, path.to_string()),
         ))
     }
 
@@ -228,10 +232,10 @@ impl Access for DbfsBackend {
         ))
     }
 
-    async fn list(&self, path: &str, _args: OpList) -> Result<(RpList, Self::Lister)> {
-        let l = DbfsLister::new(self.core.clone(), path.to_string());
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        let l = DbfsLister::new(self.core.clone(), path, args.recursive());
 
-        Ok((RpList::default(), oio::PageLister::new(l)))
+        Ok((RpList::default(), l))
     }
 
     async fn rename(&self, from: &str, to: &str, _args: OpRename) -> Result<RpRename> {
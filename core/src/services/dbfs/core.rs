@@ -69,7 +69,7 @@ impl DbfsCore {
         self.client.send(req).await
     }
 
-    pub async fn dbfs_delete(&self, path: &str) -> Result<Response<Buffer>> {
+    pub async fn dbfs_delete(&self, path: &str, recursive: bool) -> Result<Response<Buffer>> {
         let url = format!("{}/api/2.0/dbfs/delete", self.endpoint);
         let mut req = Request::post(&url);
 
@@ -82,8 +82,7 @@ impl DbfsCore {
 
         let request_body = &json!({
             "path": percent_encode_path(&p),
-            // TODO: support recursive toggle, should we add a new field in OpDelete?
-            "recursive": true,
+            "recursive": recursive,
         });
 
         let body = Buffer::from(Bytes::from(request_body.to_string()));
@@ -155,6 +154,61 @@ impl DbfsCore {
         req.body(body).map_err(new_request_build_error)
     }
 
+    pub async fn dbfs_create_handle(&self, path: &str) -> Result<Response<Buffer>> {
+        let url = format!("{}/api/2.0/dbfs/create", self.endpoint);
+        let mut req = Request::post(&url);
+
+        let auth_header_content = format!("Bearer {}", self.token);
+        req = req.header(header::AUTHORIZATION, auth_header_content);
+
+        let p = build_rooted_abs_path(&self.root, path);
+
+        let req_body = &json!({
+            "path": percent_encode_path(&p),
+            "overwrite": true,
+        });
+        let body = Buffer::from(Bytes::from(req_body.to_string()));
+
+        let req = req.body(body).map_err(new_request_build_error)?;
+
+        self.client.send(req).await
+    }
+
+    pub async fn dbfs_add_block(&self, handle: i64, body: Buffer) -> Result<Response<Buffer>> {
+        let url = format!("{}/api/2.0/dbfs/add-block", self.endpoint);
+        let mut req = Request::post(&url);
+
+        let auth_header_content = format!("Bearer {}", self.token);
+        req = req.header(header::AUTHORIZATION, auth_header_content);
+
+        let req_body = &json!({
+            "handle": handle,
+            "data": BASE64_STANDARD.encode(body.to_bytes()),
+        });
+        let body = Buffer::from(Bytes::from(req_body.to_string()));
+
+        let req = req.body(body).map_err(new_request_build_error)?;
+
+        self.client.send(req).await
+    }
+
+    pub async fn dbfs_close_handle(&self, handle: i64) -> Result<Response<Buffer>> {
+        let url = format!("{}/api/2.0/dbfs/close", self.endpoint);
+        let mut req = Request::post(&url);
+
+        let auth_header_content = format!("Bearer {}", self.token);
+        req = req.header(header::AUTHORIZATION, auth_header_content);
+
+        let req_body = &json!({
+            "handle": handle,
+        });
+        let body = Buffer::from(Bytes::from(req_body.to_string()));
+
+        let req = req.body(body).map_err(new_request_build_error)?;
+
+        self.client.send(req).await
+    }
+
     pub async fn dbfs_get_status(&self, path: &str) -> Result<Response<Buffer>> {
         let p = build_rooted_abs_path(&self.root, path)
             .trim_end_matches('/')
@@ -35,8 +35,27 @@ impl DbfsDeleter {
 }
 
 impl oio::OneShotDelete for DbfsDeleter {
-    async fn delete_once(&self, path: String, _: OpDelete) -> Result<()> {
-        let resp = self.core.dbfs_delete(&path).await?;
+    async fn delete_once(&self, path: String, args: OpDelete) -> Result<()> {
+        if args.if_match().is_some() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "DbfsDeleter doesn't support conditional delete",
+            ));
+        }
+
+        // Server returns 200 for non-existing paths, so we have to check the
+        // status first if users want to know about it.
+        if args.must_exist() {
+            let resp = self.core.dbfs_get_status(&path).await?;
+            if resp.status() != StatusCode::OK {
+                return Err(parse_error(resp));
+            }
+        }
+
+        let resp = self
+            .core
+            .dbfs_delete(&path, args.recursive().unwrap_or(true))
+            .await?;
 
         let status = resp.status();
 
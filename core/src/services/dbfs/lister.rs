@@ -15,6 +15,7 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::VecDeque;
 use std::sync::Arc;
 
 use bytes::Buf;
@@ -26,64 +27,99 @@ use super::error::parse_error;
 use crate::raw::*;
 use crate::*;
 
+/// DbfsLister lists dirs one by one, every response of DBFS `list` is a page
+/// that contains all entries of the dir.
+///
+/// - If path ends with `/`, entries under the dir will be returned.
+/// - Otherwise, the parent dir will be listed and only entries that start with
+///   the path will be returned.
+///
+/// Sub dirs will be listed in breadth-first order if recursive is enabled.
 pub struct DbfsLister {
     core: Arc<DbfsCore>,
-    path: String,
+    prefix: String,
+    recursive: bool,
+
+    dirs: VecDeque<String>,
+    entries: VecDeque<oio::Entry>,
 }
 
 impl DbfsLister {
-    pub fn new(core: Arc<DbfsCore>, path: String) -> Self {
-        Self { core, path }
+    pub fn new(core: Arc<DbfsCore>, path: &str, recursive: bool) -> Self {
+        let dir = get_dir(path);
+
+        Self {
+            core,
+            // Listing root should return all entries.
+            prefix: if path == "/" { "" } else { path }.to_string(),
+            recursive,
+
+            dirs: VecDeque::from([dir.to_string()]),
+            entries: VecDeque::new(),
+        }
     }
-}
 
-impl oio::PageList for DbfsLister {
-    async fn next_page(&self, ctx: &mut oio::PageContext) -> Result<()> {
-        let response = self.core.dbfs_list(&self.path).await?;
+    async fn list_dir(&mut self, dir: &str) -> Result<()> {
+        let response = self.core.dbfs_list(dir).await?;
 
         let status_code = response.status();
         if !status_code.is_success() {
             if status_code == StatusCode::NOT_FOUND {
-                ctx.done = true;
                 return Ok(());
             }
-            let error = parse_error(response);
-            return Err(error);
+            return Err(parse_error(response));
         }
 
         let bytes = response.into_body();
         let decoded_response: DbfsOutputList =
             serde_json::from_reader(bytes.reader()).map_err(new_json_deserialize_error)?;
 
-        ctx.done = true;
-
         for status in decoded_response.files {
-            let entry: oio::Entry = match status.is_dir {
-                true => {
-                    let normalized_path = format!("{}/", &status.path);
-                    let mut meta = Metadata::new(EntryMode::DIR);
-                    meta.set_last_modified(parse_datetime_from_from_timestamp_millis(
-                        status.modification_time,
-                    )?);
-                    oio::Entry::new(&normalized_path, meta)
-                }
-                false => {
-                    let mut meta = Metadata::new(EntryMode::FILE);
-                    meta.set_last_modified(parse_datetime_from_from_timestamp_millis(
-                        status.modification_time,
-                    )?);
-                    meta.set_content_length(status.file_size as u64);
-                    oio::Entry::new(&status.path, meta)
-                }
+            let mut path = build_rel_path(&self.core.root, &status.path);
+            let mut meta = if status.is_dir {
+                path.push('/');
+                Metadata::new(EntryMode::DIR)
+            } else {
+                let mut meta = Metadata::new(EntryMode::FILE);
+                meta.set_content_length(status.file_size as u64);
+                meta
             };
-            ctx.entries.push_back(entry);
+            meta.set_last_modified(parse_datetime_from_from_timestamp_millis(
+                status.modification_time,
+            )?);
+
+            // The dir itself or entries out of the prefix should be skipped.
+            if path == dir || !path.starts_with(&self.prefix) {
+                continue;
+            }
+            if self.recursive && status.is_dir {
+                self.dirs.push_back(path.clone());
+            }
+            self.entries.push_back(oio::Entry::new(&path, meta));
         }
         Ok(())
     }
 }
 
+impl oio::List for DbfsLister {
+    async fn next(&mut self) -> Result<Option<oio::Entry>> {
+        loop {
+            if let Some(entry) = self.entries.pop_front() {
+                return Ok(Some(entry));
+            }
+
+            let Some(dir) = self.dirs.pop_front() else {
+                return Ok(None);
+            };
+            self.list_dir(&dir).await?;
+        }
+    }
+}
+
 #[derive(Debug, Deserialize)]
 struct DbfsOutputList {
+    /// DBFS omits `files` if the dir is empty.
+    #[serde(default)]
     files: Vec<DbfsStatus>,
 }
 
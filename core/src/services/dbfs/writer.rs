@@ -17,38 +17,44 @@
 
 use std::sync::Arc;
 
+use bytes::Buf;
 use http::StatusCode;
+use serde::Deserialize;
 
 use super::core::DbfsCore;
 use super::error::parse_error;
 use crate::raw::*;
 use crate::*;
 
+/// DbfsWriter will write content via the single-shot `put` API if it's
+/// smaller than the block limit, otherwise it will be streamed via the
+/// handle-based `create`, `add-block` and `close` APIs.
 pub struct DbfsWriter {
     core: Arc<DbfsCore>,
     path: String,
+
+    buf: oio::QueueBuf,
+    handle: Option<i64>,
+    aborted: bool,
 }
 
 impl DbfsWriter {
-    const MAX_SIMPLE_SIZE: usize = 1024 * 1024;
+    /// MAX_BLOCK_SIZE_EXCEEDED will be thrown if this limit(1MB) is exceeded
+    /// by a single `put` or `add-block` call.
+    const MAX_BLOCK_SIZE: usize = 1024 * 1024;
 
     pub fn new(core: Arc<DbfsCore>, _op: OpWrite, path: String) -> Self {
-        DbfsWriter { core, path }
-    }
-}
-
-impl oio::OneShotWrite for DbfsWriter {
-    async fn write_once(&self, bs: Buffer) -> Result<Metadata> {
-        let size = bs.len();
+        DbfsWriter {
+            core,
+            path,
 
-        // MAX_BLOCK_SIZE_EXCEEDED will be thrown if this limit(1MB) is exceeded.
-        if size >= Self::MAX_SIMPLE_SIZE {
-            return Err(Error::new(
-                ErrorKind::Unsupported,
-                "AppendWrite has not been implemented for Dbfs",
-            ));
+            buf: oio::QueueBuf::new(),
+            handle: None,
+            aborted: false,
         }
+    }
 
+    async fn write_once(&self, bs: Buffer) -> Result<()> {
         let req = self
             .core
             .dbfs_create_file_request(&self.path, bs.to_bytes())?;
@@ -57,8 +63,138 @@ impl oio::OneShotWrite for DbfsWriter {
 
         let status = resp.status();
         match status {
-            StatusCode::CREATED | StatusCode::OK => Ok(Metadata::default()),
+            StatusCode::CREATED | StatusCode::OK => Ok(()),
             _ => Err(parse_error(resp)),
         }
     }
+
+    async fn create_handle(&self) -> Result<i64> {
+        let resp = self.core.dbfs_create_handle(&self.path).await?;
+
+        let status = resp.status();
+        match status {
+            StatusCode::OK => {
+                let bs = resp.into_body();
+                let resp: DbfsCreateHandleResponse =
+                    serde_json::from_reader(bs.reader()).map_err(new_json_deserialize_error)?;
+                Ok(resp.handle)
+            }
+            _ => Err(parse_error(resp)),
+        }
+    }
+
+    async fn add_block(&self, handle: i64, bs: Buffer) -> Result<()> {
+        let resp = self.core.dbfs_add_block(handle, bs).await?;
+
+        let status = resp.status();
+        match status {
+            StatusCode::OK => Ok(()),
+            _ => Err(parse_error(resp)),
+        }
+    }
+
+    async fn close_handle(&self, handle: i64) -> Result<()> {
+        let resp = self.core.dbfs_close_handle(handle).await?;
+
+        let status = resp.status();
+        match status {
+            StatusCode::OK => Ok(()),
+            _ => Err(parse_error(resp)),
+        }
+    }
+
+    /// Close the handle and remove the partial uploaded file.
+    async fn abort_handle(&mut self) -> Result<()> {
+        self.aborted = true;
+        self.buf.clear();
+        let Some(handle) = self.handle.take() else {
+            return Ok(());
+        };
+
+        self.close_handle(handle).await?;
+        let resp = self.core.dbfs_delete(&self.path, false).await?;
+        match resp.status() {
+            StatusCode::OK => Ok(()),
+            _ => Err(parse_error(resp)),
+        }
+    }
+
+    /// Abort the upload on failure.
+    ///
+    /// Blocks are appended to the handle, so the upload can't be retried
+    /// once a block failed.
+    async fn abort_on_error(&mut self, err: Error) -> Error {
+        if let Err(abort_err) = self.abort_handle().await {
+            log::warn!("dbfs writer abort handle failed: {abort_err}");
+        }
+        err.set_permanent()
+    }
+
+    async fn flush_block(&mut self, size: usize) -> Result<()> {
+        let handle = match self.handle {
+            Some(handle) => handle,
+            None => {
+                let handle = self.create_handle().await?;
+                self.handle = Some(handle);
+                handle
+            }
+        };
+
+        let bs = self.buf.clone().collect().slice(0..size);
+        if let Err(err) = self.add_block(handle, bs).await {
+            return Err(self.abort_on_error(err).await);
+        }
+        self.buf.advance(size);
+        Ok(())
+    }
+
+    fn check_aborted(&self) -> Result<()> {
+        if self.aborted {
+            return Err(Error::new(ErrorKind::Unexpected, "writer has been aborted"));
+        }
+        Ok(())
+    }
+}
+
+impl oio::Write for DbfsWriter {
+    async fn write(&mut self, bs: Buffer) -> Result<()> {
+        self.check_aborted()?;
+        self.buf.push(bs);
+
+        while self.buf.len() >= Self::MAX_BLOCK_SIZE {
+            self.flush_block(Self::MAX_BLOCK_SIZE).await?;
+        }
+        Ok(())
+    }
+
+    async fn close(&mut self) -> Result<Metadata> {
+        self.check_aborted()?;
+
+        // Small content can be written via a single request.
+        let Some(handle) = self.handle else {
+            let bs = self.buf.clone().collect();
+            self.write_once(bs).await?;
+            self.buf.clear();
+            return Ok(Metadata::default());
+        };
+
+        if !self.buf.is_empty() {
+            self.flush_block(self.buf.len()).await?;
+        }
+        if let Err(err) = self.close_handle(handle).await {
+            return Err(self.abort_on_error(err).await);
+        }
+        self.handle = None;
+
+        Ok(Metadata::default())
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.abort_handle().await
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct DbfsCreateHandleResponse {
+    handle: i64,
 }
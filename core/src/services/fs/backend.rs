@@ -17,6 +17,7 @@
 
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::Mutex;
 
 use log::debug;
 
@@ -69,6 +70,54 @@ impl FsBuilder {
 
         self
     }
+
+    /// Write zero runs that are no shorter than `threshold` bytes as holes.
+    ///
+    /// # Notes
+    ///
+    /// - Holes are created by seeking over the zero runs, filesystems without
+    ///   sparse file support will fill them with zeros instead.
+    /// - Append writes will always be written densely.
+    pub fn sparse_threshold(mut self, threshold: usize) -> Self {
+        self.config.sparse_threshold = Some(threshold.max(1));
+        self
+    }
+
+    /// Read files via memory map instead of `read` syscalls.
+    ///
+    /// It's useful for random-access workloads on large read-mostly files.
+    ///
+    /// # Notes
+    ///
+    /// - The returned buffers refer to the map without copying, the map is
+    ///   kept alive until all of them are dropped.
+    /// - We will fall back to normal reads if the file can't be mapped or is
+    ///   being written in place through the same backend. Writes and copies of
+    ///   this backend will replace mapped files instead of truncating them.
+    /// - Truncating a file by other processes while it's mapped will crash the
+    ///   process with `SIGBUS`. Please make sure files won't be truncated by
+    ///   others.
+    pub fn enable_mmap(mut self) -> Self {
+        self.config.enable_mmap = true;
+        self
+    }
+
+    /// Sync the parent dir after a writer is closed.
+    ///
+    /// The content of file is always synced before `close` returns, but the
+    /// dir entry of a newly created or renamed file could still be lost on
+    /// power failure until its parent dir is synced. Enable this for use cases
+    /// like write-ahead logs and checkpoints that need the written files to be
+    /// durable.
+    ///
+    /// # Notes
+    ///
+    /// This trades throughput for durability since every close will issue one
+    /// more `fsync`. It only takes effect on unix platforms.
+    pub fn sync_dir(mut self) -> Self {
+        self.config.sync_dir = true;
+        self
+    }
 }
 
 impl Builder for FsBuilder {
@@ -174,6 +223,10 @@ impl Builder for FsBuilder {
                 },
                 root,
                 atomic_write_dir,
+                sparse_threshold: self.config.sparse_threshold,
+                enable_mmap: self.config.enable_mmap,
+                sync_dir: self.config.sync_dir,
+                file_usages: Mutex::default(),
                 buf_pool: oio::PooledBuf::new(16).with_initial_capacity(256 * 1024),
             }),
         })
@@ -187,7 +240,7 @@ pub struct FsBackend {
 }
 
 impl Access for FsBackend {
-    type Reader = FsReader<tokio::fs::File>;
+    type Reader = TwoWays<FsReader<tokio::fs::File>, Buffer>;
     type Writer = FsWriters;
     type Lister = Option<FsLister<tokio::fs::ReadDir>>;
     type Deleter = oio::OneShotDeleter<FsDeleter>;
@@ -216,13 +269,19 @@ impl Access for FsBackend {
     ///
     /// Benchmark could be found [here](https://gist.github.com/Xuanwo/48f9cfbc3022ea5f865388bb62e1a70f)
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        if self.core.enable_mmap {
+            if let Some(bs) = self.core.fs_read_mmap(path, &args).await? {
+                return Ok((RpRead::new(), TwoWays::Two(bs)));
+            }
+        }
+
         let f = self.core.fs_read(path, &args).await?;
         let r = FsReader::new(
             self.core.clone(),
             f,
             args.range().size().unwrap_or(u64::MAX) as _,
         );
-        Ok((RpRead::new(), r))
+        Ok((RpRead::new(), TwoWays::One(r)))
     }
 
     async fn write(&self, path: &str, op: OpWrite) -> Result<(RpWrite, Self::Writer)> {
@@ -262,6 +321,11 @@ impl Access for FsBackend {
     }
 
     async fn copy(&self, from: &str, to: &str, _args: OpCopy) -> Result<RpCopy> {
+        // Copy truncates the target file in place.
+        let in_place = self
+            .core
+            .start_in_place_write(&self.core.root.join(to.trim_end_matches('/')));
+        in_place.replace_if_mapped().await?;
         self.core.fs_copy(from, to).await?;
         Ok(RpCopy::default())
     }
@@ -271,3 +335,98 @@ impl Access for FsBackend {
         Ok(RpRename::default())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::Fs;
+
+    #[tokio::test]
+    async fn test_read_mmap() -> Result<()> {
+        let root = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        let op = Operator::new(Fs::default().root(&root.to_string_lossy()).enable_mmap())?.finish();
+
+        op.write("test", "Hello, World!").await?;
+        assert_eq!(op.read("test").await?.to_vec(), b"Hello, World!");
+        assert_eq!(op.read_with("test").range(7..).await?.to_vec(), b"World!");
+        assert_eq!(op.read_with("test").range(0..5).await?.to_vec(), b"Hello");
+
+        // Files being written in place will be read normally.
+        let mut w = op.writer("test").await?;
+        w.write("Hi").await?;
+        assert!(op.read("test").await?.is_empty());
+        w.close().await?;
+        assert_eq!(op.read("test").await?.to_vec(), b"Hi");
+
+        // Overwriting a mapped file won't change the read buffers.
+        let bs = op.read("test").await?;
+        op.write("test", "Hello").await?;
+        assert_eq!(bs.to_vec(), b"Hi");
+        assert_eq!(op.read("test").await?.to_vec(), b"Hello");
+        op.write("src", "World").await?;
+        op.copy("src", "test").await?;
+        assert_eq!(bs.to_vec(), b"Hi");
+        assert_eq!(op.read("test").await?.to_vec(), b"World");
+        drop(bs);
+
+        op.write("empty", "").await?;
+        assert!(op.read("empty").await?.is_empty());
+
+        std::fs::remove_dir_all(&root).map_err(new_std_io_error)?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_write_sync_dir() -> Result<()> {
+        let root = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        let tmp = root.join(".tmp");
+        let op = Operator::new(
+            Fs::default()
+                .root(&root.to_string_lossy())
+                .atomic_write_dir(&tmp.to_string_lossy())
+                .sync_dir(),
+        )?
+        .finish();
+
+        op.write("dir/test", "Hello, World!").await?;
+        assert_eq!(op.read("dir/test").await?.to_vec(), b"Hello, World!");
+
+        op.write_with("dir/test", ", Again").append(true).await?;
+        assert_eq!(op.read("dir/test").await?.to_vec(), b"Hello, World!, Again");
+
+        std::fs::remove_dir_all(&root).map_err(new_std_io_error)?;
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_write_with_mode() -> Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let root = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        let tmp = root.join(".tmp");
+        let mode_of = |path: &str| {
+            let meta = std::fs::metadata(root.join(path)).expect("file must exist");
+            meta.permissions().mode() & 0o777
+        };
+
+        let op = Operator::new(Fs::default().root(&root.to_string_lossy()))?.finish();
+        op.write_with("script.sh", "#!/bin/sh").mode(0o755).await?;
+        assert_eq!(mode_of("script.sh"), 0o755);
+
+        op.copy("script.sh", "copied.sh").await?;
+        assert_eq!(mode_of("copied.sh"), 0o755);
+
+        let op = Operator::new(
+            Fs::default()
+                .root(&root.to_string_lossy())
+                .atomic_write_dir(&tmp.to_string_lossy()),
+        )?
+        .finish();
+        op.write_with("config", "secret").mode(0o600).await?;
+        assert_eq!(mode_of("config"), 0o600);
+
+        std::fs::remove_dir_all(&root).map_err(new_std_io_error)?;
+        Ok(())
+    }
+}
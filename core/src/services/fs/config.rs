@@ -30,4 +30,22 @@ pub struct FsConfig {
 
     /// tmp dir for atomic write
     pub atomic_write_dir: Option<String>,
+
+    /// Zero runs no shorter than this will be written as holes.
+    ///
+    /// Default to `None`, which means all data will be written densely.
+    pub sparse_threshold: Option<usize>,
+
+    /// Read files via memory map instead of `read` syscalls.
+    ///
+    /// The read buffers refer to the map without copying. Files being written
+    /// in place through the same backend will be read normally. Files must
+    /// not be truncated by others while being mapped.
+    pub enable_mmap: bool,
+
+    /// Sync the parent dir after a writer is closed, so that newly created
+    /// or renamed files survive a power failure.
+    ///
+    /// The content of file is always synced while closing.
+    pub sync_dir: bool,
 }
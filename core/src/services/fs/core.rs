@@ -15,12 +15,16 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
 use std::io::SeekFrom;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::Mutex;
 
+use bytes::Bytes;
 use chrono::DateTime;
+use log::debug;
 
 use super::error::*;
 use crate::raw::*;
@@ -31,6 +35,11 @@ pub struct FsCore {
     pub info: Arc<AccessorInfo>,
     pub root: PathBuf,
     pub atomic_write_dir: Option<PathBuf>,
+    pub sparse_threshold: Option<usize>,
+    pub enable_mmap: bool,
+    pub sync_dir: bool,
+    /// The in place writers and live maps of files.
+    pub file_usages: Mutex<HashMap<PathBuf, FileUsage>>,
     pub buf_pool: oio::PooledBuf,
 }
 
@@ -112,6 +121,84 @@ impl FsCore {
         Ok(f)
     }
 
+    /// Read the file via memory map.
+    ///
+    /// The returned buffer refers to the map without copying, and keeps the
+    /// map alive until it's dropped.
+    ///
+    /// Returns `None` if the file is being written in place or can't be mapped,
+    /// the caller should fall back to normal reads.
+    pub async fn fs_read_mmap(
+        self: &Arc<Self>,
+        path: &str,
+        args: &OpRead,
+    ) -> Result<Option<Buffer>> {
+        let p = self.root.join(path.trim_end_matches('/'));
+        let f = tokio::fs::File::open(&p)
+            .await
+            .map_err(new_std_io_error)?
+            .into_std()
+            .await;
+
+        let mmap = {
+            let mut usages = self.file_usages.lock().unwrap();
+            if usages.get(&p).is_some_and(|v| v.writes > 0) {
+                return Ok(None);
+            }
+            // SAFETY: In place writes of this backend are excluded by the lock
+            // above, and they will replace mapped files instead of truncating
+            // them. Truncations by others are documented as unsupported while
+            // `enable_mmap` is set.
+            let mmap = match unsafe { memmap2::Mmap::map(&f) } {
+                Ok(mmap) => mmap,
+                Err(err) => {
+                    debug!("mmap {} failed, fallback to read: {err}", p.display());
+                    return Ok(None);
+                }
+            };
+            usages.entry(p.clone()).or_default().maps += 1;
+            mmap
+        };
+
+        let start = args.range().offset().min(mmap.len() as u64) as usize;
+        let end = match args.range().size() {
+            Some(size) => (start as u64).saturating_add(size).min(mmap.len() as u64) as usize,
+            None => mmap.len(),
+        };
+        let owner = MmapOwner {
+            mmap,
+            _guard: MapGuard {
+                core: self.clone(),
+                path: p,
+            },
+        };
+        Ok(Some(Buffer::from(
+            Bytes::from_owner(owner).slice(start..end),
+        )))
+    }
+
+    /// Mark the file as being written in place until the returned guard dropped.
+    pub fn start_in_place_write(self: &Arc<Self>, path: &Path) -> InPlaceWriteGuard {
+        let mut usages = self.file_usages.lock().unwrap();
+        let usage = usages.entry(path.to_path_buf()).or_default();
+        usage.writes += 1;
+        InPlaceWriteGuard {
+            core: self.clone(),
+            path: path.to_path_buf(),
+            mapped: usage.maps > 0,
+        }
+    }
+
+    fn release_file_usage(&self, path: &Path, f: impl FnOnce(&mut FileUsage)) {
+        let mut usages = self.file_usages.lock().unwrap();
+        if let Some(usage) = usages.get_mut(path) {
+            f(usage);
+            if usage.writes == 0 && usage.maps == 0 {
+                usages.remove(path);
+            }
+        }
+    }
+
     pub async fn fs_write(&self, path: &PathBuf, op: &OpWrite) -> Result<tokio::fs::File> {
         let mut open_options = tokio::fs::OpenOptions::new();
         if op.if_not_exists() {
@@ -225,3 +312,64 @@ impl FsCore {
         Ok(())
     }
 }
+
+/// FileUsage tracks the in place writers and live maps of a file.
+#[derive(Debug, Default)]
+pub struct FileUsage {
+    writes: usize,
+    maps: usize,
+}
+
+/// InPlaceWriteGuard unmarks the file written in place while dropped.
+#[derive(Debug)]
+pub struct InPlaceWriteGuard {
+    core: Arc<FsCore>,
+    path: PathBuf,
+    /// Whether the file was mapped while the write started.
+    mapped: bool,
+}
+
+impl InPlaceWriteGuard {
+    /// Remove the file if it's mapped, so that truncating it won't crash the
+    /// readers of the map. The maps keep referring to the removed content.
+    pub async fn replace_if_mapped(&self) -> Result<()> {
+        if !self.mapped {
+            return Ok(());
+        }
+        match tokio::fs::remove_file(&self.path).await {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(new_std_io_error(err)),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Drop for InPlaceWriteGuard {
+    fn drop(&mut self) {
+        self.core.release_file_usage(&self.path, |v| v.writes -= 1);
+    }
+}
+
+/// MapGuard unmarks the mapped file while dropped.
+#[derive(Debug)]
+struct MapGuard {
+    core: Arc<FsCore>,
+    path: PathBuf,
+}
+
+impl Drop for MapGuard {
+    fn drop(&mut self) {
+        self.core.release_file_usage(&self.path, |v| v.maps -= 1);
+    }
+}
+
+/// MmapOwner keeps the map alive for the buffers that refer to it.
+struct MmapOwner {
+    mmap: memmap2::Mmap,
+    _guard: MapGuard,
+}
+
+impl AsRef<[u8]> for MmapOwner {
+    fn as_ref(&self) -> &[u8] {
+        &self.mmap
+    }
+}
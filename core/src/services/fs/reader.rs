@@ -15,21 +15,44 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::io::SeekFrom;
 use std::sync::Arc;
 
+use bytes::Bytes;
 use tokio::io::AsyncReadExt;
+use tokio::io::AsyncSeekExt;
 use tokio::io::ReadBuf;
 
 use super::core::*;
 use crate::raw::*;
 use crate::*;
 
+/// Use 2 MiB as default buffer size.
+const BUF_SIZE: usize = 2 * 1024 * 1024;
+
+/// Zeros returned for holes of sparse files without allocation.
+static ZEROS: [u8; BUF_SIZE] = [0; BUF_SIZE];
+
+/// The segment of file that the reader is currently in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Segment {
+    /// Need to probe the next segment.
+    Unknown,
+    /// Bytes of data left before the next hole.
+    Data(u64),
+    /// Bytes of hole left before the next data.
+    Hole(u64),
+    /// Holes can't be detected, read the file densely.
+    Dense,
+}
+
 pub struct FsReader<F> {
     core: Arc<FsCore>,
     f: F,
     read: usize,
     size: usize,
     buf_size: usize,
+    segment: Segment,
 }
 
 impl<F> FsReader<F> {
@@ -39,22 +62,114 @@ impl<F> FsReader<F> {
             f,
             read: 0,
             size,
-            // Use 2 MiB as default value.
-            buf_size: 2 * 1024 * 1024,
+            buf_size: BUF_SIZE,
+            segment: Segment::Unknown,
         }
     }
 }
 
+impl FsReader<tokio::fs::File> {
+    /// Returns the size of hole to skip, or the limit of bytes to read.
+    ///
+    /// Holes are detected via `SEEK_DATA` and `SEEK_HOLE`, we will fall back to
+    /// dense reading if they are not supported by the platform or filesystem.
+    async fn next_chunk(&mut self, limit: usize) -> Result<(bool, usize)> {
+        if matches!(self.segment, Segment::Unknown | Segment::Data(0)) {
+            self.segment = self.probe_segment().await?;
+        }
+
+        match self.segment {
+            Segment::Hole(left) => {
+                let n = limit.min(left.try_into().unwrap_or(usize::MAX));
+                self.f
+                    .seek(SeekFrom::Current(n as i64))
+                    .await
+                    .map_err(new_std_io_error)?;
+                self.segment = if n as u64 == left {
+                    Segment::Unknown
+                } else {
+                    Segment::Hole(left - n as u64)
+                };
+                Ok((true, n))
+            }
+            Segment::Data(n) => Ok((false, limit.min(n.try_into().unwrap_or(usize::MAX)))),
+            _ => Ok((false, limit)),
+        }
+    }
+
+    /// Consume `n` bytes of data from current segment.
+    fn consume_data(&mut self, n: usize) {
+        if let Segment::Data(left) = self.segment {
+            self.segment = Segment::Data(left.saturating_sub(n as u64));
+        }
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android", target_os = "freebsd"))]
+    async fn probe_segment(&mut self) -> Result<Segment> {
+        use std::os::fd::AsRawFd;
+
+        // Seek via tokio first to sync the position of the underlying file.
+        let pos = self.f.stream_position().await.map_err(new_std_io_error)?;
+        let fd = self.f.as_raw_fd();
+
+        let lseek = |whence| {
+            // SAFETY: fd is valid during the lifetime of `self.f`.
+            let v = unsafe { libc::lseek(fd, pos as libc::off_t, whence) };
+            if v < 0 {
+                Err(std::io::Error::last_os_error())
+            } else {
+                Ok(v as u64)
+            }
+        };
+
+        let segment = match lseek(libc::SEEK_DATA) {
+            Ok(data) if data > pos => Segment::Hole(data - pos),
+            Ok(_) => match lseek(libc::SEEK_HOLE) {
+                Ok(hole) if hole > pos => Segment::Data(hole - pos),
+                _ => Segment::Dense,
+            },
+            // ENXIO means there is no more data after pos, the rest is a hole.
+            Err(err) if err.raw_os_error() == Some(libc::ENXIO) => {
+                let len = self.f.metadata().await.map_err(new_std_io_error)?.len();
+                if len > pos {
+                    Segment::Hole(len - pos)
+                } else {
+                    Segment::Dense
+                }
+            }
+            Err(_) => Segment::Dense,
+        };
+
+        // Restore the position changed by lseek.
+        self.f
+            .seek(SeekFrom::Start(pos))
+            .await
+            .map_err(new_std_io_error)?;
+        Ok(segment)
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "android", target_os = "freebsd")))]
+    async fn probe_segment(&mut self) -> Result<Segment> {
+        Ok(Segment::Dense)
+    }
+}
+
 impl oio::Read for FsReader<tokio::fs::File> {
     async fn read(&mut self) -> Result<Buffer> {
         if self.read >= self.size {
             return Ok(Buffer::new());
         }
 
+        let limit = (self.size - self.read).min(self.buf_size);
+        let (is_hole, size) = self.next_chunk(limit).await?;
+        if is_hole {
+            self.read += size;
+            return Ok(Buffer::from(Bytes::from_static(&ZEROS[..size])));
+        }
+
         let mut bs = self.core.buf_pool.get();
         bs.reserve(self.buf_size);
 
-        let size = (self.size - self.read).min(self.buf_size);
         let buf = &mut bs.spare_capacity_mut()[..size];
         let mut read_buf: ReadBuf = ReadBuf::uninit(buf);
 
@@ -69,6 +184,7 @@ impl oio::Read for FsReader<tokio::fs::File> {
             .await
             .map_err(new_std_io_error)?;
         self.read += n;
+        self.consume_data(n);
 
         // Safety: We make sure that bs contains `n` more bytes.
         let filled = read_buf.filled().len();
@@ -80,4 +196,27 @@ impl oio::Read for FsReader<tokio::fs::File> {
 
         Ok(Buffer::from(frozen))
     }
+
+    async fn read_into(&mut self, dst: &mut [u8]) -> Result<usize> {
+        if self.read >= self.size {
+            return Ok(0);
+        }
+
+        let limit = (self.size - self.read).min(dst.len());
+        let (is_hole, size) = self.next_chunk(limit).await?;
+        if is_hole {
+            dst[..size].fill(0);
+            self.read += size;
+            return Ok(size);
+        }
+
+        let n = self
+            .f
+            .read(&mut dst[..size])
+            .await
+            .map_err(new_std_io_error)?;
+        self.read += n;
+        self.consume_data(n);
+        Ok(n)
+    }
 }
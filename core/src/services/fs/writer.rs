@@ -17,7 +17,11 @@
 
 use std::fs::File;
 use std::io::Write;
+use std::ops::Range;
+use std::path::Path;
 use std::path::PathBuf;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
 use bytes::Buf;
@@ -25,6 +29,7 @@ use tokio::io::AsyncWriteExt;
 
 use crate::raw::*;
 use crate::services::fs::core::FsCore;
+use crate::services::fs::core::InPlaceWriteGuard;
 use crate::*;
 
 pub type FsWriters = TwoWays<FsWriter, oio::PositionWriter<FsWriter>>;
@@ -34,6 +39,14 @@ pub struct FsWriter {
     /// The temp_path is used to specify whether we should move to target_path after the file has been closed.
     temp_path: Option<PathBuf>,
     f: tokio::fs::File,
+    /// Zero runs no shorter than this will be skipped as holes in position write.
+    sparse_threshold: Option<usize>,
+    /// The max end offset that has been written, including holes.
+    end: AtomicU64,
+    /// Sync the parent dir after closed.
+    sync_dir: bool,
+    /// Keeps the target file from being mapped while written in place.
+    _in_place: Option<InPlaceWriteGuard>,
 }
 
 impl FsWriter {
@@ -42,12 +55,21 @@ impl FsWriter {
 
         // Quick path while atomic_write_dir is not set.
         if core.atomic_write_dir.is_none() {
+            let in_place = core.start_in_place_write(&target_path);
+            if !op.append() && !op.if_not_exists() {
+                in_place.replace_if_mapped().await?;
+            }
             let target_file = core.fs_write(&target_path, &op).await?;
+            set_mode(&target_file, op.mode()).await?;
 
             return Ok(Self {
                 target_path,
                 temp_path: None,
                 f: target_file,
+                sparse_threshold: core.sparse_threshold,
+                end: AtomicU64::new(0),
+                sync_dir: core.sync_dir,
+                _in_place: Some(in_place),
             });
         }
 
@@ -64,17 +86,24 @@ impl FsWriter {
 
         // The only case we allow write in place is the file
         // exists and users request for append writing.
-        let (f, temp_path) = if !(is_append && is_exist) {
-            core.fs_tempfile_write(path).await?
+        let (f, temp_path, in_place) = if !(is_append && is_exist) {
+            let (f, temp_path) = core.fs_tempfile_write(path).await?;
+            (f, temp_path, None)
         } else {
+            let in_place = core.start_in_place_write(&target_path);
             let f = core.fs_write(&target_path, &op).await?;
-            (f, None)
+            (f, None, Some(in_place))
         };
+        set_mode(&f, op.mode()).await?;
 
         Ok(Self {
             target_path,
             temp_path,
             f,
+            sparse_threshold: core.sparse_threshold,
+            end: AtomicU64::new(0),
+            sync_dir: core.sync_dir,
+            _in_place: in_place,
         })
     }
 }
@@ -94,6 +123,11 @@ impl oio::Write for FsWriter {
         Ok(())
     }
 
+    async fn flush(&mut self) -> Result<()> {
+        self.f.flush().await.map_err(new_std_io_error)?;
+        self.f.sync_data().await.map_err(new_std_io_error)
+    }
+
     async fn close(&mut self) -> Result<Metadata> {
         self.f.flush().await.map_err(new_std_io_error)?;
         self.f.sync_all().await.map_err(new_std_io_error)?;
@@ -103,6 +137,9 @@ impl oio::Write for FsWriter {
                 .await
                 .map_err(new_std_io_error)?;
         }
+        if self.sync_dir {
+            sync_parent_dir(&self.target_path).await?;
+        }
 
         let file_meta = self.f.metadata().await.map_err(new_std_io_error)?;
         let meta = Metadata::new(EntryMode::FILE)
@@ -135,17 +172,24 @@ impl oio::PositionWrite for FsWriter {
             .into_std()
             .await;
 
+        let Some(threshold) = self.sparse_threshold else {
+            return tokio::task::spawn_blocking(move || write_all_at(&f, buf, offset))
+                .await
+                .map_err(new_task_join_error)?;
+        };
+
+        self.end
+            .fetch_max(offset + buf.len() as u64, Ordering::Relaxed);
         tokio::task::spawn_blocking(move || {
-            let mut buf = buf;
             let mut offset = offset;
-            while !buf.is_empty() {
-                match write_at(&f, buf.chunk(), offset) {
-                    Ok(n) => {
-                        buf.advance(n);
-                        offset += n as u64
-                    }
-                    Err(e) => return Err(e),
+            for bs in buf {
+                // Zero runs will be left as holes, the file will be extended
+                // to the end while closing.
+                for range in data_ranges(&bs, threshold) {
+                    let start = offset + range.start as u64;
+                    write_all_at(&f, Buffer::from(bs.slice(range)), start)?;
                 }
+                offset += bs.len() as u64;
             }
             Ok(())
         })
@@ -163,6 +207,11 @@ impl oio::PositionWrite for FsWriter {
             .await;
 
         f.flush().map_err(new_std_io_error)?;
+        // Extend the file if it ends with a hole.
+        let end = self.end.load(Ordering::Relaxed);
+        if end > f.metadata().map_err(new_std_io_error)?.len() {
+            f.set_len(end).map_err(new_std_io_error)?;
+        }
         f.sync_all().map_err(new_std_io_error)?;
 
         if let Some(temp_path) = &self.temp_path {
@@ -170,6 +219,9 @@ impl oio::PositionWrite for FsWriter {
                 .await
                 .map_err(new_std_io_error)?;
         }
+        if self.sync_dir {
+            sync_parent_dir(&self.target_path).await?;
+        }
 
         let file_meta = f.metadata().map_err(new_std_io_error)?;
         let mode = if file_meta.is_file() {
@@ -199,6 +251,72 @@ impl oio::PositionWrite for FsWriter {
     }
 }
 
+/// Set the permission bits of the file, it's a no-op on non-unix platforms.
+async fn set_mode(f: &tokio::fs::File, mode: Option<u32>) -> Result<()> {
+    #[cfg(unix)]
+    if let Some(mode) = mode {
+        use std::os::unix::fs::PermissionsExt;
+
+        f.set_permissions(std::fs::Permissions::from_mode(mode))
+            .await
+            .map_err(new_std_io_error)?;
+    }
+    #[cfg(not(unix))]
+    let _ = (f, mode);
+
+    Ok(())
+}
+
+/// Sync the parent dir of path to persist the dir entry of it.
+async fn sync_parent_dir(path: &Path) -> Result<()> {
+    // Dirs can't be opened as files on windows.
+    if cfg!(not(unix)) {
+        return Ok(());
+    }
+    let Some(parent) = path.parent() else {
+        return Ok(());
+    };
+
+    let dir = tokio::fs::File::open(parent)
+        .await
+        .map_err(new_std_io_error)?;
+    dir.sync_all().await.map_err(new_std_io_error)
+}
+
+fn write_all_at(f: &File, mut buf: Buffer, mut offset: u64) -> Result<()> {
+    while !buf.is_empty() {
+        let n = write_at(f, buf.chunk(), offset)?;
+        buf.advance(n);
+        offset += n as u64;
+    }
+    Ok(())
+}
+
+/// Returns the ranges of `bs` that need to be written, zero runs no shorter
+/// than `threshold` are skipped.
+fn data_ranges(bs: &[u8], threshold: usize) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let (mut start, mut idx) = (0, 0);
+    while let Some(n) = bs[idx..].iter().position(|b| *b == 0) {
+        let zero_start = idx + n;
+        let zero_end = bs[zero_start..]
+            .iter()
+            .position(|b| *b != 0)
+            .map_or(bs.len(), |n| zero_start + n);
+        if zero_end - zero_start >= threshold {
+            if start < zero_start {
+                ranges.push(start..zero_start);
+            }
+            start = zero_end;
+        }
+        idx = zero_end;
+    }
+    if start < bs.len() {
+        ranges.push(start..bs.len());
+    }
+    ranges
+}
+
 #[cfg(windows)]
 fn write_at(f: &File, buf: &[u8], offset: u64) -> Result<usize> {
     use std::os::windows::fs::FileExt;
@@ -210,3 +328,29 @@ fn write_at(f: &File, buf: &[u8], offset: u64) -> Result<usize> {
     use std::os::unix::fs::FileExt;
     f.write_at(buf, offset).map_err(new_std_io_error)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_data_ranges() {
+        let cases = vec![
+            ("empty", vec![], 2, vec![]),
+            ("all data", vec![1, 2, 3], 2, vec![(0, 3)]),
+            ("all zeros", vec![0, 0, 0], 2, vec![]),
+            ("short zero run", vec![1, 0, 1, 0], 2, vec![(0, 4)]),
+            ("middle hole", vec![1, 0, 0, 1], 2, vec![(0, 1), (3, 4)]),
+            ("leading hole", vec![0, 0, 0, 1, 0], 2, vec![(3, 5)]),
+            ("trailing hole", vec![1, 0, 1, 0, 0], 2, vec![(0, 3)]),
+        ];
+
+        for (name, bs, threshold, expected) in cases {
+            let ranges: Vec<_> = data_ranges(&bs, threshold)
+                .into_iter()
+                .map(|r| (r.start, r.end))
+                .collect();
+            assert_eq!(ranges, expected, "{name}");
+        }
+    }
+}
@@ -18,6 +18,7 @@
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::sync::Arc;
+use std::time::Duration;
 
 use http::Response;
 use http::StatusCode;
@@ -31,8 +32,10 @@ use super::core::*;
 use super::delete::GcsDeleter;
 use super::error::parse_error;
 use super::lister::GcsLister;
+use super::writer::GcsResumableWriter;
 use super::writer::GcsWriter;
 use super::writer::GcsWriters;
+use super::writer::RESUMABLE_CHUNK_ALIGN;
 use super::DEFAULT_SCHEME;
 use crate::raw::oio::BatchDeleter;
 use crate::raw::*;
@@ -231,6 +234,17 @@ impl GcsBuilder {
         self.config.allow_anonymous = true;
         self
     }
+
+    /// Enable resumable uploads for writes.
+    ///
+    /// By default, multi-chunk writes are uploaded via XML multipart uploads.
+    /// With resumable uploads enabled, writes will continue from the committed
+    /// offset after transient failures instead of re-uploading the chunk from
+    /// scratch. Chunks will be uploaded sequentially.
+    pub fn enable_resumable_upload(mut self) -> Self {
+        self.config.enable_resumable_upload = true;
+        self
+    }
 }
 
 impl Builder for GcsBuilder {
@@ -314,11 +328,13 @@ impl Builder for GcsBuilder {
                             stat: true,
                             stat_with_if_match: true,
                             stat_with_if_none_match: true,
+                            stat_with_headers: true,
 
                             read: true,
 
                             read_with_if_match: true,
                             read_with_if_none_match: true,
+                            read_with_headers: true,
 
                             write: true,
                             write_can_empty: true,
@@ -341,20 +357,30 @@ impl Builder for GcsBuilder {
                             } else {
                                 Some(usize::MAX)
                             },
+                            // Resumable uploads require parts to be aligned.
+                            write_multi_align_size: self
+                                .config
+                                .enable_resumable_upload
+                                .then_some(RESUMABLE_CHUNK_ALIGN),
 
                             delete: true,
                             delete_max_size: Some(100),
                             copy: true,
+                            copy_with_metadata_directive: true,
 
                             list: true,
                             list_with_limit: true,
                             list_with_start_after: true,
                             list_with_recursive: true,
+                            list_with_sorted: true,
+                            list_has_metadata: true,
 
                             presign: true,
                             presign_stat: true,
                             presign_read: true,
                             presign_write: true,
+                            // V4 signed URLs are valid for at most 7 days.
+                            presign_max_expiry: Some(Duration::from_secs(7 * 24 * 60 * 60)),
 
                             shared: true,
 
@@ -380,6 +406,7 @@ impl Builder for GcsBuilder {
                 predefined_acl: self.config.predefined_acl.clone(),
                 default_storage_class: self.config.default_storage_class.clone(),
                 allow_anonymous: self.config.allow_anonymous,
+                enable_resumable_upload: self.config.enable_resumable_upload,
             }),
         };
 
@@ -395,7 +422,7 @@ pub struct GcsBackend {
 
 impl Access for GcsBackend {
     type Reader = HttpBody;
-    type Writer = GcsWriters;
+    type Writer = TwoWays<GcsWriters, GcsResumableWriter>;
     type Lister = oio::PageLister<GcsLister>;
     type Deleter = oio::BatchDeleter<GcsDeleter>;
 
@@ -434,11 +461,16 @@ impl Access for GcsBackend {
     }
 
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        if self.core.enable_resumable_upload {
+            let w = GcsResumableWriter::new(self.core.clone(), path, args);
+            return Ok((RpWrite::default(), TwoWays::Two(w)));
+        }
+
         let concurrent = args.concurrent();
         let w = GcsWriter::new(self.core.clone(), path, args);
         let w = oio::MultipartWriter::new(self.core.info.clone(), w, concurrent);
 
-        Ok((RpWrite::default(), w))
+        Ok((RpWrite::default(), TwoWays::One(w)))
     }
 
     async fn delete(&self) -> Result<(RpDelete, Self::Deleter)> {
@@ -460,8 +492,8 @@ impl Access for GcsBackend {
         Ok((RpList::default(), oio::PageLister::new(l)))
     }
 
-    async fn copy(&self, from: &str, to: &str, _: OpCopy) -> Result<RpCopy> {
-        let resp = self.core.gcs_copy_object(from, to).await?;
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let resp = self.core.gcs_copy_object(from, to, &args).await?;
 
         if resp.status().is_success() {
             Ok(RpCopy::default())
@@ -479,7 +511,7 @@ impl Access for GcsBackend {
                 self.core
                     .gcs_insert_object_xml_request(path, v, Buffer::new())
             }
-            PresignOperation::Delete(_) => Err(Error::new(
+            _ => Err(Error::new(
                 ErrorKind::Unsupported,
                 "operation is not supported",
             )),
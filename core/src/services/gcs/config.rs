@@ -70,6 +70,11 @@ pub struct GcsConfig {
     ///
     /// Takes precedence over `credential` and `credential_path`.
     pub token: Option<String>,
+    /// Enable resumable uploads for writes instead of XML multipart uploads.
+    ///
+    /// Resumable uploads will query the committed offset and continue from
+    /// there after transient failures, but chunks are uploaded sequentially.
+    pub enable_resumable_upload: bool,
 }
 
 impl Debug for GcsConfig {
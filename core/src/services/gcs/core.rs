@@ -32,6 +32,7 @@ use http::header::CACHE_CONTROL;
 use http::header::CONTENT_DISPOSITION;
 use http::header::CONTENT_ENCODING;
 use http::header::CONTENT_LENGTH;
+use http::header::CONTENT_RANGE;
 use http::header::CONTENT_TYPE;
 use http::header::HOST;
 use http::header::IF_MATCH;
@@ -49,6 +50,7 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use super::uri::percent_encode_path;
+use crate::options::MetadataDirective;
 use crate::raw::*;
 use crate::*;
 
@@ -74,6 +76,7 @@ pub struct GcsCore {
     pub default_storage_class: Option<String>,
 
     pub allow_anonymous: bool,
+    pub enable_resumable_upload: bool,
 }
 
 impl Debug for GcsCore {
@@ -259,6 +262,7 @@ impl GcsCore {
     ) -> Result<Response<HttpBody>> {
         let mut req = self.gcs_get_object_request(path, range, args)?;
 
+        merge_extra_headers(&mut req, args.headers());
         self.sign(&mut req).await?;
         self.info.http_client().fetch(req).await
     }
@@ -451,6 +455,7 @@ impl GcsCore {
     ) -> Result<Response<Buffer>> {
         let mut req = self.gcs_head_object_request(path, args)?;
 
+        merge_extra_headers(&mut req, args.headers());
         self.sign(&mut req).await?;
 
         self.send(req).await
@@ -498,7 +503,12 @@ impl GcsCore {
         self.send(req).await
     }
 
-    pub async fn gcs_copy_object(&self, from: &str, to: &str) -> Result<Response<Buffer>> {
+    pub async fn gcs_copy_object(
+        &self,
+        from: &str,
+        to: &str,
+        args: &OpCopy,
+    ) -> Result<Response<Buffer>> {
         let source = build_abs_path(&self.root, from);
         let dest = build_abs_path(&self.root, to);
 
@@ -511,11 +521,26 @@ impl GcsCore {
             percent_encode_path(&dest)
         );
 
-        let mut req = Request::post(req_uri)
-            .header(CONTENT_LENGTH, 0)
-            .extension(Operation::Copy)
-            .body(Buffer::new())
-            .map_err(new_request_build_error)?;
+        let req = Request::post(req_uri).extension(Operation::Copy);
+
+        // Metadata in the request body will be applied to the target instead of
+        // the source's.
+        let mut req = if args.metadata_directive() == MetadataDirective::Replace {
+            let metadata = InsertRequestMetadata {
+                content_type: args.content_type(),
+                content_encoding: None,
+                storage_class: None,
+                cache_control: args.cache_control(),
+                metadata: args.user_metadata(),
+            };
+            let body = serde_json::to_vec(&metadata).map_err(new_json_serialize_error)?;
+            req.header(CONTENT_TYPE, "application/json")
+                .header(CONTENT_LENGTH, body.len())
+                .body(Buffer::from(Bytes::from(body)))
+        } else {
+            req.header(CONTENT_LENGTH, 0).body(Buffer::new())
+        }
+        .map_err(new_request_build_error)?;
 
         self.sign(&mut req).await?;
         self.send(req).await
@@ -707,6 +732,106 @@ impl GcsCore {
         self.send(req).await
     }
 
+    /// Initiate a resumable upload, the session URI will be returned in the
+    /// `Location` header.
+    ///
+    /// ref: <https://cloud.google.com/storage/docs/performing-resumable-uploads#initiate-session>
+    pub async fn gcs_initiate_resumable_upload(
+        &self,
+        path: &str,
+        op: &OpWrite,
+    ) -> Result<Response<Buffer>> {
+        let p = build_abs_path(&self.root, path);
+
+        let mut url = format!(
+            "{}/upload/storage/v1/b/{}/o?uploadType=resumable&name={}",
+            self.endpoint,
+            self.bucket,
+            percent_encode_path(&p)
+        );
+        if let Some(acl) = &self.predefined_acl {
+            write!(&mut url, "&predefinedAcl={acl}").unwrap();
+        }
+        if op.if_not_exists() {
+            write!(&mut url, "&ifGenerationMatch=0").unwrap();
+        }
+
+        let request_metadata = InsertRequestMetadata {
+            storage_class: self.default_storage_class.as_deref(),
+            cache_control: op.cache_control(),
+            content_type: op.content_type(),
+            content_encoding: op.content_encoding(),
+            metadata: op.user_metadata(),
+        };
+        let content = serde_json::to_vec(&request_metadata).map_err(new_json_serialize_error)?;
+
+        let mut req = Request::post(&url)
+            .header(CONTENT_LENGTH, content.len())
+            .header(CONTENT_TYPE, "application/json; charset=UTF-8")
+            .extension(Operation::Write)
+            .body(Buffer::from(content))
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
+    /// Upload a chunk to the resumable upload session.
+    ///
+    /// `total` should only be set for the last chunk. The session URI itself
+    /// authorizes the upload, so the request is not signed.
+    pub async fn gcs_upload_resumable_chunk(
+        &self,
+        session: &str,
+        offset: u64,
+        total: Option<u64>,
+        body: Buffer,
+    ) -> Result<Response<Buffer>> {
+        let size = body.len() as u64;
+        let total = total.map_or("*".to_string(), |v| v.to_string());
+        let range = if size == 0 {
+            format!("bytes */{total}")
+        } else {
+            format!("bytes {}-{}/{total}", offset, offset + size - 1)
+        };
+
+        let req = Request::put(session)
+            .header(CONTENT_LENGTH, size)
+            .header(CONTENT_RANGE, range)
+            .extension(Operation::Write)
+            .body(body)
+            .map_err(new_request_build_error)?;
+
+        self.send(req).await
+    }
+
+    /// Query the status of the resumable upload session.
+    ///
+    /// ref: <https://cloud.google.com/storage/docs/performing-resumable-uploads#status-check>
+    pub async fn gcs_query_resumable_upload(&self, session: &str) -> Result<Response<Buffer>> {
+        let req = Request::put(session)
+            .header(CONTENT_LENGTH, 0)
+            .header(CONTENT_RANGE, "bytes */*")
+            .extension(Operation::Write)
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
+
+        self.send(req).await
+    }
+
+    /// Cancel the resumable upload session.
+    ///
+    /// ref: <https://cloud.google.com/storage/docs/performing-resumable-uploads#cancel-upload>
+    pub async fn gcs_cancel_resumable_upload(&self, session: &str) -> Result<Response<Buffer>> {
+        let req = Request::delete(session)
+            .header(CONTENT_LENGTH, 0)
+            .extension(Operation::Write)
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
+
+        self.send(req).await
+    }
+
     pub fn build_metadata_from_object_response(path: &str, data: Buffer) -> Result<Metadata> {
         let meta: GetObjectJsonResponse =
             serde_json::from_reader(data.reader()).map_err(new_json_deserialize_error)?;
@@ -715,6 +840,9 @@ impl GcsCore {
 
         m.set_etag(&meta.etag);
         m.set_content_md5(&meta.md5_hash);
+        if !meta.crc32c.is_empty() {
+            m.set_content_crc32c(&meta.crc32c);
+        }
 
         let size = meta
             .size
@@ -862,6 +990,10 @@ struct GetObjectJsonResponse {
     ///
     /// For example: `"md5Hash": "fHcEH1vPwA6eTPqxuasXcg=="`
     md5_hash: String,
+    /// Content crc32c checksum in big-endian byte order.
+    ///
+    /// For example: `"crc32c": "j/un9g=="`
+    crc32c: String,
     /// Content type of this object.
     ///
     /// For example: `"contentType": "image/png",`
@@ -926,6 +1058,7 @@ mod tests {
             )
         );
         assert_eq!(meta.content_md5(), Some("fHcEH1vPwA6eTPqxuasXcg=="));
+        assert_eq!(meta.content_crc32c(), Some("j/un9g=="));
         assert_eq!(meta.etag(), Some("CKWasoTgyPkCEAE="));
         assert_eq!(meta.content_type(), Some("image/png"));
         assert_eq!(meta.content_encoding(), Some("br"));
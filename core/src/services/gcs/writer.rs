@@ -18,6 +18,9 @@
 use std::sync::Arc;
 
 use bytes::Buf;
+use http::header::RANGE;
+use http::HeaderMap;
+use http::Response;
 use http::StatusCode;
 
 use super::core::CompleteMultipartUploadRequestPart;
@@ -161,3 +164,413 @@ impl oio::MultipartWrite for GcsWriter {
         }
     }
 }
+
+/// GCS requires every chunk of resumable uploads except the last one to be a
+/// multiple of 256 KiB.
+///
+/// ref: <https://cloud.google.com/storage/docs/performing-resumable-uploads#chunked-upload>
+pub(super) const RESUMABLE_CHUNK_ALIGN: usize = 256 * 1024;
+/// The data will be buffered until it reaches this size before uploading.
+const RESUMABLE_CHUNK_SIZE: usize = 8 * 1024 * 1024;
+
+/// GCS returns `499 Client Closed Request` once the upload has been cancelled.
+const STATUS_CLIENT_CLOSED_REQUEST: u16 = 499;
+
+/// GcsResumableWriter uploads data via resumable uploads.
+///
+/// Data that hasn't been persisted by GCS is retained, so that the upload can
+/// continue from the committed offset after transient failures:
+///
+/// - On failure, the writer queries the session status and drops the data that
+///   has been committed. The error is returned so that callers like `RetryLayer`
+///   can retry the same write.
+/// - On retry, the data that has been committed by the failed attempt is skipped.
+/// - If the session is no longer resumable, the upload will restart from scratch
+///   if nothing has been committed, otherwise a permanent error will be returned.
+pub struct GcsResumableWriter {
+    w: GcsWriter,
+
+    /// The session URI of the resumable upload.
+    session: Option<String>,
+    /// The bytes persisted by GCS.
+    committed: u64,
+    /// The bytes accepted by `write`.
+    written: u64,
+    /// The data in `committed..written` that has not been persisted yet.
+    buf: oio::QueueBuf,
+}
+
+enum SessionStatus {
+    /// The upload is active with given committed bytes.
+    Active(u64),
+    /// The upload has been finalized.
+    Finalized(Box<Metadata>),
+    /// The session is no longer resumable.
+    Expired,
+}
+
+impl GcsResumableWriter {
+    pub fn new(core: Arc<GcsCore>, path: &str, op: OpWrite) -> Self {
+        GcsResumableWriter {
+            w: GcsWriter::new(core, path, op),
+            session: None,
+            committed: 0,
+            written: 0,
+            buf: oio::QueueBuf::new(),
+        }
+    }
+
+    async fn session(&mut self) -> Result<String> {
+        if let Some(session) = &self.session {
+            return Ok(session.clone());
+        }
+
+        let resp = self
+            .w
+            .core
+            .gcs_initiate_resumable_upload(&self.w.path, &self.w.op)
+            .await?;
+        if !resp.status().is_success() {
+            return Err(parse_error(resp));
+        }
+
+        let session = parse_location(resp.headers())?
+            .ok_or_else(|| {
+                Error::new(
+                    ErrorKind::Unexpected,
+                    "session uri not present in returning response",
+                )
+            })?
+            .to_string();
+        self.session = Some(session.clone());
+        Ok(session)
+    }
+
+    /// Drop the data that has been persisted by GCS.
+    fn commit(&mut self, committed: u64) {
+        if committed <= self.committed {
+            return;
+        }
+
+        let n = (committed - self.committed).min(self.buf.len() as u64);
+        self.buf.advance(n as usize);
+        self.committed = committed;
+    }
+
+    async fn query(&self, session: &str) -> Result<SessionStatus> {
+        let resp = self.w.core.gcs_query_resumable_upload(session).await?;
+
+        match resp.status() {
+            StatusCode::PERMANENT_REDIRECT => {
+                parse_committed(resp.headers()).map(SessionStatus::Active)
+            }
+            StatusCode::OK | StatusCode::CREATED => {
+                GcsCore::build_metadata_from_object_response(&self.w.path, resp.into_body())
+                    .map(|meta| SessionStatus::Finalized(Box::new(meta)))
+            }
+            StatusCode::NOT_FOUND | StatusCode::GONE => Ok(SessionStatus::Expired),
+            _ => Err(parse_error(resp)),
+        }
+    }
+
+    /// Recover the state from a failed upload.
+    ///
+    /// Returns the metadata if the upload has been finalized, otherwise the
+    /// error that should be reported.
+    async fn recover(&mut self, err: Error) -> Result<Metadata> {
+        if !err.is_temporary() {
+            return Err(err);
+        }
+        let Some(session) = self.session.clone() else {
+            return Err(err);
+        };
+
+        match self.query(&session).await {
+            Ok(SessionStatus::Active(committed)) => self.commit(committed),
+            Ok(SessionStatus::Finalized(meta)) => return Ok(*meta),
+            Ok(SessionStatus::Expired) => {
+                self.session = None;
+                // Data before the committed offset has been dropped, we can't
+                // restart the upload anymore.
+                if self.committed > 0 {
+                    return Err(Error::new(
+                        ErrorKind::Unexpected,
+                        "resumable upload session is no longer resumable",
+                    )
+                    .with_context("committed", self.committed.to_string())
+                    .set_source(err));
+                }
+            }
+            // Keep the original error if the status is unknown.
+            Err(_) => {}
+        }
+        Err(err)
+    }
+
+    /// Upload `body` starting at the committed offset.
+    ///
+    /// `total` should only be set for the last chunk.
+    async fn upload(&mut self, total: Option<u64>, body: Buffer) -> Result<Response<Buffer>> {
+        let session = self.session().await?;
+        self.w
+            .core
+            .gcs_upload_resumable_chunk(&session, self.committed, total, body)
+            .await
+    }
+}
+
+impl oio::Write for GcsResumableWriter {
+    async fn write(&mut self, mut bs: Buffer) -> Result<()> {
+        let size = bs.len() as u64;
+        // Skip the data that has been committed by a failed attempt.
+        let skip = self.committed.saturating_sub(self.written).min(size);
+        bs.advance(skip as usize);
+
+        if self.buf.len() + bs.len() < RESUMABLE_CHUNK_SIZE {
+            self.buf.push(bs);
+            self.written += size;
+            return Ok(());
+        }
+
+        let mut queue = self.buf.clone();
+        queue.push(bs);
+        let len = queue.len() - queue.len() % RESUMABLE_CHUNK_ALIGN;
+        let body = queue.clone().collect().slice(..len);
+
+        let res = match self.upload(None, body).await {
+            Ok(resp) if resp.status() == StatusCode::PERMANENT_REDIRECT => {
+                parse_committed(resp.headers())
+            }
+            Ok(resp) => Err(parse_error(resp)),
+            Err(err) => Err(err),
+        };
+        match res {
+            Ok(committed) => {
+                self.buf = queue;
+                self.written += size;
+                self.commit(committed);
+                Ok(())
+            }
+            Err(err) => {
+                self.recover(err).await?;
+                Err(Error::new(
+                    ErrorKind::Unexpected,
+                    "resumable upload finalized unexpectedly",
+                ))
+            }
+        }
+    }
+
+    async fn close(&mut self) -> Result<Metadata> {
+        if self.session.is_none() {
+            if self.committed > 0 {
+                return Err(Error::new(
+                    ErrorKind::Unexpected,
+                    "resumable upload session is no longer resumable",
+                ));
+            }
+
+            // Nothing has been uploaded, write all data at once.
+            let body = self.buf.clone().collect();
+            let meta = oio::MultipartWrite::write_once(&self.w, self.written, body).await?;
+            self.buf.clear();
+            return Ok(meta);
+        }
+
+        loop {
+            let body = self.buf.clone().collect();
+            let res = match self.upload(Some(self.written), body).await {
+                Ok(resp) => match resp.status() {
+                    StatusCode::OK | StatusCode::CREATED => {
+                        GcsCore::build_metadata_from_object_response(&self.w.path, resp.into_body())
+                            .map(Some)
+                    }
+                    // Not all data has been persisted, continue from the committed offset.
+                    StatusCode::PERMANENT_REDIRECT => {
+                        let committed = parse_committed(resp.headers())?;
+                        if committed <= self.committed {
+                            return Err(Error::new(
+                                ErrorKind::Unexpected,
+                                "resumable upload made no progress",
+                            )
+                            .set_temporary());
+                        }
+                        self.commit(committed);
+                        Ok(None)
+                    }
+                    _ => Err(parse_error(resp)),
+                },
+                Err(err) => Err(err),
+            };
+
+            let meta = match res {
+                Ok(Some(meta)) => meta,
+                Ok(None) => continue,
+                Err(err) => self.recover(err).await?,
+            };
+            self.commit(self.written);
+            return Ok(meta);
+        }
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.buf.clear();
+        let Some(session) = self.session.take() else {
+            return Ok(());
+        };
+
+        let resp = self.w.core.gcs_cancel_resumable_upload(&session).await?;
+        match resp.status().as_u16() {
+            STATUS_CLIENT_CLOSED_REQUEST | 204 | 404 | 410 => Ok(()),
+            _ => Err(parse_error(resp)),
+        }
+    }
+}
+
+/// Parse the committed bytes from the `Range` header like `bytes=0-42`.
+///
+/// The header will be absent if no bytes have been committed.
+fn parse_committed(headers: &HeaderMap) -> Result<u64> {
+    let Some(range) = parse_header_to_str(headers, RANGE)? else {
+        return Ok(0);
+    };
+
+    range
+        .strip_prefix("bytes=0-")
+        .and_then(|v| v.parse::<u64>().ok())
+        .map(|v| v + 1)
+        .ok_or_else(|| {
+            Error::new(ErrorKind::Unexpected, "range header is invalid")
+                .with_context("range", range)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    use http::header::CONTENT_RANGE;
+    use http::header::LOCATION;
+    use http::HeaderValue;
+    use http::Method;
+
+    use super::*;
+    use crate::layers::HttpClientLayer;
+    use crate::layers::RetryLayer;
+    use crate::services::Gcs;
+
+    const SESSION: &str = "http://gcs.test/session";
+
+    /// A mocked resumable upload server which fails the first chunk after
+    /// persisting part of it.
+    #[derive(Default)]
+    struct MockServer {
+        data: Mutex<Vec<u8>>,
+        failed: Mutex<bool>,
+    }
+
+    impl MockServer {
+        fn response(status: u16, committed: usize, body: &str) -> http::Response<HttpBody> {
+            let mut resp = http::Response::builder().status(status);
+            if status == 308 && committed > 0 {
+                resp = resp.header(RANGE, format!("bytes=0-{}", committed - 1));
+            }
+            if status == 200 && body.is_empty() {
+                resp = resp.header(LOCATION, SESSION);
+            }
+            let body = Buffer::from(body.to_string());
+            let size = body.len() as u64;
+            resp.body(HttpBody::new(
+                futures::stream::iter(vec![Ok(body)]),
+                Some(size),
+            ))
+            .unwrap()
+        }
+
+        fn finalized(size: usize) -> http::Response<HttpBody> {
+            Self::response(
+                200,
+                size,
+                &format!(
+                    r#"{{"size":"{size}","etag":"etag","updated":"2022-08-15T11:33:34.866Z"}}"#
+                ),
+            )
+        }
+    }
+
+    impl HttpFetch for Arc<MockServer> {
+        async fn fetch(&self, req: http::Request<Buffer>) -> Result<http::Response<HttpBody>> {
+            if req.method() == Method::POST {
+                assert!(req.uri().to_string().contains("uploadType=resumable"));
+                return Ok(MockServer::response(200, 0, ""));
+            }
+            assert_eq!(req.uri().to_string(), SESSION);
+
+            let range = req.headers()[CONTENT_RANGE].to_str().unwrap().to_string();
+            let mut data = self.data.lock().unwrap();
+            let (range, total) = range
+                .strip_prefix("bytes ")
+                .unwrap()
+                .split_once('/')
+                .unwrap();
+            let total = total.parse::<usize>().ok();
+            if range != "*" {
+                let start = range.split_once('-').unwrap().0.parse::<usize>().unwrap();
+                assert_eq!(start, data.len(), "chunk must start at committed offset");
+
+                let body = req.into_body().to_vec();
+                let mut failed = self.failed.lock().unwrap();
+                if !*failed {
+                    *failed = true;
+                    data.extend_from_slice(&body[..RESUMABLE_CHUNK_ALIGN]);
+                    return Err(
+                        Error::new(ErrorKind::Unexpected, "connection reset").set_temporary()
+                    );
+                }
+                data.extend_from_slice(&body);
+            }
+
+            match total {
+                Some(total) if total == data.len() => Ok(MockServer::finalized(total)),
+                _ => Ok(MockServer::response(308, data.len(), "")),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resumable_upload() {
+        let server = Arc::new(MockServer::default());
+        let builder = Gcs::default()
+            .bucket("test")
+            .endpoint("http://gcs.test")
+            .token("token".to_string())
+            .enable_resumable_upload();
+        let op = Operator::new(builder)
+            .unwrap()
+            .layer(HttpClientLayer::new(HttpClient::with(server.clone())))
+            .layer(RetryLayer::new().with_min_delay(Duration::from_millis(1)))
+            .finish();
+
+        let content: Vec<u8> = (0..RESUMABLE_CHUNK_SIZE + 1024)
+            .map(|v| (v % 251) as u8)
+            .collect();
+        let meta = op.write("test", content.clone()).await.unwrap();
+        assert_eq!(meta.content_length(), content.len() as u64);
+
+        assert!(*server.failed.lock().unwrap());
+        assert_eq!(*server.data.lock().unwrap(), content);
+    }
+
+    #[test]
+    fn test_parse_committed() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(parse_committed(&headers).unwrap(), 0);
+
+        headers.insert(RANGE, HeaderValue::from_static("bytes=0-262143"));
+        assert_eq!(parse_committed(&headers).unwrap(), 262144);
+
+        headers.insert(RANGE, HeaderValue::from_static("bytes=1-2"));
+        assert!(parse_committed(&headers).is_err());
+    }
+}
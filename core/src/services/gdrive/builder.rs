@@ -150,6 +150,8 @@ impl Builder for GdriveBuilder {
                 read: true,
 
                 list: true,
+                list_with_modified_after: true,
+                list_with_modified_before: true,
 
                 write: true,
 
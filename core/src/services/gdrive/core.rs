@@ -23,6 +23,7 @@ use bytes;
 use bytes::Buf;
 use bytes::Bytes;
 use chrono::DateTime;
+use chrono::SecondsFormat;
 use chrono::Utc;
 use http::header;
 use http::Request;
@@ -100,8 +101,22 @@ impl GdriveCore {
         file_id: &str,
         page_size: i32,
         next_page_token: &str,
+        args: &OpList,
     ) -> Result<Response<Buffer>> {
-        let q = format!("'{file_id}' in parents and trashed = false");
+        let mut q = format!("'{file_id}' in parents and trashed = false");
+        // Folders are always returned so that they can still be walked.
+        if let Some(t) = args.modified_after() {
+            q.push_str(&format!(
+                " and (mimeType = 'application/vnd.google-apps.folder' or modifiedTime > '{}')",
+                t.to_rfc3339_opts(SecondsFormat::Millis, true)
+            ));
+        }
+        if let Some(t) = args.modified_before() {
+            q.push_str(&format!(
+                " and (mimeType = 'application/vnd.google-apps.folder' or modifiedTime < '{}')",
+                t.to_rfc3339_opts(SecondsFormat::Millis, true)
+            ));
+        }
         let url = "https://www.googleapis.com/drive/v3/files";
         let mut url = QueryPairsWriter::new(url);
         url = url.push("pageSize", &page_size.to_string());
@@ -28,11 +28,12 @@ use crate::*;
 pub struct GdriveLister {
     path: String,
     core: Arc<GdriveCore>,
+    args: OpList,
 }
 
 impl GdriveLister {
-    pub fn new(path: String, core: Arc<GdriveCore>) -> Self {
-        Self { path, core }
+    pub fn new(path: String, core: Arc<GdriveCore>, args: OpList) -> Self {
+        Self { path, core, args }
     }
 }
 
@@ -50,7 +51,7 @@ impl oio::PageList for GdriveLister {
 
         let resp = self
             .core
-            .gdrive_list(file_id.as_str(), 100, &ctx.token)
+            .gdrive_list(file_id.as_str(), 100, &ctx.token, &self.args)
             .await?;
 
         let bytes = match resp.status() {
@@ -23,6 +23,7 @@ use http::Response;
 use http::StatusCode;
 use log::debug;
 
+use super::core::parse_ranges_response;
 use super::core::HttpCore;
 use super::error::parse_error;
 use super::DEFAULT_SCHEME;
@@ -163,11 +164,15 @@ impl Builder for HttpBuilder {
                 stat: true,
                 stat_with_if_match: true,
                 stat_with_if_none_match: true,
+                stat_with_headers: true,
 
                 read: true,
 
                 read_with_if_match: true,
                 read_with_if_none_match: true,
+                read_with_headers: true,
+                read_with_multi_range: true,
+                read_with_decompress: cfg!(feature = "layers-decompress"),
 
                 presign: auth.is_none(),
                 presign_read: auth.is_none(),
@@ -197,6 +202,12 @@ impl Builder for HttpBuilder {
     }
 }
 
+/// The reader of content-encoded responses decompressed on the fly.
+#[cfg(feature = "layers-decompress")]
+type DecompressReader = crate::layers::DecompressReader<HttpBody>;
+#[cfg(not(feature = "layers-decompress"))]
+type DecompressReader = ();
+
 /// Backend is used to serve `Accessor` support for http.
 #[derive(Clone)]
 pub struct HttpBackend {
@@ -211,8 +222,61 @@ impl Debug for HttpBackend {
     }
 }
 
+impl HttpBackend {
+    /// Read the whole object with `Accept-Encoding` and decompress it if the
+    /// response is content-encoded, the range is applied after decompression.
+    #[cfg(feature = "layers-decompress")]
+    async fn read_decompressed(
+        &self,
+        path: &str,
+        args: OpRead,
+    ) -> Result<(RpRead, <Self as Access>::Reader)> {
+        use crate::layers::DecompressCodec;
+
+        if !args.ranges().is_empty() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "multiple ranges can't be used together with decompress",
+            ));
+        }
+
+        let range = args.range();
+        let resp = self
+            .core
+            .http_get(path, BytesRange::default(), &args)
+            .await?;
+        if resp.status() != StatusCode::OK {
+            let (part, mut body) = resp.into_parts();
+            let buf = body.to_buffer().await?;
+            return Err(parse_error(Response::from_parts(part, buf)));
+        }
+
+        let codec = parse_content_encoding(resp.headers())?
+            .and_then(DecompressCodec::from_content_encoding);
+        if let Some(codec) = codec {
+            let r = DecompressReader::new(resp.into_body(), codec, range.offset(), range.size())?;
+            return Ok((RpRead::default(), ThreeWays::Three(r)));
+        }
+
+        // The server returns the stored bytes, apply the range by ourselves.
+        if range.is_full() {
+            let meta = parse_read_metadata(path, resp.headers())?;
+            return Ok((
+                RpRead::default().with_metadata(meta),
+                ThreeWays::One(resp.into_body()),
+            ));
+        }
+        let buf = resp.into_body().to_buffer().await?;
+        let start = (range.offset() as usize).min(buf.len());
+        let end = range
+            .size()
+            .map_or(buf.len(), |size| (start + size as usize).min(buf.len()));
+        Ok((RpRead::default(), ThreeWays::Two(buf.slice(start..end))))
+    }
+}
+
 impl Access for HttpBackend {
-    type Reader = HttpBody;
+    type Reader = ThreeWays<HttpBody, Buffer, DecompressReader>;
     type Writer = ();
     type Lister = ();
     type Deleter = ();
@@ -243,13 +307,27 @@ impl Access for HttpBackend {
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        #[cfg(feature = "layers-decompress")]
+        if args.decompress() {
+            return self.read_decompressed(path, args).await;
+        }
+
         let resp = self.core.http_get(path, args.range(), &args).await?;
 
         let status = resp.status();
 
         match status {
             StatusCode::OK | StatusCode::PARTIAL_CONTENT => {
-                Ok((RpRead::default(), resp.into_body()))
+                if args.ranges().is_empty() {
+                    let meta = parse_read_metadata(path, resp.headers())?;
+                    let rp = RpRead::default().with_metadata(meta);
+                    return Ok((rp, ThreeWays::One(resp.into_body())));
+                }
+
+                let (parts, mut body) = resp.into_parts();
+                let buf = body.to_buffer().await?;
+                let buf = parse_ranges_response(&parts, buf, args.ranges())?;
+                Ok((RpRead::default(), ThreeWays::Two(buf)))
             }
             _ => {
                 let (part, mut body) = resp.into_parts();
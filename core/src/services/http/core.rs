@@ -24,6 +24,7 @@ use http::header::IF_MATCH;
 use http::header::IF_NONE_MATCH;
 use http::Request;
 use http::Response;
+use http::StatusCode;
 
 use crate::raw::*;
 use crate::*;
@@ -75,7 +76,18 @@ impl HttpCore {
             req = req.header(header::AUTHORIZATION, auth.clone())
         }
 
-        if !range.is_full() {
+        if args.decompress() {
+            req = req.header(header::ACCEPT_ENCODING, "gzip, zstd");
+        }
+
+        if !args.ranges().is_empty() {
+            let ranges = args
+                .ranges()
+                .iter()
+                .map(|range| range.to_string())
+                .collect::<Vec<_>>();
+            req = req.header(header::RANGE, format!("bytes={}", ranges.join(",")));
+        } else if !range.is_full() {
             req = req.header(header::RANGE, range.to_header());
         }
 
@@ -90,7 +102,8 @@ impl HttpCore {
         range: BytesRange,
         args: &OpRead,
     ) -> Result<Response<HttpBody>> {
-        let req = self.http_get_request(path, range, args)?;
+        let mut req = self.http_get_request(path, range, args)?;
+        merge_extra_headers(&mut req, args.headers());
         self.info.http_client().fetch(req).await
     }
 
@@ -119,7 +132,104 @@ impl HttpCore {
     }
 
     pub async fn http_head(&self, path: &str, args: &OpStat) -> Result<Response<Buffer>> {
-        let req = self.http_head_request(path, args)?;
+        let mut req = self.http_head_request(path, args)?;
+        merge_extra_headers(&mut req, args.headers());
         self.info.http_client().send(req).await
     }
 }
+
+/// Pick the content of requested ranges from the response of a multi-range
+/// request in the given order.
+///
+/// Servers could respond with a `multipart/byteranges` body, a single range
+/// that covers all requested ranges or the whole content.
+pub fn parse_ranges_response(
+    parts: &http::response::Parts,
+    body: Buffer,
+    ranges: &[BytesRange],
+) -> Result<Buffer> {
+    let mut chunks = Vec::new();
+    if parts.status == StatusCode::PARTIAL_CONTENT {
+        let is_multipart = parse_content_type(&parts.headers)?
+            .is_some_and(|v| v.starts_with("multipart/byteranges"));
+        match parse_multipart_boundary(&parts.headers)? {
+            Some(boundary) if is_multipart => {
+                for (range, bs) in parse_multipart_byteranges(boundary, body.to_bytes())? {
+                    let Some(range) = range.range() else {
+                        continue;
+                    };
+                    chunks.push((range.start, bs));
+                }
+            }
+            _ => {
+                let range = parse_content_range(&parts.headers)?
+                    .and_then(|v| v.range())
+                    .ok_or_else(|| {
+                        Error::new(
+                            ErrorKind::Unexpected,
+                            "partial content response has no content range",
+                        )
+                    })?;
+                chunks.push((range.start, body.to_bytes()));
+            }
+        }
+    } else {
+        chunks.push((0, body.to_bytes()));
+    }
+
+    let mut bufs = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        let (start, bs) = chunks
+            .iter()
+            .find(|(start, bs)| {
+                // The chunk must cover the whole range, otherwise we would
+                // return less data than requested.
+                let end = start + bs.len() as u64;
+                let range_end = range.size().map_or(range.offset(), |v| range.offset() + v);
+                *start <= range.offset() && range_end <= end
+            })
+            .ok_or_else(|| {
+                Error::new(
+                    ErrorKind::Unexpected,
+                    "response doesn't contain the requested range",
+                )
+                .with_context("range", range)
+            })?;
+        let range = BytesRange::new(range.offset() - start, range.size());
+        bufs.push(bs.slice(range.to_range_as_usize_within(bs.len())));
+    }
+    Ok(bufs.into_iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use http::header::CONTENT_RANGE;
+
+    use super::*;
+
+    fn partial_content(content_range: &str) -> http::response::Parts {
+        Response::builder()
+            .status(StatusCode::PARTIAL_CONTENT)
+            .header(CONTENT_RANGE, content_range)
+            .body(())
+            .unwrap()
+            .into_parts()
+            .0
+    }
+
+    #[test]
+    fn test_parse_ranges_response() {
+        let parts = partial_content("bytes 2-7/10");
+        let ranges = [BytesRange::new(2, Some(2)), BytesRange::new(5, Some(3))];
+        let buf = parse_ranges_response(&parts, Buffer::from("234567"), &ranges).unwrap();
+        assert_eq!(buf.to_bytes(), "23567");
+
+        // The response is shorter than the requested range.
+        let parts = partial_content("bytes 2-4/10");
+        let ranges = [BytesRange::new(2, Some(5))];
+        let err = parse_ranges_response(&parts, Buffer::from("234"), &ranges)
+            .map(|_| ())
+            .expect_err("truncated range must be rejected");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+    }
+}
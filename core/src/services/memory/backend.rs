@@ -76,6 +76,7 @@ impl MemoryAccessor {
         info.set_root("/");
         info.set_native_capability(Capability {
             read: true,
+            read_with_multi_range: true,
             write: true,
             write_can_empty: true,
             write_with_cache_control: true,
@@ -86,6 +87,7 @@ impl MemoryAccessor {
             stat: true,
             list: true,
             list_with_recursive: true,
+            list_with_sorted: true,
             shared: false,
             ..Default::default()
         });
@@ -143,10 +145,21 @@ impl Access for MemoryAccessor {
             }
         };
 
-        Ok((
-            RpRead::new(),
-            value.content.slice(args.range().to_range_as_usize()),
-        ))
+        if !args.ranges().is_empty() {
+            let buf = args
+                .ranges()
+                .iter()
+                .flat_map(|range| {
+                    let range = range.to_range_as_usize_within(value.content.len());
+                    value.content.slice(range)
+                })
+                .collect();
+            return Ok((RpRead::new(), buf));
+        }
+
+        let range = args.range().to_range_as_usize_within(value.content.len());
+        let rp = RpRead::new().with_metadata(Some(value.metadata));
+        Ok((rp, value.content.slice(range)))
     }
 
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
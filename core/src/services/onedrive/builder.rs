@@ -27,6 +27,7 @@ use services::onedrive::core::OneDriveSigner;
 use tokio::sync::Mutex;
 
 use super::backend::OnedriveBackend;
+use super::writer::OneDriveWriter;
 use super::DEFAULT_SCHEME;
 use crate::raw::normalize_root;
 use crate::raw::Access;
@@ -157,6 +158,8 @@ impl Builder for OnedriveBuilder {
                 // However, we can't enable this, otherwise OpenDAL behavior tests will try to test creating huge
                 // file up to this size.
                 // write_total_max_size: Some(250 * 1024 * 1024 * 1024),
+                // Upload sessions require every chunk except the last one to be aligned.
+                write_multi_align_size: Some(OneDriveWriter::CHUNK_ALIGN),
                 copy: true,
                 rename: true,
 
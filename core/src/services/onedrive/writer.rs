@@ -38,8 +38,9 @@ impl OneDriveWriter {
     const MAX_SIMPLE_SIZE: usize = 4 * 1024 * 1024; // 4MB
 
     // OneDrive demands the chunk size to be a multiple to to 320 KiB.
+    pub(super) const CHUNK_ALIGN: usize = 320 * 1024;
     // Choose a value smaller than `MAX_SIMPLE_SIZE`
-    const CHUNK_SIZE_FACTOR: usize = 327_680 * 12; // floor(MAX_SIMPLE_SIZE / 320KB)
+    const CHUNK_SIZE_FACTOR: usize = Self::CHUNK_ALIGN * 12; // floor(MAX_SIMPLE_SIZE / 320KB)
 
     pub fn new(core: Arc<OneDriveCore>, op: OpWrite, path: String) -> Self {
         OneDriveWriter { core, op, path }
@@ -23,11 +23,15 @@ use std::str::FromStr;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 use std::sync::LazyLock;
+use std::time::Duration;
 
 use base64::prelude::BASE64_STANDARD;
 use base64::Engine;
+use bytes::Buf;
 use constants::X_AMZ_META_PREFIX;
 use constants::X_AMZ_VERSION_ID;
+use http::header::CONTENT_LENGTH;
+use http::Request;
 use http::Response;
 use http::StatusCode;
 use log::debug;
@@ -77,6 +81,7 @@ impl Configurator for S3Config {
         S3Builder {
             config: self,
             customized_credential_load: None,
+            request_signer: None,
 
             http_client: None,
         }
@@ -92,6 +97,7 @@ pub struct S3Builder {
     config: S3Config,
 
     customized_credential_load: Option<Box<dyn AwsCredentialLoad>>,
+    request_signer: Option<Box<dyn RequestSigner>>,
 
     #[deprecated(since = "0.53.0", note = "Use `Operator::update_http_client` instead")]
     http_client: Option<HttpClient>,
@@ -475,6 +481,16 @@ impl S3Builder {
         self
     }
 
+    /// Sign requests with the given signer instead of the built-in AWS SigV4 signer.
+    ///
+    /// It's useful for S3-compatible gateways with custom authentication schemes.
+    /// The signer will be called for every request including presign, and
+    /// credential loading will be skipped.
+    pub fn request_signer(mut self, signer: Box<dyn RequestSigner>) -> Self {
+        self.request_signer = Some(signer);
+        self
+    }
+
     /// Specify the http client that used by this service.
     ///
     /// # Notes
@@ -600,6 +616,12 @@ impl S3Builder {
         self
     }
 
+    /// Enable delete with if match so that opendal will send delete request with if match headers.
+    pub fn enable_delete_with_if_match(mut self) -> Self {
+        self.config.enable_delete_with_if_match = true;
+        self
+    }
+
     /// Detect region of S3 bucket.
     ///
     /// # Args
@@ -923,6 +945,8 @@ impl Builder for S3Builder {
                                 .config
                                 .disable_stat_with_override,
                             stat_with_version: self.config.enable_versioning,
+                            stat_with_tags: true,
+                            stat_with_headers: true,
 
                             read: true,
                             read_with_if_match: true,
@@ -933,6 +957,8 @@ impl Builder for S3Builder {
                             read_with_override_content_disposition: true,
                             read_with_override_content_type: true,
                             read_with_version: self.config.enable_versioning,
+                            read_with_suffix_range: true,
+                            read_with_headers: true,
 
                             write: true,
                             write_can_empty: true,
@@ -943,8 +969,13 @@ impl Builder for S3Builder {
                             write_with_content_type: true,
                             write_with_content_encoding: true,
                             write_with_if_match: !self.config.disable_write_with_if_match,
+                            write_with_encryption: true,
                             write_with_if_not_exists: true,
                             write_with_user_metadata: true,
+                            write_with_storage_class: true,
+                            write_with_retention: true,
+                            write_with_legal_hold: true,
+                            write_with_headers: true,
 
                             // The min multipart size of S3 is 5 MiB.
                             //
@@ -962,20 +993,32 @@ impl Builder for S3Builder {
                             delete: true,
                             delete_max_size: Some(delete_max_size),
                             delete_with_version: self.config.enable_versioning,
+                            delete_with_if_match: self.config.enable_delete_with_if_match,
 
                             copy: true,
+                            copy_with_metadata_directive: true,
 
                             list: true,
                             list_with_limit: true,
                             list_with_start_after: true,
                             list_with_recursive: true,
+                            list_with_sorted: true,
+                            list_has_metadata: true,
                             list_with_versions: self.config.enable_versioning,
                             list_with_deleted: self.config.enable_versioning,
 
+                            set_tags: true,
+
+                            abort_multipart: true,
+                            list_multipart_uploads: true,
+
                             presign: true,
                             presign_stat: true,
                             presign_read: true,
                             presign_write: true,
+                            presign_multipart: true,
+                            // SigV4 presigned URLs are valid for at most 7 days.
+                            presign_max_expiry: Some(Duration::from_secs(7 * 24 * 60 * 60)),
 
                             shared: true,
 
@@ -1003,6 +1046,7 @@ impl Builder for S3Builder {
                 disable_list_objects_v2: self.config.disable_list_objects_v2,
                 enable_request_payer: self.config.enable_request_payer,
                 signer,
+                request_signer: self.request_signer,
                 loader,
                 credential_loaded: AtomicBool::new(false),
                 checksum_algorithm,
@@ -1028,6 +1072,8 @@ impl Access for S3Backend {
     }
 
     async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let with_tags = args.tags();
+        let version = args.version().map(|v| v.to_string());
         let resp = self.core.s3_head_object(path, args).await?;
 
         let status = resp.status();
@@ -1046,6 +1092,30 @@ impl Access for S3Backend {
                     meta.set_version(v);
                 }
 
+                parse_object_lock_headers(headers, &mut meta)?;
+                parse_checksum_headers(headers, &mut meta)?;
+
+                if with_tags {
+                    let resp = self
+                        .core
+                        .s3_get_object_tagging(path, version.as_deref())
+                        .await?;
+                    if resp.status() != StatusCode::OK {
+                        return Err(parse_error(resp));
+                    }
+
+                    let output: Tagging = quick_xml::de::from_reader(resp.into_body().reader())
+                        .map_err(new_xml_deserialize_error)?;
+                    meta = meta.with_tags(
+                        output
+                            .tag_set
+                            .tag
+                            .into_iter()
+                            .map(|tag| (tag.key, tag.value))
+                            .collect(),
+                    );
+                }
+
                 Ok(RpStat::new(meta))
             }
             _ => Err(parse_error(resp)),
@@ -1058,7 +1128,20 @@ impl Access for S3Backend {
         let status = resp.status();
         match status {
             StatusCode::OK | StatusCode::PARTIAL_CONTENT => {
-                Ok((RpRead::default(), resp.into_body()))
+                let headers = resp.headers();
+                let mut meta = parse_read_metadata(path, headers)?;
+                if let Some(mut m) = meta.take() {
+                    let user_meta = parse_prefixed_headers(headers, X_AMZ_META_PREFIX);
+                    if !user_meta.is_empty() {
+                        m = m.with_user_metadata(user_meta);
+                    }
+                    if let Some(v) = parse_header_to_str(headers, X_AMZ_VERSION_ID)? {
+                        m.set_version(v);
+                    }
+                    parse_checksum_headers(headers, &mut m)?;
+                    meta = Some(m);
+                }
+                Ok((RpRead::default().with_metadata(meta), resp.into_body()))
             }
             _ => {
                 let (part, mut body) = resp.into_parts();
@@ -1115,8 +1198,8 @@ impl Access for S3Backend {
         Ok((RpList::default(), l))
     }
 
-    async fn copy(&self, from: &str, to: &str, _args: OpCopy) -> Result<RpCopy> {
-        let resp = self.core.s3_copy_object(from, to).await?;
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let resp = self.core.s3_copy_object(from, to, &args).await?;
 
         let status = resp.status();
 
@@ -1126,6 +1209,85 @@ impl Access for S3Backend {
         }
     }
 
+    async fn set_tags(&self, path: &str, args: OpSetTags) -> Result<RpSetTags> {
+        let resp = self.core.s3_put_object_tagging(path, args.tags()).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => Ok(RpSetTags::default()),
+            _ => Err(parse_error(resp)),
+        }
+    }
+
+    async fn abort_multipart(
+        &self,
+        path: &str,
+        args: OpAbortMultipart,
+    ) -> Result<RpAbortMultipart> {
+        let resp = self
+            .core
+            .s3_abort_multipart_upload(path, args.upload_id(), &OpWrite::default())
+            .await?;
+
+        let status = resp.status();
+
+        match status {
+            // s3 returns code 204 if abort succeeds.
+            StatusCode::NO_CONTENT => Ok(RpAbortMultipart::new()),
+            _ => Err(parse_error(resp)),
+        }
+    }
+
+    async fn list_multipart_uploads(
+        &self,
+        path: &str,
+        _: OpListMultipartUploads,
+    ) -> Result<RpListMultipartUploads> {
+        let mut uploads = Vec::new();
+        let mut key_marker = String::new();
+        let mut upload_id_marker = String::new();
+
+        loop {
+            let resp = self
+                .core
+                .s3_list_multipart_uploads(path, &key_marker, &upload_id_marker)
+                .await?;
+            if resp.status() != StatusCode::OK {
+                return Err(parse_error(resp));
+            }
+
+            let output: ListMultipartUploadsOutput =
+                quick_xml::de::from_reader(resp.into_body().reader())
+                    .map_err(new_xml_deserialize_error)?;
+
+            for upload in output.upload {
+                let mut v = MultipartUpload::new(
+                    &build_rel_path(&self.core.root, &upload.key),
+                    &upload.upload_id,
+                );
+                if let Some(initiated) = upload.initiated {
+                    v = v.with_initiated(parse_datetime_from_rfc3339(&initiated)?);
+                }
+                uploads.push(v);
+            }
+
+            match (
+                output.is_truncated,
+                output.next_key_marker,
+                output.next_upload_id_marker,
+            ) {
+                (Some(true), Some(key), Some(upload_id)) => {
+                    key_marker = key;
+                    upload_id_marker = upload_id;
+                }
+                _ => break,
+            }
+        }
+
+        Ok(RpListMultipartUploads::new(uploads))
+    }
+
     async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
         let (expire, op) = args.into_parts();
         // We will not send this request out, just for signing.
@@ -1135,14 +1297,30 @@ impl Access for S3Backend {
                 self.core
                     .s3_get_object_request(path, BytesRange::default(), &v)
             }
-            PresignOperation::Write(_) => {
+            PresignOperation::Write(v) => {
                 self.core
-                    .s3_put_object_request(path, None, &OpWrite::default(), Buffer::new())
+                    .s3_put_object_request(path, None, &v, Buffer::new())
             }
             PresignOperation::Delete(_) => Err(Error::new(
                 ErrorKind::Unsupported,
                 "operation is not supported",
             )),
+            PresignOperation::CreateMultipart(v) => {
+                self.core.s3_initiate_multipart_upload_request(path, &v)
+            }
+            // The body of following requests will be decided by the client, so we
+            // must not sign the content length.
+            PresignOperation::UploadPart {
+                upload_id,
+                part_number,
+            } => self
+                .core
+                .s3_upload_part_request(path, &upload_id, part_number, 0, Buffer::new(), None)
+                .map(without_content_length),
+            PresignOperation::CompleteMultipart { upload_id } => self
+                .core
+                .s3_complete_multipart_upload_request(path, &upload_id, vec![])
+                .map(without_content_length),
         };
         let mut req = req?;
 
@@ -1159,8 +1337,15 @@ impl Access for S3Backend {
     }
 }
 
+fn without_content_length(mut req: Request<Buffer>) -> Request<Buffer> {
+    req.headers_mut().remove(CONTENT_LENGTH);
+    req
+}
+
 #[cfg(test)]
 mod tests {
+    use http::Method;
+
     use super::*;
 
     #[test]
@@ -1264,4 +1449,216 @@ mod tests {
             assert_eq!(region.as_deref(), expected, "{name}");
         }
     }
+
+    #[derive(Debug)]
+    struct TestSigner;
+
+    impl RequestSigner for TestSigner {
+        fn sign(&self, req: &mut Request<Buffer>, expire: Option<Duration>) -> Result<()> {
+            match expire {
+                Some(expire) => {
+                    let uri = format!("{}?sig=test&expire={}", req.uri(), expire.as_secs());
+                    *req.uri_mut() = uri.parse().expect("uri must be valid");
+                }
+                None => {
+                    req.headers_mut()
+                        .insert(http::header::AUTHORIZATION, "test".parse().unwrap());
+                }
+            }
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_request_signer() {
+        let op = Operator::new(
+            S3Builder::default()
+                .bucket("test")
+                .region("us-east-1")
+                .endpoint("http://127.0.0.1:9000")
+                .request_signer(Box::new(TestSigner)),
+        )
+        .unwrap()
+        .finish();
+
+        let req = op
+            .presign_read("file", Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert_eq!(req.uri().query(), Some("sig=test&expire=60"));
+    }
+
+    /// VersionServer records the requests and serves two versions of `file`.
+    #[derive(Default)]
+    struct VersionServer {
+        requests: std::sync::Mutex<Vec<(Method, String)>>,
+    }
+
+    impl HttpFetch for Arc<VersionServer> {
+        async fn fetch(&self, req: Request<Buffer>) -> Result<Response<HttpBody>> {
+            let uri = req.uri().to_string();
+            self.requests
+                .lock()
+                .unwrap()
+                .push((req.method().clone(), uri.clone()));
+
+            let list_versions = req
+                .uri()
+                .query()
+                .is_some_and(|v| v.split('&').any(|v| v == "versions"));
+            let body = if list_versions {
+                r#"<ListVersionsResult>
+                    <IsTruncated>false</IsTruncated>
+                    <Version>
+                        <Key>file</Key>
+                        <VersionId>v2</VersionId>
+                        <IsLatest>true</IsLatest>
+                        <LastModified>2009-12-09T00:18:23.000Z</LastModified>
+                        <Size>13</Size>
+                    </Version>
+                    <Version>
+                        <Key>file</Key>
+                        <VersionId>v1</VersionId>
+                        <IsLatest>false</IsLatest>
+                        <LastModified>2009-12-09T00:18:08.000Z</LastModified>
+                        <Size>5</Size>
+                    </Version>
+                </ListVersionsResult>"#
+            } else if req.method() == Method::DELETE {
+                ""
+            } else {
+                "Hello"
+            };
+            let status = match req.method() {
+                &Method::DELETE => StatusCode::NO_CONTENT,
+                _ => StatusCode::OK,
+            };
+            let size = body.len() as u64;
+            let body = Buffer::from(body);
+            Ok(Response::builder()
+                .status(status)
+                .header(CONTENT_LENGTH, size)
+                .header(constants::X_AMZ_VERSION_ID, "v1")
+                .body(HttpBody::new(futures::stream::iter([Ok(body)]), Some(size)))
+                .unwrap())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_versions() -> Result<()> {
+        let server = Arc::new(VersionServer::default());
+        let op = Operator::new(
+            S3Builder::default()
+                .bucket("test")
+                .region("us-east-1")
+                .endpoint("http://127.0.0.1:9000")
+                .access_key_id("access_key_id")
+                .secret_access_key("secret_access_key")
+                .enable_versioning(true),
+        )?
+        .layer(crate::layers::HttpClientLayer::new(HttpClient::with(
+            server.clone(),
+        )))
+        .finish();
+
+        let entries = op.list_with("").versions(true).await?;
+        let versions: Vec<_> = entries
+            .iter()
+            .map(|e| (e.path(), e.metadata().version()))
+            .collect();
+        assert_eq!(versions, [("file", Some("v2")), ("file", Some("v1"))]);
+
+        assert_eq!(op.read_with("file").version("v1").await?.to_vec(), b"Hello");
+        let meta = op.stat_with("file").version("v1").await?;
+        assert_eq!(meta.version(), Some("v1"));
+        op.delete_with("file").version("v1").await?;
+
+        // Every request targets the given version instead of the latest one.
+        let requests = server.requests.lock().unwrap();
+        for method in [Method::GET, Method::HEAD, Method::DELETE] {
+            let (_, uri) = requests
+                .iter()
+                .rfind(|(m, _)| *m == method)
+                .expect("request must be sent");
+            assert!(uri.contains("versionId=v1"), "{method} {uri}");
+        }
+        Ok(())
+    }
+
+    const ETAG: &str = "\"5d41402abc4b2a76b9719d911017c592\"";
+    const LAST_MODIFIED: &str = "Wed, 09 Dec 2009 00:18:08 GMT";
+
+    /// ConditionalServer serves a file with fixed etag and last modified,
+    /// like S3, it returns 304 if the conditions of GET are not matched.
+    struct ConditionalServer;
+
+    impl HttpFetch for ConditionalServer {
+        async fn fetch(&self, req: Request<Buffer>) -> Result<Response<HttpBody>> {
+            let headers = req.headers();
+            let none_match = headers
+                .get(http::header::IF_NONE_MATCH)
+                .is_some_and(|v| v == ETAG);
+            let not_modified = match headers.get(http::header::IF_MODIFIED_SINCE) {
+                Some(v) => {
+                    parse_datetime_from_rfc2822(v.to_str().unwrap())?
+                        >= parse_datetime_from_rfc2822(LAST_MODIFIED)?
+                }
+                None => false,
+            };
+
+            let (status, body) = if none_match || not_modified {
+                (StatusCode::NOT_MODIFIED, "")
+            } else {
+                (StatusCode::OK, "Hello")
+            };
+            let size = body.len() as u64;
+            let body = Buffer::from(body);
+            Ok(Response::builder()
+                .status(status)
+                .header(CONTENT_LENGTH, size)
+                .header(http::header::ETAG, ETAG)
+                .header(http::header::LAST_MODIFIED, LAST_MODIFIED)
+                .body(HttpBody::new(futures::stream::iter([Ok(body)]), Some(size)))
+                .unwrap())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_conditional_read() -> Result<()> {
+        let op = Operator::new(
+            S3Builder::default()
+                .bucket("test")
+                .region("us-east-1")
+                .endpoint("http://127.0.0.1:9000")
+                .access_key_id("access_key_id")
+                .secret_access_key("secret_access_key"),
+        )?
+        .layer(crate::layers::HttpClientLayer::new(HttpClient::with(
+            ConditionalServer,
+        )))
+        .finish();
+
+        let cap = op.info().full_capability();
+        assert!(cap.read_with_if_none_match);
+        assert!(cap.read_with_if_modified_since);
+
+        let err = op.read_with("file").if_none_match(ETAG).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+        let bs = op.read_with("file").if_none_match("\"other\"").await?;
+        assert_eq!(bs.to_vec(), b"Hello");
+
+        let last_modified = parse_datetime_from_rfc2822(LAST_MODIFIED)?;
+        let err = op
+            .read_with("file")
+            .if_modified_since(last_modified)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+        let bs = op
+            .read_with("file")
+            .if_modified_since(last_modified - chrono::Duration::seconds(1))
+            .await?;
+        assert_eq!(bs.to_vec(), b"Hello");
+        Ok(())
+    }
 }
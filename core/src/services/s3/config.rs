@@ -207,6 +207,12 @@ pub struct S3Config {
     /// Enable write with append so that opendal will send write request with append headers.
     pub enable_write_with_append: bool,
 
+    /// Enable delete with if match so that opendal will send delete request with if match headers.
+    ///
+    /// Not all S3 compatible services support conditional delete, some of them
+    /// will ignore the header and delete the object anyway.
+    pub enable_delete_with_if_match: bool,
+
     /// OpenDAL uses List Objects V2 by default to list objects.
     /// However, some legacy services do not yet support V2.
     /// This option allows users to switch back to the older List Objects V1.
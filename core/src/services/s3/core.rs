@@ -15,6 +15,7 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
 use std::fmt;
 use std::fmt::Debug;
 use std::fmt::Display;
@@ -28,6 +29,7 @@ use std::time::Duration;
 use base64::prelude::BASE64_STANDARD;
 use base64::Engine;
 use bytes::Bytes;
+use chrono::SecondsFormat;
 use constants::X_AMZ_META_PREFIX;
 use http::header::HeaderName;
 use http::header::CACHE_CONTROL;
@@ -40,6 +42,7 @@ use http::header::IF_MATCH;
 use http::header::IF_MODIFIED_SINCE;
 use http::header::IF_NONE_MATCH;
 use http::header::IF_UNMODIFIED_SINCE;
+use http::HeaderMap;
 use http::HeaderValue;
 use http::Request;
 use http::Response;
@@ -49,11 +52,13 @@ use reqsign::AwsV4Signer;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::options::MetadataDirective;
 use crate::raw::*;
 use crate::*;
 
 pub mod constants {
     pub const X_AMZ_COPY_SOURCE: &str = "x-amz-copy-source";
+    pub const X_AMZ_METADATA_DIRECTIVE: &str = "x-amz-metadata-directive";
 
     pub const X_AMZ_SERVER_SIDE_ENCRYPTION: &str = "x-amz-server-side-encryption";
     pub const X_AMZ_SERVER_REQUEST_PAYER: (&str, &str) = ("x-amz-request-payer", "requester");
@@ -65,6 +70,7 @@ pub mod constants {
         "x-amz-server-side-encryption-customer-key-md5";
     pub const X_AMZ_SERVER_SIDE_ENCRYPTION_AWS_KMS_KEY_ID: &str =
         "x-amz-server-side-encryption-aws-kms-key-id";
+    pub const X_AMZ_SERVER_SIDE_ENCRYPTION_CONTEXT: &str = "x-amz-server-side-encryption-context";
     pub const X_AMZ_STORAGE_CLASS: &str = "x-amz-storage-class";
 
     pub const X_AMZ_COPY_SOURCE_SERVER_SIDE_ENCRYPTION_CUSTOMER_ALGORITHM: &str =
@@ -79,7 +85,13 @@ pub mod constants {
     pub const X_AMZ_META_PREFIX: &str = "x-amz-meta-";
 
     pub const X_AMZ_VERSION_ID: &str = "x-amz-version-id";
+    pub const X_AMZ_OBJECT_LOCK_MODE: &str = "x-amz-object-lock-mode";
+    pub const X_AMZ_OBJECT_LOCK_RETAIN_UNTIL_DATE: &str = "x-amz-object-lock-retain-until-date";
+    pub const X_AMZ_OBJECT_LOCK_LEGAL_HOLD: &str = "x-amz-object-lock-legal-hold";
     pub const X_AMZ_OBJECT_SIZE: &str = "x-amz-object-size";
+    pub const X_AMZ_CHECKSUM_MODE: &str = "x-amz-checksum-mode";
+    pub const X_AMZ_CHECKSUM_CRC32C: &str = "x-amz-checksum-crc32c";
+    pub const X_AMZ_CHECKSUM_SHA256: &str = "x-amz-checksum-sha256";
 
     pub const RESPONSE_CONTENT_DISPOSITION: &str = "response-content-disposition";
     pub const RESPONSE_CONTENT_TYPE: &str = "response-content-type";
@@ -105,6 +117,7 @@ pub struct S3Core {
     pub enable_request_payer: bool,
 
     pub signer: AwsV4Signer,
+    pub request_signer: Option<Box<dyn RequestSigner>>,
     pub loader: Box<dyn AwsCredentialLoad>,
     pub credential_loaded: AtomicBool,
     pub checksum_algorithm: Option<ChecksumAlgorithm>,
@@ -157,7 +170,11 @@ impl S3Core {
         ))
     }
 
-    pub async fn sign<T>(&self, req: &mut Request<T>) -> Result<()> {
+    pub async fn sign(&self, req: &mut Request<Buffer>) -> Result<()> {
+        if let Some(signer) = &self.request_signer {
+            return signer.sign(req, None);
+        }
+
         let cred = if let Some(cred) = self.load_credential().await? {
             cred
         } else {
@@ -179,7 +196,11 @@ impl S3Core {
         Ok(())
     }
 
-    pub async fn sign_query<T>(&self, req: &mut Request<T>, duration: Duration) -> Result<()> {
+    pub async fn sign_query(&self, req: &mut Request<Buffer>, duration: Duration) -> Result<()> {
+        if let Some(signer) = &self.request_signer {
+            return signer.sign(req, Some(duration));
+        }
+
         let cred = if let Some(cred) = self.load_credential().await? {
             cred
         } else {
@@ -266,6 +287,97 @@ impl S3Core {
 
         req
     }
+
+    /// Set SSE headers for requests that create objects.
+    ///
+    /// The encryption set in `args` will override the default one set in builder.
+    pub fn insert_write_sse_headers(
+        &self,
+        mut req: http::request::Builder,
+        args: &OpWrite,
+    ) -> Result<http::request::Builder> {
+        let Some(encryption) = args.encryption() else {
+            return Ok(self.insert_sse_headers(req, true));
+        };
+
+        let (algorithm, key_id, context) = match encryption {
+            Encryption::SseS3 => ("AES256", None, None),
+            Encryption::SseKms { key_id, context } => {
+                ("aws:kms", key_id.as_deref(), context.as_ref())
+            }
+        };
+
+        req = req.header(
+            HeaderName::from_static(constants::X_AMZ_SERVER_SIDE_ENCRYPTION),
+            algorithm,
+        );
+        if let Some(v) = key_id {
+            let mut v = HeaderValue::from_str(v).map_err(|err| {
+                Error::new(ErrorKind::Unexpected, "kms key id is invalid").set_source(err)
+            })?;
+            v.set_sensitive(true);
+
+            req = req.header(
+                HeaderName::from_static(constants::X_AMZ_SERVER_SIDE_ENCRYPTION_AWS_KMS_KEY_ID),
+                v,
+            );
+        }
+        if let Some(v) = context {
+            // The context is a base64-encoded UTF-8 string holding JSON.
+            let v = serde_json::to_string(v).map_err(new_json_serialize_error)?;
+            req = req.header(
+                HeaderName::from_static(constants::X_AMZ_SERVER_SIDE_ENCRYPTION_CONTEXT),
+                BASE64_STANDARD.encode(v),
+            );
+        }
+
+        Ok(req)
+    }
+
+    /// Set object lock headers for requests that create objects.
+    pub fn insert_object_lock_headers(
+        &self,
+        mut req: http::request::Builder,
+        args: &OpWrite,
+    ) -> http::request::Builder {
+        if let Some(retention) = args.retention() {
+            let mode = match retention.mode {
+                RetentionMode::Governance => "GOVERNANCE",
+                RetentionMode::Compliance => "COMPLIANCE",
+            };
+            req = req.header(
+                HeaderName::from_static(constants::X_AMZ_OBJECT_LOCK_MODE),
+                mode,
+            );
+            req = req.header(
+                HeaderName::from_static(constants::X_AMZ_OBJECT_LOCK_RETAIN_UNTIL_DATE),
+                retention
+                    .retain_until
+                    .to_rfc3339_opts(SecondsFormat::Secs, true),
+            );
+        }
+
+        if let Some(legal_hold) = args.legal_hold() {
+            req = req.header(
+                HeaderName::from_static(constants::X_AMZ_OBJECT_LOCK_LEGAL_HOLD),
+                if legal_hold { "ON" } else { "OFF" },
+            );
+        }
+
+        req
+    }
+
+    /// S3 requires an integrity check for writes with object lock, returns the
+    /// Content-MD5 of body if no checksum algorithm is configured.
+    pub fn object_lock_content_md5(&self, args: &OpWrite, body: &Buffer) -> Option<String> {
+        if self.checksum_algorithm.is_some()
+            || (args.retention().is_none() && args.legal_hold().is_none())
+        {
+            return None;
+        }
+        Some(format_content_md5(&body.to_bytes()))
+    }
+
     pub fn calculate_checksum(&self, body: &Buffer) -> Option<String> {
         match self.checksum_algorithm {
             None => None,
@@ -288,6 +400,18 @@ impl S3Core {
         req
     }
 
+    /// S3 only returns the checksum of object while checksum mode is enabled,
+    /// which is enabled while checksum algorithm is configured.
+    pub fn insert_checksum_mode_header(
+        &self,
+        mut req: http::request::Builder,
+    ) -> http::request::Builder {
+        if self.checksum_algorithm.is_some() {
+            req = req.header(constants::X_AMZ_CHECKSUM_MODE, "ENABLED");
+        }
+        req
+    }
+
     pub fn insert_checksum_type_header(
         &self,
         mut req: http::request::Builder,
@@ -332,8 +456,10 @@ impl S3Core {
             req = req.header(IF_NONE_MATCH, "*");
         }
 
-        // Set storage class header
-        if let Some(v) = &self.default_storage_class {
+        // Set storage class header, the one of write takes precedence.
+        if let Some(v) = args.storage_class() {
+            req = req.header(HeaderName::from_static(constants::X_AMZ_STORAGE_CLASS), v);
+        } else if let Some(v) = &self.default_storage_class {
             req = req.header(HeaderName::from_static(constants::X_AMZ_STORAGE_CLASS), v);
         }
 
@@ -404,6 +530,8 @@ impl S3Core {
 
         req = self.insert_sse_headers(req, false);
 
+        req = self.insert_checksum_mode_header(req);
+
         if let Some(if_none_match) = args.if_none_match() {
             req = req.header(IF_NONE_MATCH, if_none_match);
         }
@@ -482,7 +610,9 @@ impl S3Core {
 
         let mut req = Request::get(&url);
 
-        if !range.is_full() {
+        if let Some(n) = args.suffix_range() {
+            req = req.header(http::header::RANGE, format!("bytes=-{n}"));
+        } else if !range.is_full() {
             req = req.header(http::header::RANGE, range.to_header());
         }
 
@@ -515,6 +645,8 @@ impl S3Core {
         // TODO: how will this work with presign?
         req = self.insert_sse_headers(req, false);
 
+        req = self.insert_checksum_mode_header(req);
+
         // Inject operation to the request.
         req = req.extension(Operation::Read);
 
@@ -531,6 +663,7 @@ impl S3Core {
     ) -> Result<Response<HttpBody>> {
         let mut req = self.s3_get_object_request(path, range, args)?;
 
+        merge_extra_headers(&mut req, args.headers());
         self.sign(&mut req).await?;
 
         self.info.http_client().fetch(req).await
@@ -555,12 +688,17 @@ impl S3Core {
         req = self.insert_request_payer_header(req);
 
         // Set SSE headers.
-        req = self.insert_sse_headers(req, true);
+        req = self.insert_write_sse_headers(req, args)?;
+
+        // Set object lock headers.
+        req = self.insert_object_lock_headers(req, args);
 
         // Calculate Checksum.
         if let Some(checksum) = self.calculate_checksum(&body) {
             // Set Checksum header.
             req = self.insert_checksum_header(req, &checksum);
+        } else if let Some(content_md5) = self.object_lock_content_md5(args, &body) {
+            req = req.header("CONTENT-MD5", content_md5);
         }
 
         // Inject operation to the request.
@@ -598,7 +736,7 @@ impl S3Core {
         req = self.insert_request_payer_header(req);
 
         // Set SSE headers.
-        req = self.insert_sse_headers(req, true);
+        req = self.insert_write_sse_headers(req, args)?;
 
         // Inject operation to the request.
         req = req.extension(Operation::Write);
@@ -610,8 +748,10 @@ impl S3Core {
     }
 
     pub async fn s3_head_object(&self, path: &str, args: OpStat) -> Result<Response<Buffer>> {
+        let headers = args.headers().cloned();
         let mut req = self.s3_head_object_request(path, args)?;
 
+        merge_extra_headers(&mut req, headers.as_ref());
         self.sign(&mut req).await?;
 
         self.send(req).await
@@ -638,6 +778,10 @@ impl S3Core {
 
         let mut req = Request::delete(&url);
 
+        if let Some(if_match) = args.if_match() {
+            req = req.header(IF_MATCH, if_match);
+        }
+
         // Set request payer header if enabled.
         req = self.insert_request_payer_header(req);
 
@@ -652,7 +796,12 @@ impl S3Core {
         self.send(req).await
     }
 
-    pub async fn s3_copy_object(&self, from: &str, to: &str) -> Result<Response<Buffer>> {
+    pub async fn s3_copy_object(
+        &self,
+        from: &str,
+        to: &str,
+        args: &OpCopy,
+    ) -> Result<Response<Buffer>> {
         let from = build_abs_path(&self.root, from);
         let to = build_abs_path(&self.root, to);
 
@@ -661,6 +810,22 @@ impl S3Core {
 
         let mut req = Request::put(&target);
 
+        if args.metadata_directive() == MetadataDirective::Replace {
+            req = req.header(constants::X_AMZ_METADATA_DIRECTIVE, "REPLACE");
+
+            if let Some(v) = args.content_type() {
+                req = req.header(CONTENT_TYPE, v);
+            }
+            if let Some(v) = args.cache_control() {
+                req = req.header(CACHE_CONTROL, v);
+            }
+            if let Some(user_metadata) = args.user_metadata() {
+                for (key, value) in user_metadata {
+                    req = req.header(format!("{X_AMZ_META_PREFIX}{key}"), value)
+                }
+            }
+        }
+
         // Set SSE headers.
         req = self.insert_sse_headers(req, true);
 
@@ -812,6 +977,19 @@ impl S3Core {
         path: &str,
         args: &OpWrite,
     ) -> Result<Response<Buffer>> {
+        let mut req = self.s3_initiate_multipart_upload_request(path, args)?;
+
+        merge_extra_headers(&mut req, args.headers());
+        self.sign(&mut req).await?;
+
+        self.send(req).await
+    }
+
+    pub fn s3_initiate_multipart_upload_request(
+        &self,
+        path: &str,
+        args: &OpWrite,
+    ) -> Result<Request<Buffer>> {
         let p = build_abs_path(&self.root, path);
 
         let url = format!("{}/{}?uploads", self.endpoint, percent_encode_path(&p));
@@ -830,8 +1008,10 @@ impl S3Core {
             req = req.header(CACHE_CONTROL, cache_control)
         }
 
-        // Set storage class header
-        if let Some(v) = &self.default_storage_class {
+        // Set storage class header, the one of write takes precedence.
+        if let Some(v) = args.storage_class() {
+            req = req.header(HeaderName::from_static(constants::X_AMZ_STORAGE_CLASS), v);
+        } else if let Some(v) = &self.default_storage_class {
             req = req.header(HeaderName::from_static(constants::X_AMZ_STORAGE_CLASS), v);
         }
 
@@ -846,7 +1026,10 @@ impl S3Core {
         req = self.insert_request_payer_header(req);
 
         // Set SSE headers.
-        req = self.insert_sse_headers(req, true);
+        req = self.insert_write_sse_headers(req, args)?;
+
+        // Set object lock headers.
+        req = self.insert_object_lock_headers(req, args);
 
         // Set SSE headers.
         req = self.insert_checksum_type_header(req);
@@ -854,11 +1037,9 @@ impl S3Core {
         // Inject operation to the request.
         req = req.extension(Operation::Write);
 
-        let mut req = req.body(Buffer::new()).map_err(new_request_build_error)?;
-
-        self.sign(&mut req).await?;
+        let req = req.body(Buffer::new()).map_err(new_request_build_error)?;
 
-        self.send(req).await
+        Ok(req)
     }
 
     pub fn s3_upload_part_request(
@@ -909,7 +1090,22 @@ impl S3Core {
         path: &str,
         upload_id: &str,
         parts: Vec<CompleteMultipartUploadRequestPart>,
+        args: &OpWrite,
     ) -> Result<Response<Buffer>> {
+        let mut req = self.s3_complete_multipart_upload_request(path, upload_id, parts)?;
+
+        merge_extra_headers(&mut req, args.headers());
+        self.sign(&mut req).await?;
+
+        self.send(req).await
+    }
+
+    pub fn s3_complete_multipart_upload_request(
+        &self,
+        path: &str,
+        upload_id: &str,
+        parts: Vec<CompleteMultipartUploadRequestPart>,
+    ) -> Result<Request<Buffer>> {
         let p = build_abs_path(&self.root, path);
 
         let url = format!(
@@ -937,13 +1133,11 @@ impl S3Core {
         // Inject operation to the request.
         req = req.extension(Operation::Write);
 
-        let mut req = req
+        let req = req
             .body(Buffer::from(Bytes::from(content)))
             .map_err(new_request_build_error)?;
 
-        self.sign(&mut req).await?;
-
-        self.send(req).await
+        Ok(req)
     }
 
     /// Abort an on-going multipart upload.
@@ -951,6 +1145,7 @@ impl S3Core {
         &self,
         path: &str,
         upload_id: &str,
+        args: &OpWrite,
     ) -> Result<Response<Buffer>> {
         let p = build_abs_path(&self.root, path);
 
@@ -972,6 +1167,7 @@ impl S3Core {
             .body(Buffer::new())
             .map_err(new_request_build_error)?;
 
+        merge_extra_headers(&mut req, args.headers());
         self.sign(&mut req).await?;
         self.send(req).await
     }
@@ -990,6 +1186,7 @@ impl S3Core {
                 .map(|(path, op)| DeleteObjectsRequestObject {
                     key: build_abs_path(&self.root, &path),
                     version_id: op.version().map(|v| v.to_owned()),
+                    etag: op.if_match().map(|v| v.to_owned()),
                 })
                 .collect(),
         })
@@ -1017,6 +1214,85 @@ impl S3Core {
         self.send(req).await
     }
 
+    pub async fn s3_get_object_tagging(
+        &self,
+        path: &str,
+        version: Option<&str>,
+    ) -> Result<Response<Buffer>> {
+        let p = build_abs_path(&self.root, path);
+
+        let mut url = format!("{}/{}?tagging", self.endpoint, percent_encode_path(&p));
+        if let Some(version) = version {
+            write!(
+                url,
+                "&{}={}",
+                constants::S3_QUERY_VERSION_ID,
+                percent_encode_path(version)
+            )
+            .expect("write into string must succeed");
+        }
+
+        let mut req = Request::get(&url);
+
+        // Set request payer header if enabled.
+        req = self.insert_request_payer_header(req);
+
+        let mut req = req
+            // Inject operation to the request.
+            .extension(Operation::Stat)
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+
+        self.send(req).await
+    }
+
+    pub async fn s3_put_object_tagging(
+        &self,
+        path: &str,
+        tags: &HashMap<String, String>,
+    ) -> Result<Response<Buffer>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!("{}/{}?tagging", self.endpoint, percent_encode_path(&p));
+
+        let mut req = Request::put(&url);
+
+        let mut tags: Vec<_> = tags
+            .iter()
+            .map(|(key, value)| Tag {
+                key: key.clone(),
+                value: value.clone(),
+            })
+            .collect();
+        // Keep the body stable for the same tags.
+        tags.sort_by(|a, b| a.key.cmp(&b.key));
+        let content = quick_xml::se::to_string(&Tagging {
+            tag_set: TagSet { tag: tags },
+        })
+        .map_err(new_xml_serialize_error)?;
+
+        req = req.header(CONTENT_LENGTH, content.len());
+        req = req.header(CONTENT_TYPE, "application/xml");
+        // Set content-md5 as required by API.
+        req = req.header("CONTENT-MD5", format_content_md5(content.as_bytes()));
+
+        // Set request payer header if enabled.
+        req = self.insert_request_payer_header(req);
+
+        // Inject operation to the request.
+        req = req.extension(Operation::SetTags);
+
+        let mut req = req
+            .body(Buffer::from(Bytes::from(content)))
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+
+        self.send(req).await
+    }
+
     pub async fn s3_list_object_versions(
         &self,
         prefix: &str,
@@ -1067,6 +1343,68 @@ impl S3Core {
 
         self.send(req).await
     }
+
+    pub async fn s3_list_multipart_uploads(
+        &self,
+        prefix: &str,
+        key_marker: &str,
+        upload_id_marker: &str,
+    ) -> Result<Response<Buffer>> {
+        let p = build_abs_path(&self.root, prefix);
+
+        let mut url = format!("{}?uploads", self.endpoint);
+        if !p.is_empty() {
+            write!(url, "&prefix={}", percent_encode_path(p.as_str()))
+                .expect("write into string must succeed");
+        }
+        if !key_marker.is_empty() {
+            write!(url, "&key-marker={}", percent_encode_path(key_marker))
+                .expect("write into string must succeed");
+        }
+        if !upload_id_marker.is_empty() {
+            write!(
+                url,
+                "&upload-id-marker={}",
+                percent_encode_path(upload_id_marker)
+            )
+            .expect("write into string must succeed");
+        }
+
+        let mut req = Request::get(&url);
+
+        // Set request payer header if enabled.
+        req = self.insert_request_payer_header(req);
+
+        let mut req = req
+            // Inject operation to the request.
+            .extension(Operation::ListMultipartUploads)
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+
+        self.send(req).await
+    }
+}
+
+/// Request and result of PutObjectTagging and GetObjectTagging
+#[derive(Default, Debug, Serialize, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct Tagging {
+    pub tag_set: TagSet,
+}
+
+#[derive(Default, Debug, Serialize, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct TagSet {
+    pub tag: Vec<Tag>,
+}
+
+#[derive(Default, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct Tag {
+    pub key: String,
+    pub value: String,
 }
 
 /// Result of CreateMultipartUpload
@@ -1149,6 +1487,8 @@ pub struct DeleteObjectsRequestObject {
     pub key: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub version_id: Option<String>,
+    #[serde(rename = "ETag", skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
 }
 
 /// Result of DeleteObjects.
@@ -1261,6 +1601,24 @@ pub struct ListObjectVersionsOutputDeleteMarker {
     pub last_modified: String,
 }
 
+/// Output of ListMultipartUploads
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct ListMultipartUploadsOutput {
+    pub is_truncated: Option<bool>,
+    pub next_key_marker: Option<String>,
+    pub next_upload_id_marker: Option<String>,
+    pub upload: Vec<ListMultipartUploadsOutputUpload>,
+}
+
+#[derive(Default, Debug, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ListMultipartUploadsOutputUpload {
+    pub key: String,
+    pub upload_id: String,
+    pub initiated: Option<String>,
+}
+
 pub enum ChecksumAlgorithm {
     Crc32c,
 }
@@ -1283,6 +1641,51 @@ impl Display for ChecksumAlgorithm {
     }
 }
 
+/// Parse the object lock headers of head or get object response into metadata.
+pub fn parse_object_lock_headers(headers: &HeaderMap, meta: &mut Metadata) -> Result<()> {
+    let mode = parse_header_to_str(headers, constants::X_AMZ_OBJECT_LOCK_MODE)?;
+    let retain_until =
+        parse_header_to_str(headers, constants::X_AMZ_OBJECT_LOCK_RETAIN_UNTIL_DATE)?;
+    if let (Some(mode), Some(retain_until)) = (mode, retain_until) {
+        // Ignore the modes we don't know instead of failing the whole request.
+        let mode = match mode {
+            "GOVERNANCE" => Some(RetentionMode::Governance),
+            "COMPLIANCE" => Some(RetentionMode::Compliance),
+            _ => None,
+        };
+        if let Some(mode) = mode {
+            meta.set_retention(Retention {
+                mode,
+                retain_until: parse_datetime_from_rfc3339(retain_until)?,
+            });
+        }
+    }
+
+    if let Some(v) = parse_header_to_str(headers, constants::X_AMZ_OBJECT_LOCK_LEGAL_HOLD)? {
+        meta.set_legal_hold(v == "ON");
+    }
+
+    Ok(())
+}
+
+/// Parse the full object checksums of head or get object response into metadata.
+///
+/// Composite checksums of multipart uploads like `xxx-3` are ignored since they
+/// are not the checksum of content.
+pub fn parse_checksum_headers(headers: &HeaderMap, meta: &mut Metadata) -> Result<()> {
+    if let Some(v) = parse_header_to_str(headers, constants::X_AMZ_CHECKSUM_CRC32C)? {
+        if !v.contains('-') {
+            meta.set_content_crc32c(v);
+        }
+    }
+    if let Some(v) = parse_header_to_str(headers, constants::X_AMZ_CHECKSUM_SHA256)? {
+        if !v.contains('-') {
+            meta.set_content_sha256(v);
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use bytes::Buf;
@@ -1357,6 +1760,49 @@ mod tests {
         )
     }
 
+    /// This example is from https://docs.aws.amazon.com/AmazonS3/latest/API/API_GetObjectTagging.html#API_GetObjectTagging_Examples
+    #[test]
+    fn test_deserialize_tagging() {
+        let bs = Bytes::from(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+            <Tagging xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+              <TagSet>
+                 <Tag>
+                   <Key>tag1</Key>
+                   <Value>val1</Value>
+                 </Tag>
+                 <Tag>
+                   <Key>tag2</Key>
+                   <Value>val2</Value>
+                 </Tag>
+              </TagSet>
+            </Tagging>"#,
+        );
+
+        let out: Tagging = quick_xml::de::from_reader(bs.reader()).expect("must success");
+
+        assert_eq!(
+            out.tag_set.tag,
+            vec![
+                Tag {
+                    key: "tag1".to_string(),
+                    value: "val1".to_string(),
+                },
+                Tag {
+                    key: "tag2".to_string(),
+                    value: "val2".to_string(),
+                },
+            ]
+        );
+
+        let actual = quick_xml::se::to_string(&out).expect("must succeed");
+        pretty_assertions::assert_eq!(
+            actual,
+            "<Tagging><TagSet><Tag><Key>tag1</Key><Value>val1</Value></Tag>\
+             <Tag><Key>tag2</Key><Value>val2</Value></Tag></TagSet></Tagging>"
+        );
+    }
+
     /// this example is from: https://docs.aws.amazon.com/AmazonS3/latest/API/API_CompleteMultipartUpload.html
     #[test]
     fn test_deserialize_complete_multipart_upload_result() {
@@ -1406,6 +1852,45 @@ mod tests {
         assert_eq!(out.request_id, "656c76696e6727732072657175657374");
     }
 
+    #[test]
+    fn test_parse_object_lock_headers() -> Result<()> {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            constants::X_AMZ_OBJECT_LOCK_MODE,
+            "COMPLIANCE".parse().unwrap(),
+        );
+        headers.insert(
+            constants::X_AMZ_OBJECT_LOCK_RETAIN_UNTIL_DATE,
+            "2030-01-01T00:00:00Z".parse().unwrap(),
+        );
+        headers.insert(
+            constants::X_AMZ_OBJECT_LOCK_LEGAL_HOLD,
+            "ON".parse().unwrap(),
+        );
+
+        let mut meta = Metadata::new(EntryMode::FILE);
+        parse_object_lock_headers(&headers, &mut meta)?;
+        assert_eq!(
+            meta.retention(),
+            Some(&Retention {
+                mode: RetentionMode::Compliance,
+                retain_until: parse_datetime_from_rfc3339("2030-01-01T00:00:00Z")?,
+            })
+        );
+        assert_eq!(meta.legal_hold(), Some(true));
+
+        headers.insert(
+            constants::X_AMZ_OBJECT_LOCK_MODE,
+            "UNKNOWN".parse().unwrap(),
+        );
+        // Unknown modes are ignored.
+        let mut meta = Metadata::new(EntryMode::FILE);
+        parse_object_lock_headers(&headers, &mut meta)?;
+        assert_eq!(meta.retention(), None);
+        assert_eq!(meta.legal_hold(), Some(true));
+        Ok(())
+    }
+
     /// This example is from https://docs.aws.amazon.com/AmazonS3/latest/API/API_DeleteObjects.html#API_DeleteObjects_Examples
     #[test]
     fn test_serialize_delete_objects_request() {
@@ -1414,10 +1899,17 @@ mod tests {
                 DeleteObjectsRequestObject {
                     key: "sample1.txt".to_string(),
                     version_id: None,
+                    etag: None,
                 },
                 DeleteObjectsRequestObject {
                     key: "sample2.txt".to_string(),
                     version_id: Some("11111".to_owned()),
+                    etag: None,
+                },
+                DeleteObjectsRequestObject {
+                    key: "sample3.txt".to_string(),
+                    version_id: None,
+                    etag: Some("\"etag\"".to_owned()),
                 },
             ],
         };
@@ -1434,6 +1926,10 @@ mod tests {
                <Key>sample2.txt</Key>
                <VersionId>11111</VersionId>
              </Object>
+             <Object>
+               <Key>sample3.txt</Key>
+               <ETag>"etag"</ETag>
+             </Object>
              </Delete>"#
                 // Cleanup space and new line
                 .replace([' ', '\n'], "")
@@ -1738,4 +2234,76 @@ mod tests {
             },]
         );
     }
+
+    #[test]
+    fn test_parse_list_multipart_uploads() {
+        let bs = bytes::Bytes::from(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+                <ListMultipartUploadsResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+                <Bucket>bucket</Bucket>
+                <KeyMarker></KeyMarker>
+                <UploadIdMarker></UploadIdMarker>
+                <NextKeyMarker>my-movie.m2ts</NextKeyMarker>
+                <NextUploadIdMarker>YW55IGlkZWEgd2h5IGVsdmluZydzIHVwbG9hZCBmYWlsZWQ</NextUploadIdMarker>
+                <MaxUploads>3</MaxUploads>
+                <IsTruncated>true</IsTruncated>
+                <Upload>
+                    <Key>my-divisor</Key>
+                    <UploadId>XMgbGlrZSBlbHZpbmcncyBub3QgaGF2aW5nIG11Y2ggbHVjaw</UploadId>
+                    <Initiator>
+                        <ID>arn:aws:iam::111122223333:user/user1-11111a31-17b5-4fb7-9df5-b111111f13de</ID>
+                        <DisplayName>user1-11111a31-17b5-4fb7-9df5-b111111f13de</DisplayName>
+                    </Initiator>
+                    <StorageClass>REDUCED_REDUNDANCY</StorageClass>
+                    <Initiated>2010-11-10T20:48:33.000Z</Initiated>
+                </Upload>
+                <Upload>
+                    <Key>my-movie.m2ts</Key>
+                    <UploadId>YW55IGlkZWEgd2h5IGVsdmluZydzIHVwbG9hZCBmYWlsZWQ</UploadId>
+                    <StorageClass>STANDARD</StorageClass>
+                    <Initiated>2010-11-10T20:48:33.000Z</Initiated>
+                </Upload>
+                </ListMultipartUploadsResult>"#,
+        );
+
+        let output: ListMultipartUploadsOutput =
+            quick_xml::de::from_reader(bs.reader()).expect("must succeed");
+
+        assert!(output.is_truncated.unwrap());
+        assert_eq!(output.next_key_marker, Some("my-movie.m2ts".to_owned()));
+        assert_eq!(
+            output.next_upload_id_marker,
+            Some("YW55IGlkZWEgd2h5IGVsdmluZydzIHVwbG9hZCBmYWlsZWQ".to_owned())
+        );
+        assert_eq!(
+            output.upload,
+            vec![
+                ListMultipartUploadsOutputUpload {
+                    key: "my-divisor".to_owned(),
+                    upload_id: "XMgbGlrZSBlbHZpbmcncyBub3QgaGF2aW5nIG11Y2ggbHVjaw".to_owned(),
+                    initiated: Some("2010-11-10T20:48:33.000Z".to_owned()),
+                },
+                ListMultipartUploadsOutputUpload {
+                    key: "my-movie.m2ts".to_owned(),
+                    upload_id: "YW55IGlkZWEgd2h5IGVsdmluZydzIHVwbG9hZCBmYWlsZWQ".to_owned(),
+                    initiated: Some("2010-11-10T20:48:33.000Z".to_owned()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_checksum_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            constants::X_AMZ_CHECKSUM_CRC32C,
+            "yZRlqg==".parse().unwrap(),
+        );
+        headers.insert(constants::X_AMZ_CHECKSUM_SHA256, "abc=-3".parse().unwrap());
+
+        let mut meta = Metadata::new(EntryMode::FILE);
+        parse_checksum_headers(&headers, &mut meta).unwrap();
+        assert_eq!(meta.content_crc32c(), Some("yZRlqg=="));
+        assert_eq!(meta.content_sha256(), None);
+    }
 }
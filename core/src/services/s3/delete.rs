@@ -15,6 +15,7 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use bytes::Buf;
@@ -59,6 +60,20 @@ impl oio::BatchDelete for S3Deleter {
     }
 
     async fn delete_batch(&self, batch: Vec<(String, OpDelete)>) -> Result<BatchDeleteResult> {
+        // The result doesn't carry conditions like `if_match`, keep the args so that
+        // we can report them back as they were passed in.
+        let mut args: HashMap<(String, Option<String>), OpDelete> = batch
+            .iter()
+            .map(|(path, op)| ((path.clone(), op.version().map(String::from)), op.clone()))
+            .collect();
+        let mut take_args = |path: &str, version: Option<&str>| {
+            args.remove(&(path.to_string(), version.map(String::from)))
+                .unwrap_or_else(|| match version {
+                    Some(v) => OpDelete::new().with_version(v),
+                    None => OpDelete::new(),
+                })
+        };
+
         let resp = self.core.s3_delete_objects(batch).await?;
 
         let status = resp.status();
@@ -77,18 +92,12 @@ impl oio::BatchDelete for S3Deleter {
         };
         for i in result.deleted {
             let path = build_rel_path(&self.core.root, &i.key);
-            let mut op = OpDelete::new();
-            if let Some(version_id) = i.version_id {
-                op = op.with_version(version_id.as_str());
-            }
+            let op = take_args(&path, i.version_id.as_deref());
             batched_result.succeeded.push((path, op));
         }
         for i in result.error {
             let path = build_rel_path(&self.core.root, &i.key);
-            let mut op = OpDelete::new();
-            if let Some(version_id) = &i.version_id {
-                op = op.with_version(version_id.as_str());
-            }
+            let op = take_args(&path, i.version_id.as_deref());
             batched_result
                 .failed
                 .push((path, op, parse_delete_objects_result_error(i)));
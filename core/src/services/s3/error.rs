@@ -114,6 +114,10 @@ pub fn parse_s3_error_code(code: &str) -> Option<(ErrorKind, bool)> {
         // indicates a temporary issue with the service or server, such as high load,
         // maintenance, or an internal problem.
         "ServiceUnavailable" => Some((ErrorKind::Unexpected, true)),
+        // > At least one of the preconditions you specified did not hold.
+        //
+        // Returned by DeleteObjects for objects deleted with a mismatched ETag.
+        "PreconditionFailed" => Some((ErrorKind::ConditionNotMatch, false)),
         _ => None,
     }
 }
@@ -20,6 +20,7 @@ use std::sync::Arc;
 use bytes::Buf;
 use constants::X_AMZ_OBJECT_SIZE;
 use constants::X_AMZ_VERSION_ID;
+use http::HeaderValue;
 use http::StatusCode;
 
 use super::core::*;
@@ -70,6 +71,7 @@ impl oio::MultipartWrite for S3Writer {
             .core
             .s3_put_object_request(&self.path, Some(size), &self.op, body)?;
 
+        merge_extra_headers(&mut req, self.op.headers());
         self.core.sign(&mut req).await?;
 
         let resp = self.core.send(req).await?;
@@ -116,6 +118,7 @@ impl oio::MultipartWrite for S3Writer {
         let part_number = part_number + 1;
 
         let checksum = self.core.calculate_checksum(&body);
+        let content_md5 = self.core.object_lock_content_md5(&self.op, &body);
 
         let mut req = self.core.s3_upload_part_request(
             &self.path,
@@ -125,7 +128,14 @@ impl oio::MultipartWrite for S3Writer {
             body,
             checksum.clone(),
         )?;
+        if let Some(v) = content_md5 {
+            req.headers_mut().insert(
+                "content-md5",
+                HeaderValue::from_str(&v).expect("content md5 must be valid header value"),
+            );
+        }
 
+        merge_extra_headers(&mut req, self.op.headers());
         self.core.sign(&mut req).await?;
 
         let resp = self.core.send(req).await?;
@@ -178,7 +188,7 @@ impl oio::MultipartWrite for S3Writer {
 
         let resp = self
             .core
-            .s3_complete_multipart_upload(&self.path, upload_id, parts)
+            .s3_complete_multipart_upload(&self.path, upload_id, parts, &self.op)
             .await?;
 
         let status = resp.status();
@@ -215,7 +225,7 @@ impl oio::MultipartWrite for S3Writer {
     async fn abort_part(&self, upload_id: &str) -> Result<()> {
         let resp = self
             .core
-            .s3_abort_multipart_upload(&self.path, upload_id)
+            .s3_abort_multipart_upload(&self.path, upload_id, &self.op)
             .await?;
         match resp.status() {
             // s3 returns code 204 if abort succeeds.
@@ -227,10 +237,11 @@ impl oio::MultipartWrite for S3Writer {
 
 impl oio::AppendWrite for S3Writer {
     async fn offset(&self) -> Result<u64> {
-        let resp = self
-            .core
-            .s3_head_object(&self.path, OpStat::default())
-            .await?;
+        let mut args = OpStat::default();
+        if let Some(headers) = self.op.headers() {
+            args = args.with_headers(headers.clone());
+        }
+        let resp = self.core.s3_head_object(&self.path, args).await?;
 
         let status = resp.status();
 
@@ -246,6 +257,7 @@ impl oio::AppendWrite for S3Writer {
             .core
             .s3_append_object_request(&self.path, offset, size, &self.op, body)?;
 
+        merge_extra_headers(&mut req, self.op.headers());
         self.core.sign(&mut req).await?;
 
         let resp = self.core.send(req).await?;
@@ -190,12 +190,12 @@ impl SeafileCore {
 
         let req = Request::post(upload_url).extension(Operation::Write);
 
-        let (filename, relative_path) = if path.ends_with('/') {
-            ("", build_abs_path(&self.root, path))
+        let filename = if path.ends_with('/') {
+            ""
         } else {
-            let (filename, relative_path) = (get_basename(path), get_parent(path));
-            (filename, build_abs_path(&self.root, relative_path))
+            get_basename(path)
         };
+        let relative_path = build_abs_path(&self.root, get_dir(path));
 
         let file_part = FormDataPart::new("file")
             .header(
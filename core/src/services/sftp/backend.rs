@@ -17,20 +17,20 @@
 
 use std::fmt::Debug;
 use std::fmt::Formatter;
+use std::future::Future;
 use std::io::SeekFrom;
-use std::path::Path;
-use std::path::PathBuf;
 use std::sync::Arc;
 
 use log::debug;
 use openssh::KnownHosts;
+use openssh_sftp_client::metadata::Permissions;
 use tokio::io::AsyncSeekExt;
 use tokio::sync::OnceCell;
 
+use super::core::session_scope;
 use super::core::SftpCore;
 use super::delete::SftpDeleter;
 use super::error::is_not_found;
-use super::error::is_sftp_protocol_error;
 use super::error::parse_sftp_error;
 use super::lister::SftpLister;
 use super::reader::SftpReader;
@@ -70,6 +70,40 @@ impl Debug for SftpBuilder {
 }
 
 impl SftpBuilder {
+    /// Run the future in a session scope.
+    ///
+    /// By default, every operation acquires a session from the connection pool.
+    /// Operations awaited in the scope will share one session per service
+    /// instead, which is useful for bulk operations like walking a tree and
+    /// stat every entry.
+    ///
+    /// # Notes
+    ///
+    /// - Sessions will be returned to the pool once the scope ends.
+    /// - The scope is bound to the current task, operations in spawned tasks
+    ///   will still acquire their own sessions.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use opendal::services::Sftp;
+    /// # use opendal::Operator;
+    /// # use opendal::Result;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// Sftp::session_scope(async {
+    ///     for entry in op.list("dir/").await? {
+    ///         let meta = op.stat(entry.path()).await?;
+    ///         println!("{}: {}", entry.path(), meta.content_length());
+    ///     }
+    ///     Ok(())
+    /// })
+    /// .await
+    /// # }
+    /// ```
+    pub async fn session_scope<F: Future>(f: F) -> F::Output {
+        session_scope(f).await
+    }
+
     /// set endpoint for sftp backend.
     /// The format is same as `openssh`, using either `[user@]hostname` or `ssh://[user@]hostname[:port]`. A username or port that is specified in the endpoint overrides the one set in the builder (but does not change the builder).
     pub fn endpoint(mut self, endpoint: &str) -> Self {
@@ -138,6 +172,16 @@ impl SftpBuilder {
 
         self
     }
+
+    /// Set the max size of the connection pool for sftp backend.
+    ///
+    /// Established sessions will be reused across operations, and dead
+    /// sessions will be discarded when checked out. Default to 64.
+    pub fn connection_pool_max_size(mut self, max_size: u32) -> Self {
+        self.config.connection_pool_max_size = Some(max_size);
+
+        self
+    }
 }
 
 impl Builder for SftpBuilder {
@@ -152,6 +196,14 @@ impl Builder for SftpBuilder {
 
         let user = self.config.user.clone();
 
+        let connection_pool_max_size = self.config.connection_pool_max_size.unwrap_or(64);
+        if connection_pool_max_size == 0 {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "connection_pool_max_size must be greater than 0",
+            ));
+        }
+
         let root = self
             .config
             .root
@@ -191,6 +243,7 @@ impl Builder for SftpBuilder {
 
                 create_dir: true,
                 delete: true,
+                delete_with_recursive: true,
 
                 list: true,
                 list_with_limit: true,
@@ -211,6 +264,7 @@ impl Builder for SftpBuilder {
             user,
             key: self.config.key.clone(),
             known_hosts_strategy,
+            connection_pool_max_size,
 
             client: OnceCell::new(),
         });
@@ -246,23 +300,7 @@ impl Access for SftpBackend {
 
     async fn create_dir(&self, path: &str, _: OpCreateDir) -> Result<RpCreateDir> {
         let client = self.core.connect().await?;
-        let mut fs = client.fs();
-        fs.set_cwd(&self.core.root);
-
-        let paths = Path::new(&path).components();
-        let mut current = PathBuf::from(&self.core.root);
-        for p in paths {
-            current = current.join(p);
-            let res = fs.create_dir(p).await;
-
-            if let Err(e) = res {
-                // ignore error if dir already exists
-                if !is_sftp_protocol_error(&e) {
-                    return Err(parse_sftp_error(e));
-                }
-            }
-            fs.set_cwd(&current);
-        }
+        self.core.create_dir(&client, path).await?;
 
         Ok(RpCreateDir::default())
     }
@@ -303,12 +341,11 @@ impl Access for SftpBackend {
     }
 
     async fn write(&self, path: &str, op: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let client = self.core.connect().await?;
         if let Some((dir, _)) = path.rsplit_once('/') {
-            self.create_dir(dir, OpCreateDir::default()).await?;
+            self.core.create_dir(&client, dir).await?;
         }
 
-        let client = self.core.connect().await?;
-
         let mut fs = client.fs();
         fs.set_cwd(&self.core.root);
         let path = fs.canonicalize(path).await.map_err(parse_sftp_error)?;
@@ -321,7 +358,12 @@ impl Access for SftpBackend {
             option.write(true).truncate(true);
         }
 
-        let file = option.open(path).await.map_err(parse_sftp_error)?;
+        let mut file = option.open(path).await.map_err(parse_sftp_error)?;
+        if let Some(mode) = op.mode() {
+            file.set_permissions(Permissions::from((mode & 0o7777) as u16))
+                .await
+                .map_err(parse_sftp_error)?;
+        }
 
         Ok((RpWrite::new(), SftpWriter::new(file)))
     }
@@ -358,14 +400,14 @@ impl Access for SftpBackend {
         ))
     }
 
-    async fn copy(&self, from: &str, to: &str, _: OpCopy) -> Result<RpCopy> {
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
         let client = self.core.connect().await?;
 
         let mut fs = client.fs();
         fs.set_cwd(&self.core.root);
 
         if let Some((dir, _)) = to.rsplit_once('/') {
-            self.create_dir(dir, OpCreateDir::default()).await?;
+            self.core.create_dir(&client, dir).await?;
         }
 
         let src = fs.canonicalize(from).await.map_err(parse_sftp_error)?;
@@ -378,6 +420,16 @@ impl Access for SftpBackend {
             .await
             .map_err(parse_sftp_error)?;
 
+        if args.preserve_mode() {
+            let meta = src_file.metadata().await.map_err(parse_sftp_error)?;
+            if let Some(perm) = meta.permissions() {
+                dst_file
+                    .set_permissions(perm)
+                    .await
+                    .map_err(parse_sftp_error)?;
+            }
+        }
+
         Ok(RpCopy::default())
     }
 
@@ -388,7 +440,7 @@ impl Access for SftpBackend {
         fs.set_cwd(&self.core.root);
 
         if let Some((dir, _)) = to.rsplit_once('/') {
-            self.create_dir(dir, OpCreateDir::default()).await?;
+            self.core.create_dir(&client, dir).await?;
         }
         fs.rename(from, to).await.map_err(parse_sftp_error)?;
 
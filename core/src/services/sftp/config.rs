@@ -38,6 +38,10 @@ pub struct SftpConfig {
     pub known_hosts_strategy: Option<String>,
     /// enable_copy of this backend
     pub enable_copy: bool,
+    /// The max size of the connection pool of this backend.
+    ///
+    /// Default to 64.
+    pub connection_pool_max_size: Option<u32>,
 }
 
 impl Debug for SftpConfig {
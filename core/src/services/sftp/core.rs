@@ -15,11 +15,15 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::fmt::Formatter;
+use std::future::Future;
+use std::ops::Deref;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::Mutex;
 
 use bb8::PooledConnection;
 use bb8::RunError;
@@ -36,6 +40,36 @@ use super::error::parse_ssh_error;
 use crate::raw::*;
 use crate::*;
 
+tokio::task_local! {
+    /// The sessions held by current session scope, keyed by [`SftpCore::session_key`].
+    static SESSIONS: Mutex<HashMap<String, Arc<PooledConnection<'static, Manager>>>>;
+}
+
+/// Run the future in a session scope.
+///
+/// All sftp operations awaited in the scope will share one session per
+/// service, the sessions will be returned to the pool once the scope ends.
+pub async fn session_scope<F: Future>(f: F) -> F::Output {
+    SESSIONS.scope(Mutex::default(), f).await
+}
+
+/// A session acquired from the pool or shared in a session scope.
+pub enum SftpSession {
+    Pooled(PooledConnection<'static, Manager>),
+    Scoped(Arc<PooledConnection<'static, Manager>>),
+}
+
+impl Deref for SftpSession {
+    type Target = Sftp;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Self::Pooled(conn) => conn,
+            Self::Scoped(conn) => conn,
+        }
+    }
+}
+
 pub struct SftpCore {
     pub info: Arc<AccessorInfo>,
     pub endpoint: String,
@@ -43,6 +77,7 @@ pub struct SftpCore {
     pub user: Option<String>,
     pub key: Option<String>,
     pub known_hosts_strategy: KnownHosts,
+    pub connection_pool_max_size: u32,
 
     pub client: OnceCell<bb8::Pool<Manager>>,
 }
@@ -57,12 +92,63 @@ impl Debug for SftpCore {
 }
 
 impl SftpCore {
-    pub async fn connect(&self) -> Result<PooledConnection<'static, Manager>> {
+    fn session_key(&self) -> String {
+        format!(
+            "{}@{}:{}",
+            self.user.as_deref().unwrap_or_default(),
+            self.endpoint,
+            self.root
+        )
+    }
+
+    /// Get a session, the session of current session scope will be reused.
+    pub async fn connect(&self) -> Result<SftpSession> {
+        let key = self.session_key();
+        let scoped = SESSIONS.try_with(|sessions| sessions.lock().unwrap().get(&key).cloned());
+        match scoped {
+            // Not in a session scope.
+            Err(_) => Ok(SftpSession::Pooled(self.connect_pooled().await?)),
+            Ok(Some(conn)) => Ok(SftpSession::Scoped(conn)),
+            Ok(None) => {
+                let conn = Arc::new(self.connect_pooled().await?);
+                // Another operation in the same scope could have inserted a session
+                // while we are connecting, the extra one will be returned to pool.
+                let conn = SESSIONS
+                    .with(|sessions| sessions.lock().unwrap().entry(key).or_insert(conn).clone());
+                Ok(SftpSession::Scoped(conn))
+            }
+        }
+    }
+
+    /// Create the dir and all its parents with given session.
+    pub async fn create_dir(&self, client: &Sftp, path: &str) -> Result<()> {
+        let mut fs = client.fs();
+        fs.set_cwd(&self.root);
+
+        let paths = Path::new(path).components();
+        let mut current = PathBuf::from(&self.root);
+        for p in paths {
+            current = current.join(p);
+            let res = fs.create_dir(p).await;
+
+            if let Err(e) = res {
+                // ignore error if dir already exists
+                if !is_sftp_protocol_error(&e) {
+                    return Err(parse_sftp_error(e));
+                }
+            }
+            fs.set_cwd(&current);
+        }
+
+        Ok(())
+    }
+
+    async fn connect_pooled(&self) -> Result<PooledConnection<'static, Manager>> {
         let client = self
             .client
             .get_or_try_init(|| async {
                 bb8::Pool::builder()
-                    .max_size(64)
+                    .max_size(self.connection_pool_max_size)
                     .build(Manager {
                         endpoint: self.endpoint.clone(),
                         root: self.root.clone(),
@@ -152,3 +238,60 @@ impl bb8::ManageConnection for Manager {
         false
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_core(endpoint: &str, root: &str, user: Option<&str>) -> SftpCore {
+        SftpCore {
+            info: Arc::new(AccessorInfo::default()),
+            endpoint: endpoint.to_string(),
+            root: root.to_string(),
+            user: user.map(|v| v.to_string()),
+            key: None,
+            known_hosts_strategy: KnownHosts::Strict,
+            connection_pool_max_size: 10,
+            client: OnceCell::new(),
+        }
+    }
+
+    #[test]
+    fn test_session_key() {
+        let key = new_core("ssh://host", "/root", Some("alice")).session_key();
+        assert_eq!(
+            key,
+            new_core("ssh://host", "/root", Some("alice")).session_key()
+        );
+
+        // Services with different endpoint, root or user must not share sessions.
+        for core in [
+            new_core("ssh://other", "/root", Some("alice")),
+            new_core("ssh://host", "/other", Some("alice")),
+            new_core("ssh://host", "/root", Some("bob")),
+            new_core("ssh://host", "/root", None),
+        ] {
+            assert_ne!(key, core.session_key());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_session_scope() {
+        assert!(SESSIONS.try_with(|_| ()).is_err());
+
+        let output = session_scope(async {
+            assert!(SESSIONS
+                .try_with(|sessions| sessions.lock().unwrap().is_empty())
+                .unwrap());
+
+            // Spawned tasks are not in the scope.
+            let spawned = tokio::spawn(async { SESSIONS.try_with(|_| ()).is_err() });
+            assert!(spawned.await.unwrap());
+            42
+        })
+        .await;
+        assert_eq!(output, 42);
+
+        assert!(SESSIONS.try_with(|_| ()).is_err());
+    }
+}
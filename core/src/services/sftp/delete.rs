@@ -17,6 +17,10 @@
 
 use std::sync::Arc;
 
+use futures::StreamExt;
+use openssh_sftp_client::fs::Fs;
+use openssh_sftp_client::Error as SftpClientError;
+
 use super::core::SftpCore;
 use super::error::is_not_found;
 use super::error::parse_sftp_error;
@@ -34,14 +38,25 @@ impl SftpDeleter {
 }
 
 impl oio::OneShotDelete for SftpDeleter {
-    async fn delete_once(&self, path: String, _: OpDelete) -> Result<()> {
+    async fn delete_once(&self, path: String, args: OpDelete) -> Result<()> {
+        if args.if_match().is_some() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "SftpDeleter doesn't support conditional delete",
+            ));
+        }
+
         let client = self.core.connect().await?;
 
         let mut fs = client.fs();
         fs.set_cwd(&self.core.root);
 
         let res = if path.ends_with('/') {
-            fs.remove_dir(path).await
+            if args.recursive() == Some(true) {
+                remove_dir_all(&mut fs, path).await
+            } else {
+                fs.remove_dir(path).await
+            }
         } else {
             fs.remove_file(path).await
         };
@@ -53,3 +68,49 @@ impl oio::OneShotDelete for SftpDeleter {
         }
     }
 }
+
+/// Remove the dir and all its contents.
+///
+/// SFTP's RMDIR requires the dir to be empty, so we walk the tree with an
+/// explicit stack: files are removed while visiting, and dirs are removed
+/// after all their children have been removed.
+async fn remove_dir_all(fs: &mut Fs, path: String) -> Result<(), SftpClientError> {
+    // The bool marks whether the dir's children have been removed.
+    let mut stack = vec![(path, false)];
+
+    while let Some((dir, visited)) = stack.pop() {
+        if visited {
+            if let Err(e) = fs.remove_dir(&dir).await {
+                if !is_not_found(&e) {
+                    return Err(e);
+                }
+            }
+            continue;
+        }
+
+        let mut entries = match fs.open_dir(&dir).await {
+            Ok(d) => Box::pin(d.read_dir()),
+            Err(e) if is_not_found(&e) => continue,
+            Err(e) => return Err(e),
+        };
+        stack.push((dir.clone(), true));
+
+        while let Some(entry) = entries.next().await {
+            let entry = entry?;
+            let name = entry.filename().to_string_lossy();
+            if name == "." || name == ".." {
+                continue;
+            }
+
+            if entry.file_type().is_some_and(|t| t.is_dir()) {
+                stack.push((format!("{dir}{name}/"), false));
+            } else if let Err(e) = fs.remove_file(format!("{dir}{name}")).await {
+                if !is_not_found(&e) {
+                    return Err(e);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -15,18 +15,17 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use bb8::PooledConnection;
 use bytes::BytesMut;
 use openssh_sftp_client::file::File;
 
-use super::core::Manager;
+use super::core::SftpSession;
 use super::error::parse_sftp_error;
 use crate::raw::*;
 use crate::*;
 
 pub struct SftpReader {
     /// Keep the connection alive while data stream is alive.
-    _conn: PooledConnection<'static, Manager>,
+    _conn: SftpSession,
 
     file: File,
     chunk: usize,
@@ -36,7 +35,7 @@ pub struct SftpReader {
 }
 
 impl SftpReader {
-    pub fn new(conn: PooledConnection<'static, Manager>, file: File, size: Option<u64>) -> Self {
+    pub fn new(conn: SftpSession, file: File, size: Option<u64>) -> Self {
         Self {
             _conn: conn,
             file,
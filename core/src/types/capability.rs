@@ -16,6 +16,7 @@
 // under the License.
 
 use std::fmt::Debug;
+use std::time::Duration;
 
 /// Capability defines the supported operations and their constraints for a storage Operator.
 ///
@@ -82,6 +83,10 @@ pub struct Capability {
     pub stat_with_override_content_type: bool,
     /// Indicates if versions stat operations are supported.
     pub stat_with_version: bool,
+    /// Indicates if stat operations can fetch tags.
+    pub stat_with_tags: bool,
+    /// Indicates if extra headers can be sent with stat operations.
+    pub stat_with_headers: bool,
 
     /// Indicates if the operator supports read operations.
     pub read: bool,
@@ -101,6 +106,17 @@ pub struct Capability {
     pub read_with_override_content_type: bool,
     /// Indicates if versions read operations are supported.
     pub read_with_version: bool,
+    /// Indicates if reading the last n bytes via suffix range is supported.
+    ///
+    /// Services should report the file size in `RpRead`, otherwise files
+    /// shorter than n bytes can't be read.
+    pub read_with_suffix_range: bool,
+    /// Indicates if extra headers can be sent with read operations.
+    pub read_with_headers: bool,
+    /// Indicates if multiple ranges can be read in one request.
+    pub read_with_multi_range: bool,
+    /// Indicates if content-encoded objects can be decompressed while reading.
+    pub read_with_decompress: bool,
 
     /// Indicates if the operator supports write operations.
     pub write: bool,
@@ -122,16 +138,30 @@ pub struct Capability {
     pub write_with_if_match: bool,
     /// Indicates if conditional write operations using If-None-Match are supported.
     pub write_with_if_none_match: bool,
+    /// Indicates if server side encryption can be set per write operation.
+    pub write_with_encryption: bool,
     /// Indicates if write operations can be conditional on object non-existence.
     pub write_with_if_not_exists: bool,
     /// Indicates if custom user metadata can be attached during write operations.
     pub write_with_user_metadata: bool,
+    /// Indicates if extra headers can be sent with write operations.
+    pub write_with_headers: bool,
+    /// Indicates if storage class can be specified during write operations.
+    pub write_with_storage_class: bool,
+    /// Indicates if object lock retention can be set during write operations.
+    pub write_with_retention: bool,
+    /// Indicates if legal hold can be set during write operations.
+    pub write_with_legal_hold: bool,
     /// Maximum size supported for multipart uploads.
     /// For example, AWS S3 supports up to 5GiB per part in multipart uploads.
     pub write_multi_max_size: Option<usize>,
     /// Minimum size required for multipart uploads (except for the last part).
     /// For example, AWS S3 requires at least 5MiB per part.
     pub write_multi_min_size: Option<usize>,
+    /// Alignment required for multipart uploads (except for the last part).
+    /// For example, parts must be a multiple of 320KiB for some upload session APIs.
+    /// Only takes effect if `write_can_multi` is enabled.
+    pub write_multi_align_size: Option<usize>,
     /// Maximum total size supported for write operations.
     /// For example, Cloudflare D1 has a 1MB total size limit.
     pub write_total_max_size: Option<usize>,
@@ -143,6 +173,12 @@ pub struct Capability {
     pub delete: bool,
     /// Indicates if versions delete operations are supported.
     pub delete_with_version: bool,
+    /// Indicates if recursive delete operations are supported.
+    pub delete_with_recursive: bool,
+    /// Indicates if delete operations can report non-existing paths as errors.
+    pub delete_with_must_exist: bool,
+    /// Indicates if conditional delete operations with if-match are supported.
+    pub delete_with_if_match: bool,
     /// Maximum size supported for single delete operations.
     pub delete_max_size: Option<usize>,
 
@@ -150,6 +186,8 @@ pub struct Capability {
     pub copy: bool,
     /// Indicates if conditional copy operations with if-not-exists are supported.
     pub copy_with_if_not_exists: bool,
+    /// Indicates if copy operations can replace metadata via metadata directive.
+    pub copy_with_metadata_directive: bool,
 
     /// Indicates if rename operations are supported.
     pub rename: bool,
@@ -162,6 +200,10 @@ pub struct Capability {
     pub list_with_start_after: bool,
     /// Indicates if recursive listing is supported.
     pub list_with_recursive: bool,
+    /// Indicates if recursive listing returns entries in lexicographic order natively.
+    pub list_with_sorted: bool,
+    /// Indicates if listing returns entries with full metadata, like `content_length` and `last_modified`.
+    pub list_has_metadata: bool,
     /// Indicates if versions listing is supported.
     #[deprecated(since = "0.51.1", note = "use with_versions instead")]
     pub list_with_version: bool,
@@ -169,6 +211,18 @@ pub struct Capability {
     pub list_with_versions: bool,
     /// Indicates if listing with deleted files included is supported.
     pub list_with_deleted: bool,
+    /// Indicates if listing supports filtering files modified after a time on the server side.
+    pub list_with_modified_after: bool,
+    /// Indicates if listing supports filtering files modified before a time on the server side.
+    pub list_with_modified_before: bool,
+
+    /// Indicates if set tags operations are supported.
+    pub set_tags: bool,
+
+    /// Indicates if aborting multipart uploads by id is supported.
+    pub abort_multipart: bool,
+    /// Indicates if listing in-progress multipart uploads is supported.
+    pub list_multipart_uploads: bool,
 
     /// Indicates if presigned URL generation is supported.
     pub presign: bool,
@@ -180,6 +234,11 @@ pub struct Capability {
     pub presign_write: bool,
     /// Indicates if presigned URLs for delete operations are supported.
     pub presign_delete: bool,
+    /// Indicates if presigned URLs for multipart upload operations are supported.
+    pub presign_multipart: bool,
+    /// Maximum expiry supported for presigned URLs.
+    /// For example, SigV4 based services reject expiries beyond 7 days.
+    pub presign_max_expiry: Option<Duration>,
 
     /// Indicate if the operator supports shared access.
     pub shared: bool,
@@ -207,3 +266,128 @@ impl Debug for Capability {
         Ok(())
     }
 }
+
+/// Collect names of the given boolean capabilities that are enabled in `required`
+/// but not in `cap`.
+macro_rules! missing_capabilities {
+    ($cap:expr, $required:expr, [$($field:ident),* $(,)?]) => {{
+        let mut missing = Vec::new();
+        $(
+            if $required.$field && !$cap.$field {
+                missing.push(stringify!($field));
+            }
+        )*
+        missing
+    }};
+}
+
+impl Capability {
+    /// Returns the names of capabilities that are enabled in `required` but
+    /// not supported by `self`.
+    ///
+    /// Only boolean capabilities are compared, limitations like
+    /// `write_multi_max_size` are ignored.
+    pub(crate) fn missing(&self, required: &Capability) -> Vec<&'static str> {
+        missing_capabilities!(
+            self,
+            required,
+            [
+                stat,
+                stat_with_if_match,
+                stat_with_if_none_match,
+                stat_with_if_modified_since,
+                stat_with_if_unmodified_since,
+                stat_with_override_cache_control,
+                stat_with_override_content_disposition,
+                stat_with_override_content_type,
+                stat_with_version,
+                stat_with_tags,
+                stat_with_headers,
+                read,
+                read_with_if_match,
+                read_with_if_none_match,
+                read_with_if_modified_since,
+                read_with_if_unmodified_since,
+                read_with_override_cache_control,
+                read_with_override_content_disposition,
+                read_with_override_content_type,
+                read_with_version,
+                read_with_suffix_range,
+                read_with_headers,
+                read_with_multi_range,
+                read_with_decompress,
+                write,
+                write_can_multi,
+                write_can_empty,
+                write_can_append,
+                write_with_content_type,
+                write_with_content_disposition,
+                write_with_content_encoding,
+                write_with_cache_control,
+                write_with_if_match,
+                write_with_if_none_match,
+                write_with_encryption,
+                write_with_if_not_exists,
+                write_with_user_metadata,
+                write_with_headers,
+                write_with_storage_class,
+                write_with_retention,
+                write_with_legal_hold,
+                create_dir,
+                delete,
+                delete_with_version,
+                delete_with_recursive,
+                delete_with_must_exist,
+                delete_with_if_match,
+                copy,
+                copy_with_if_not_exists,
+                copy_with_metadata_directive,
+                rename,
+                list,
+                list_with_limit,
+                list_with_start_after,
+                list_with_recursive,
+                list_with_sorted,
+                list_has_metadata,
+                list_with_versions,
+                list_with_deleted,
+                list_with_modified_after,
+                list_with_modified_before,
+                set_tags,
+                abort_multipart,
+                list_multipart_uploads,
+                presign,
+                presign_read,
+                presign_stat,
+                presign_write,
+                presign_delete,
+                presign_multipart,
+                shared,
+            ]
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing() {
+        let cap = Capability {
+            read: true,
+            write: true,
+            ..Default::default()
+        };
+
+        let required = Capability {
+            read: true,
+            write: true,
+            write_with_if_match: true,
+            list: true,
+            ..Default::default()
+        };
+        assert_eq!(cap.missing(&required), vec!["write_with_if_match", "list"]);
+        assert!(cap.missing(&Capability::default()).is_empty());
+    }
+}
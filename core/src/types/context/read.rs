@@ -20,6 +20,7 @@ use std::ops::Range;
 use std::ops::RangeBounds;
 use std::sync::Arc;
 
+use crate::layers::check_max_size;
 use crate::raw::*;
 use crate::*;
 
@@ -42,7 +43,7 @@ impl ReadContext {
         Self {
             acc,
             path,
-            args,
+            args: args.with_shared_stat(),
             options,
         }
     }
@@ -86,11 +87,26 @@ impl ReadContext {
             Bound::Included(v) => v + 1,
             Bound::Excluded(v) => *v,
             Bound::Unbounded => {
+                // Stat returns the size of stored bytes, which is not the size
+                // of decompressed content.
+                if self.args().decompress() {
+                    return Err(Error::new(
+                        ErrorKind::ConfigInvalid,
+                        "decompress requires a bounded range since the decompressed size is unknown",
+                    )
+                    .with_operation("Reader::read")
+                    .with_context("path", self.path()));
+                }
+
                 let mut op_stat = OpStat::new();
 
                 if let Some(v) = self.args().version() {
                     op_stat = op_stat.with_version(v);
                 }
+                // The file could be stat right after it has been written.
+                if let Some((max, backoff)) = self.args().read_after_write_retry() {
+                    op_stat = op_stat.with_read_after_write_retry(max, backoff);
+                }
 
                 self.accessor()
                     .stat(self.path(), op_stat)
@@ -100,6 +116,10 @@ impl ReadContext {
             }
         };
 
+        // Chunked reads are checked here since every chunk is read separately.
+        if let Some(max_size) = self.args().max_size() {
+            check_max_size(end.saturating_sub(start), max_size)?;
+        }
         Ok(start..end)
     }
 }
@@ -15,8 +15,11 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::VecDeque;
 use std::sync::Arc;
 
+use tokio::sync::OwnedSemaphorePermit;
+
 use crate::raw::oio::Write;
 use crate::raw::*;
 use crate::*;
@@ -74,25 +77,43 @@ impl WriteContext {
     /// Returns the chunk size and if the chunk size is exact.
     fn calculate_chunk_size(&self) -> (Option<usize>, bool) {
         let cap = self.accessor().info().full_capability();
+        calculate_chunk_size(&cap, self.options().chunk())
+    }
+}
 
-        let exact = self.options().chunk().is_some();
-        let chunk_size = self
-            .options()
-            .chunk()
-            .or(cap.write_multi_min_size)
-            .map(|mut size| {
-                if let Some(v) = cap.write_multi_max_size {
-                    size = size.min(v);
-                }
-                if let Some(v) = cap.write_multi_min_size {
-                    size = size.max(v);
+/// Pick a legal chunk size that fulfills the multipart limitations of given capability.
+///
+/// Writes must be in exact chunk size if users specify the chunk or the service
+/// requires aligned parts.
+fn calculate_chunk_size(cap: &Capability, chunk: Option<usize>) -> (Option<usize>, bool) {
+    // Alignment only matters if the data could be written in multiple parts.
+    let align = cap
+        .write_multi_align_size
+        .filter(|v| *v > 0 && cap.write_can_multi);
+
+    let exact = chunk.is_some() || align.is_some();
+    let chunk_size = chunk
+        .or(cap.write_multi_min_size)
+        .or(align)
+        .map(|mut size| {
+            if let Some(v) = cap.write_multi_max_size {
+                size = size.min(v);
+            }
+            if let Some(v) = cap.write_multi_min_size {
+                size = size.max(v);
+            }
+            if let Some(v) = align {
+                size = size.div_ceil(v) * v;
+                // Round down instead if rounding up exceeds the max size.
+                if let Some(max) = cap.write_multi_max_size.filter(|max| size > *max) {
+                    size = (max / v).max(1) * v;
                 }
+            }
 
-                size
-            });
+            size
+        });
 
-        (chunk_size, exact)
-    }
+    (chunk_size, exact)
 }
 
 pub struct WriteGenerator<W> {
@@ -103,20 +124,40 @@ pub struct WriteGenerator<W> {
     /// If `exact` is true, the size of the data written to the underlying storage is
     /// exactly `chunk_size` bytes.
     exact: bool,
+    /// The max size of data written to the underlying storage at once.
+    max_size: Option<usize>,
     buffer: oio::QueueBuf,
+
+    /// The pool to reserve chunks from, the reserved chunk will be returned
+    /// once the part has been uploaded by the underlying storage.
+    pool: Option<BufferPool>,
+    permit: Option<OwnedSemaphorePermit>,
+    /// The reservations of parts that may still be uploading in background.
+    ///
+    /// Writers upload at most `concurrent` parts at the same time, so parts
+    /// older than that must have been finished.
+    in_flight: VecDeque<OwnedSemaphorePermit>,
+    concurrent: usize,
 }
 
 impl WriteGenerator<oio::Writer> {
     /// Create a new exact buf writer.
     pub async fn create(ctx: Arc<WriteContext>) -> Result<Self> {
         let (chunk_size, exact) = ctx.calculate_chunk_size();
+        let max_size = ctx.accessor().info().full_capability().write_multi_max_size;
         let (_, w) = ctx.acc.write(ctx.path(), ctx.args().clone()).await?;
 
         Ok(Self {
             w,
             chunk_size,
             exact,
+            max_size,
             buffer: oio::QueueBuf::new(),
+
+            pool: ctx.options().buffer_pool().cloned(),
+            permit: None,
+            in_flight: VecDeque::new(),
+            concurrent: ctx.args().concurrent(),
         })
     }
 
@@ -127,8 +168,59 @@ impl WriteGenerator<oio::Writer> {
             w,
             chunk_size,
             exact,
+            max_size: None,
             buffer: oio::QueueBuf::new(),
+
+            pool: None,
+            permit: None,
+            in_flight: VecDeque::new(),
+            concurrent: 1,
+        }
+    }
+
+    /// Reserve `size` bytes from the pool before buffering data.
+    async fn reserve(&mut self, size: usize) -> Result<()> {
+        if self.permit.is_some() {
+            return Ok(());
+        }
+        let Some(pool) = self.pool.clone() else {
+            return Ok(());
+        };
+
+        if let Some(permit) = pool.try_acquire(size) {
+            self.permit = Some(permit);
+            return Ok(());
+        }
+        // The pool is exhausted, wait for our own parts to be uploaded before
+        // waiting for other writers, so that we never wait for ourselves.
+        if !self.in_flight.is_empty() {
+            self.w.flush().await?;
+            self.in_flight.clear();
+        }
+        self.permit = Some(pool.acquire(size).await);
+        Ok(())
+    }
+
+    /// Send given data to the underlying storage, the reservation will be
+    /// kept until the part has been uploaded.
+    async fn send(&mut self, bs: Buffer) -> Result<()> {
+        self.w.write_dyn(bs).await?;
+
+        // Non-concurrent writers have finished the part while returning.
+        let Some(permit) = self.permit.take().filter(|_| self.concurrent > 1) else {
+            return Ok(());
+        };
+        self.in_flight.push_back(permit);
+        while self.in_flight.len() > self.concurrent {
+            self.in_flight.pop_front();
         }
+        Ok(())
+    }
+
+    /// Send all buffered data to the underlying storage.
+    async fn write_buffer(&mut self) -> Result<()> {
+        let buf = self.buffer.take().collect();
+        self.send(buf).await
     }
 }
 
@@ -137,12 +229,18 @@ impl WriteGenerator<oio::Writer> {
     pub async fn write(&mut self, mut bs: Buffer) -> Result<usize> {
         let Some(chunk_size) = self.chunk_size else {
             let size = bs.len();
-            self.w.write_dyn(bs).await?;
+            if size > 0 {
+                self.reserve(size).await?;
+            }
+            self.send(bs).await?;
             return Ok(size);
         };
 
         if self.buffer.len() + bs.len() < chunk_size {
             let size = bs.len();
+            if size > 0 {
+                self.reserve(chunk_size).await?;
+            }
             self.buffer.push(bs);
             return Ok(size);
         }
@@ -151,12 +249,14 @@ impl WriteGenerator<oio::Writer> {
         // - exact is false
         // - buffer + bs is larger than chunk_size.
         // Action:
-        // - write buffer + bs directly.
+        // - write buffer + bs directly, but no more than max_size.
         if !self.exact {
+            if let Some(max_size) = self.max_size {
+                bs.truncate(max_size.saturating_sub(self.buffer.len()).max(1));
+            }
             let fill_size = bs.len();
             self.buffer.push(bs);
-            let buf = self.buffer.take().collect();
-            self.w.write_dyn(buf).await?;
+            self.write_buffer().await?;
             return Ok(fill_size);
         }
 
@@ -167,8 +267,7 @@ impl WriteGenerator<oio::Writer> {
         // Action:
         // - write existing buffer in chunk_size to make more rooms for writing data.
         if self.buffer.len() >= chunk_size {
-            let buf = self.buffer.take().collect();
-            self.w.write_dyn(buf).await?;
+            self.write_buffer().await?;
         }
 
         // Condition
@@ -179,10 +278,23 @@ impl WriteGenerator<oio::Writer> {
         let remaining = chunk_size - self.buffer.len();
         bs.truncate(remaining);
         let n = bs.len();
+        if n > 0 {
+            self.reserve(chunk_size).await?;
+        }
         self.buffer.push(bs);
         Ok(n)
     }
 
+    /// Flush the data that has been sent to the underlying writer.
+    ///
+    /// Data kept in the buffer will not be flushed since it's smaller than a
+    /// chunk, flushing it could break the part size requirements of services.
+    pub async fn flush(&mut self) -> Result<()> {
+        self.w.flush().await?;
+        self.in_flight.clear();
+        Ok(())
+    }
+
     /// Finish the write process.
     pub async fn close(&mut self) -> Result<Metadata> {
         loop {
@@ -190,22 +302,28 @@ impl WriteGenerator<oio::Writer> {
                 break;
             }
 
-            let buf = self.buffer.take().collect();
-            self.w.write_dyn(buf).await?;
+            self.write_buffer().await?;
         }
 
-        self.w.close().await
+        let meta = self.w.close().await?;
+        self.in_flight.clear();
+        Ok(meta)
     }
 
     /// Abort the write process.
     pub async fn abort(&mut self) -> Result<()> {
         self.buffer.clear();
-        self.w.abort().await
+        self.permit = None;
+        let res = self.w.abort().await;
+        self.in_flight.clear();
+        res
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use bytes::Buf;
     use bytes::BufMut;
     use bytes::Bytes;
@@ -394,6 +512,139 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_inexact_buf_writer_max_size() -> Result<()> {
+        let buf = Arc::new(Mutex::new(vec![]));
+        let mut w = WriteGenerator::new(Box::new(MockWriter { buf: buf.clone() }), Some(10), false);
+        w.max_size = Some(12);
+
+        let mut rng = thread_rng();
+        let mut expected = vec![0; 15];
+        rng.fill_bytes(&mut expected);
+
+        let mut bs = Bytes::from(expected.clone());
+        // Only max_size bytes could be written at once.
+        let n = w.write(bs.clone().into()).await?;
+        assert_eq!(12, n);
+        bs.advance(n);
+        while !bs.is_empty() {
+            let n = w.write(bs.clone().into()).await?;
+            bs.advance(n);
+        }
+
+        w.close().await?;
+
+        let buf = buf.lock().await;
+        assert_eq!(
+            format!("{:x}", Sha256::digest(&*buf)),
+            format!("{:x}", Sha256::digest(&expected))
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_buf_writer_with_pool() -> Result<()> {
+        let pool = BufferPool::new(10);
+        let buf = Arc::new(Mutex::new(vec![]));
+        let new_writer = || {
+            let mut w =
+                WriteGenerator::new(Box::new(MockWriter { buf: buf.clone() }), Some(10), true);
+            w.pool = Some(pool.clone());
+            w
+        };
+
+        let mut w1 = new_writer();
+        let mut w2 = new_writer();
+        assert_eq!(w1.write(Buffer::from(vec![1; 5])).await?, 5);
+        assert_eq!(pool.available(), 0);
+
+        // The pool is exhausted, w2 must wait for w1 to return its chunk.
+        let write = w2.write(Buffer::from(vec![2; 5]));
+        let res = tokio::time::timeout(Duration::from_millis(10), write).await;
+        assert!(res.is_err(), "write must wait for the pool");
+
+        w1.close().await?;
+        assert_eq!(pool.available(), 10);
+        assert_eq!(w2.write(Buffer::from(vec![2; 5])).await?, 5);
+        w2.abort().await?;
+        assert_eq!(pool.available(), 10);
+
+        // Dropped writers will return the chunk too.
+        let mut w3 = new_writer();
+        w3.write(Buffer::from(vec![3; 5])).await?;
+        drop(w3);
+        assert_eq!(pool.available(), 10);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_buf_writer_with_pool_in_flight() -> Result<()> {
+        let pool = BufferPool::new(10);
+        let buf = Arc::new(Mutex::new(vec![]));
+        let new_writer = |chunk_size| {
+            let mut w =
+                WriteGenerator::new(Box::new(MockWriter { buf: buf.clone() }), chunk_size, true);
+            w.pool = Some(pool.clone());
+            w.concurrent = 2;
+            w
+        };
+
+        // Writes without chunk are reserved until the parts are uploaded.
+        let mut w = new_writer(None);
+        assert_eq!(w.write(Buffer::from(vec![1; 4])).await?, 4);
+        assert_eq!(pool.available(), 6);
+        assert_eq!(w.write(Buffer::from(vec![1; 4])).await?, 4);
+        assert_eq!(pool.available(), 2);
+        w.flush().await?;
+        assert_eq!(pool.available(), 10);
+        w.close().await?;
+
+        // Writers must wait for their own parts instead of the pool.
+        let mut w = new_writer(Some(10));
+        assert_eq!(w.write(Buffer::from(vec![2; 10])).await?, 10);
+        assert_eq!(pool.available(), 0);
+        let write = w.write(Buffer::from(vec![2; 5]));
+        let n = tokio::time::timeout(Duration::from_secs(1), write)
+            .await
+            .expect("write must not wait for itself")?;
+        assert_eq!(n, 5);
+        w.close().await?;
+        assert_eq!(pool.available(), 10);
+        Ok(())
+    }
+
+    #[test]
+    fn test_calculate_chunk_size() {
+        let cap = Capability {
+            write_multi_min_size: Some(5),
+            write_multi_max_size: Some(100),
+            ..Default::default()
+        };
+        assert_eq!(calculate_chunk_size(&cap, None), (Some(5), false));
+        assert_eq!(calculate_chunk_size(&cap, Some(1)), (Some(5), true));
+        assert_eq!(calculate_chunk_size(&cap, Some(1000)), (Some(100), true));
+        assert_eq!(
+            calculate_chunk_size(&Capability::default(), None),
+            (None, false)
+        );
+
+        let cap = Capability {
+            write_multi_align_size: Some(16),
+            ..Default::default()
+        };
+        assert_eq!(calculate_chunk_size(&cap, None), (None, false));
+
+        let cap = Capability {
+            write_can_multi: true,
+            write_multi_max_size: Some(100),
+            write_multi_align_size: Some(16),
+            ..Default::default()
+        };
+        assert_eq!(calculate_chunk_size(&cap, None), (Some(16), true));
+        assert_eq!(calculate_chunk_size(&cap, Some(20)), (Some(32), true));
+        assert_eq!(calculate_chunk_size(&cap, Some(1000)), (Some(96), true));
+    }
+
     #[tokio::test]
     async fn test_fuzz_exact_buf_writer() -> Result<()> {
         let _ = tracing_subscriber::fmt()
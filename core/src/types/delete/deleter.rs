@@ -86,6 +86,8 @@ pub struct Deleter {
 
     max_size: usize,
     cur_size: usize,
+    /// The number of paths that have been deleted.
+    deleted: usize,
 }
 
 impl Deleter {
@@ -97,14 +99,14 @@ impl Deleter {
             deleter,
             max_size,
             cur_size: 0,
+            deleted: 0,
         })
     }
 
     /// Delete a path.
     pub async fn delete(&mut self, input: impl IntoDeleteInput) -> Result<()> {
         if self.cur_size >= self.max_size {
-            let deleted = self.deleter.flush_dyn().await?;
-            self.cur_size -= deleted;
+            self.flush().await?;
         }
 
         let input = input.into_delete_input();
@@ -112,6 +114,9 @@ impl Deleter {
         if let Some(version) = &input.version {
             op = op.with_version(version);
         }
+        if let Some(etag) = &input.if_match {
+            op = op.with_if_match(etag);
+        }
 
         self.deleter.delete_dyn(&input.path, op)?;
         self.cur_size += 1;
@@ -196,12 +201,33 @@ impl Deleter {
     }
 
     /// Flush the deleter, returns the number of deleted paths.
+    ///
+    /// For services that support batch delete, paths failed permanently within a
+    /// batch will be dropped and reported together, use [`DeleteFailures::from_error`]
+    /// to fetch them from the returned error.
     pub async fn flush(&mut self) -> Result<usize> {
-        let deleted = self.deleter.flush_dyn().await?;
+        let deleted = match self.deleter.flush_dyn().await {
+            Ok(deleted) => deleted,
+            Err(err) => {
+                // Paths of the failed batch have been dropped by the underlying
+                // deleter, keep the counters in sync before returning the error.
+                if let Some(failures) = DeleteFailures::from_error(&err) {
+                    self.cur_size -= failures.deleted() + failures.failed().len();
+                    self.deleted += failures.deleted();
+                }
+                return Err(err);
+            }
+        };
         self.cur_size -= deleted;
+        self.deleted += deleted;
         Ok(deleted)
     }
 
+    /// Returns the number of paths that have been deleted so far.
+    pub(crate) fn deleted(&self) -> usize {
+        self.deleted
+    }
+
     /// Close the deleter, this will flush the deleter and wait until all paths are deleted.
     pub async fn close(&mut self) -> Result<()> {
         loop {
@@ -218,3 +244,52 @@ impl Deleter {
         FuturesDeleteSink::new(self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct MockDeleter;
+
+    impl oio::BatchDelete for MockDeleter {
+        async fn delete_once(&self, _: String, _: OpDelete) -> Result<()> {
+            Ok(())
+        }
+
+        async fn delete_batch(
+            &self,
+            batch: Vec<(String, OpDelete)>,
+        ) -> Result<oio::BatchDeleteResult> {
+            let mut result = oio::BatchDeleteResult::default();
+            for (path, args) in batch {
+                if path.starts_with("denied") {
+                    let err = Error::new(ErrorKind::PermissionDenied, "access denied");
+                    result.failed.push((path, args, err));
+                } else {
+                    result.succeeded.push((path, args));
+                }
+            }
+            Ok(result)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_deleter_counts_failed_batch() -> Result<()> {
+        let mut d = Deleter {
+            deleter: Box::new(oio::BatchDeleter::new(MockDeleter)),
+            max_size: 10,
+            cur_size: 0,
+            deleted: 0,
+        };
+        d.delete_iter(["a", "denied", "b"]).await?;
+
+        let err = d.flush().await.expect_err("flush must fail");
+        assert_eq!(DeleteFailures::from_error(&err).unwrap().failed().len(), 1);
+        assert_eq!(d.cur_size, 0);
+        assert_eq!(d.deleted(), 2);
+
+        // The failed paths have been dropped, close must not loop forever.
+        d.close().await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,121 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use crate::*;
+
+/// A path that failed to be deleted permanently.
+#[derive(Debug)]
+pub struct DeleteFailure {
+    path: String,
+    version: Option<String>,
+    error: Error,
+}
+
+impl DeleteFailure {
+    /// Create a new delete failure.
+    pub(crate) fn new(path: String, version: Option<String>, error: Error) -> Self {
+        Self {
+            path,
+            version,
+            error,
+        }
+    }
+
+    /// The path that failed to be deleted.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The version that failed to be deleted, `None` means the latest version.
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    /// The error returned by the service for this path.
+    pub fn error(&self) -> &Error {
+        &self.error
+    }
+}
+
+/// The paths that failed to be deleted permanently within a batch.
+///
+/// Failed paths will not be retried and have been dropped from the deleter.
+/// `DeleteFailures` is attached to the error returned by [`Deleter::flush`],
+/// use [`DeleteFailures::from_error`] to fetch it.
+///
+/// # Examples
+///
+/// ```
+/// # use opendal::DeleteFailures;
+/// # use opendal::Operator;
+/// # use opendal::Result;
+/// # async fn test(op: Operator) -> Result<()> {
+/// let mut d = op.deleter().await?;
+/// d.delete("path/to/file").await?;
+/// if let Err(err) = d.flush().await {
+///     if let Some(failures) = DeleteFailures::from_error(&err) {
+///         for f in failures.failed() {
+///             println!("failed to delete {}: {}", f.path(), f.error());
+///         }
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct DeleteFailures {
+    deleted: usize,
+    failed: Vec<DeleteFailure>,
+}
+
+impl DeleteFailures {
+    /// Create a new delete failures.
+    pub(crate) fn new(deleted: usize, failed: Vec<DeleteFailure>) -> Self {
+        Self { deleted, failed }
+    }
+
+    /// Fetch the delete failures carried by given error.
+    pub fn from_error(err: &Error) -> Option<&Self> {
+        std::error::Error::source(err)?.downcast_ref()
+    }
+
+    /// The number of paths that have been deleted within the same batch.
+    pub fn deleted(&self) -> usize {
+        self.deleted
+    }
+
+    /// The paths that failed to be deleted.
+    pub fn failed(&self) -> &[DeleteFailure] {
+        &self.failed
+    }
+}
+
+impl Display for DeleteFailures {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} paths failed to delete, {} paths deleted",
+            self.failed.len(),
+            self.deleted
+        )
+    }
+}
+
+impl std::error::Error for DeleteFailures {}
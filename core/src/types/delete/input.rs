@@ -26,6 +26,8 @@ pub struct DeleteInput {
     pub path: String,
     /// The version of the path to delete.
     pub version: Option<String>,
+    /// Only delete the path if its current etag matches.
+    pub if_match: Option<String>,
 }
 
 /// IntoDeleteInput is a helper trait that makes it easier for users to play with `Deleter`.
@@ -75,6 +77,9 @@ impl IntoDeleteInput for (String, OpDelete) {
         if let Some(version) = args.version() {
             input.version = Some(version.to_string());
         }
+        if let Some(etag) = args.if_match() {
+            input.if_match = Some(etag.to_string());
+        }
         input
     }
 }
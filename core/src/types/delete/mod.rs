@@ -22,5 +22,9 @@ pub use input::IntoDeleteInput;
 mod deleter;
 pub use deleter::Deleter;
 
+mod failures;
+pub use failures::DeleteFailure;
+pub use failures::DeleteFailures;
+
 mod futures_delete_sink;
 pub use futures_delete_sink::FuturesDeleteSink;
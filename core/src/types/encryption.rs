@@ -0,0 +1,35 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+
+/// Encryption is the server side encryption settings for a write operation.
+///
+/// Check [`crate::Capability::write_with_encryption`] before using it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Encryption {
+    /// Encrypt the object with keys managed by the service, like `AES256` in S3.
+    SseS3,
+    /// Encrypt the object with the given key managed by KMS.
+    SseKms {
+        /// The id of the KMS key, the service default key will be used if not set.
+        key_id: Option<String>,
+        /// The encryption context used to encrypt the object.
+        context: Option<HashMap<String, String>>,
+    },
+}
@@ -368,6 +368,28 @@ impl Error {
         self
     }
 
+    /// Create a copy of this error with context values rewritten by given
+    /// function, used to redact sensitive data before logging.
+    ///
+    /// The source error can't be cloned, so its message will be kept instead
+    /// and passed to the function with the key `source`.
+    pub(crate) fn redact(&self, f: impl Fn(&str, &str) -> String) -> Self {
+        Self {
+            kind: self.kind,
+            message: self.message.clone(),
+
+            status: self.status,
+            operation: self.operation,
+            context: self.context.iter().map(|(k, v)| (*k, f(k, v))).collect(),
+            source: self
+                .source
+                .as_ref()
+                .map(|v| anyhow::anyhow!(f("source", &format!("{v:#}")))),
+
+            backtrace: None,
+        }
+    }
+
     /// Operate on error with map.
     pub fn map<F>(self, f: F) -> Self
     where
@@ -435,6 +457,16 @@ impl Error {
         self.kind
     }
 
+    /// Return the value of error's context by key.
+    ///
+    /// For example, errors built from http responses carry the `status` context.
+    pub fn context(&self, key: &str) -> Option<&str> {
+        self.context
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
     /// Check if this error is permanent.
     pub fn is_permanent(&self) -> bool {
         self.status == ErrorStatus::Permanent
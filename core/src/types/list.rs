@@ -28,13 +28,20 @@ use crate::*;
 /// Lister is designed to list entries at given path in an asynchronous
 /// manner.
 ///
-/// - Lister implements `Stream<Item = Result<Entry>>`.
+/// - Lister implements `Stream<Item = Result<Entry>>`, so it can be used with
+///   combinators like `StreamExt::filter_map` directly.
 /// - Lister will return `None` if there is no more entries or error has been returned.
+/// - Lister fetches the next page only while being polled, dropping it stops the
+///   pagination.
 pub struct Lister {
     lister: Option<oio::Lister>,
 
     fut: Option<BoxedStaticFuture<(oio::Lister, Result<Option<oio::Entry>>)>>,
     errored: bool,
+    /// The path of the last returned entry, used as the continuation token.
+    last_path: Option<String>,
+    /// Whether the listing can be resumed via `start_after`.
+    resumable: bool,
 }
 
 /// # Safety
@@ -45,6 +52,14 @@ unsafe impl Sync for Lister {}
 impl Lister {
     /// Create a new lister.
     pub(crate) async fn create(acc: Accessor, path: &str, args: OpList) -> Result<Self> {
+        // Resuming from the last path requires the service to list in order and
+        // apply `start_after` natively, which is not the case for versions and
+        // recursive list simulated by walking dirs.
+        let cap = acc.info().native_capability();
+        let resumable = cap.list_with_start_after
+            && !args.versions()
+            && !args.deleted()
+            && (!args.recursive() || cap.list_with_recursive);
         let (_, lister) = acc.list(path, args).await?;
 
         Ok(Self {
@@ -52,8 +67,31 @@ impl Lister {
 
             fut: None,
             errored: false,
+            last_path: None,
+            resumable,
         })
     }
+
+    /// Get the continuation token of this lister.
+    ///
+    /// The token is the path of the last returned entry, not the native token of
+    /// services. Users can persist it and resume listing later by passing it to
+    /// `start_after` like `op.lister_with(path).start_after(token)`.
+    ///
+    /// Returns `None` if no entry has been returned yet or the listing can't be
+    /// resumed this way:
+    ///
+    /// - the service doesn't support `list_with_start_after` natively.
+    /// - `versions` or `deleted` is requested, entries of the same path can't be
+    ///   told apart by path.
+    /// - `recursive` is requested but the service can't list recursively natively,
+    ///   the walk of dirs can't be resumed from a path.
+    pub fn continuation_token(&self) -> Option<&str> {
+        if !self.resumable {
+            return None;
+        }
+        self.last_path.as_deref()
+    }
 }
 
 impl Stream for Lister {
@@ -79,7 +117,10 @@ impl Stream for Lister {
             self.fut = None;
 
             return match entry {
-                Ok(Some(oe)) => Poll::Ready(Some(Ok(oe.into_entry()))),
+                Ok(Some(oe)) => {
+                    self.last_path = Some(oe.path().to_string());
+                    Poll::Ready(Some(Ok(oe.into_entry())))
+                }
                 Ok(None) => {
                     self.lister = None;
                     Poll::Ready(None)
@@ -134,4 +175,52 @@ mod tests {
 
         Ok(())
     }
+
+    #[cfg(feature = "tests")]
+    #[tokio::test]
+    async fn test_lister_stream() -> Result<()> {
+        use crate::raw::tests::MockAccess;
+
+        // The recursive list is emulated by listing dirs one by one.
+        let mock = MockAccess::new().with_capability(|cap| Capability {
+            list_with_recursive: false,
+            ..cap
+        });
+        mock.insert("dir/a", "Hello");
+        mock.insert("dir/sub/b", "World");
+        let op = OperatorBuilder::new(mock.clone()).finish();
+
+        // Errors are returned as items of the stream.
+        mock.inject_error(Operation::List, "dir/sub/", 1, || {
+            Error::new(ErrorKind::Unexpected, "mock error")
+        });
+        let results: Vec<_> = op
+            .lister_with("dir/")
+            .recursive(true)
+            .await?
+            .collect()
+            .await;
+        assert!(results.iter().any(|v| v.is_ok()));
+        assert_eq!(
+            results.last().unwrap().as_ref().unwrap_err().kind(),
+            ErrorKind::Unexpected
+        );
+
+        let paths: Vec<_> = op
+            .lister_with("dir/")
+            .recursive(true)
+            .await?
+            .filter_map(|entry| future::ready(entry.ok().map(|v| v.path().to_string())))
+            .collect()
+            .await;
+        assert!(paths.contains(&"dir/sub/b".to_string()));
+
+        // Dropping the lister stops the pagination.
+        let mut lister = op.lister_with("dir/").recursive(true).await?;
+        let calls = mock.calls(Operation::List, "dir/sub/");
+        assert_eq!(lister.next().await.unwrap()?.path(), "dir/a");
+        drop(lister);
+        assert_eq!(mock.calls(Operation::List, "dir/sub/"), calls);
+        Ok(())
+    }
 }
@@ -58,6 +58,8 @@ pub struct Metadata {
     content_disposition: Option<String>,
     content_length: Option<u64>,
     content_md5: Option<String>,
+    content_crc32c: Option<String>,
+    content_sha256: Option<String>,
     content_range: Option<BytesContentRange>,
     content_type: Option<String>,
     content_encoding: Option<String>,
@@ -66,6 +68,9 @@ pub struct Metadata {
     version: Option<String>,
 
     user_metadata: Option<HashMap<String, String>>,
+    tags: Option<HashMap<String, String>>,
+    retention: Option<Retention>,
+    legal_hold: Option<bool>,
 }
 
 impl Metadata {
@@ -80,6 +85,8 @@ impl Metadata {
             cache_control: None,
             content_length: None,
             content_md5: None,
+            content_crc32c: None,
+            content_sha256: None,
             content_type: None,
             content_encoding: None,
             content_range: None,
@@ -88,6 +95,9 @@ impl Metadata {
             content_disposition: None,
             version: None,
             user_metadata: None,
+            tags: None,
+            retention: None,
+            legal_hold: None,
         }
     }
 
@@ -237,6 +247,12 @@ impl Metadata {
         self
     }
 
+    /// Unset content length of this entry if it's unknown.
+    pub(crate) fn unset_content_length(&mut self) -> &mut Self {
+        self.content_length = None;
+        self
+    }
+
     /// Content MD5 of this entry.
     ///
     /// Content MD5 is defined by [RFC 2616](http://www.w3.org/Protocols/rfc2616/rfc2616-sec14.html).
@@ -259,6 +275,46 @@ impl Metadata {
         self
     }
 
+    /// Content CRC32C of this entry returned by the service natively, for
+    /// example, `x-amz-checksum-crc32c` of S3 and `crc32c` of GCS.
+    ///
+    /// The value is base64 or hex encoded big-endian checksum of the full content.
+    pub fn content_crc32c(&self) -> Option<&str> {
+        self.content_crc32c.as_deref()
+    }
+
+    /// Set content CRC32C of this entry.
+    pub fn set_content_crc32c(&mut self, v: &str) -> &mut Self {
+        self.content_crc32c = Some(v.to_string());
+        self
+    }
+
+    /// Set content CRC32C of this entry.
+    pub fn with_content_crc32c(mut self, v: String) -> Self {
+        self.content_crc32c = Some(v);
+        self
+    }
+
+    /// Content SHA256 of this entry returned by the service natively, for
+    /// example, `x-amz-checksum-sha256` of S3.
+    ///
+    /// The value is base64 or hex encoded checksum of the full content.
+    pub fn content_sha256(&self) -> Option<&str> {
+        self.content_sha256.as_deref()
+    }
+
+    /// Set content SHA256 of this entry.
+    pub fn set_content_sha256(&mut self, v: &str) -> &mut Self {
+        self.content_sha256 = Some(v.to_string());
+        self
+    }
+
+    /// Set content SHA256 of this entry.
+    pub fn with_content_sha256(mut self, v: String) -> Self {
+        self.content_sha256 = Some(v);
+        self
+    }
+
     /// Content Type of this entry.
     ///
     /// Content Type is defined by [RFC 9110](https://httpwg.org/specs/rfc9110.html#field.content-type).
@@ -433,4 +489,56 @@ impl Metadata {
         self.user_metadata = Some(data);
         self
     }
+
+    /// Tags of this entry
+    ///
+    /// Tags are only fetched while stat with `tags` enabled, `None` means tags
+    /// are not fetched.
+    pub fn tags(&self) -> Option<&HashMap<String, String>> {
+        self.tags.as_ref()
+    }
+
+    /// With tags of this entry
+    pub fn with_tags(mut self, tags: HashMap<String, String>) -> Self {
+        self.tags = Some(tags);
+        self
+    }
+
+    /// Object lock retention of this entry
+    ///
+    /// `None` means the entry has no retention or the service doesn't return it.
+    pub fn retention(&self) -> Option<&Retention> {
+        self.retention.as_ref()
+    }
+
+    /// Set the object lock retention of this entry
+    pub fn set_retention(&mut self, v: Retention) -> &mut Self {
+        self.retention = Some(v);
+        self
+    }
+
+    /// With the object lock retention of this entry
+    pub fn with_retention(mut self, v: Retention) -> Self {
+        self.retention = Some(v);
+        self
+    }
+
+    /// Legal hold status of this entry
+    ///
+    /// `None` means the service doesn't return the legal hold status.
+    pub fn legal_hold(&self) -> Option<bool> {
+        self.legal_hold
+    }
+
+    /// Set the legal hold status of this entry
+    pub fn set_legal_hold(&mut self, v: bool) -> &mut Self {
+        self.legal_hold = Some(v);
+        self
+    }
+
+    /// With the legal hold status of this entry
+    pub fn with_legal_hold(mut self, v: bool) -> Self {
+        self.legal_hold = Some(v);
+        self
+    }
 }
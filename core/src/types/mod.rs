@@ -27,6 +27,13 @@ pub use entry::Entry;
 mod metadata;
 pub use metadata::Metadata;
 
+mod encryption;
+pub use encryption::Encryption;
+
+mod retention;
+pub use retention::Retention;
+pub use retention::RetentionMode;
+
 mod read;
 pub use read::*;
 
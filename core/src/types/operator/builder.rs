@@ -466,7 +466,7 @@ impl<A: Access> OperatorBuilder<A> {
         // Make sure error context layer has been attached.
         OperatorBuilder { accessor }
             .layer(ErrorContextLayer)
-            .layer(CompleteLayer)
+            .layer(CompleteLayer::default())
             .layer(CorrectnessCheckLayer)
     }
 
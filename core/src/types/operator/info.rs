@@ -18,6 +18,7 @@
 use std::str::FromStr;
 use std::sync::Arc;
 
+use crate::layers::RetryPolicy;
 use crate::raw::*;
 use crate::*;
 
@@ -60,4 +61,13 @@ impl OperatorInfo {
     pub fn native_capability(&self) -> Capability {
         self.0.native_capability()
     }
+
+    /// Get the [`RetryPolicy`] of the outermost [`RetryLayer`] applied to operator.
+    ///
+    /// Returns `None` if operator doesn't retry.
+    ///
+    /// [`RetryLayer`]: crate::layers::RetryLayer
+    pub fn retry_policy(&self) -> Option<RetryPolicy> {
+        self.0.retry_policy()
+    }
 }
@@ -15,12 +15,15 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
 use std::future::Future;
+use std::ops::RangeBounds;
 use std::time::Duration;
 
 use futures::Stream;
 use futures::StreamExt;
 use futures::TryStreamExt;
+use log::warn;
 
 use crate::operator_futures::*;
 use crate::raw::oio::DeleteDyn;
@@ -185,6 +188,54 @@ impl Operator {
         OperatorInfo::new(self.accessor.info())
     }
 
+    /// Check if the operator supports all the required capabilities.
+    ///
+    /// This function will not send any request to the service, so it's useful
+    /// to validate operators built from user provided config before any IO
+    /// happens.
+    ///
+    /// # Notes
+    ///
+    /// Only boolean capabilities will be checked, limitations like
+    /// `write_multi_max_size` are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ErrorKind::Unsupported`] listing all missing capabilities in
+    /// the `missing` context.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use opendal::Result;
+    /// use opendal::Capability;
+    /// use opendal::Operator;
+    ///
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// op.require(Capability {
+    ///     write: true,
+    ///     write_with_if_match: true,
+    ///     ..Default::default()
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn require(&self, required: Capability) -> Result<()> {
+        let info = self.info();
+        let missing = info.full_capability().missing(&required);
+        if missing.is_empty() {
+            return Ok(());
+        }
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "service doesn't support required capabilities",
+        )
+        .with_context("service", info.scheme())
+        .with_context("name", info.name())
+        .with_context("missing", missing.join(", ")))
+    }
+
     /// Get the executor used by current operator.
     pub fn executor(&self) -> Executor {
         self.accessor.info().executor()
@@ -278,6 +329,54 @@ impl Operator {
         }
     }
 
+    /// Wait for in-flight operations to finish before shutting down.
+    ///
+    /// # Notes
+    ///
+    /// This API requires [`ConcurrentLimitLayer`](crate::layers::ConcurrentLimitLayer)
+    /// to track the in-flight operations, returns [`ErrorKind::Unsupported`] if it's
+    /// not applied.
+    ///
+    /// - New operations will be rejected once drain started.
+    /// - In-flight operations including readers, writers, listers and deleters are
+    ///   waited up to `timeout`.
+    /// - Operations still running at the timeout will be aborted: they will return
+    ///   errors on the next call, and writers will abort their uploads.
+    ///
+    /// All operators sharing the same `ConcurrentLimitLayer` will be drained.
+    ///
+    /// Drain is a terminal shutdown state, drained operators will reject all new
+    /// operations forever and can't be resumed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use anyhow::Result;
+    /// use opendal::Operator;
+    ///
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let summary = op.drain(Duration::from_secs(30)).await?;
+    /// println!(
+    ///     "{} completed, {} aborted",
+    ///     summary.completed, summary.aborted
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn drain(&self, timeout: Duration) -> Result<layers::DrainSummary> {
+        let Some(tracker) = self.inner().info().in_flight_tracker() else {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "drain requires ConcurrentLimitLayer to track in-flight operations",
+            )
+            .with_operation("Operator::drain")
+            .with_context("service", self.info().scheme()));
+        };
+
+        Ok(tracker.drain(timeout).await)
+    }
+
     /// Retrieve the metadata for the specified path.
     ///
     /// # Notes
@@ -408,6 +507,34 @@ impl Operator {
         Ok(rp.into_metadata())
     }
 
+    /// Retrieve the metadata for the specified path, returns `None` if it doesn't exist.
+    ///
+    /// # Notes
+    ///
+    /// Only [`ErrorKind::NotFound`] will be mapped to `Ok(None)`, other errors such as
+    /// [`ErrorKind::PermissionDenied`] will be returned as is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// #
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// if let Some(meta) = op.stat_if_exists("test").await? {
+    ///     println!("file size: {}", meta.content_length())
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn stat_if_exists(&self, path: &str) -> Result<Option<Metadata>> {
+        match self.stat(path).await {
+            Ok(meta) => Ok(Some(meta)),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
     /// Check whether this path exists.
     ///
     /// # Example
@@ -424,12 +551,60 @@ impl Operator {
     /// }
     /// ```
     pub async fn exists(&self, path: &str) -> Result<bool> {
-        let r = self.stat(path).await;
-        match r {
-            Ok(_) => Ok(true),
-            Err(err) if err.kind() == ErrorKind::NotFound => Ok(false),
-            Err(err) => Err(err),
+        Ok(self.stat_if_exists(path).await?.is_some())
+    }
+
+    /// Get tags of the file at given path.
+    ///
+    /// This is a shortcut of `stat_with(path).tags(true)`, check
+    /// [`Capability::stat_with_tags`] before using this.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let tags = op.tags("path/to/file").await?;
+    /// let classification = tags.get("classification");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn tags(&self, path: &str) -> Result<HashMap<String, String>> {
+        let meta = self.stat_with(path).tags(true).await?;
+        Ok(meta.tags().cloned().unwrap_or_default())
+    }
+
+    /// Set tags of the file at given path, existing tags will be replaced.
+    ///
+    /// Check [`Capability::set_tags`] before using this.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::collections::HashMap;
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let tags = HashMap::from([("classification".to_string(), "public".to_string())]);
+    /// op.set_tags("path/to/file", tags).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn set_tags(&self, path: &str, tags: HashMap<String, String>) -> Result<()> {
+        let path = normalize_path(path);
+
+        if !validate_path(&path, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "set tags path is a directory")
+                    .with_operation("Operator::set_tags")
+                    .with_context("service", self.info().scheme())
+                    .with_context("path", &path),
+            );
         }
+
+        self.inner().set_tags(&path, OpSetTags::new(tags)).await?;
+        Ok(())
     }
 
     /// Create a directory at the specified path.
@@ -577,6 +752,64 @@ impl Operator {
             );
         }
 
+        // The size of decompressed content is unknown until it's fully decoded,
+        // so it can't be split into chunks or located from the end.
+        if opts.decompress && (opts.chunk.is_some() || opts.suffix_range.is_some()) {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "decompress can't be used together with chunk or suffix_range",
+            )
+            .with_operation("read")
+            .with_context("service", acc.info().scheme())
+            .with_context("path", &path));
+        }
+
+        let mut opts = opts;
+        if let Some(n) = opts.suffix_range {
+            if !opts.range.is_full() {
+                return Err(Error::new(
+                    ErrorKind::ConfigInvalid,
+                    "suffix_range can't be used together with range",
+                )
+                .with_operation("read")
+                .with_context("service", acc.info().scheme())
+                .with_context("path", &path));
+            }
+            // `bytes=-0` is not a valid range.
+            if n == 0 {
+                return Err(Error::new(
+                    ErrorKind::ConfigInvalid,
+                    "suffix_range must be greater than 0",
+                )
+                .with_operation("read")
+                .with_context("service", acc.info().scheme())
+                .with_context("path", &path));
+            }
+
+            // Read the suffix range in one request if service supports it.
+            if acc.info().full_capability().read_with_suffix_range {
+                let (args, _) = opts.into();
+                let (_, mut r) = acc.read(&path, args).await?;
+                return oio::Read::read_all(&mut r).await;
+            }
+
+            // Fall back to calculate the range via stat.
+            let mut op_stat = OpStat::new();
+            if let Some(v) = &opts.version {
+                op_stat = op_stat.with_version(v);
+            }
+            if let Some((max, backoff)) = opts.read_after_write_retry {
+                op_stat = op_stat.with_read_after_write_retry(max, backoff);
+            }
+            let size = acc
+                .stat(&path, op_stat)
+                .await?
+                .into_metadata()
+                .content_length();
+            opts.range = (size.saturating_sub(n)..size).into();
+            opts.suffix_range = None;
+        }
+
         let (args, opts) = opts.into();
         let range = args.range();
         let context = ReadContext::new(acc, path, args, opts);
@@ -585,6 +818,165 @@ impl Operator {
         Ok(buf)
     }
 
+    /// Read at most the first `n` bytes of the file at given path.
+    ///
+    /// # Notes
+    ///
+    /// This function sends a single range read of `0..n` and stops as soon as
+    /// `n` bytes have arrived, dropping the underlying reader to release the
+    /// connection even if the service streams more data than requested.
+    ///
+    /// Files smaller than `n` bytes are returned as is, no error will be
+    /// returned for them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// // Peek the magic bytes of a file.
+    /// let head = op.read_head("path/to/file", 4).await?;
+    /// assert!(head.len() <= 4);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn read_head(&self, path: &str, n: u64) -> Result<Buffer> {
+        let path = normalize_path(path);
+
+        if !validate_path(&path, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "read path is a directory")
+                    .with_operation("Operator::read_head")
+                    .with_context("service", self.info().scheme())
+                    .with_context("path", &path),
+            );
+        }
+
+        if n == 0 {
+            return Ok(Buffer::new());
+        }
+
+        let args = OpRead::new()
+            .with_range(BytesRange::new(0, Some(n)))
+            .with_lenient(true);
+        let mut r = match self.inner().read(&path, args).await {
+            Ok((_, r)) => r,
+            // Empty files can't satisfy any range.
+            Err(err) if err.kind() == ErrorKind::RangeNotSatisfied => return Ok(Buffer::new()),
+            Err(err) => return Err(err),
+        };
+
+        let mut bufs = Vec::new();
+        let mut read = 0;
+        while read < n {
+            let buf = r.read_dyn().await?;
+            if buf.is_empty() {
+                break;
+            }
+            read += buf.len() as u64;
+            bufs.push(buf);
+        }
+        // Release the underlying connection as early as possible.
+        drop(r);
+
+        let mut buf = bufs.into_iter().flatten().collect::<Buffer>();
+        buf.truncate(n as usize);
+        Ok(buf)
+    }
+
+    /// Read the given range of the file at given path along with its metadata.
+    ///
+    /// # Notes
+    ///
+    /// The metadata is taken from the read response if the service returns
+    /// full metadata in it, like most HTTP based services. Otherwise, an extra
+    /// `stat` will be sent.
+    ///
+    /// Like [`Operator::read_head`], files shorter than the range are returned
+    /// as is, no error will be returned for them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// // Detect the file type by both content type and magic bytes.
+    /// let (meta, head) = op.read_with_meta("path/to/file", 0..16).await?;
+    /// println!("{:?} {:?}", meta.content_type(), head.to_vec());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn read_with_meta(
+        &self,
+        path: &str,
+        range: impl RangeBounds<u64>,
+    ) -> Result<(Metadata, Buffer)> {
+        let path = normalize_path(path);
+
+        if !validate_path(&path, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "read path is a directory")
+                    .with_operation("Operator::read_with_meta")
+                    .with_context("service", self.info().scheme())
+                    .with_context("path", &path),
+            );
+        }
+
+        let args = OpRead::new()
+            .with_range(BytesRange::from(range))
+            .with_lenient(true);
+        let (meta, buf) = match self.inner().read(&path, args).await {
+            Ok((rp, mut r)) => {
+                let mut bufs = Vec::new();
+                loop {
+                    let buf = r.read_dyn().await?;
+                    if buf.is_empty() {
+                        break;
+                    }
+                    bufs.push(buf);
+                }
+                (rp.metadata().cloned(), bufs.into_iter().flatten().collect())
+            }
+            // Empty files can't satisfy any range.
+            Err(err) if err.kind() == ErrorKind::RangeNotSatisfied => (None, Buffer::new()),
+            Err(err) => return Err(err),
+        };
+
+        let meta = match meta {
+            Some(meta) => meta,
+            None => self.stat(&path).await?,
+        };
+        Ok((meta, buf))
+    }
+
+    /// Read the whole path as a stream of lines.
+    ///
+    /// # Notes
+    ///
+    /// Lines are split by `\n` or `\r\n` by default. To use other terminators, lossy
+    /// decoding or reader options like `concurrent`, please use [`Reader::into_lines_stream`]
+    /// instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// # use futures::TryStreamExt;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let mut lines = op.read_lines("path/to/file").await?;
+    /// while let Some(line) = lines.try_next().await? {
+    ///     println!("{line}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn read_lines(&self, path: &str) -> Result<FuturesLinesStream> {
+        self.reader(path).await?.into_lines_stream(..).await
+    }
+
     /// Create a new reader of given path.
     ///
     /// # Notes
@@ -688,6 +1080,16 @@ impl Operator {
             );
         }
 
+        if options.decompress && options.chunk.is_some() {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "decompress can't be used together with chunk",
+            )
+            .with_operation("Operator::reader")
+            .with_context("service", acc.info().scheme())
+            .with_context("path", path));
+        }
+
         let (args, opts) = options.into();
         let context = ReadContext::new(acc, path, args, opts);
         Ok(Reader::new(context))
@@ -734,6 +1136,55 @@ impl Operator {
         self.write_with(path, bs).await
     }
 
+    /// Write all data from the stream to the specified path.
+    ///
+    /// # Notes
+    ///
+    /// Every chunk will be written before pulling the next one from the stream.
+    /// The writer will be closed once the stream ends, or aborted if the stream
+    /// or the write returns an error.
+    ///
+    /// For options like `chunk` and `concurrent`, please use [`Operator::writer_with`]
+    /// and [`Writer::into_bytes_sink`] instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// use bytes::Bytes;
+    /// use futures::stream;
+    ///
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let stream = stream::iter([Ok(Bytes::from("Hello, ")), Ok(Bytes::from("World!"))]);
+    /// op.write_from("path/to/file", stream).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write_from<S, T>(&self, path: &str, stream: S) -> Result<Metadata>
+    where
+        S: Stream<Item = Result<T>>,
+        T: Into<Buffer>,
+    {
+        let mut w = self.writer(path).await?;
+
+        let mut stream = std::pin::pin!(stream);
+        while let Some(bs) = stream.next().await {
+            let res = match bs {
+                Ok(bs) => w.write(bs).await,
+                Err(err) => Err(err),
+            };
+            if let Err(err) = res {
+                if let Err(abort_err) = w.abort().await {
+                    warn!("abort writer failed: {abort_err}");
+                }
+                return Err(err);
+            }
+        }
+
+        w.close().await
+    }
+
     /// Write all data to the specified path at once with additional options.
     ///
     /// # Notes
@@ -857,6 +1308,102 @@ impl Operator {
         w.close().await
     }
 
+    /// Write data to the path only if it's not changed since `current_etag`.
+    ///
+    /// # Notes
+    ///
+    /// `write_cas` provides atomic compare-and-swap semantics on top of conditional writes:
+    ///
+    /// - `Some(etag)`: write only if the current ETag of the path matches, which
+    ///   requires [`Capability::write_with_if_match`].
+    /// - `None`: write only if the path doesn't exist, which requires
+    ///   [`Capability::write_with_if_not_exists`] or [`Capability::write_with_if_none_match`].
+    ///
+    /// Returns [`ErrorKind::ConditionNotMatch`] if the path has been changed by others,
+    /// and [`ErrorKind::Unsupported`] if the service can't write conditionally. OpenDAL
+    /// will never emulate it via `stat` and `write` since that's racy.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// use opendal::ErrorKind;
+    ///
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let meta = op.write_cas("path/to/lock", None, "v1").await?;
+    /// let etag = meta.etag().map(|v| v.to_string());
+    /// match op.write_cas("path/to/lock", etag, "v2").await {
+    ///     Ok(_) => println!("lock updated"),
+    ///     Err(err) if err.kind() == ErrorKind::ConditionNotMatch => println!("lock changed by others"),
+    ///     Err(err) => return Err(err),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write_cas(
+        &self,
+        path: &str,
+        current_etag: Option<String>,
+        bs: impl Into<Buffer>,
+    ) -> Result<Metadata> {
+        let cap = self.info().full_capability();
+        let opts = match current_etag {
+            Some(etag) if cap.write_with_if_match => options::WriteOptions {
+                if_match: Some(etag),
+                ..Default::default()
+            },
+            None if cap.write_with_if_not_exists => options::WriteOptions {
+                if_not_exists: true,
+                ..Default::default()
+            },
+            None if cap.write_with_if_none_match => options::WriteOptions {
+                if_none_match: Some("*".to_string()),
+                ..Default::default()
+            },
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::Unsupported,
+                    "service doesn't support conditional write required by write_cas",
+                )
+                .with_operation("Operator::write_cas")
+                .with_context("service", self.info().scheme())
+                .with_context("path", path));
+            }
+        };
+
+        self.write_options(path, bs, opts).await
+    }
+
+    /// Write data to the specified path only if it doesn't exist yet.
+    ///
+    /// Returns `true` if the data has been written, or `false` if the path
+    /// already exists. Only one of the concurrent callers will get `true`, so
+    /// it can be used for leader election and one-time initialization markers.
+    ///
+    /// It's built on [`Operator::write_cas`], so [`ErrorKind::Unsupported`] will
+    /// be returned if the service can't check the existence atomically.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// if op.write_if_not_exists("jobs/init.lock", "node-1").await? {
+    ///     // We are the first one, do the initialization.
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write_if_not_exists(&self, path: &str, bs: impl Into<Buffer>) -> Result<bool> {
+        match self.write_cas(path, None, bs).await {
+            Ok(_) => Ok(true),
+            Err(err) if err.kind() == ErrorKind::ConditionNotMatch => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
     /// Create a new writer of given path.
     ///
     /// # Notes
@@ -1015,6 +1562,77 @@ impl Operator {
         Ok(w)
     }
 
+    /// Abort an in-progress multipart upload of the file by upload id.
+    ///
+    /// This is useful to clean up uploads left by crashed processes, since
+    /// only the upload id survives. Live writers should use [`Writer::abort`]
+    /// instead.
+    ///
+    /// Check [`Capability::abort_multipart`] before using this.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// for upload in op.list_multipart_uploads("path/to/").await? {
+    ///     op.abort_multipart(upload.path(), upload.upload_id())
+    ///         .await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn abort_multipart(&self, path: &str, upload_id: &str) -> Result<()> {
+        let path = normalize_path(path);
+
+        if !validate_path(&path, EntryMode::FILE) {
+            return Err(Error::new(
+                ErrorKind::IsADirectory,
+                "abort multipart path is a directory",
+            )
+            .with_operation("Operator::abort_multipart")
+            .with_context("service", self.info().scheme())
+            .with_context("path", &path));
+        }
+
+        self.inner()
+            .abort_multipart(&path, OpAbortMultipart::new(upload_id))
+            .await?;
+        Ok(())
+    }
+
+    /// List in-progress multipart uploads of files starting with the prefix.
+    ///
+    /// Check [`Capability::list_multipart_uploads`] before using this.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chrono::Duration;
+    /// # use chrono::Utc;
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let deadline = Utc::now() - Duration::days(7);
+    /// let stale = op
+    ///     .list_multipart_uploads("path/to/")
+    ///     .await?
+    ///     .into_iter()
+    ///     .filter(|v| v.initiated().is_some_and(|t| t < deadline));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_multipart_uploads(&self, prefix: &str) -> Result<Vec<MultipartUpload>> {
+        let prefix = normalize_path(prefix);
+
+        let rp = self
+            .inner()
+            .list_multipart_uploads(&prefix, OpListMultipartUploads::new())
+            .await?;
+        Ok(rp.into_uploads())
+    }
+
     /// Copy a file from `from` to `to`.
     ///
     /// # Notes
@@ -1023,6 +1641,8 @@ impl Operator {
     /// - `to` will be overwritten if it exists.
     /// - If `from` and `to` are the same,  an `IsSameFile` error will occur.
     /// - `copy` is idempotent. For same `from` and `to` input, the result will be the same.
+    /// - If the service doesn't support copy natively, the source will be streamed into
+    ///   the destination in chunks without buffering the whole file in memory.
     ///
     /// # Examples
     ///
@@ -1068,6 +1688,16 @@ impl Operator {
             );
         }
 
+        if !self.info().full_capability().copy {
+            return Self::copy_by_stream(
+                self.inner().clone(),
+                from,
+                to,
+                options::CopyOptions::default(),
+            )
+            .await;
+        }
+
         self.inner().copy(&from, &to, OpCopy::new()).await?;
 
         Ok(())
@@ -1186,12 +1816,89 @@ impl Operator {
             );
         }
 
-        let mut op = OpCopy::new();
-        if opts.if_not_exists {
-            op = op.with_if_not_exists(true);
+        if !acc.info().full_capability().copy {
+            return Self::copy_by_stream(acc, from, to, opts).await;
         }
 
-        acc.copy(&from, &to, op).await.map(|_| ())
+        acc.copy(&from, &to, opts.into()).await.map(|_| ())
+    }
+
+    /// Copy by streaming the source into the destination for services that
+    /// can't copy natively.
+    ///
+    /// The destination will be written in the chunk size decided by the
+    /// service's capability, and will be aborted if copy failed halfway.
+    /// Metadata of the source will be carried to the destination unless it's
+    /// replaced.
+    async fn copy_by_stream(
+        acc: Accessor,
+        from: String,
+        to: String,
+        opts: options::CopyOptions,
+    ) -> Result<()> {
+        let meta = acc.stat(&from, OpStat::new()).await?.into_metadata();
+        let (_, mut r) = acc.read(&from, OpRead::new()).await?;
+
+        let cap = acc.info().full_capability();
+        let mut args = OpWrite::new()
+            .with_if_not_exists(opts.if_not_exists)
+            .with_content_length(meta.content_length());
+        match opts.metadata_directive {
+            options::MetadataDirective::Copy => {
+                if let Some(v) = meta.content_type() {
+                    args = args.with_content_type(v);
+                }
+                if let Some(v) = meta.cache_control() {
+                    args = args.with_cache_control(v);
+                }
+                if let Some(v) = meta.content_disposition() {
+                    args = args.with_content_disposition(v);
+                }
+                // Source metadata is best-effort, don't fail the copy if the
+                // destination can't store it.
+                if let Some(v) = meta.user_metadata() {
+                    if cap.write_with_user_metadata {
+                        args = args.with_user_metadata(v.clone());
+                    }
+                }
+            }
+            options::MetadataDirective::Replace => {
+                if let Some(v) = &opts.content_type {
+                    args = args.with_content_type(v);
+                }
+                if let Some(v) = &opts.cache_control {
+                    args = args.with_cache_control(v);
+                }
+                if let Some(v) = opts.user_metadata {
+                    args = args.with_user_metadata(v);
+                }
+            }
+        }
+        let ctx = WriteContext::new(acc, to, args, OpWriter::new());
+        let mut w = Writer::new(ctx).await?;
+
+        // The writer enforces `content_length`, a source that changed during
+        // copy will fail on write or close.
+        let res = async {
+            loop {
+                let bs = r.read_dyn().await?;
+                if bs.is_empty() {
+                    break;
+                }
+                w.write(bs).await?;
+            }
+            w.close().await.map(|_| ())
+        }
+        .await;
+
+        if let Err(err) = res {
+            if let Err(abort_err) = w.abort().await {
+                warn!("abort writer failed after copy failed: {abort_err}");
+            }
+            return Err(err);
+        }
+
+        Ok(())
     }
 
     /// Rename a file from `from` to `to`.
@@ -1201,6 +1908,10 @@ impl Operator {
     /// - `from` and `to` must be a file.
     /// - `to` will be overwritten if it exists.
     /// - If `from` and `to` are the same, an `IsSameFile` error will occur.
+    /// - Rename is only performed by services' native rename and will never be emulated
+    ///   by copy and delete. Services without [`Capability::rename`] will return
+    ///   [`ErrorKind::Unsupported`], users can use [`Operator::copy`] and [`Operator::delete`]
+    ///   by themselves if non-atomic rename is acceptable.
     ///
     /// # Examples
     ///
@@ -1428,6 +2139,9 @@ impl Operator {
     ///
     /// # Notes
     ///
+    /// If underlying services support delete with recursive, we will delete
+    /// the dir in one request instead.
+    ///
     /// If underlying services support delete in batch, we will use batch
     /// delete instead.
     ///
@@ -1444,12 +2158,93 @@ impl Operator {
     /// # }
     /// ```
     pub async fn remove_all(&self, path: &str) -> Result<()> {
-        match self.stat(path).await {
+        if path.ends_with('/') && self.info().full_capability().delete_with_recursive {
+            return self.delete_with(path).recursive(true).await;
+        }
+
+        self.remove_all_with(path).await.map(|_| ())
+    }
+
+    /// Remove the path and all nested dirs and files recursively with additional
+    /// options, returns the number of removed entries.
+    ///
+    /// # Notes
+    ///
+    /// Unlike [`Operator::remove_all`], entries will always be listed and deleted in
+    /// batches even if services support delete with recursive, so that the progress
+    /// can be reported.
+    ///
+    /// Removing is best-effort: entries created after the listing passed them will
+    /// not be removed, and entries that have been removed by others will be ignored.
+    ///
+    /// # Options
+    ///
+    /// Visit [`options::RemoveAllOptions`] for all available options.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// #
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let removed = op
+    ///     .remove_all_with("path/to/dir/")
+    ///     .progress(|n| println!("removed {n} entries"))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn remove_all_with(
+        &self,
+        path: &str,
+    ) -> FutureRemoveAll<impl Future<Output = Result<usize>>> {
+        let path = normalize_path(path);
+
+        OperatorFuture::new(
+            self.inner().clone(),
+            path,
+            options::RemoveAllOptions::default(),
+            Self::remove_all_inner,
+        )
+    }
+
+    /// Remove the path and all nested dirs and files recursively with additional
+    /// options, returns the number of removed entries.
+    ///
+    /// Visit [`Operator::remove_all_with`] for more details.
+    pub async fn remove_all_options(
+        &self,
+        path: &str,
+        opts: options::RemoveAllOptions,
+    ) -> Result<usize> {
+        let path = normalize_path(path);
+        Self::remove_all_inner(self.inner().clone(), path, opts).await
+    }
+
+    async fn remove_all_inner(
+        acc: Accessor,
+        path: String,
+        opts: options::RemoveAllOptions,
+    ) -> Result<usize> {
+        let op = Operator::from_inner(acc);
+        let mut deleter = op.deleter().await?;
+        let report = |deleter: &Deleter, reported: &mut usize| {
+            if deleter.deleted() != *reported {
+                *reported = deleter.deleted();
+                if let Some(progress) = &opts.progress {
+                    progress(*reported);
+                }
+            }
+        };
+        let mut reported = 0;
+
+        match op.stat(&path).await {
             // If object exists.
             Ok(metadata) => {
                 // If the object is a file, we can delete it.
                 if metadata.mode() != EntryMode::DIR {
-                    self.delete(path).await?;
+                    deleter.delete(path.as_str()).await?;
                     // There may still be objects prefixed with the path in some backend, so we can't return here.
                 }
             }
@@ -1462,9 +2257,15 @@ impl Operator {
             Err(e) => return Err(e),
         };
 
-        let lister = self.lister_with(path).recursive(true).await?;
-        self.delete_try_stream(lister).await?;
-        Ok(())
+        let mut lister = op.lister_with(&path).recursive(true).await?;
+        while let Some(entry) = lister.try_next().await? {
+            deleter.delete(entry).await?;
+            report(&deleter, &mut reported);
+        }
+        deleter.close().await?;
+        report(&deleter, &mut reported);
+
+        Ok(deleter.deleted())
     }
 
     /// List entries in the parent directory that start with the specified `path`.
@@ -2233,4 +3034,106 @@ impl Operator {
         let rp = acc.presign(&path, op).await?;
         Ok(rp.into_presigned_request())
     }
+
+    /// Presign an operation for creating a multipart upload.
+    ///
+    /// Multipart presign allows clients like browsers to upload huge files in parallel
+    /// without proxying bytes through the server:
+    ///
+    /// 1. Create the multipart upload via [`Self::presign_create_multipart`] and get the
+    ///    upload id from the response.
+    /// 2. Upload parts via [`Self::presign_upload_part`].
+    /// 3. Complete the upload via [`Self::presign_complete_multipart`] with all uploaded
+    ///    parts in request body.
+    ///
+    /// The response and request body are service specific, for example, they are XML
+    /// documents for S3.
+    ///
+    /// # Notes
+    ///
+    /// Services that don't support `presign_multipart` will return [`ErrorKind::Unsupported`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use anyhow::Result;
+    /// use opendal::Operator;
+    ///
+    /// async fn test(op: Operator) -> Result<()> {
+    ///     let create = op
+    ///         .presign_create_multipart("test.txt", Duration::from_secs(3600))
+    ///         .await?;
+    ///     let upload_id = "<upload id returned by create>";
+    ///     let part = op
+    ///         .presign_upload_part("test.txt", upload_id, 1, Duration::from_secs(3600))
+    ///         .await?;
+    ///     let complete = op
+    ///         .presign_complete_multipart("test.txt", upload_id, Duration::from_secs(3600))
+    ///         .await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn presign_create_multipart(
+        &self,
+        path: &str,
+        expire: Duration,
+    ) -> Result<PresignedRequest> {
+        let path = normalize_path(path);
+
+        let op = OpPresign::new(PresignOperation::CreateMultipart(OpWrite::new()), expire);
+
+        let rp = self.inner().presign(&path, op).await?;
+        Ok(rp.into_presigned_request())
+    }
+
+    /// Presign an operation for uploading a part of given multipart upload.
+    ///
+    /// The `part_number` will be sent to the service as is, for example, S3 requires
+    /// it to be between 1 and 10000.
+    ///
+    /// Visit [`Self::presign_create_multipart`] for more details.
+    pub async fn presign_upload_part(
+        &self,
+        path: &str,
+        upload_id: &str,
+        part_number: usize,
+        expire: Duration,
+    ) -> Result<PresignedRequest> {
+        let path = normalize_path(path);
+
+        let op = OpPresign::new(
+            PresignOperation::UploadPart {
+                upload_id: upload_id.to_string(),
+                part_number,
+            },
+            expire,
+        );
+
+        let rp = self.inner().presign(&path, op).await?;
+        Ok(rp.into_presigned_request())
+    }
+
+    /// Presign an operation for completing given multipart upload.
+    ///
+    /// Visit [`Self::presign_create_multipart`] for more details.
+    pub async fn presign_complete_multipart(
+        &self,
+        path: &str,
+        upload_id: &str,
+        expire: Duration,
+    ) -> Result<PresignedRequest> {
+        let path = normalize_path(path);
+
+        let op = OpPresign::new(
+            PresignOperation::CompleteMultipart {
+                upload_id: upload_id.to_string(),
+            },
+            expire,
+        );
+
+        let rp = self.inner().presign(&path, op).await?;
+        Ok(rp.into_presigned_request())
+    }
 }
@@ -22,11 +22,13 @@
 use std::collections::HashMap;
 use std::future::IntoFuture;
 use std::ops::RangeBounds;
+use std::sync::Arc;
 use std::time::Duration;
 
 use chrono::DateTime;
 use chrono::Utc;
 use futures::Future;
+use http::HeaderMap;
 
 use crate::raw::*;
 use crate::*;
@@ -127,6 +129,22 @@ impl<F: Future<Output = Result<Metadata>>> FutureStat<F> {
         self.args.version = Some(v.to_string());
         self
     }
+
+    /// Set whether to fetch tags for this operation.
+    ///
+    /// Refer to [`options::StatOptions::tags`] for more details.
+    pub fn tags(mut self, v: bool) -> Self {
+        self.args.tags = v;
+        self
+    }
+
+    /// Set extra headers for this operation.
+    ///
+    /// Refer to [`options::StatOptions::headers`] for more details.
+    pub fn headers(mut self, v: HeaderMap) -> Self {
+        self.args.headers = Some(v);
+        self
+    }
 }
 
 /// Future that generated by [`Operator::presign_stat_with`].
@@ -422,6 +440,144 @@ impl<F: Future<Output = Result<Buffer>>> FutureRead<F> {
         self.args.if_unmodified_since = Some(v);
         self
     }
+
+    /// Set `timeout` for this `read` request.
+    ///
+    /// The timeout will override the io timeout configured in [`crate::layers::TimeoutLayer`].
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let _ = op.read_with("path/to/file").timeout(Duration::from_secs(300)).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.args.timeout = Some(timeout);
+        self
+    }
+
+    /// Set `skip_checksum` for this `read` request.
+    ///
+    /// Skip the checksum verification done by `ChecksumLayer`, which is useful when the
+    /// caller doesn't need an end-to-end digest check for this request.
+    ///
+    /// ```
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let _ = op.read_with("path/to/file").skip_checksum(true).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn skip_checksum(mut self, v: bool) -> Self {
+        self.args.skip_checksum = v;
+        self
+    }
+
+    /// Set `suffix_range` for this `read` request to read the last `n` bytes.
+    ///
+    /// Services with [`Capability::read_with_suffix_range`] will read it in one request,
+    /// others will `stat` the file first to calculate the range.
+    ///
+    /// `n` must be greater than 0 and can't be used together with `range`.
+    ///
+    /// ```
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// // Read the footer of a parquet file.
+    /// let footer = op.read_with("path/to/file").suffix_range(8).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn suffix_range(mut self, n: u64) -> Self {
+        self.args.suffix_range = Some(n);
+        self
+    }
+
+    /// Set `max_size` for this `read` request to limit the size of data.
+    ///
+    /// Refer to [`options::ReadOptions::max_size`] for more details.
+    ///
+    /// ```
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// // Refuse to load files larger than 16 MiB.
+    /// let bs = op
+    ///     .read_with("path/to/file")
+    ///     .max_size(16 * 1024 * 1024)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn max_size(mut self, n: u64) -> Self {
+        self.args.max_size = Some(n);
+        self
+    }
+
+    /// Set extra headers for this `read` request.
+    ///
+    /// Refer to [`options::ReadOptions::headers`] for more details.
+    ///
+    /// ```
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// use http::HeaderMap;
+    ///
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let mut headers = HeaderMap::new();
+    /// headers.insert("x-tenant-id", "tenant-a".parse().unwrap());
+    /// let bs = op.read_with("path/to/file").headers(headers).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn headers(mut self, v: HeaderMap) -> Self {
+        self.args.headers = Some(v);
+        self
+    }
+
+    /// Retry `NotFound` for a short window after a known write.
+    ///
+    /// Refer to [`options::ReadOptions::read_after_write_retry`] for more details.
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// op.write("path/to/file", "Hello, World!").await?;
+    /// let bs = op
+    ///     .read_with("path/to/file")
+    ///     .read_after_write_retry(3, Duration::from_millis(100))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_after_write_retry(mut self, max: usize, backoff: Duration) -> Self {
+        self.args.read_after_write_retry = Some((max, backoff));
+        self
+    }
+
+    /// Decompress content-encoded objects transparently for this `read` request.
+    ///
+    /// Refer to [`options::ReadOptions::decompress`] for more details.
+    ///
+    /// ```
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let bs = op.read_with("path/to/file.json").decompress(true).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn decompress(mut self, v: bool) -> Self {
+        self.args.decompress = v;
+        self
+    }
 }
 
 /// Future that generated by [`Operator::read_with`] or [`Operator::reader_with`].
@@ -619,6 +775,66 @@ impl<F: Future<Output = Result<Reader>>> FutureReader<F> {
         self.args.if_unmodified_since = Some(v);
         self
     }
+
+    /// Set `timeout` for this `reader` request.
+    ///
+    /// The timeout will override the io timeout configured in [`crate::layers::TimeoutLayer`].
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let _ = op.reader_with("path/to/file").timeout(Duration::from_secs(300)).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.args.timeout = Some(timeout);
+        self
+    }
+
+    /// Set `skip_checksum` for this `reader` request.
+    ///
+    /// Skip the checksum verification done by `ChecksumLayer`, which is useful when the
+    /// caller doesn't need an end-to-end digest check for this request.
+    ///
+    /// ```
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let _ = op.reader_with("path/to/file").skip_checksum(true).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn skip_checksum(mut self, v: bool) -> Self {
+        self.args.skip_checksum = v;
+        self
+    }
+
+    /// Set extra headers for this `reader` request.
+    ///
+    /// Refer to [`options::ReaderOptions::headers`] for more details.
+    pub fn headers(mut self, v: HeaderMap) -> Self {
+        self.args.headers = Some(v);
+        self
+    }
+
+    /// Retry `NotFound` for a short window after a known write.
+    ///
+    /// Refer to [`options::ReaderOptions::read_after_write_retry`] for more details.
+    pub fn read_after_write_retry(mut self, max: usize, backoff: Duration) -> Self {
+        self.args.read_after_write_retry = Some((max, backoff));
+        self
+    }
+
+    /// Decompress content-encoded objects transparently for this `reader` request.
+    ///
+    /// Refer to [`options::ReaderOptions::decompress`] for more details.
+    pub fn decompress(mut self, v: bool) -> Self {
+        self.args.decompress = v;
+        self
+    }
 }
 
 /// Future that generated by [`Operator::write_with`].
@@ -918,6 +1134,193 @@ impl<F: Future<Output = Result<Metadata>>> FutureWrite<F> {
         self.args.0.user_metadata = Some(HashMap::from_iter(data));
         self
     }
+
+    /// Set `timeout` for this `write` request.
+    ///
+    /// The timeout will override the io timeout configured in [`crate::layers::TimeoutLayer`].
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let _ = op.write_with("path/to/file", "hello").timeout(Duration::from_secs(300)).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.args.0.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets whether to detect content type for this `write` request.
+    ///
+    /// If content type is not set, OpenDAL will guess it from the magic bytes of the
+    /// first written buffer. The detected content type never overrides the one set by
+    /// [`Self::content_type`].
+    ///
+    /// ```
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let _ = op
+    ///     .write_with("path/to/file", vec![0; 4096])
+    ///     .content_type_detection(true)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn content_type_detection(mut self, v: bool) -> Self {
+        self.args.0.content_type_detection = v;
+        self
+    }
+
+    /// Sets server side encryption for this `write` request.
+    ///
+    /// The encryption overrides the default encryption configured in the service builder.
+    ///
+    /// ### Capability
+    ///
+    /// Check [`Capability::write_with_encryption`] before using this feature.
+    ///
+    /// ```
+    /// # use opendal::Encryption;
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let _ = op
+    ///     .write_with("path/to/file", vec![0; 4096])
+    ///     .encryption(Encryption::SseKms {
+    ///         key_id: Some("my-key".to_string()),
+    ///         context: None,
+    ///     })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn encryption(mut self, v: Encryption) -> Self {
+        self.args.0.encryption = Some(v);
+        self
+    }
+
+    /// Sets the max size of written data to retain for replaying this `write` request.
+    ///
+    /// Retained data will be written again if `close` is retried after temporary errors,
+    /// this is useful with [`crate::layers::RetryLayer`] for services that consume
+    /// the data while writing.
+    ///
+    /// ```
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let _ = op
+    ///     .write_with("path/to/file", vec![0; 4096])
+    ///     .replay_buffer(8 * 1024 * 1024)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn replay_buffer(mut self, v: usize) -> Self {
+        self.args.0.replay_buffer = Some(v);
+        self
+    }
+
+    /// Sets the pool to reserve buffered chunks from.
+    ///
+    /// Refer to [`options::WriteOptions::buffer_pool`] for more details.
+    pub fn buffer_pool(mut self, v: BufferPool) -> Self {
+        self.args.0.buffer_pool = Some(v);
+        self
+    }
+
+    /// Set extra headers for this `write` request.
+    ///
+    /// Refer to [`options::WriteOptions::headers`] for more details.
+    pub fn headers(mut self, v: HeaderMap) -> Self {
+        self.args.0.headers = Some(v);
+        self
+    }
+
+    /// Declare the expected size of data for this `write` request.
+    ///
+    /// Refer to [`options::WriteOptions::content_length`] for more details.
+    pub fn content_length(mut self, v: u64) -> Self {
+        self.args.0.content_length = Some(v);
+        self
+    }
+
+    /// Verify the written object by stat after this `write` request.
+    ///
+    /// Refer to [`options::WriteOptions::verify`] for more details.
+    pub fn verify(mut self, v: bool) -> Self {
+        self.args.0.verify = v;
+        self
+    }
+
+    /// Set the permission bits of the written file, like `0o755`.
+    ///
+    /// Refer to [`options::WriteOptions::mode`] for more details.
+    pub fn mode(mut self, v: u32) -> Self {
+        self.args.0.mode = Some(v);
+        self
+    }
+
+    /// Stat the written object after this `write` request to fill the returned metadata.
+    ///
+    /// Refer to [`options::WriteOptions::stat_after_close`] for more details.
+    ///
+    /// ```
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let meta = op
+    ///     .write_with("path/to/file", vec![0; 4096])
+    ///     .stat_after_close(true)
+    ///     .await?;
+    /// let _ = meta.content_md5();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn stat_after_close(mut self, v: bool) -> Self {
+        self.args.0.stat_after_close = v;
+        self
+    }
+
+    /// Set the storage class of the written object.
+    ///
+    /// Refer to [`options::WriteOptions::storage_class`] for more details.
+    ///
+    /// ```
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let _ = op
+    ///     .write_with("path/to/file", vec![0; 4096])
+    ///     .storage_class("STANDARD_IA")
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn storage_class(mut self, v: &str) -> Self {
+        self.args.0.storage_class = Some(v.to_string());
+        self
+    }
+
+    /// Set the object lock retention of the written object.
+    ///
+    /// Refer to [`options::WriteOptions::retention`] for more details.
+    pub fn retention(mut self, retain_until: DateTime<Utc>, mode: RetentionMode) -> Self {
+        self.args.0.retention = Some(Retention { mode, retain_until });
+        self
+    }
+
+    /// Set the legal hold status of the written object.
+    ///
+    /// Refer to [`options::WriteOptions::legal_hold`] for more details.
+    pub fn legal_hold(mut self, v: bool) -> Self {
+        self.args.0.legal_hold = Some(v);
+        self
+    }
 }
 
 /// Future that generated by [`Operator::writer_with`].
@@ -1257,12 +1660,205 @@ impl<F: Future<Output = Result<Writer>>> FutureWriter<F> {
         self.args.user_metadata = Some(HashMap::from_iter(data));
         self
     }
-}
-
-/// Future that generated by [`Operator::delete_with`].
-///
-/// Users can add more options by public functions provided by this struct.
-pub type FutureDelete<F> = OperatorFuture<options::DeleteOptions, (), F>;
+
+    /// Set `timeout` for this `writer` request.
+    ///
+    /// The timeout will override the io timeout configured in [`crate::layers::TimeoutLayer`].
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let _ = op.writer_with("path/to/file").timeout(Duration::from_secs(300)).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.args.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets whether to detect content type for this `writer` request.
+    ///
+    /// If content type is not set, OpenDAL will guess it from the magic bytes of the
+    /// first written buffer. The detected content type never overrides the one set by
+    /// [`Self::content_type`].
+    ///
+    /// ```
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let _ = op
+    ///     .writer_with("path/to/file")
+    ///     .content_type_detection(true)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn content_type_detection(mut self, v: bool) -> Self {
+        self.args.content_type_detection = v;
+        self
+    }
+
+    /// Sets server side encryption for this `writer` request.
+    ///
+    /// The encryption overrides the default encryption configured in the service builder.
+    ///
+    /// ### Capability
+    ///
+    /// Check [`Capability::write_with_encryption`] before using this feature.
+    ///
+    /// ```
+    /// # use opendal::Encryption;
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let _ = op
+    ///     .writer_with("path/to/file")
+    ///     .encryption(Encryption::SseKms {
+    ///         key_id: Some("my-key".to_string()),
+    ///         context: None,
+    ///     })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn encryption(mut self, v: Encryption) -> Self {
+        self.args.encryption = Some(v);
+        self
+    }
+
+    /// Sets the max size of written data to retain for replaying this `writer` request.
+    ///
+    /// Retained data will be written again if `close` is retried after temporary errors.
+    /// Close failures will be returned as persistent errors once written data exceeds
+    /// the size since they can't be retried anymore.
+    ///
+    /// ```
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let _ = op
+    ///     .writer_with("path/to/file")
+    ///     .replay_buffer(8 * 1024 * 1024)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn replay_buffer(mut self, v: usize) -> Self {
+        self.args.replay_buffer = Some(v);
+        self
+    }
+
+    /// Sets the pool to reserve buffered chunks from.
+    ///
+    /// Writers sharing the same pool will wait for each other once the buffered
+    /// bytes reach its capacity. Refer to [`options::WriteOptions::buffer_pool`]
+    /// for more details.
+    pub fn buffer_pool(mut self, v: BufferPool) -> Self {
+        self.args.buffer_pool = Some(v);
+        self
+    }
+
+    /// Set extra headers for this `writer` request.
+    ///
+    /// Refer to [`options::WriteOptions::headers`] for more details.
+    pub fn headers(mut self, v: HeaderMap) -> Self {
+        self.args.headers = Some(v);
+        self
+    }
+
+    /// Declare the expected size of data for this `writer`.
+    ///
+    /// Refer to [`options::WriteOptions::content_length`] for more details.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let mut w = op.writer_with("path/to/file").content_length(10).await?;
+    /// w.write(vec![0; 4]).await?;
+    /// // Close fails since only 4 of 10 bytes have been written.
+    /// assert!(w.close().await.is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn content_length(mut self, v: u64) -> Self {
+        self.args.content_length = Some(v);
+        self
+    }
+
+    /// Verify the written object by stat after this `writer` has been closed.
+    ///
+    /// Refer to [`options::WriteOptions::verify`] for more details.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let mut w = op.writer_with("path/to/file").verify(true).await?;
+    /// w.write(vec![0; 4096]).await?;
+    /// // Close fails if the stored object doesn't match the written data.
+    /// w.close().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn verify(mut self, v: bool) -> Self {
+        self.args.verify = v;
+        self
+    }
+
+    /// Set the permission bits of the written file, like `0o755`.
+    ///
+    /// Refer to [`options::WriteOptions::mode`] for more details.
+    pub fn mode(mut self, v: u32) -> Self {
+        self.args.mode = Some(v);
+        self
+    }
+
+    /// Stat the written object after this `writer` has been closed to fill the
+    /// returned metadata.
+    ///
+    /// Refer to [`options::WriteOptions::stat_after_close`] for more details.
+    pub fn stat_after_close(mut self, v: bool) -> Self {
+        self.args.stat_after_close = v;
+        self
+    }
+
+    /// Set the storage class of the written object.
+    ///
+    /// Refer to [`options::WriteOptions::storage_class`] for more details.
+    pub fn storage_class(mut self, v: &str) -> Self {
+        self.args.storage_class = Some(v.to_string());
+        self
+    }
+
+    /// Set the object lock retention of the written object.
+    ///
+    /// Refer to [`options::WriteOptions::retention`] for more details.
+    pub fn retention(mut self, retain_until: DateTime<Utc>, mode: RetentionMode) -> Self {
+        self.args.retention = Some(Retention { mode, retain_until });
+        self
+    }
+
+    /// Set the legal hold status of the written object.
+    ///
+    /// Refer to [`options::WriteOptions::legal_hold`] for more details.
+    pub fn legal_hold(mut self, v: bool) -> Self {
+        self.args.legal_hold = Some(v);
+        self
+    }
+}
+
+/// Future that generated by [`Operator::delete_with`].
+///
+/// Users can add more options by public functions provided by this struct.
+pub type FutureDelete<F> = OperatorFuture<options::DeleteOptions, (), F>;
 
 impl<F: Future<Output = Result<()>>> FutureDelete<F> {
     /// Change the version of this delete operation.
@@ -1270,6 +1866,42 @@ impl<F: Future<Output = Result<()>>> FutureDelete<F> {
         self.args.version = Some(v.to_string());
         self
     }
+
+    /// Delete the dir and all its contents recursively.
+    ///
+    /// This option only works for services that support `delete_with_recursive`.
+    pub fn recursive(mut self, v: bool) -> Self {
+        self.args.recursive = Some(v);
+        self
+    }
+
+    /// Return a `NotFound` error if the path does not exist.
+    ///
+    /// This option only works for services that support `delete_with_must_exist`.
+    pub fn must_exist(mut self, v: bool) -> Self {
+        self.args.must_exist = v;
+        self
+    }
+
+    /// Only delete the file if its current `ETag` matches the given one.
+    ///
+    /// Refer to [`options::DeleteOptions::if_match`] for more details.
+    ///
+    /// ```
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let meta = op.stat("path/to/file").await?;
+    /// if let Some(etag) = meta.etag() {
+    ///     op.delete_with("path/to/file").if_match(etag).await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn if_match(mut self, v: &str) -> Self {
+        self.args.if_match = Some(v.to_string());
+        self
+    }
 }
 
 /// Future that generated by [`Operator::deleter_with`].
@@ -1310,6 +1942,90 @@ impl<F: Future<Output = Result<Vec<Entry>>>> FutureList<F> {
         self
     }
 
+    /// Only return entries whose path relative to the listed dir matches the glob pattern.
+    ///
+    /// `*` and `?` never match `/`, and `**` matches any number of path segments. Use it
+    /// with `recursive(true)` to match nested entries like `**/*.parquet`.
+    ///
+    /// Requires the `list-glob` feature.
+    pub fn glob(mut self, v: &str) -> Self {
+        self.args.glob = Some(v.to_string());
+        self
+    }
+
+    /// Set the number of dirs that can be listed concurrently for recursive list.
+    ///
+    /// This only takes effect on services that can't list recursively natively.
+    /// Entries will be returned in breadth-first order if `v` is larger than 1.
+    ///
+    /// It also bounds the number of concurrent stats while fetching `metadata`.
+    pub fn concurrent(mut self, v: usize) -> Self {
+        self.args.concurrent = v.max(1);
+        self
+    }
+
+    /// Only return dirs in the list result.
+    ///
+    /// This is useful to build a tree view of the storage without fetching
+    /// all files. Default to `false`.
+    pub fn dirs_only(mut self, v: bool) -> Self {
+        self.args.dirs_only = v;
+        self
+    }
+
+    /// Return entries in lexicographic order of their paths.
+    ///
+    /// The order is unspecified by default. Recursive lists of services that list
+    /// in order natively are used directly, otherwise entries will be buffered and
+    /// sorted, see [`options::ListOptions::sorted`] for the memory usage. Default
+    /// to `false`.
+    pub fn sorted(mut self, v: bool) -> Self {
+        self.args.sorted = v;
+        self
+    }
+
+    /// Return entries with full metadata like `content_length` and `last_modified`.
+    ///
+    /// Files will be stat concurrently if the service can't return full metadata
+    /// while listing, use `concurrent` to control the number of concurrent stats.
+    /// Default to `false`.
+    pub fn metadata(mut self, v: bool) -> Self {
+        self.args.metadata = v;
+        self
+    }
+
+    /// Only return files modified after the given time.
+    ///
+    /// Refer to [`options::ListOptions::modified_after`] for more details.
+    pub fn modified_after(mut self, v: DateTime<Utc>) -> Self {
+        self.args.modified_after = Some(v);
+        self
+    }
+
+    /// Only return files modified before the given time.
+    ///
+    /// Refer to [`options::ListOptions::modified_before`] for more details.
+    pub fn modified_before(mut self, v: DateTime<Utc>) -> Self {
+        self.args.modified_before = Some(v);
+        self
+    }
+
+    /// Stop listing once the given number of entries have been returned.
+    ///
+    /// Refer to [`options::ListOptions::max_entries`] for more details.
+    pub fn max_entries(mut self, v: usize) -> Self {
+        self.args.max_entries = Some(v);
+        self
+    }
+
+    /// Skip the entries that have been returned already.
+    ///
+    /// Refer to [`options::ListOptions::dedup`] for more details.
+    pub fn dedup(mut self, v: bool) -> Self {
+        self.args.dedup = v;
+        self
+    }
+
     /// Controls whether the `list` operation should return file versions.
     ///
     /// This function allows you to specify if the `list` operation, when executed, should include
@@ -1371,6 +2087,90 @@ impl<F: Future<Output = Result<Lister>>> FutureLister<F> {
         self
     }
 
+    /// Only return entries whose path relative to the listed dir matches the glob pattern.
+    ///
+    /// `*` and `?` never match `/`, and `**` matches any number of path segments. Use it
+    /// with `recursive(true)` to match nested entries like `**/*.parquet`.
+    ///
+    /// Requires the `list-glob` feature.
+    pub fn glob(mut self, v: &str) -> Self {
+        self.args.glob = Some(v.to_string());
+        self
+    }
+
+    /// Set the number of dirs that can be listed concurrently for recursive list.
+    ///
+    /// This only takes effect on services that can't list recursively natively.
+    /// Entries will be returned in breadth-first order if `v` is larger than 1.
+    ///
+    /// It also bounds the number of concurrent stats while fetching `metadata`.
+    pub fn concurrent(mut self, v: usize) -> Self {
+        self.args.concurrent = v.max(1);
+        self
+    }
+
+    /// Only return dirs in the list result.
+    ///
+    /// This is useful to build a tree view of the storage without fetching
+    /// all files. Default to `false`.
+    pub fn dirs_only(mut self, v: bool) -> Self {
+        self.args.dirs_only = v;
+        self
+    }
+
+    /// Return entries in lexicographic order of their paths.
+    ///
+    /// The order is unspecified by default. Recursive lists of services that list
+    /// in order natively are used directly, otherwise entries will be buffered and
+    /// sorted, see [`options::ListOptions::sorted`] for the memory usage. Default
+    /// to `false`.
+    pub fn sorted(mut self, v: bool) -> Self {
+        self.args.sorted = v;
+        self
+    }
+
+    /// Return entries with full metadata like `content_length` and `last_modified`.
+    ///
+    /// Files will be stat concurrently if the service can't return full metadata
+    /// while listing, use `concurrent` to control the number of concurrent stats.
+    /// Default to `false`.
+    pub fn metadata(mut self, v: bool) -> Self {
+        self.args.metadata = v;
+        self
+    }
+
+    /// Only return files modified after the given time.
+    ///
+    /// Refer to [`options::ListOptions::modified_after`] for more details.
+    pub fn modified_after(mut self, v: DateTime<Utc>) -> Self {
+        self.args.modified_after = Some(v);
+        self
+    }
+
+    /// Only return files modified before the given time.
+    ///
+    /// Refer to [`options::ListOptions::modified_before`] for more details.
+    pub fn modified_before(mut self, v: DateTime<Utc>) -> Self {
+        self.args.modified_before = Some(v);
+        self
+    }
+
+    /// Stop listing once the given number of entries have been returned.
+    ///
+    /// Refer to [`options::ListOptions::max_entries`] for more details.
+    pub fn max_entries(mut self, v: usize) -> Self {
+        self.args.max_entries = Some(v);
+        self
+    }
+
+    /// Skip the entries that have been returned already.
+    ///
+    /// Refer to [`options::ListOptions::dedup`] for more details.
+    pub fn dedup(mut self, v: bool) -> Self {
+        self.args.dedup = v;
+        self
+    }
+
     /// Controls whether the `list` operation should return file versions.
     ///
     /// This function allows you to specify if the `list` operation, when executed, should include
@@ -1427,4 +2227,70 @@ impl<F: Future<Output = Result<()>>> FutureCopy<F> {
         self.args.0.if_not_exists = v;
         self
     }
+
+    /// Sets how the metadata of target will be decided.
+    ///
+    /// Refer to [`options::CopyOptions::metadata_directive`] for more details.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// use opendal::options::MetadataDirective;
+    ///
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let _ = op
+    ///     .copy_with("source/path", "target/path")
+    ///     .metadata_directive(MetadataDirective::Replace)
+    ///     .content_type("text/plain")
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn metadata_directive(mut self, v: options::MetadataDirective) -> Self {
+        self.args.0.metadata_directive = v;
+        self
+    }
+
+    /// Sets `Content-Type` of target, only used with [`options::MetadataDirective::Replace`].
+    pub fn content_type(mut self, v: &str) -> Self {
+        self.args.0.content_type = Some(v.to_string());
+        self
+    }
+
+    /// Sets `Cache-Control` of target, only used with [`options::MetadataDirective::Replace`].
+    pub fn cache_control(mut self, v: &str) -> Self {
+        self.args.0.cache_control = Some(v.to_string());
+        self
+    }
+
+    /// Sets user metadata of target, only used with [`options::MetadataDirective::Replace`].
+    pub fn user_metadata(mut self, data: impl IntoIterator<Item = (String, String)>) -> Self {
+        self.args.0.user_metadata = Some(HashMap::from_iter(data));
+        self
+    }
+
+    /// Preserves the permission bits of source on target.
+    ///
+    /// Refer to [`options::CopyOptions::preserve_mode`] for more details.
+    pub fn preserve_mode(mut self, v: bool) -> Self {
+        self.args.0.preserve_mode = v;
+        self
+    }
+}
+
+/// Future that generated by [`Operator::remove_all_with`].
+///
+/// Users can add more options by public functions provided by this struct.
+pub type FutureRemoveAll<F> = OperatorFuture<options::RemoveAllOptions, usize, F>;
+
+impl<F: Future<Output = Result<usize>>> FutureRemoveAll<F> {
+    /// Sets the callback to report progress.
+    ///
+    /// Refer to [`options::RemoveAllOptions::progress`] for more details.
+    pub fn progress(mut self, f: impl Fn(usize) + Send + Sync + 'static) -> Self {
+        self.args.progress = Some(Arc::new(f));
+        self
+    }
 }
@@ -18,17 +18,63 @@
 //! Options module provides options definitions for operations.
 
 use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::Arc;
+use std::time::Duration;
 
 use chrono::DateTime;
 use chrono::Utc;
+use http::HeaderMap;
 
 use crate::raw::BytesRange;
+use crate::BufferPool;
+use crate::Encryption;
+use crate::Retention;
 
 /// Options for delete operations.
 #[derive(Debug, Clone, Default, Eq, PartialEq)]
 pub struct DeleteOptions {
     /// The version of the file to delete.
     pub version: Option<String>,
+    /// Whether to delete the dir and all its contents recursively.
+    ///
+    /// If not set, the service's own default will be used. For example, dbfs
+    /// deletes recursively unless `recursive` is explicitly set to `false`.
+    ///
+    /// This option only works for services that support `delete_with_recursive`.
+    pub recursive: Option<bool>,
+    /// Whether to return a `NotFound` error if the path does not exist.
+    ///
+    /// By default, deleting a non-existing path succeeds silently.
+    ///
+    /// This option only works for services that support `delete_with_must_exist`.
+    pub must_exist: bool,
+    /// Only delete the file if its current `ETag` matches the given one.
+    ///
+    /// An error with kind [`ErrorKind::ConditionNotMatch`] will be returned if the
+    /// file has been changed.
+    ///
+    /// This option only works for services that support `delete_with_if_match`.
+    pub if_match: Option<String>,
+}
+
+/// Options for remove all operations.
+#[derive(Clone, Default)]
+pub struct RemoveAllOptions {
+    /// Sets the callback to report progress.
+    ///
+    /// The callback will be called with the total number of removed entries
+    /// every time a batch has been deleted.
+    pub progress: Option<Arc<dyn Fn(usize) + Send + Sync>>,
+}
+
+impl Debug for RemoveAllOptions {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RemoveAllOptions")
+            .field("progress", &self.progress.is_some())
+            .finish()
+    }
 }
 
 /// Options for list operations.
@@ -66,6 +112,102 @@ pub struct ListOptions {
     ///
     /// Default to `false`
     pub deleted: bool,
+    /// The glob is used to filter entries by their path relative to the listed dir.
+    ///
+    /// - `*` and `?` never match `/`.
+    /// - `**` matches any number of path segments.
+    /// - The literal prefix of the pattern will be used to narrow the list request
+    ///   for recursive listing.
+    /// - Requires the `list-glob` feature, otherwise an `Unsupported` error will be returned.
+    ///
+    /// Default to `None`, which means all entries will be returned.
+    pub glob: Option<String>,
+    /// The concurrent is used to control how many dirs can be listed at the same time.
+    ///
+    /// This option only takes effect for recursive list on services that can't list
+    /// recursively natively. Entries will be returned in breadth-first order if
+    /// concurrent is larger than 1, so nested dirs are no longer guaranteed to show
+    /// up before their parents.
+    ///
+    /// It also bounds the number of stats while fetching metadata, see `metadata`.
+    ///
+    /// Default to `1`, which means dirs will be listed one by one.
+    pub concurrent: usize,
+    /// The dirs_only is used to control whether only dirs should be returned.
+    ///
+    /// - If `false`, list operation will return both files and dirs.
+    /// - If `true`, list operation will only return dirs, for example, the common
+    ///   prefixes of object storage services.
+    ///
+    /// Default to `false`
+    pub dirs_only: bool,
+    /// The sorted is used to control whether entries should be returned in
+    /// lexicographic order of their paths.
+    ///
+    /// - If `false`, the order of entries is unspecified and depends on the
+    ///   underlying service.
+    /// - If `true`, entries will be returned in lexicographic order. Recursive
+    ///   list is forwarded directly to services that return sorted results
+    ///   natively, otherwise it will buffer the dirs along the current path
+    ///   while walking. Non-recursive list always buffers the whole dir, so
+    ///   the memory usage grows with the number of entries in the dir.
+    ///
+    /// Default to `false`
+    pub sorted: bool,
+    /// The metadata is used to control whether entries should be returned with
+    /// full metadata like `content_length` and `last_modified`.
+    ///
+    /// - If `false`, entries only carry the metadata returned by list, which could
+    ///   be only the mode on some services.
+    /// - If `true`, entries will be returned with full metadata. Services that
+    ///   support [`Capability::list_has_metadata`] are forwarded directly, otherwise
+    ///   every file will be stat with at most `concurrent` stats at the same time.
+    ///
+    /// Default to `false`
+    pub metadata: bool,
+    /// The modified_after is used to only return files modified after the given time.
+    ///
+    /// - Services that support [`Capability::list_with_modified_after`] will filter
+    ///   entries on the server side.
+    /// - Otherwise, entries will be filtered by their `last_modified`, files will be
+    ///   stat like `metadata` if the service can't return it while listing.
+    ///
+    /// Dirs and files without `last_modified` are always returned.
+    ///
+    /// Default to `None`
+    pub modified_after: Option<DateTime<Utc>>,
+    /// The modified_before is used to only return files modified before the given time.
+    ///
+    /// It works in the same way as `modified_after`, see [`Capability::list_with_modified_before`].
+    ///
+    /// Default to `None`
+    pub modified_before: Option<DateTime<Utc>>,
+    /// The max_entries is used to stop listing once the given number of entries
+    /// have been returned.
+    ///
+    /// - Unlike `limit` which only controls the page size, no more pages will be
+    ///   requested once `max_entries` entries have been returned, and the
+    ///   underlying lister will be released.
+    /// - Entries are counted after filtering like `glob` and `dirs_only`.
+    /// - If `limit` is not set, it will be set to `max_entries` so that the first
+    ///   page won't fetch more than needed. It's left to the service if entries
+    ///   could be filtered, like `glob`, `dirs_only` and `dedup`, since pages of
+    ///   `max_entries` could be mostly dropped.
+    ///
+    /// Default to `None`
+    pub max_entries: Option<usize>,
+    /// The dedup is used to skip entries that have been returned already, for
+    /// example, the same key returned twice across pages by eventually
+    /// consistent services.
+    ///
+    /// - For sorted listing, only the last returned key will be compared.
+    /// - Otherwise, the keys of the last 10,000 entries will be kept to detect
+    ///   duplicates, which bounds the memory usage. Duplicates that are further
+    ///   apart than that won't be skipped.
+    /// - Entries of the same path but different versions are not duplicates.
+    ///
+    /// Default to `false`
+    pub dedup: bool,
 }
 
 /// Options for read operations.
@@ -160,6 +302,68 @@ pub struct ReadOptions {
     ///
     /// This option is only meaningful when used along with presign.
     pub override_content_disposition: Option<String>,
+
+    /// Set `timeout` for this operation.
+    ///
+    /// The timeout will override the io timeout configured in [`crate::layers::TimeoutLayer`]
+    /// for this operation. The io timeout of the layer will be used if not set.
+    pub timeout: Option<Duration>,
+
+    /// Set `skip_checksum` for this operation.
+    ///
+    /// Skip the verification done by `ChecksumLayer` for this operation.
+    pub skip_checksum: bool,
+
+    /// Set `suffix_range` to read the last `n` bytes of the file.
+    ///
+    /// - The whole file will be returned if it's smaller than `n` bytes.
+    /// - `n` must be greater than 0.
+    /// - It can't be used together with `range`.
+    /// - Services without [`Capability::read_with_suffix_range`] will fall back to
+    ///   `stat` the file first to calculate the range.
+    pub suffix_range: Option<u64>,
+
+    /// Set extra `headers` to send with the read request.
+    ///
+    /// - Headers that have been set by the service will not be overridden.
+    /// - Reserved headers like `Host`, `Content-Length` and signing headers can't be set.
+    /// - Headers will be merged before the request is signed, so they are covered by the signature.
+    ///
+    /// Check [`Capability::read_with_headers`] before using this feature.
+    pub headers: Option<HeaderMap>,
+
+    /// Set `max_size` to limit the size of data that can be read.
+    ///
+    /// The read will fail with [`ErrorKind::RangeNotSatisfied`] once more than `max_size`
+    /// bytes are returned, the data beyond the limit will never be buffered.
+    ///
+    /// This is useful to protect services from reading unexpectedly large files.
+    pub max_size: Option<u64>,
+
+    /// Set `read_after_write_retry` to retry [`ErrorKind::NotFound`] for a short window
+    /// after a known write.
+    ///
+    /// Some eventually consistent services may return `NotFound` for a file that has
+    /// just been written. With `Some((max, backoff))`, the read will be retried up to
+    /// `max` times on `NotFound`, sleeping `backoff` between attempts. The `stat`
+    /// issued to resolve the size of the file is retried as well.
+    ///
+    /// This is different from [`crate::layers::RetryLayer`] which never retries
+    /// `NotFound`. Only enable it for paths that are known to have been written,
+    /// otherwise reading a missing file will be delayed for nothing.
+    pub read_after_write_retry: Option<(usize, Duration)>,
+
+    /// Set `decompress` to decompress content-encoded objects transparently.
+    ///
+    /// - Services with [`Capability::read_with_decompress`] will send `Accept-Encoding`
+    ///   and decompress the response if it's encoded with `gzip` or `zstd`.
+    /// - The whole object will be fetched for range reads, the range is applied to
+    ///   the decompressed content.
+    /// - By default, the stored bytes are returned as is. Checksum verification is
+    ///   skipped while decompressing since the digest is computed over stored bytes.
+    /// - `chunk` and `suffix_range` are rejected with `ConfigInvalid` since the
+    ///   decompressed size is unknown before decoding.
+    pub decompress: bool,
 }
 
 /// Options for reader operations.
@@ -245,6 +449,51 @@ pub struct ReaderOptions {
     /// This option helps prevent memory exhaustion when reading large files with high
     /// concurrency settings.
     pub prefetch: usize,
+
+    /// Set `timeout` for this operation.
+    ///
+    /// The timeout will override the io timeout configured in [`crate::layers::TimeoutLayer`]
+    /// for this operation. The io timeout of the layer will be used if not set.
+    pub timeout: Option<Duration>,
+
+    /// Set `skip_checksum` for this operation.
+    ///
+    /// Skip the verification done by `ChecksumLayer` for this operation.
+    pub skip_checksum: bool,
+
+    /// Set extra `headers` to send with the read request.
+    ///
+    /// - Headers that have been set by the service will not be overridden.
+    /// - Reserved headers like `Host`, `Content-Length` and signing headers can't be set.
+    /// - Headers will be merged before the request is signed, so they are covered by the signature.
+    ///
+    /// Check [`Capability::read_with_headers`] before using this feature.
+    pub headers: Option<HeaderMap>,
+
+    /// Set `read_after_write_retry` to retry [`ErrorKind::NotFound`] for a short window
+    /// after a known write.
+    ///
+    /// Some eventually consistent services may return `NotFound` for a file that has
+    /// just been written. With `Some((max, backoff))`, the read will be retried up to
+    /// `max` times on `NotFound`, sleeping `backoff` between attempts. The `stat`
+    /// issued to resolve the size of the file is retried as well.
+    ///
+    /// This is different from [`crate::layers::RetryLayer`] which never retries
+    /// `NotFound`. Only enable it for paths that are known to have been written,
+    /// otherwise reading a missing file will be delayed for nothing.
+    pub read_after_write_retry: Option<(usize, Duration)>,
+
+    /// Set `decompress` to decompress content-encoded objects transparently.
+    ///
+    /// - Services with [`Capability::read_with_decompress`] will send `Accept-Encoding`
+    ///   and decompress the response if it's encoded with `gzip` or `zstd`.
+    /// - The whole object will be fetched for range reads, the range is applied to
+    ///   the decompressed content.
+    /// - By default, the stored bytes are returned as is. Checksum verification is
+    ///   skipped while decompressing since the digest is computed over stored bytes.
+    /// - `chunk` is rejected with `ConfigInvalid`, and reads must use a bounded range
+    ///   or be consumed as a stream since the decompressed size is unknown before decoding.
+    pub decompress: bool,
 }
 
 /// Options for stat operations.
@@ -298,6 +547,23 @@ pub struct StatOptions {
     ///
     /// This option is only meaningful when used along with presign.
     pub override_content_disposition: Option<String>,
+
+    /// Set `tags` for this operation.
+    ///
+    /// If enabled, the tags of the file will be fetched and returned via [`Metadata::tags`].
+    /// This may take an extra request.
+    ///
+    /// Check [`Capability::stat_with_tags`] before using this feature.
+    pub tags: bool,
+
+    /// Set extra `headers` to send with the stat request.
+    ///
+    /// - Headers that have been set by the service will not be overridden.
+    /// - Reserved headers like `Host`, `Content-Length` and signing headers can't be set.
+    /// - Headers will be merged before the request is signed, so they are covered by the signature.
+    ///
+    /// Check [`Capability::stat_with_headers`] before using this feature.
+    pub headers: Option<HeaderMap>,
 }
 
 /// Options for write operations.
@@ -315,6 +581,8 @@ pub struct WriteOptions {
     /// - When append is set to true:
     ///   - New data will be appended to the end of existing file
     ///   - If file doesn't exist, it will be created
+    ///   - The returned metadata carries the size of file after append, which is
+    ///     left unset if the service doesn't return it and the file can't be stat
     /// - If not supported, will return an error
     ///
     /// This operation allows adding data to existing files instead of overwriting them.
@@ -525,6 +793,151 @@ pub struct WriteOptions {
     /// - Lower operation costs
     /// - Better utilize network bandwidth
     pub chunk: Option<usize>,
+
+    /// Set `timeout` for this operation.
+    ///
+    /// The timeout will override the io timeout configured in [`crate::layers::TimeoutLayer`]
+    /// for this operation. The io timeout of the layer will be used if not set.
+    pub timeout: Option<Duration>,
+
+    /// Sets whether to detect content type for this operation.
+    ///
+    /// ### Behavior
+    ///
+    /// - If `content_type` is not set, OpenDAL will guess the content type from the
+    ///   magic bytes of the first written buffer.
+    /// - The detected content type will never override `content_type` set by users.
+    /// - Nothing will be sent to the service until the first buffer is written.
+    ///
+    /// ### Capability
+    ///
+    /// This option only takes effect on services with [`Capability::write_with_content_type`].
+    pub content_type_detection: bool,
+
+    /// Sets server side encryption for this operation.
+    ///
+    /// ### Behavior
+    ///
+    /// - The encryption overrides the default encryption configured in the service builder.
+    /// - If not set, the default encryption of the service will be used.
+    ///
+    /// ### Capability
+    ///
+    /// Check [`Capability::write_with_encryption`] before using this feature.
+    pub encryption: Option<Encryption>,
+
+    /// Sets the max size of written data to retain for replaying the write.
+    ///
+    /// ### Behavior
+    ///
+    /// - Written data will be retained in memory until the writer is closed, so that
+    ///   a retried `close` can write the data again after temporary errors.
+    /// - Once written data exceeds the size, the retained data will be dropped and close
+    ///   failures will be returned as persistent errors since they can't be retried.
+    /// - This option is ignored for append writes.
+    pub replay_buffer: Option<usize>,
+
+    /// Set extra `headers` to send with the write requests.
+    ///
+    /// - Headers that have been set by the service will not be overridden.
+    /// - Reserved headers like `Host`, `Content-Length` and signing headers can't be set.
+    /// - Headers will be merged before the request is signed, so they are covered by the signature.
+    ///
+    /// Check [`Capability::write_with_headers`] before using this feature.
+    pub headers: Option<HeaderMap>,
+
+    /// Declare the expected size of data to write.
+    ///
+    /// - Every write will fail once the written data exceeds the size.
+    /// - Close will fail without committing the data if less data has been written.
+    pub content_length: Option<u64>,
+
+    /// Verify the written object after close.
+    ///
+    /// ### Behavior
+    ///
+    /// - After close, the object will be stat to make sure its size equals to the
+    ///   written data, and its etag equals to the one returned by close if both exist.
+    /// - If the service returns `content_md5` in stat, it will be compared with the
+    ///   md5 of the written data too.
+    /// - Close will return error if they don't match, the object has been committed
+    ///   and won't be cleaned up.
+    /// - Errors of the stat will not be retried by `RetryLayer` since the writer
+    ///   has been closed.
+    /// - This option is ignored for append writes.
+    pub verify: bool,
+
+    /// Sets the permission bits of the written file, like `0o755`.
+    ///
+    /// ### Behavior
+    ///
+    /// - Services with POSIX modes like `fs` (on unix) and `sftp` will set the
+    ///   mode of the written file, regardless of the umask.
+    /// - Other services will ignore this option.
+    pub mode: Option<u32>,
+
+    /// Stat the written object after close to fill the metadata returned by close.
+    ///
+    /// ### Behavior
+    ///
+    /// - Close always returns the `ETag`, version and `Content-MD5` if the service
+    ///   returns them in the response of the write request.
+    /// - With this option enabled, the object will be stat after close if any of
+    ///   them or `Last-Modified` is missing, and the missing fields will be filled
+    ///   from the stat result. Fields returned by close will never be overridden.
+    /// - The data has been committed after close, so a failed stat will only be
+    ///   logged and the metadata will be returned as is.
+    /// - The object will be stat at most once after close, the stat sent by `verify`
+    ///   or `append` will be reused.
+    /// - Only `Content-MD5` is surfaced as the server checksum, other checksums like
+    ///   S3's `x-amz-checksum-*` are not exposed in the metadata.
+    pub stat_after_close: bool,
+
+    /// Sets the storage class of the written object, like `STANDARD_IA` on s3.
+    ///
+    /// ### Behavior
+    ///
+    /// - It overrides the default storage class configured on the service.
+    /// - The value will be passed through to the service without validation so that
+    ///   classes of s3 compatible services can be used too.
+    ///
+    /// Check [`Capability::write_with_storage_class`] before using this feature.
+    pub storage_class: Option<String>,
+
+    /// Sets the object lock retention of the written object.
+    ///
+    /// ### Behavior
+    ///
+    /// - The object can't be overwritten or deleted until `retain_until`, see
+    ///   [`crate::RetentionMode`] for the differences between modes.
+    /// - Object lock must be enabled on the bucket.
+    ///
+    /// Check [`Capability::write_with_retention`] before using this feature.
+    pub retention: Option<Retention>,
+
+    /// Sets the legal hold status of the written object.
+    ///
+    /// ### Behavior
+    ///
+    /// - The object can't be overwritten or deleted while the legal hold is on,
+    ///   regardless of its retention.
+    /// - Object lock must be enabled on the bucket.
+    ///
+    /// Check [`Capability::write_with_legal_hold`] before using this feature.
+    pub legal_hold: Option<bool>,
+
+    /// Sets the pool to reserve buffered chunks from.
+    ///
+    /// ### Behavior
+    ///
+    /// - The writer will reserve a whole chunk from the pool before buffering data,
+    ///   and wait if the pool is exhausted.
+    /// - The chunk will be returned once it has been uploaded by the service, or
+    ///   the writer has been closed, aborted or dropped. Chunks uploaded
+    ///   concurrently keep their reservations until they are done.
+    /// - Without `chunk`, the writer will reserve the size of every write instead.
+    /// - Share the same pool between writers to bound their total buffered bytes.
+    pub buffer_pool: Option<BufferPool>,
 }
 
 /// Options for copy operations.
@@ -545,4 +958,49 @@ pub struct CopyOptions {
     /// This operation provides a way to ensure copy operations only create new resources
     /// without overwriting existing ones, useful for implementing "copy if not exists" logic.
     pub if_not_exists: bool,
+
+    /// Sets how the metadata of target will be decided.
+    ///
+    /// ### Capability
+    ///
+    /// Check [`Capability::copy_with_metadata_directive`] before using
+    /// [`MetadataDirective::Replace`].
+    ///
+    /// ### Behavior
+    ///
+    /// - [`MetadataDirective::Copy`] (default) copies metadata from the source, all other
+    ///   metadata options will be ignored.
+    /// - [`MetadataDirective::Replace`] replaces metadata with `content_type`, `cache_control`
+    ///   and `user_metadata`, metadata that is not set will be cleared.
+    /// - If not supported, [`MetadataDirective::Replace`] will return an error with kind
+    ///   [`ErrorKind::Unsupported`].
+    ///
+    /// This allows rewriting metadata of existing files without downloading and uploading
+    /// the content again.
+    pub metadata_directive: MetadataDirective,
+    /// Sets `Content-Type` of target, only used with [`MetadataDirective::Replace`].
+    pub content_type: Option<String>,
+    /// Sets `Cache-Control` of target, only used with [`MetadataDirective::Replace`].
+    pub cache_control: Option<String>,
+    /// Sets user metadata of target, only used with [`MetadataDirective::Replace`].
+    pub user_metadata: Option<HashMap<String, String>>,
+    /// Preserves the permission bits of source on target.
+    ///
+    /// ### Behavior
+    ///
+    /// - Services with POSIX modes like `sftp` will set the mode of target to
+    ///   the mode of source.
+    /// - `fs` always preserves the mode since it's copied along with the content.
+    /// - Other services will ignore this option.
+    pub preserve_mode: bool,
+}
+
+/// Directive of how the metadata of copy target will be decided.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum MetadataDirective {
+    /// Copy metadata from the source.
+    #[default]
+    Copy,
+    /// Replace metadata with the given ones.
+    Replace,
 }
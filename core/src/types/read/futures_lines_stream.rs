@@ -0,0 +1,222 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::ops::RangeBounds;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::ready;
+use std::task::Context;
+use std::task::Poll;
+
+use futures::Stream;
+use futures::StreamExt;
+
+use crate::*;
+
+/// FuturesLinesStream is the adapter of [`Stream`] generated by [`Reader::into_lines_stream`].
+///
+/// Each item is a line without the terminator. By default, lines are terminated by `\n`
+/// with an optional `\r` before it. Use [`FuturesLinesStream::with_terminator`] to split
+/// by other terminators.
+///
+/// Lines that are not valid UTF-8 will be returned as an error with kind
+/// [`ErrorKind::Unexpected`], unless lossy mode is enabled by
+/// [`FuturesLinesStream::with_lossy`].
+///
+/// FuturesLinesStream also implements [`Unpin`], [`Send`] and [`Sync`].
+pub struct FuturesLinesStream {
+    stream: BufferStream,
+    /// Custom terminator, `None` means `\n` or `\r\n`.
+    terminator: Option<Vec<u8>>,
+    lossy: bool,
+
+    buf: Vec<u8>,
+    /// The start of the current line in `buf`.
+    start: usize,
+    /// Bytes before this offset have been searched for the terminator.
+    searched: usize,
+    eof: bool,
+}
+
+/// Safety: FuturesLinesStream only exposes `&mut self` to the outside world,
+unsafe impl Sync for FuturesLinesStream {}
+
+impl FuturesLinesStream {
+    /// NOTE: don't allow users to create FuturesLinesStream directly.
+    pub(crate) async fn new(ctx: Arc<ReadContext>, range: impl RangeBounds<u64>) -> Result<Self> {
+        let stream = BufferStream::create(ctx, range).await?;
+
+        Ok(FuturesLinesStream {
+            stream,
+            terminator: None,
+            lossy: false,
+            buf: Vec::new(),
+            start: 0,
+            searched: 0,
+            eof: false,
+        })
+    }
+
+    /// Split lines by given terminator instead of `\n` and `\r\n`.
+    ///
+    /// Empty terminator will be ignored.
+    pub fn with_terminator(mut self, terminator: impl Into<Vec<u8>>) -> Self {
+        let terminator = terminator.into();
+        if !terminator.is_empty() {
+            self.terminator = Some(terminator);
+        }
+        self
+    }
+
+    /// Replace invalid UTF-8 sequences with `U+FFFD` instead of returning an error.
+    pub fn with_lossy(mut self, lossy: bool) -> Self {
+        self.lossy = lossy;
+        self
+    }
+
+    /// Find the end of the next line and the length of its terminator.
+    fn find_line(&mut self) -> Option<(usize, usize)> {
+        let terminator = self.terminator.as_deref().unwrap_or(b"\n");
+        let from = self.searched.max(self.start);
+        let found = self.buf[from..]
+            .windows(terminator.len())
+            .position(|w| w == terminator);
+
+        match found {
+            Some(pos) => {
+                let end = from + pos;
+                self.searched = end + terminator.len();
+                Some((end, terminator.len()))
+            }
+            None => {
+                // Keep the tail that may be the prefix of a terminator split across buffers.
+                self.searched = (self.buf.len() + 1).saturating_sub(terminator.len());
+                None
+            }
+        }
+    }
+
+    fn decode(&self, mut line: &[u8]) -> Result<String> {
+        if self.terminator.is_none() {
+            line = line.strip_suffix(b"\r").unwrap_or(line);
+        }
+
+        if self.lossy {
+            return Ok(String::from_utf8_lossy(line).into_owned());
+        }
+        String::from_utf8(line.to_vec()).map_err(|err| {
+            Error::new(ErrorKind::Unexpected, "line is not valid utf-8").set_source(err)
+        })
+    }
+}
+
+impl Stream for FuturesLinesStream {
+    type Item = Result<String>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Some((end, len)) = this.find_line() {
+                let line = this.decode(&this.buf[this.start..end]);
+                this.start = end + len;
+                return Poll::Ready(Some(line));
+            }
+
+            if this.eof {
+                if this.start == this.buf.len() {
+                    return Poll::Ready(None);
+                }
+                // The last line without terminator.
+                let line = this.decode(&this.buf[this.start..]);
+                this.start = this.buf.len();
+                return Poll::Ready(Some(line));
+            }
+
+            match ready!(this.stream.poll_next_unpin(cx)) {
+                Some(Ok(buf)) => {
+                    // Drop consumed lines before appending new data.
+                    this.buf.drain(..this.start);
+                    this.searched -= this.start.min(this.searched);
+                    this.start = 0;
+                    for bs in buf {
+                        this.buf.extend_from_slice(&bs);
+                    }
+                }
+                Some(Err(err)) => return Poll::Ready(Some(Err(err))),
+                None => this.eof = true,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::TryStreamExt;
+
+    use super::*;
+
+    async fn read_lines(
+        content: &'static str,
+        f: impl FnOnce(FuturesLinesStream) -> FuturesLinesStream,
+    ) -> Result<Vec<String>> {
+        let op = Operator::via_iter(Scheme::Memory, [])?;
+        op.write("test", content).await?;
+
+        // Use a small chunk to make sure lines are split across buffers.
+        let r = op.reader_with("test").chunk(2).await?;
+        f(r.into_lines_stream(..).await?).try_collect().await
+    }
+
+    #[tokio::test]
+    async fn test_lines() -> Result<()> {
+        let lines = read_lines("hello\r\nworld\n\nlast", |s| s).await?;
+        assert_eq!(lines, vec!["hello", "world", "", "last"]);
+
+        let lines = read_lines("a\nb\n", |s| s).await?;
+        assert_eq!(lines, vec!["a", "b"]);
+
+        let lines = read_lines("a||b|||c|", |s| s.with_terminator("||")).await?;
+        assert_eq!(lines, vec!["a", "b", "|c|"]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_lines_utf8() -> Result<()> {
+        let lines = read_lines("你好\n世界", |s| s).await?;
+        assert_eq!(lines, vec!["你好", "世界"]);
+
+        let op = Operator::via_iter(Scheme::Memory, [])?;
+        op.write("test", vec![b'a', 0xff, b'\n']).await?;
+        let err = op
+            .read_lines("test")
+            .await?
+            .try_collect::<Vec<_>>()
+            .await
+            .expect_err("invalid utf-8 must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+
+        let lines: Vec<_> = op
+            .read_lines("test")
+            .await?
+            .with_lossy(true)
+            .try_collect()
+            .await?;
+        assert_eq!(lines, vec!["a\u{FFFD}"]);
+        Ok(())
+    }
+}
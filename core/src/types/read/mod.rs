@@ -27,3 +27,6 @@ pub use futures_async_reader::FuturesAsyncReader;
 
 mod futures_bytes_stream;
 pub use futures_bytes_stream::FuturesBytesStream;
+
+mod futures_lines_stream;
+pub use futures_lines_stream::FuturesLinesStream;
@@ -19,10 +19,13 @@ use std::ops::Range;
 use std::ops::RangeBounds;
 use std::sync::Arc;
 
+use bytes::Buf;
 use bytes::BufMut;
 use futures::TryStreamExt;
 
+use crate::raw::oio;
 use crate::raw::Access;
+use crate::raw::BytesRange;
 use crate::raw::ConcurrentTasks;
 use crate::*;
 
@@ -143,9 +146,38 @@ impl Reader {
     ///
     /// The returning `Buffer` may share the same underlying memory without
     /// any extra copy.
+    ///
+    /// Services with [`Capability::read_with_multi_range`] will read all merged
+    /// ranges in one request, others will read them one by one.
     pub async fn fetch(&self, ranges: Vec<Range<u64>>) -> Result<Vec<Buffer>> {
         let merged_ranges = self.merge_ranges(ranges.clone());
 
+        let merged_bufs = if merged_ranges.len() > 1
+            && self
+                .ctx
+                .accessor()
+                .info()
+                .full_capability()
+                .read_with_multi_range
+        {
+            self.read_multi_range(&merged_ranges).await?
+        } else {
+            self.read_ranges(&merged_ranges).await?
+        };
+
+        let mut bufs = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            let idx = merged_ranges.partition_point(|v| v.start <= range.start) - 1;
+            let start = range.start - merged_ranges[idx].start;
+            let end = range.end - merged_ranges[idx].start;
+            bufs.push(merged_bufs[idx].slice(start as usize..end as usize));
+        }
+
+        Ok(bufs)
+    }
+
+    /// Read given ranges one by one with concurrent.
+    async fn read_ranges(&self, ranges: &[Range<u64>]) -> Result<Vec<Buffer>> {
         #[derive(Clone)]
         struct FetchInput {
             reader: Reader,
@@ -164,24 +196,44 @@ impl Reader {
             },
         );
 
-        for range in merged_ranges.clone() {
+        for range in ranges.iter().cloned() {
             let reader = self.clone();
             tasks.execute(FetchInput { reader, range }).await?;
         }
 
-        let mut merged_bufs = vec![];
+        let mut bufs = vec![];
         while let Some(b) = tasks.next().await {
-            merged_bufs.push(b?);
+            bufs.push(b?);
         }
+        Ok(bufs)
+    }
+
+    /// Read given ranges in one request.
+    ///
+    /// The service returns the content of all ranges concatenated in order.
+    async fn read_multi_range(&self, ranges: &[Range<u64>]) -> Result<Vec<Buffer>> {
+        let args = self
+            .ctx
+            .args()
+            .clone()
+            .with_ranges(ranges.iter().cloned().map(BytesRange::from).collect());
+        let (_, mut r) = self.ctx.accessor().read(self.ctx.path(), args).await?;
+        let mut buf = oio::Read::read_all(&mut r).await?;
 
         let mut bufs = Vec::with_capacity(ranges.len());
         for range in ranges {
-            let idx = merged_ranges.partition_point(|v| v.start <= range.start) - 1;
-            let start = range.start - merged_ranges[idx].start;
-            let end = range.end - merged_ranges[idx].start;
-            bufs.push(merged_bufs[idx].slice(start as usize..end as usize));
+            let size = (range.end - range.start) as usize;
+            if buf.len() < size {
+                return Err(Error::new(
+                    ErrorKind::Unexpected,
+                    "multi range read got too little data",
+                )
+                .with_context("path", self.ctx.path())
+                .with_context("range", format!("{range:?}")));
+            }
+            bufs.push(buf.slice(..size));
+            buf.advance(size);
         }
-
         Ok(bufs)
     }
 
@@ -424,6 +476,39 @@ impl Reader {
     ) -> Result<FuturesBytesStream> {
         FuturesBytesStream::new(self.ctx, range).await
     }
+
+    /// Convert reader into [`FuturesLinesStream`] which yields lines of given range.
+    ///
+    /// Lines are split by `\n` or `\r\n` by default, visit [`FuturesLinesStream`] for
+    /// more options.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::TryStreamExt;
+    /// use opendal::Operator;
+    /// use opendal::Result;
+    ///
+    /// async fn test(op: Operator) -> Result<()> {
+    ///     let lines: Vec<String> = op
+    ///         .reader("hello.txt")
+    ///         .await?
+    ///         .into_lines_stream(..)
+    ///         .await?
+    ///         .with_terminator("\0")
+    ///         .try_collect()
+    ///         .await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[inline]
+    pub async fn into_lines_stream(
+        self,
+        range: impl RangeBounds<u64>,
+    ) -> Result<FuturesLinesStream> {
+        FuturesLinesStream::new(self.ctx, range).await
+    }
 }
 
 #[cfg(test)]
@@ -527,6 +612,66 @@ mod tests {
         Ok(())
     }
 
+    /// Count the latency rounds needed to read a 16B file in 4B chunks with
+    /// given concurrent, chunks transferred together share a round.
+    #[cfg(feature = "tests")]
+    async fn read_rounds(concurrent: usize) -> (usize, usize) {
+        use std::time::Duration;
+
+        use crate::raw::tests::MockAccess;
+        use crate::raw::tests::MockClock;
+
+        const LATENCY: Duration = Duration::from_secs(1);
+
+        let clock = MockClock::new();
+        let mock = MockAccess::new().with_transfer_latency(clock.clone(), LATENCY);
+        let content = gen_fixed_bytes(16);
+        mock.insert("test_file", content.clone());
+        let op = OperatorBuilder::new(mock.clone()).finish();
+
+        let handle = tokio::spawn(async move {
+            op.read_with("test_file")
+                .chunk(4)
+                .concurrent(concurrent)
+                .await
+                .unwrap()
+        });
+
+        let mut rounds = 0;
+        loop {
+            // Let the spawned tasks reach their sleeps before advancing.
+            for _ in 0..16 {
+                tokio::task::yield_now().await;
+            }
+            if handle.is_finished() {
+                break;
+            }
+            clock.advance(LATENCY);
+            rounds += 1;
+        }
+
+        assert_eq!(handle.await.unwrap().to_vec(), content);
+        (rounds, mock.calls(Operation::Read, "test_file"))
+    }
+
+    #[cfg(feature = "tests")]
+    #[tokio::test]
+    async fn test_reader_read_concurrent_ranges() {
+        // All four chunks are transferred at the same time.
+        let (rounds, calls) = read_rounds(4).await;
+        assert_eq!(calls, 4);
+        assert_eq!(rounds, 1);
+
+        let (rounds, calls) = read_rounds(2).await;
+        assert_eq!(calls, 4);
+        assert_eq!(rounds, 2);
+
+        // Chunks are transferred one by one without concurrent.
+        let (rounds, calls) = read_rounds(1).await;
+        assert_eq!(calls, 4);
+        assert_eq!(rounds, 4);
+    }
+
     #[tokio::test]
     async fn test_reader_read_into() -> Result<()> {
         let op = Operator::via_iter(Scheme::Memory, [])?;
@@ -548,6 +693,47 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_reader_decompress_rejects_unknown_size() -> Result<()> {
+        let op = Operator::via_iter(Scheme::Memory, [])?;
+        let path = "test_file";
+        op.write(path, gen_fixed_bytes(1024)).await?;
+
+        let err = op
+            .reader_with(path)
+            .decompress(true)
+            .chunk(16)
+            .await
+            .map(|_| ())
+            .expect_err("decompress with chunk must fail");
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+
+        let err = op
+            .read_with(path)
+            .decompress(true)
+            .chunk(16)
+            .await
+            .expect_err("decompress with chunk must fail");
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+
+        let err = op
+            .read_with(path)
+            .decompress(true)
+            .suffix_range(16)
+            .await
+            .expect_err("decompress with suffix_range must fail");
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+
+        let reader = op.reader_with(path).decompress(true).await?;
+        let err = reader
+            .into_futures_async_read(..)
+            .await
+            .map(|_| ())
+            .expect_err("decompress with unbounded range must fail");
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_merge_ranges() -> Result<()> {
         let op = Operator::new(services::Memory::default()).unwrap().finish();
@@ -0,0 +1,41 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use chrono::DateTime;
+use chrono::Utc;
+
+/// RetentionMode is the object lock mode of a [`Retention`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RetentionMode {
+    /// Users with special permissions can still overwrite, delete or shorten
+    /// the retention of the object.
+    Governance,
+    /// No one can overwrite, delete or shorten the retention of the object
+    /// until it expires.
+    Compliance,
+}
+
+/// Retention is the object lock retention settings of an object.
+///
+/// Check [`crate::Capability::write_with_retention`] before using it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Retention {
+    /// The mode of the retention.
+    pub mode: RetentionMode,
+    /// The object is protected until this time.
+    pub retain_until: DateTime<Utc>,
+}
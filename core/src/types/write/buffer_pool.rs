@@ -0,0 +1,124 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+use tokio::sync::OwnedSemaphorePermit;
+use tokio::sync::Semaphore;
+
+/// BufferPool bounds the total bytes buffered by writers.
+///
+/// # Notes
+///
+/// Writers using the pool will reserve a whole chunk from the pool before
+/// buffering data for it, and return the reservation once the chunk has been
+/// uploaded by the service, or the writer has been closed, aborted or dropped.
+/// Chunks uploaded concurrently keep their reservations until they are done.
+/// Writers without `chunk` will reserve the size of every write instead.
+///
+/// Writers will wait for other writers to return their chunks if the pool is
+/// exhausted. A single chunk will reserve at most the capacity of the pool.
+/// The pool is cheap to clone, all clones share the same capacity.
+///
+/// # Examples
+///
+/// ```
+/// # use opendal::BufferPool;
+/// # use opendal::Operator;
+/// # use opendal::Result;
+/// # async fn test(op: Operator) -> Result<()> {
+/// // At most 256MiB will be buffered by all writers using this pool.
+/// let pool = BufferPool::new(256 * 1024 * 1024);
+///
+/// let mut w = op
+///     .writer_with("path/to/file")
+///     .chunk(8 * 1024 * 1024)
+///     .buffer_pool(pool.clone())
+///     .await?;
+/// w.write(vec![0; 4096]).await?;
+/// w.close().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct BufferPool {
+    semaphore: Arc<Semaphore>,
+    capacity: usize,
+}
+
+impl BufferPool {
+    /// Create a new pool that holds at most `capacity` bytes.
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.clamp(1, Semaphore::MAX_PERMITS);
+        Self {
+            semaphore: Arc::new(Semaphore::new(capacity)),
+            capacity,
+        }
+    }
+
+    /// Get the total bytes of this pool.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Get the bytes that haven't been reserved by writers.
+    pub fn available(&self) -> usize {
+        self.semaphore.available_permits()
+    }
+
+    /// Reserve `size` bytes from the pool, the bytes will be returned once
+    /// the permit is dropped.
+    pub(crate) async fn acquire(&self, size: usize) -> OwnedSemaphorePermit {
+        self.semaphore
+            .clone()
+            .acquire_many_owned(self.permits_of(size))
+            .await
+            .expect("semaphore must be valid")
+    }
+
+    /// Reserve `size` bytes from the pool without waiting, returns `None` if
+    /// the pool is exhausted.
+    pub(crate) fn try_acquire(&self, size: usize) -> Option<OwnedSemaphorePermit> {
+        self.semaphore
+            .clone()
+            .try_acquire_many_owned(self.permits_of(size))
+            .ok()
+    }
+
+    fn permits_of(&self, size: usize) -> u32 {
+        size.min(self.capacity).min(u32::MAX as usize) as u32
+    }
+}
+
+impl Debug for BufferPool {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BufferPool")
+            .field("capacity", &self.capacity)
+            .field("available", &self.available())
+            .finish()
+    }
+}
+
+impl PartialEq for BufferPool {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.semaphore, &other.semaphore)
+    }
+}
+
+impl Eq for BufferPool {}
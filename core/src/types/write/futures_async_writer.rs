@@ -133,4 +133,55 @@ mod tests {
 
         let _: Box<dyn Unpin + MaybeSend + Sync + 'static> = Box::new(v);
     }
+
+    #[cfg(feature = "tests")]
+    #[tokio::test]
+    async fn test_batch_small_writes() {
+        use futures::AsyncWriteExt;
+
+        use crate::raw::tests::MockAccess;
+
+        let mock = MockAccess::new();
+        let op = OperatorBuilder::new(mock.clone()).finish();
+
+        let mut w = op
+            .writer_with("file")
+            .chunk(8)
+            .await
+            .unwrap()
+            .into_futures_async_write();
+        for b in b"Hello, World!Hello" {
+            assert_eq!(w.write(&[*b]).await.unwrap(), 1);
+        }
+        // Small writes are buffered without touching the backend.
+        assert!(mock.take_written_parts("file").is_empty());
+        w.close().await.unwrap();
+
+        assert_eq!(mock.take_written_parts("file"), vec![8, 8, 2]);
+        assert_eq!(
+            op.read("file").await.unwrap().to_vec(),
+            b"Hello, World!Hello"
+        );
+    }
+
+    #[cfg(feature = "tests")]
+    #[tokio::test]
+    async fn test_close_error() {
+        use futures::AsyncWriteExt;
+
+        use crate::raw::tests::MockAccess;
+
+        let mock = MockAccess::new();
+        mock.inject_error(Operation::Write, "file", 1, || {
+            Error::new(ErrorKind::PermissionDenied, "denied")
+        });
+        let op = OperatorBuilder::new(mock.clone()).finish();
+
+        let mut w = op.writer("file").await.unwrap().into_futures_async_write();
+        w.write_all(b"Hello").await.unwrap();
+        let err = w.close().await.unwrap_err();
+
+        assert_eq!(err.kind(), io::ErrorKind::PermissionDenied);
+        assert!(!op.exists("file").await.unwrap());
+    }
 }
@@ -18,6 +18,11 @@
 mod writer;
 pub use writer::Writer;
 
+mod multipart_upload;
+pub use multipart_upload::MultipartUpload;
+
+mod buffer_pool;
+pub use buffer_pool::BufferPool;
 mod buffer_sink;
 pub use buffer_sink::BufferSink;
 mod futures_async_writer;
@@ -0,0 +1,63 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use chrono::DateTime;
+use chrono::Utc;
+
+/// An in-progress multipart upload returned by [`crate::Operator::list_multipart_uploads`].
+///
+/// Uploads that are never completed or aborted will keep their parts in the
+/// storage service. They can be aborted via [`crate::Operator::abort_multipart`]
+/// with the [`MultipartUpload::upload_id`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultipartUpload {
+    path: String,
+    upload_id: String,
+    initiated: Option<DateTime<Utc>>,
+}
+
+impl MultipartUpload {
+    /// Create a new multipart upload of the path.
+    pub fn new(path: &str, upload_id: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            upload_id: upload_id.to_string(),
+            initiated: None,
+        }
+    }
+
+    /// Set the time the upload was initiated.
+    pub fn with_initiated(mut self, v: DateTime<Utc>) -> Self {
+        self.initiated = Some(v);
+        self
+    }
+
+    /// Path of the file being uploaded.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Id of the upload assigned by the service.
+    pub fn upload_id(&self) -> &str {
+        &self.upload_id
+    }
+
+    /// Time the upload was initiated.
+    pub fn initiated(&self) -> Option<DateTime<Utc>> {
+        self.initiated
+    }
+}
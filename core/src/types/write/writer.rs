@@ -159,6 +159,44 @@ impl Writer {
         self.write(bs).await
     }
 
+    /// Flush the written data to the service without closing the writer.
+    ///
+    /// Returns once the data sent so far has been accepted by the service,
+    /// which is useful to surface errors early for long-running writes like logs.
+    ///
+    /// ## Notes
+    ///
+    /// What flushed data guarantees depends on the service:
+    ///
+    /// - `fs` syncs the file to disk, but data written to a temp file is lost if
+    ///   the writer is never closed.
+    /// - Multipart uploads wait for in-flight parts to finish, but the parts are
+    ///   discarded by the service if the upload is never completed.
+    /// - Services without flush support will treat this as a no-op.
+    /// - Data buffered by `chunk` will be kept until a whole chunk is filled.
+    ///
+    /// Only data that has been written by a successful `close` is guaranteed
+    /// to be durable and visible.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use opendal::Operator;
+    /// use opendal::Result;
+    ///
+    /// async fn test(op: Operator) -> Result<()> {
+    ///     let mut w = op.writer_with("app.log").append(true).await?;
+    ///     w.write("hello, world\n").await?;
+    ///     w.flush().await?;
+    ///
+    ///     w.close().await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn flush(&mut self) -> Result<()> {
+        self.inner.flush().await
+    }
+
     /// Abort the writer and clean up all written data.
     ///
     /// ## Notes
@@ -171,6 +209,10 @@ impl Writer {
 
     /// Close the writer and make sure all data have been committed.
     ///
+    /// The returned metadata contains the size of written data, and the `ETag`,
+    /// version and `Content-MD5` if the service returns them. Use
+    /// [`crate::options::WriteOptions::stat_after_close`] to fill the missing ones.
+    ///
     /// ## Notes
     ///
     /// Close should only be called when the writer is not closed or
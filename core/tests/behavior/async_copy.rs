@@ -15,6 +15,8 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
+
 use anyhow::Result;
 use sha2::Digest;
 use sha2::Sha256;
@@ -38,6 +40,17 @@ pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
         ))
     }
 
+    // Copy will be simulated by streaming if the service can't copy natively.
+    if cap.read && cap.write && !cap.copy {
+        tests.extend(async_trials!(
+            op,
+            test_copy_file_with_ascii_name,
+            test_copy_non_existing_source,
+            test_copy_overwrite,
+            test_copy_by_stream_keeps_metadata
+        ))
+    }
+
     if cap.read && cap.write && cap.copy && cap.copy_with_if_not_exists {
         tests.extend(async_trials!(
             op,
@@ -45,6 +58,18 @@ pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
             test_copy_with_if_not_exists_to_existing_file
         ))
     }
+
+    // Copy by streaming can replace metadata while writing the target.
+    if cap.read && cap.write && (!cap.copy || cap.copy_with_metadata_directive) {
+        tests.extend(async_trials!(op, test_copy_with_metadata_directive_replace))
+    }
+
+    if cap.read && cap.write && cap.copy && !cap.copy_with_metadata_directive {
+        tests.extend(async_trials!(
+            op,
+            test_copy_with_metadata_directive_replace_unsupported
+        ))
+    }
 }
 
 /// Copy a file with ascii name and test contents.
@@ -312,3 +337,116 @@ pub async fn test_copy_with_if_not_exists_to_existing_file(op: Operator) -> Resu
     op.delete(&target_path).await.expect("delete must succeed");
     Ok(())
 }
+
+/// Copy with metadata directive replace should rewrite metadata of target.
+pub async fn test_copy_with_metadata_directive_replace(op: Operator) -> Result<()> {
+    let cap = op.info().full_capability();
+    let source_path = uuid::Uuid::new_v4().to_string();
+    let (source_content, _) = gen_bytes(cap);
+
+    let mut w = op.write_with(&source_path, source_content.clone());
+    if cap.write_with_content_type {
+        w = w.content_type("text/plain");
+    }
+    w.await?;
+
+    let target_path = uuid::Uuid::new_v4().to_string();
+    let user_metadata = [("location".to_string(), "everywhere".to_string())];
+    let mut c = op
+        .copy_with(&source_path, &target_path)
+        .metadata_directive(options::MetadataDirective::Replace)
+        .content_type("application/json");
+    if cap.write_with_user_metadata {
+        c = c.user_metadata(user_metadata.clone());
+    }
+    c.await?;
+
+    let target_content = op
+        .read(&target_path)
+        .await
+        .expect("read must succeed")
+        .to_bytes();
+    assert_eq!(
+        format!("{:x}", Sha256::digest(target_content)),
+        format!("{:x}", Sha256::digest(&source_content)),
+    );
+
+    let meta = op.stat(&target_path).await?;
+    if cap.write_with_content_type {
+        assert_eq!(meta.content_type(), Some("application/json"));
+    }
+    if cap.write_with_user_metadata {
+        assert_eq!(
+            meta.user_metadata(),
+            Some(&HashMap::from_iter(user_metadata))
+        );
+    }
+
+    op.delete(&source_path).await.expect("delete must succeed");
+    op.delete(&target_path).await.expect("delete must succeed");
+    Ok(())
+}
+
+/// Copy by streaming should carry metadata of source to target.
+pub async fn test_copy_by_stream_keeps_metadata(op: Operator) -> Result<()> {
+    let cap = op.info().full_capability();
+    let source_path = uuid::Uuid::new_v4().to_string();
+    let (source_content, _) = gen_bytes(cap);
+
+    let user_metadata = [("location".to_string(), "everywhere".to_string())];
+    let mut w = op.write_with(&source_path, source_content.clone());
+    if cap.write_with_content_type {
+        w = w.content_type("text/plain");
+    }
+    if cap.write_with_user_metadata {
+        w = w.user_metadata(user_metadata.clone());
+    }
+    w.await?;
+
+    let target_path = uuid::Uuid::new_v4().to_string();
+    op.copy(&source_path, &target_path).await?;
+
+    let target_content = op
+        .read(&target_path)
+        .await
+        .expect("read must succeed")
+        .to_bytes();
+    assert_eq!(
+        format!("{:x}", Sha256::digest(target_content)),
+        format!("{:x}", Sha256::digest(&source_content)),
+    );
+
+    let meta = op.stat(&target_path).await?;
+    if cap.write_with_content_type {
+        assert_eq!(meta.content_type(), Some("text/plain"));
+    }
+    if cap.write_with_user_metadata {
+        assert_eq!(
+            meta.user_metadata(),
+            Some(&HashMap::from_iter(user_metadata))
+        );
+    }
+
+    op.delete(&source_path).await.expect("delete must succeed");
+    op.delete(&target_path).await.expect("delete must succeed");
+    Ok(())
+}
+
+/// Copy with metadata directive replace should fail if not supported.
+pub async fn test_copy_with_metadata_directive_replace_unsupported(op: Operator) -> Result<()> {
+    let source_path = uuid::Uuid::new_v4().to_string();
+    let (source_content, _) = gen_bytes(op.info().full_capability());
+
+    op.write(&source_path, source_content).await?;
+
+    let target_path = uuid::Uuid::new_v4().to_string();
+    let err = op
+        .copy_with(&source_path, &target_path)
+        .metadata_directive(options::MetadataDirective::Replace)
+        .await
+        .expect_err("copy must fail");
+    assert_eq!(err.kind(), ErrorKind::Unsupported);
+
+    op.delete(&source_path).await.expect("delete must succeed");
+    Ok(())
+}
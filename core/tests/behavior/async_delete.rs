@@ -15,6 +15,10 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
 use anyhow::Result;
 use futures::TryStreamExt;
 use log::warn;
@@ -37,11 +41,18 @@ pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
             test_remove_one_file,
             test_delete_with_version,
             test_delete_with_not_existing_version,
+            test_delete_with_recursive,
+            test_delete_with_must_exist,
+            test_delete_with_if_match,
             test_batch_delete,
             test_batch_delete_with_version
         ));
         if cap.list_with_recursive {
-            tests.extend(async_trials!(op, test_remove_all_basic));
+            tests.extend(async_trials!(
+                op,
+                test_remove_all_basic,
+                test_remove_all_with_progress
+            ));
             if !cap.create_dir {
                 tests.extend(async_trials!(op, test_remove_all_with_prefix_exists));
             }
@@ -205,6 +216,40 @@ pub async fn test_remove_all_basic(op: Operator) -> Result<()> {
     test_blocking_remove_all_with_objects(op, parent, ["a/b", "a/c", "a/d/e"]).await
 }
 
+/// Remove all under a prefix with progress reported
+pub async fn test_remove_all_with_progress(op: Operator) -> Result<()> {
+    let parent = format!("{}/", uuid::Uuid::new_v4());
+    for path in ["a", "b/c", "b/d/e"] {
+        let (content, _) = gen_bytes(op.info().full_capability());
+        op.write(&format!("{parent}{path}"), content)
+            .await
+            .expect("write must succeed");
+    }
+
+    let reported = Arc::new(AtomicUsize::new(0));
+    let progress = reported.clone();
+    let removed = op
+        .remove_all_with(&parent)
+        .progress(move |n| progress.store(n, Ordering::Relaxed))
+        .await?;
+    // Dirs may be listed and removed too.
+    assert!(removed >= 3, "all files should be counted");
+    assert_eq!(reported.load(Ordering::Relaxed), removed);
+
+    let found = op
+        .lister_with(&parent)
+        .recursive(true)
+        .await
+        .expect("list must succeed")
+        .try_next()
+        .await
+        .expect("list must succeed")
+        .is_some();
+    assert!(!found, "all objects should be removed");
+
+    Ok(())
+}
+
 /// Remove all under a prefix, while the prefix itself is also an object
 pub async fn test_remove_all_with_prefix_exists(op: Operator) -> Result<()> {
     let parent = uuid::Uuid::new_v4().to_string();
@@ -251,6 +296,88 @@ pub async fn test_delete_with_version(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Delete dir with recursive should remove all nested dirs and files.
+pub async fn test_delete_with_recursive(op: Operator) -> Result<()> {
+    let cap = op.info().full_capability();
+    if !cap.delete_with_recursive || !cap.create_dir {
+        return Ok(());
+    }
+
+    let dir = TEST_FIXTURE.new_dir_path();
+    let (content, _) = gen_bytes(cap);
+    for file in ["a", "b/c", "b/d/e"] {
+        op.write(&format!("{dir}{file}"), content.clone())
+            .await
+            .expect("write must succeed");
+    }
+
+    op.delete_with(&dir)
+        .recursive(true)
+        .await
+        .expect("delete must succeed");
+
+    assert!(!op.exists(&format!("{dir}b/d/e")).await?);
+    assert!(!op.exists(&dir).await?);
+
+    Ok(())
+}
+
+/// Delete not existing file with must_exist should return NotFound.
+pub async fn test_delete_with_must_exist(op: Operator) -> Result<()> {
+    if !op.info().full_capability().delete_with_must_exist {
+        return Ok(());
+    }
+
+    let path = uuid::Uuid::new_v4().to_string();
+
+    let res = op.delete_with(&path).must_exist(true).await;
+    assert!(res.is_err());
+    assert_eq!(res.unwrap_err().kind(), ErrorKind::NotFound);
+
+    let (path, content, _) = TEST_FIXTURE.new_file(op.clone());
+    op.write(&path, content).await.expect("write must succeed");
+
+    op.delete_with(&path)
+        .must_exist(true)
+        .await
+        .expect("delete must succeed");
+    assert!(!op.exists(&path).await?);
+
+    Ok(())
+}
+
+/// Delete file with if_match should fail if the file has been changed.
+pub async fn test_delete_with_if_match(op: Operator) -> Result<()> {
+    if !op.info().full_capability().delete_with_if_match {
+        return Ok(());
+    }
+
+    let (path, content, _) = TEST_FIXTURE.new_file(op.clone());
+    let meta = op.write(&path, content).await.expect("write must succeed");
+    let etag = match meta.etag() {
+        Some(etag) => etag.to_string(),
+        None => op
+            .stat(&path)
+            .await?
+            .etag()
+            .expect("etag must exist")
+            .to_string(),
+    };
+
+    let res = op.delete_with(&path).if_match("\"invalid_etag\"").await;
+    assert!(res.is_err());
+    assert_eq!(res.unwrap_err().kind(), ErrorKind::ConditionNotMatch);
+    assert!(op.exists(&path).await?);
+
+    op.delete_with(&path)
+        .if_match(&etag)
+        .await
+        .expect("delete must succeed");
+    assert!(!op.exists(&path).await?);
+
+    Ok(())
+}
+
 pub async fn test_delete_with_not_existing_version(op: Operator) -> Result<()> {
     if !op.info().full_capability().delete_with_version {
         return Ok(());
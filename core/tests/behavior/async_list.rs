@@ -42,9 +42,18 @@ pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
             test_list_nested_dir,
             test_list_dir_with_file_path,
             test_list_with_start_after,
+            test_list_with_continuation_token,
+            test_list_with_glob,
+            test_list_dirs_only,
+            test_list_with_max_entries,
+            test_list_sorted,
+            test_list_with_metadata,
+            test_list_with_modified_time,
             test_list_non_exist_dir_with_recursive,
             test_list_dir_with_recursive,
             test_list_dir_with_recursive_no_trailing_slash,
+            test_list_dir_with_recursive_concurrent,
+            test_list_dir_with_recursive_concurrent_many_dirs,
             test_list_file_with_recursive,
             test_list_root_with_recursive,
             test_remove_all,
@@ -403,6 +412,314 @@ pub async fn test_list_with_start_after(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// List with glob should only return matched entries.
+pub async fn test_list_with_glob(op: Operator) -> Result<()> {
+    let dir = &format!("{}/", uuid::Uuid::new_v4());
+    for name in ["a.parquet", "b.csv", "2024/c.parquet", "2024/01/d.parquet"] {
+        op.write(&format!("{dir}{name}"), "content").await?;
+    }
+
+    let mut actual: Vec<String> = op
+        .list_with(dir)
+        .glob("*.parquet")
+        .await?
+        .into_iter()
+        .map(|e| e.path().to_string())
+        .collect();
+    actual.sort();
+    assert_eq!(actual, vec![format!("{dir}a.parquet")]);
+
+    let mut actual: Vec<String> = op
+        .list_with(dir)
+        .recursive(true)
+        .glob("**/*.parquet")
+        .await?
+        .into_iter()
+        .map(|e| e.path().to_string())
+        .collect();
+    actual.sort();
+    assert_eq!(
+        actual,
+        vec![
+            format!("{dir}2024/01/d.parquet"),
+            format!("{dir}2024/c.parquet"),
+            format!("{dir}a.parquet"),
+        ]
+    );
+
+    let mut actual: Vec<String> = op
+        .list_with(dir)
+        .recursive(true)
+        .glob("2024/*.parquet")
+        .await?
+        .into_iter()
+        .map(|e| e.path().to_string())
+        .collect();
+    actual.sort();
+    assert_eq!(actual, vec![format!("{dir}2024/c.parquet")]);
+
+    op.remove_all(dir).await?;
+    Ok(())
+}
+
+/// List with continuation token should resume listing where it stopped.
+pub async fn test_list_with_continuation_token(op: Operator) -> Result<()> {
+    let dir = &format!("{}/", uuid::Uuid::new_v4());
+    op.create_dir(dir).await?;
+
+    // The listing can't be resumed without native start_after support.
+    if !op.info().native_capability().list_with_start_after {
+        op.write(&format!("{dir}file"), "content").await?;
+        let mut objects = op.lister(dir).await?;
+        while objects.try_next().await?.is_some() {}
+        assert_eq!(objects.continuation_token(), None);
+
+        op.remove_all(dir).await?;
+        return Ok(());
+    }
+
+    let given: Vec<String> = ["file-0", "file-1", "file-2", "file-3", "file-4", "file-5"]
+        .iter()
+        .map(|name| format!("{dir}{name}-{}", uuid::Uuid::new_v4()))
+        .collect();
+    for name in given.iter() {
+        op.write(name, "content")
+            .await
+            .expect("create must succeed");
+    }
+
+    let mut actual = vec![];
+    let mut objects = op.lister(dir).await?;
+    while let Some(o) = objects.try_next().await? {
+        if o.path() != dir {
+            actual.push(o.path().to_string());
+        }
+        if actual.len() == 3 {
+            break;
+        }
+    }
+    let token = objects
+        .continuation_token()
+        .expect("token must exist")
+        .to_string();
+    drop(objects);
+
+    let mut objects = op.lister_with(dir).start_after(&token).await?;
+    while let Some(o) = objects.try_next().await? {
+        if o.path() != dir {
+            actual.push(o.path().to_string());
+        }
+    }
+
+    assert_eq!(given, actual);
+
+    op.remove_all(dir).await?;
+    Ok(())
+}
+
+/// List with dirs_only should only return dirs.
+pub async fn test_list_dirs_only(op: Operator) -> Result<()> {
+    let parent = format!("{}/", uuid::Uuid::new_v4());
+
+    for path in ["a/", "b/", "b/c/", "x", "b/y"] {
+        if path.ends_with('/') {
+            op.create_dir(&format!("{parent}{path}")).await?;
+        } else {
+            op.write(&format!("{parent}{path}"), "test_list").await?;
+        }
+    }
+
+    let mut actual: Vec<String> = op
+        .list_with(&parent)
+        .dirs_only(true)
+        .await?
+        .into_iter()
+        .map(|e| e.path().to_string())
+        .filter(|path| path != &parent)
+        .collect();
+    actual.sort();
+    assert_eq!(actual, vec![format!("{parent}a/"), format!("{parent}b/")]);
+
+    let mut actual: Vec<String> = op
+        .list_with(&parent)
+        .recursive(true)
+        .dirs_only(true)
+        .await?
+        .into_iter()
+        .map(|e| e.path().to_string())
+        .filter(|path| path != &parent)
+        .collect();
+    actual.sort();
+    assert_eq!(
+        actual,
+        vec![
+            format!("{parent}a/"),
+            format!("{parent}b/"),
+            format!("{parent}b/c/")
+        ]
+    );
+
+    op.remove_all(&parent).await?;
+    Ok(())
+}
+
+pub async fn test_list_with_max_entries(op: Operator) -> Result<()> {
+    let parent = format!("{}/", uuid::Uuid::new_v4());
+    for i in 0..5 {
+        op.write(&format!("{parent}{i}"), "test_list").await?;
+    }
+
+    let entries = op.list_with(&parent).max_entries(3).await?;
+    assert_eq!(entries.len(), 3);
+
+    let entries = op
+        .list_with(&parent)
+        .recursive(true)
+        .limit(1)
+        .max_entries(3)
+        .await?;
+    assert_eq!(entries.len(), 3);
+
+    let entries = op.list_with(&parent).max_entries(0).await?;
+    assert!(entries.is_empty());
+
+    let files = op
+        .list_with(&parent)
+        .max_entries(100)
+        .await?
+        .into_iter()
+        .filter(|e| e.metadata().is_file())
+        .count();
+    assert_eq!(files, 5);
+
+    op.remove_all(&parent).await?;
+    Ok(())
+}
+
+pub async fn test_list_sorted(op: Operator) -> Result<()> {
+    let parent = format!("{}/", uuid::Uuid::new_v4());
+
+    let files = ["b", "a/z", "a/b/c", "a-x", "c/d", "a0"];
+    for path in files {
+        op.write(&format!("{parent}{path}"), "test_list").await?;
+    }
+
+    let actual: Vec<String> = op
+        .list_with(&parent)
+        .sorted(true)
+        .await?
+        .into_iter()
+        .map(|e| e.path().to_string())
+        .filter(|path| path != &parent)
+        .collect();
+    let expected: Vec<String> = ["a-x", "a/", "a0", "b", "c/"]
+        .iter()
+        .map(|v| format!("{parent}{v}"))
+        .collect();
+    assert_eq!(actual, expected, "non-recursive list must be sorted");
+
+    let actual: Vec<String> = op
+        .list_with(&parent)
+        .recursive(true)
+        .sorted(true)
+        .await?
+        .into_iter()
+        .map(|e| e.path().to_string())
+        .collect();
+    let mut expected = actual.clone();
+    expected.sort();
+    assert_eq!(actual, expected, "recursive list must be sorted");
+    for path in files {
+        assert!(
+            actual.contains(&format!("{parent}{path}")),
+            "file {path} must be listed"
+        );
+    }
+
+    op.remove_all(&parent).await?;
+    Ok(())
+}
+
+pub async fn test_list_with_metadata(op: Operator) -> Result<()> {
+    let parent = format!("{}/", uuid::Uuid::new_v4());
+
+    let mut expected = HashMap::new();
+    for (idx, path) in ["x", "y", "z/w"].iter().enumerate() {
+        let path = format!("{parent}{path}");
+        op.write(&path, gen_fixed_bytes(idx + 1)).await?;
+        expected.insert(path, idx as u64 + 1);
+    }
+
+    let entries = op
+        .list_with(&parent)
+        .recursive(true)
+        .metadata(true)
+        .concurrent(2)
+        .await?;
+    let mut actual = HashMap::new();
+    for de in entries {
+        if de.metadata().is_file() {
+            actual.insert(de.path().to_string(), de.metadata().content_length());
+        }
+    }
+    assert_eq!(actual, expected, "files must be listed with content length");
+
+    op.remove_all(&parent).await?;
+    Ok(())
+}
+
+/// List with modified time should only return files modified in the range.
+pub async fn test_list_with_modified_time(op: Operator) -> Result<()> {
+    let parent = format!("{}/", uuid::Uuid::new_v4());
+    let path = format!("{parent}x/y");
+    op.write(&path, gen_fixed_bytes(8)).await?;
+    let Some(last_modified) = op.stat(&path).await?.last_modified() else {
+        op.remove_all(&parent).await?;
+        return Ok(());
+    };
+
+    let files = |entries: Vec<Entry>| {
+        entries
+            .into_iter()
+            .filter(|de| de.metadata().is_file())
+            .map(|de| de.path().to_string())
+            .collect::<Vec<_>>()
+    };
+
+    for recursive in [false, true] {
+        let dir = if recursive {
+            parent.clone()
+        } else {
+            format!("{parent}x/")
+        };
+
+        let entries = op
+            .list_with(&dir)
+            .recursive(recursive)
+            .modified_after(last_modified - chrono::Duration::seconds(1))
+            .modified_before(last_modified + chrono::Duration::seconds(1))
+            .await?;
+        assert_eq!(files(entries), vec![path.clone()], "file must be listed");
+
+        let entries = op
+            .list_with(&dir)
+            .recursive(recursive)
+            .modified_after(last_modified + chrono::Duration::seconds(1))
+            .await?;
+        assert!(files(entries).is_empty(), "newer files must be skipped");
+
+        let entries = op
+            .list_with(&dir)
+            .recursive(recursive)
+            .modified_before(last_modified - chrono::Duration::seconds(1))
+            .await?;
+        assert!(files(entries).is_empty(), "older files must be skipped");
+    }
+
+    op.remove_all(&parent).await?;
+    Ok(())
+}
+
 pub async fn test_list_non_exist_dir_with_recursive(op: Operator) -> Result<()> {
     let dir = format!("{}/", uuid::Uuid::new_v4());
 
@@ -507,6 +824,88 @@ pub async fn test_list_dir_with_recursive_no_trailing_slash(op: Operator) -> Res
     Ok(())
 }
 
+pub async fn test_list_dir_with_recursive_concurrent(op: Operator) -> Result<()> {
+    let parent = uuid::Uuid::new_v4().to_string();
+
+    let paths = [
+        "x/", "x/a/", "x/a/x/", "x/a/y", "x/b/", "x/b/x/", "x/b/x/y", "x/c/", "x/c/y", "x/y",
+    ];
+    for path in paths.iter() {
+        if path.ends_with('/') {
+            op.create_dir(&format!("{parent}/{path}")).await?;
+        } else {
+            op.write(&format!("{parent}/{path}"), "test_scan").await?;
+        }
+    }
+
+    let mut results = vec![];
+    for concurrent in [1, 4] {
+        let w = op
+            .lister_with(&format!("{parent}/x/"))
+            .recursive(true)
+            .concurrent(concurrent)
+            .await?;
+        let mut actual = w
+            .try_collect::<Vec<_>>()
+            .await?
+            .into_iter()
+            .map(|v| {
+                v.path()
+                    .strip_prefix(&format!("{parent}/"))
+                    .unwrap()
+                    .to_string()
+            })
+            .collect::<Vec<_>>();
+        actual.sort();
+        results.push(actual);
+    }
+
+    assert_eq!(results[0], paths.to_vec());
+    assert_eq!(results[1], paths.to_vec());
+    Ok(())
+}
+
+pub async fn test_list_dir_with_recursive_concurrent_many_dirs(op: Operator) -> Result<()> {
+    let parent = uuid::Uuid::new_v4().to_string();
+
+    // More dirs than the concurrent lister will queue at the same time.
+    let mut expected = vec!["x/".to_string()];
+    for i in 0..40 {
+        expected.push(format!("x/{i:02}/"));
+        expected.push(format!("x/{i:02}/y/"));
+        expected.push(format!("x/{i:02}/y/z"));
+    }
+    for path in expected.iter() {
+        if path.ends_with('/') {
+            op.create_dir(&format!("{parent}/{path}")).await?;
+        } else {
+            op.write(&format!("{parent}/{path}"), "test_scan").await?;
+        }
+    }
+
+    let w = op
+        .lister_with(&format!("{parent}/x/"))
+        .recursive(true)
+        .concurrent(2)
+        .await?;
+    let mut actual = w
+        .try_collect::<Vec<_>>()
+        .await?
+        .into_iter()
+        .map(|v| {
+            v.path()
+                .strip_prefix(&format!("{parent}/"))
+                .unwrap()
+                .to_string()
+        })
+        .collect::<Vec<_>>();
+    actual.sort();
+
+    expected.sort();
+    assert_eq!(actual, expected);
+    Ok(())
+}
+
 pub async fn test_list_file_with_recursive(op: Operator) -> Result<()> {
     let parent = uuid::Uuid::new_v4().to_string();
 
@@ -37,7 +37,8 @@ pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
             test_presign_write,
             test_presign_read,
             test_presign_stat,
-            test_presign_delete
+            test_presign_delete,
+            test_presign_multipart
         ))
     }
 }
@@ -173,3 +174,81 @@ pub async fn test_presign_delete(op: Operator) -> Result<()> {
     assert!(!op.exists(&path).await.expect("delete must succeed"));
     Ok(())
 }
+
+/// Presign multipart upload should succeed.
+pub async fn test_presign_multipart(op: Operator) -> Result<()> {
+    let cap = op.info().full_capability();
+    if !cap.presign_multipart {
+        return Ok(());
+    }
+
+    let path = uuid::Uuid::new_v4().to_string();
+    debug!("Generate a random file: {path}");
+    let (content, size) = gen_bytes(cap);
+    let client = reqwest::Client::new();
+    let build = |signed_req: raw::PresignedRequest| {
+        let mut req = client.request(
+            signed_req.method().clone(),
+            Url::from_str(&signed_req.uri().to_string()).expect("must be valid url"),
+        );
+        for (k, v) in signed_req.header() {
+            req = req.header(k, v);
+        }
+        req
+    };
+
+    let signed_req = op
+        .presign_create_multipart(&path, Duration::from_secs(3600))
+        .await?;
+    debug!("Generated request: {signed_req:?}");
+    let resp = build(signed_req)
+        .send()
+        .await
+        .expect("send request must succeed");
+    let body = resp.text().await.expect("read response must succeed");
+    let upload_id = body
+        .split_once("<UploadId>")
+        .and_then(|(_, v)| v.split_once("</UploadId>"))
+        .map(|(v, _)| v.to_string())
+        .expect("upload id must be returned");
+
+    let signed_req = op
+        .presign_upload_part(&path, &upload_id, 1, Duration::from_secs(3600))
+        .await?;
+    debug!("Generated request: {signed_req:?}");
+    let resp = build(signed_req)
+        .header(header::CONTENT_LENGTH, content.len())
+        .body(reqwest::Body::from(content))
+        .send()
+        .await
+        .expect("send request must succeed");
+    assert_eq!(resp.status(), http::StatusCode::OK, "status ok");
+    let etag = resp
+        .headers()
+        .get(header::ETAG)
+        .expect("etag must be returned")
+        .to_str()
+        .expect("etag must be valid string")
+        .to_string();
+
+    let signed_req = op
+        .presign_complete_multipart(&path, &upload_id, Duration::from_secs(3600))
+        .await?;
+    debug!("Generated request: {signed_req:?}");
+    let body = format!(
+        "<CompleteMultipartUpload><Part><PartNumber>1</PartNumber><ETag>{etag}</ETag></Part></CompleteMultipartUpload>"
+    );
+    let resp = build(signed_req)
+        .header(header::CONTENT_LENGTH, body.len())
+        .body(body)
+        .send()
+        .await
+        .expect("send request must succeed");
+    assert_eq!(resp.status(), http::StatusCode::OK, "status ok");
+
+    let meta = op.stat(&path).await.expect("stat must succeed");
+    assert_eq!(meta.content_length(), size as u64);
+
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
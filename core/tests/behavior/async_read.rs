@@ -37,7 +37,11 @@ pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
             op,
             test_read_full,
             test_read_range,
+            test_read_with_suffix_range,
+            test_read_head,
+            test_read_with_meta,
             test_reader,
+            test_reader_fetch,
             test_reader_with_if_match,
             test_reader_with_if_none_match,
             test_reader_with_if_modified_since,
@@ -119,6 +123,140 @@ pub async fn test_read_range(op: Operator) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Read with suffix range should return the tail of file.
+pub async fn test_read_with_suffix_range(op: Operator) -> anyhow::Result<()> {
+    let (path, content, size) = TEST_FIXTURE.new_file(op.clone());
+    let (_, length) = gen_offset_length(size);
+
+    op.write(&path, content.clone())
+        .await
+        .expect("write must succeed");
+
+    let bs = op.read_with(&path).suffix_range(length).await?.to_bytes();
+    assert_eq!(bs.len() as u64, length, "read size");
+    assert_eq!(
+        format!("{:x}", Sha256::digest(&bs)),
+        format!(
+            "{:x}",
+            Sha256::digest(&content[(size as u64 - length) as usize..])
+        ),
+        "read content"
+    );
+
+    // The whole file should be returned if suffix is larger than the file.
+    let bs = op
+        .read_with(&path)
+        .suffix_range(size as u64 + 1024)
+        .await?
+        .to_bytes();
+    assert_eq!(bs.len(), size, "read size");
+
+    let err = op
+        .read_with(&path)
+        .suffix_range(0)
+        .await
+        .expect_err("empty suffix range must be rejected");
+    assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+
+    let err = op
+        .read_with(&path)
+        .range(0..1)
+        .suffix_range(length)
+        .await
+        .expect_err("suffix range with range must be rejected");
+    assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+
+    Ok(())
+}
+
+/// Fetch should return the content of every given range.
+pub async fn test_reader_fetch(op: Operator) -> anyhow::Result<()> {
+    let (path, content, size) = TEST_FIXTURE.new_file(op.clone());
+
+    op.write(&path, content.clone())
+        .await
+        .expect("write must succeed");
+
+    let size = size as u64;
+    let ranges = vec![
+        size / 2..size,
+        0..size / 4,
+        size / 4 + 1..size / 4 + 2,
+        size / 2..size / 2 + 1,
+    ];
+    // Don't merge ranges so that they could be read in one request.
+    let r = op.reader_with(&path).gap(0).await?;
+    let bufs = r.fetch(ranges.clone()).await?;
+
+    assert_eq!(bufs.len(), ranges.len());
+    for (range, buf) in ranges.into_iter().zip(bufs) {
+        assert_eq!(
+            buf.to_bytes(),
+            content[range.start as usize..range.end as usize],
+            "read content of range {range:?}"
+        );
+    }
+    Ok(())
+}
+
+/// Read head should return at most the first n bytes.
+pub async fn test_read_head(op: Operator) -> anyhow::Result<()> {
+    let (path, content, size) = TEST_FIXTURE.new_file(op.clone());
+    let (_, length) = gen_offset_length(size);
+
+    op.write(&path, content.clone())
+        .await
+        .expect("write must succeed");
+
+    let bs = op.read_head(&path, length).await?.to_bytes();
+    assert_eq!(bs.len() as u64, length, "read size");
+    assert_eq!(
+        format!("{:x}", Sha256::digest(&bs)),
+        format!("{:x}", Sha256::digest(&content[..length as usize])),
+        "read content"
+    );
+
+    let bs = op.read_head(&path, size as u64).await?.to_bytes();
+    assert_eq!(bs.len(), size, "read size");
+
+    // The whole file should be returned if n is larger than the file.
+    let bs = op.read_head(&path, size as u64 + 1024).await?.to_bytes();
+    assert_eq!(bs.len(), size, "read size");
+
+    let bs = op.read_head(&path, 0).await?;
+    assert!(bs.is_empty(), "read size");
+
+    Ok(())
+}
+
+/// Read range along with metadata should match.
+pub async fn test_read_with_meta(op: Operator) -> anyhow::Result<()> {
+    let (path, content, size) = TEST_FIXTURE.new_file(op.clone());
+    let (offset, length) = gen_offset_length(size);
+
+    op.write(&path, content.clone())
+        .await
+        .expect("write must succeed");
+
+    let (meta, bs) = op.read_with_meta(&path, offset..offset + length).await?;
+    assert_eq!(meta.content_length(), size as u64, "content length");
+    assert_eq!(
+        format!("{:x}", Sha256::digest(bs.to_bytes())),
+        format!(
+            "{:x}",
+            Sha256::digest(&content[offset as usize..(offset + length) as usize])
+        ),
+        "read content"
+    );
+
+    // The whole file should be returned if range is larger than the file.
+    let (meta, bs) = op.read_with_meta(&path, 0..size as u64 + 1024).await?;
+    assert_eq!(meta.content_length(), size as u64, "content length");
+    assert_eq!(bs.len(), size, "read size");
+
+    Ok(())
+}
+
 /// Read full content should match.
 pub async fn test_reader(op: Operator) -> anyhow::Result<()> {
     let (path, content, size) = TEST_FIXTURE.new_file(op.clone());
@@ -15,6 +15,7 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
 use std::str::FromStr;
 use std::time::Duration;
 
@@ -47,7 +48,9 @@ pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
             test_stat_with_override_content_type,
             test_stat_root,
             test_stat_with_version,
-            stat_with_not_existing_version
+            stat_with_not_existing_version,
+            test_stat_with_tags,
+            test_stat_with_tags_unsupported
         ))
     }
 
@@ -75,6 +78,9 @@ pub async fn test_stat_file(op: Operator) -> Result<()> {
     assert_eq!(meta.mode(), EntryMode::FILE);
     assert_eq!(meta.content_length(), size as u64);
 
+    let meta = op.stat_if_exists(&path).await?.expect("file must exist");
+    assert_eq!(meta.content_length(), size as u64);
+
     // Stat a file with trailing slash should return `NotFound`.
     if op.info().full_capability().create_dir {
         let result = op.stat(&format!("{path}/")).await;
@@ -170,6 +176,7 @@ pub async fn test_stat_not_exist(op: Operator) -> Result<()> {
     let meta = op.stat(&path).await;
     assert!(meta.is_err());
     assert_eq!(meta.unwrap_err().kind(), ErrorKind::NotFound);
+    assert!(op.stat_if_exists(&path).await?.is_none());
 
     // Stat not exist dir should also return NotFound.
     if op.info().full_capability().create_dir {
@@ -626,3 +633,51 @@ pub async fn stat_with_not_existing_version(op: Operator) -> Result<()> {
 
     Ok(())
 }
+
+/// Tags set via `set_tags` should be returned by `stat_with(path).tags(true)`.
+pub async fn test_stat_with_tags(op: Operator) -> Result<()> {
+    let cap = op.info().full_capability();
+    if !(cap.stat_with_tags && cap.set_tags) {
+        return Ok(());
+    }
+
+    let (path, content, _) = TEST_FIXTURE.new_file(op.clone());
+    op.write(&path, content).await.expect("write must succeed");
+
+    let tags = HashMap::from([
+        ("project".to_string(), "opendal".to_string()),
+        ("env".to_string(), "test".to_string()),
+    ]);
+    op.set_tags(&path, tags.clone())
+        .await
+        .expect("set tags must succeed");
+
+    let meta = op.stat_with(&path).tags(true).await?;
+    assert_eq!(meta.tags(), Some(&tags));
+    assert_eq!(op.tags(&path).await?, tags);
+
+    // Tags should not be fetched unless requested.
+    let meta = op.stat(&path).await?;
+    assert_eq!(meta.tags(), None);
+
+    Ok(())
+}
+
+/// Stat with tags should fail if the service doesn't support it.
+pub async fn test_stat_with_tags_unsupported(op: Operator) -> Result<()> {
+    if op.info().full_capability().stat_with_tags {
+        return Ok(());
+    }
+
+    let (path, content, _) = TEST_FIXTURE.new_file(op.clone());
+    op.write(&path, content).await.expect("write must succeed");
+
+    let err = op
+        .stat_with(&path)
+        .tags(true)
+        .await
+        .expect_err("stat with tags must fail");
+    assert_eq!(err.kind(), ErrorKind::Unsupported);
+
+    Ok(())
+}
@@ -43,18 +43,24 @@ pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
             test_write_with_special_chars,
             test_write_with_cache_control,
             test_write_with_content_type,
+            test_write_with_content_type_detection,
             test_write_with_content_disposition,
             test_write_with_content_encoding,
             test_write_with_if_none_match,
             test_write_with_if_not_exists,
             test_write_with_if_match,
+            test_write_cas,
+            test_write_if_not_exists,
             test_write_with_user_metadata,
             test_write_returns_metadata,
             test_writer_write,
             test_writer_write_with_overwrite,
             test_writer_write_with_concurrent,
+            test_writer_write_with_flush,
             test_writer_sink,
             test_writer_sink_with_concurrent,
+            test_write_from_stream,
+            test_write_from_stream_with_error,
             test_writer_abort,
             test_writer_abort_with_concurrent,
             test_writer_futures_copy,
@@ -186,6 +192,42 @@ pub async fn test_write_with_content_type(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Write a single file with content type detection should succeed.
+pub async fn test_write_with_content_type_detection(op: Operator) -> Result<()> {
+    if !op.info().full_capability().write_with_content_type {
+        return Ok(());
+    }
+
+    let path = TEST_FIXTURE.new_file_path();
+    let mut content = b"\x89PNG\r\n\x1a\n".to_vec();
+    content.extend(gen_bytes(op.info().full_capability()).0);
+
+    op.write_with(&path, content.clone())
+        .content_type_detection(true)
+        .await?;
+
+    let meta = op.stat(&path).await.expect("stat must succeed");
+    assert_eq!(
+        meta.content_type().expect("content type must exist"),
+        "image/png"
+    );
+    assert_eq!(meta.content_length(), content.len() as u64);
+
+    // Content type set by users should not be overridden.
+    op.write_with(&path, content)
+        .content_type("application/json")
+        .content_type_detection(true)
+        .await?;
+
+    let meta = op.stat(&path).await.expect("stat must succeed");
+    assert_eq!(
+        meta.content_type().expect("content type must exist"),
+        "application/json"
+    );
+
+    Ok(())
+}
+
 /// Write a single file with content disposition should succeed.
 pub async fn test_write_with_content_disposition(op: Operator) -> Result<()> {
     if !op.info().full_capability().write_with_content_disposition {
@@ -355,6 +397,40 @@ pub async fn test_writer_write(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Flush data in the middle of writing should not finalize the file.
+pub async fn test_writer_write_with_flush(op: Operator) -> Result<()> {
+    if !(op.info().full_capability().write_can_multi) {
+        return Ok(());
+    }
+
+    let path = TEST_FIXTURE.new_file_path();
+    let size = 5 * 1024 * 1024; // write file with 5 MiB
+    let content_a = gen_fixed_bytes(size);
+    let content_b = gen_fixed_bytes(size);
+
+    let mut w = op.writer(&path).await?;
+    w.write(content_a.clone()).await?;
+    w.flush().await?;
+    w.write(content_b.clone()).await?;
+    w.flush().await?;
+    w.close().await?;
+
+    let bs = op.read(&path).await?.to_bytes();
+    assert_eq!(bs.len(), size * 2, "read size");
+    assert_eq!(
+        format!("{:x}", Sha256::digest(&bs[..size])),
+        format!("{:x}", Sha256::digest(content_a)),
+        "read content a"
+    );
+    assert_eq!(
+        format!("{:x}", Sha256::digest(&bs[size..])),
+        format!("{:x}", Sha256::digest(content_b)),
+        "read content b"
+    );
+
+    Ok(())
+}
+
 /// Append data into writer
 pub async fn test_writer_write_with_concurrent(op: Operator) -> Result<()> {
     if !(op.info().full_capability().write_can_multi) {
@@ -444,6 +520,58 @@ pub async fn test_writer_sink(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Write data from a stream
+pub async fn test_write_from_stream(op: Operator) -> Result<()> {
+    let cap = op.info().full_capability();
+    if !(cap.write && cap.write_can_multi) {
+        return Ok(());
+    }
+
+    let path = TEST_FIXTURE.new_file_path();
+    let size = 1024 * 1024;
+    let content_a = gen_fixed_bytes(size);
+    let content_b = gen_fixed_bytes(size);
+    let stream = stream::iter(vec![
+        Bytes::from(content_a.clone()),
+        Bytes::from(content_b.clone()),
+    ])
+    .map(Ok);
+
+    let meta = op.write_from(&path, stream).await?;
+    assert_eq!(meta.content_length(), (size * 2) as u64);
+
+    let bs = op.read(&path).await?.to_bytes();
+    assert_eq!(bs.len(), size * 2, "read size");
+    assert_eq!(&bs[..size], content_a, "read content a");
+    assert_eq!(&bs[size..], content_b, "read content b");
+
+    Ok(())
+}
+
+/// Write data from a stream which returns an error
+pub async fn test_write_from_stream_with_error(op: Operator) -> Result<()> {
+    let cap = op.info().full_capability();
+    if !(cap.write && cap.write_can_multi) {
+        return Ok(());
+    }
+
+    let path = TEST_FIXTURE.new_file_path();
+    let stream = stream::iter(vec![
+        Ok(Bytes::from(gen_fixed_bytes(1024))),
+        Err(Error::new(ErrorKind::Unexpected, "stream failed")),
+    ]);
+
+    let err = op
+        .write_from(&path, stream)
+        .await
+        .expect_err("write must fail");
+    // The error of stream must be returned instead of the abort error.
+    assert_eq!(err.kind(), ErrorKind::Unexpected);
+    assert!(err.to_string().contains("stream failed"), "{err}");
+
+    Ok(())
+}
+
 /// Streaming data into writer
 pub async fn test_writer_sink_with_concurrent(op: Operator) -> Result<()> {
     let cap = op.info().full_capability();
@@ -780,6 +908,56 @@ pub async fn test_write_with_if_not_exists(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Write with write_cas will get a ConditionNotMatch error if the file has been changed.
+pub async fn test_write_cas(op: Operator) -> Result<()> {
+    let cap = op.info().full_capability();
+    let (path, content, _) = TEST_FIXTURE.new_file(op.clone());
+
+    if !cap.write_with_if_not_exists && !cap.write_with_if_none_match {
+        let res = op.write_cas(&path, None, content).await;
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::Unsupported);
+        return Ok(());
+    }
+
+    op.write_cas(&path, None, content.clone()).await?;
+    let res = op.write_cas(&path, None, content.clone()).await;
+    assert_eq!(res.unwrap_err().kind(), ErrorKind::ConditionNotMatch);
+
+    if !cap.write_with_if_match {
+        return Ok(());
+    }
+
+    let etag = op.stat(&path).await?.etag().map(|v| v.to_string());
+    op.write_cas(&path, etag.clone(), "new content").await?;
+    // The etag has been changed by the previous write.
+    let res = op.write_cas(&path, etag, content).await;
+    assert_eq!(res.unwrap_err().kind(), ErrorKind::ConditionNotMatch);
+
+    Ok(())
+}
+
+/// Write if not exists should only succeed for the first writer.
+pub async fn test_write_if_not_exists(op: Operator) -> Result<()> {
+    let cap = op.info().full_capability();
+    let (path, content, _) = TEST_FIXTURE.new_file(op.clone());
+
+    if !cap.write_with_if_not_exists && !cap.write_with_if_none_match {
+        let res = op.write_if_not_exists(&path, content).await;
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::Unsupported);
+        return Ok(());
+    }
+
+    assert!(op.write_if_not_exists(&path, content.clone()).await?);
+    assert!(!op.write_if_not_exists(&path, "new content").await?);
+    assert_eq!(
+        op.read(&path).await?.to_vec(),
+        content,
+        "first write must win"
+    );
+
+    Ok(())
+}
+
 /// Write an file with if_match will get a ConditionNotMatch error if file's etag does not match.
 pub async fn test_write_with_if_match(op: Operator) -> Result<()> {
     if !op.info().full_capability().write_with_if_match {